@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
 
@@ -10,22 +10,31 @@ use parking_lot::RwLock;
 use tokio::runtime::{Handle, Runtime};
 use tokio::sync::Mutex;
 
+use segment::spaces::tools::mertic_object;
 use segment::types::{
-    Condition, Filter, HasIdCondition, PayloadKeyType, PayloadSchemaInfo, PointIdType, ScoredPoint,
-    SegmentType, VectorElementType, WithPayload,
+    Condition, Distance, Filter, HasIdCondition, OrderBy, PayloadKeyType, PayloadSchemaInfo,
+    PayloadSchemaType, PointIdType, ScoreType, ScoredPoint, SegmentType, Vector, VectorElementType,
+    WithPayload, WithPayloadInterface, DEFAULT_VECTOR_NAME,
 };
 
 use crate::collection_builder::optimizers_builder::build_optimizers;
 use crate::collection_manager::collection_managers::CollectionSearcher;
 use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
-use crate::config::CollectionConfig;
+use crate::config::{check_payload, CollectionConfig, PayloadSchema};
+use crate::grouping::{group_by, GroupRequest};
 use crate::operations::config_diff::{DiffConfig, OptimizersConfigDiff};
 use crate::operations::types::{
-    CollectionError, CollectionInfo, CollectionResult, CollectionStatus, RecommendRequest,
-    ScrollRequest, ScrollResult, SearchRequest, UpdateResult, UpdateStatus,
+    CollectionError, CollectionInfo, CollectionResult, CollectionStatus, CountRequest, CountResult,
+    GroupsResult, NamedVector, NamedVectorStruct, RecommendExample, RecommendGroupsRequest,
+    RecommendRequest, RecommendStrategy, ScrollRequest, ScrollResult, SearchGroupsRequest,
+    SearchRequest, SearchRequestBatch, UpdateResult, UpdateStatus,
 };
-use crate::operations::CollectionUpdateOperations;
+use crate::operations::{CollectionUpdateOperations, CreateFieldIndex, FieldIndexOperations};
+use crate::recommendations::{
+    example_weights, score_by_examples, ExampleScoring, RecommendExamples,
+};
+use crate::snapshot::pack_snapshot;
 use crate::update_handler::{OperationData, UpdateHandler, UpdateSignal};
 use crate::wal::SerdeWal;
 use async_channel::Sender;
@@ -75,9 +84,16 @@ impl Collection {
     /// Explicitly waits for result to be updated.
     pub async fn update(
         &self,
-        operation: CollectionUpdateOperations,
+        mut operation: CollectionUpdateOperations,
         wait: bool,
     ) -> CollectionResult<UpdateResult> {
+        if let Some(payload_schema) = &self.config.read().await.params.payload_schema {
+            // Payload is normalized before the operation is written into WAL
+            for payload in operation.payloads_mut() {
+                check_payload(payload_schema, payload)?;
+            }
+        }
+
         let sndr = self.update_sender.clone();
         let (callback_sender, callback_receiver) = if wait {
             let (tx, rx) = async_channel::unbounded();
@@ -139,97 +155,589 @@ impl Collection {
             });
         }
 
+        if let Some(order_by) = &request.order_by {
+            return self
+                .scroll_ordered(
+                    order_by,
+                    offset,
+                    limit,
+                    &request,
+                    with_payload_interface,
+                    with_vector,
+                    segment_searcher,
+                )
+                .await;
+        }
+
         // ToDo: Make faster points selection with a set
         let segments = self.segments();
-        let point_ids = segments
+        // Read one extra point: it becomes the offset of the next page, if there is one
+        let mut point_ids = segments
             .read()
             .iter()
             .flat_map(|(_, segment)| {
                 segment
                     .get()
                     .read()
-                    .read_filtered(offset, limit, request.filter.as_ref())
+                    .read_filtered(offset, limit + 1, request.filter.as_ref())
             })
             .sorted()
             .dedup()
-            .take(limit)
+            .take(limit + 1)
             .collect_vec();
 
+        let next_page_offset = if point_ids.len() > limit {
+            point_ids.pop()
+        } else {
+            None
+        };
+
         let with_payload = WithPayload::from(with_payload_interface);
         let mut points = segment_searcher
             .retrieve(segments, &point_ids, &with_payload, with_vector)
             .await?;
         points.sort_by_key(|point| point.id);
 
-        let next_page_offset = if point_ids.len() < limit {
-            None
+        Ok(ScrollResult {
+            points,
+            next_page_offset,
+            next_page_order_value: None,
+        })
+    }
+
+    /// Check, that points could be ordered by the field: it should be indexed.
+    /// If the field is declared in the payload schema, it should also be numeric
+    async fn check_order_by(&self, order_by: &OrderBy) -> CollectionResult<()> {
+        if let Some(field_schema) = self
+            .config
+            .read()
+            .await
+            .params
+            .payload_schema
+            .as_ref()
+            .and_then(|payload_schema| payload_schema.get(&order_by.key))
+        {
+            if !matches!(
+                field_schema.field_type,
+                PayloadSchemaType::Integer | PayloadSchemaType::Float | PayloadSchemaType::Datetime
+            ) {
+                return Err(CollectionError::BadInput {
+                    description: format!(
+                        "Can't order by field \"{}\" of {:?} type, it should be numeric",
+                        order_by.key, field_schema.field_type
+                    ),
+                });
+            }
+        }
+        let is_indexed = self.segments.read().iter().any(|(_idx, segment)| {
+            segment
+                .get()
+                .read()
+                .get_indexed_fields()
+                .contains(&order_by.key)
+        });
+        if !is_indexed {
+            return Err(CollectionError::BadInput {
+                description: format!(
+                    "Can't order by field \"{}\", it is not indexed. Create index of the field first",
+                    order_by.key
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Scroll points in the order of the payload field values.
+    /// Points with the same value are ordered by id
+    #[allow(clippy::too_many_arguments)]
+    async fn scroll_ordered(
+        &self,
+        order_by: &OrderBy,
+        offset: PointIdType,
+        limit: usize,
+        request: &ScrollRequest,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: bool,
+        segment_searcher: &(dyn CollectionSearcher),
+    ) -> CollectionResult<ScrollResult> {
+        self.check_order_by(order_by).await?;
+
+        let segments = self.segments();
+        // Same point may temporary be stored in multiple segments, so points are deduplicated.
+        // Read one extra point: it becomes the cursor of the next page, if there is one
+        let mut ordered_points = segments
+            .read()
+            .iter()
+            .flat_map(|(_, segment)| {
+                segment.get().read().read_ordered_filtered(
+                    offset,
+                    limit + 1,
+                    request.filter.as_ref(),
+                    order_by,
+                )
+            })
+            .sorted_by(|(value, point_id), (other_value, other_id)| {
+                order_by
+                    .cmp_values(value, other_value)
+                    .then(point_id.cmp(other_id))
+            })
+            .unique_by(|(_, point_id)| *point_id)
+            .take(limit + 1)
+            .collect_vec();
+
+        let (next_page_order_value, next_page_offset) = if ordered_points.len() > limit {
+            ordered_points
+                .pop()
+                .map(|(value, point_id)| (Some(value), Some(point_id)))
+                .unwrap()
         } else {
-            Some(point_ids.last().unwrap() + 1)
+            (None, None)
         };
 
+        let point_ids = ordered_points
+            .iter()
+            .map(|(_, point_id)| *point_id)
+            .collect_vec();
+        let with_payload = WithPayload::from(with_payload_interface);
+        let mut points = segment_searcher
+            .retrieve(segments, &point_ids, &with_payload, with_vector)
+            .await?;
+        // Retrieved points are returned in the order of the field values
+        let positions: HashMap<PointIdType, usize> = point_ids
+            .iter()
+            .enumerate()
+            .map(|(position, point_id)| (*point_id, position))
+            .collect();
+        points.sort_by_key(|point| positions.get(&point.id).cloned());
+
         Ok(ScrollResult {
             points,
             next_page_offset,
+            next_page_order_value,
         })
     }
 
+    /// Recommend points using positive and negative examples of the request
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - recommendation request
+    /// * `lookup_collection` - collection, resolved from `lookup_from` of the request.
+    ///    Point ids of the examples are looked up in the current collection, if not provided
     pub async fn recommend_by(
         &self,
         request: Arc<RecommendRequest>,
+        lookup_collection: Option<&Collection>,
         segment_searcher: &(dyn CollectionSearcher),
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<ScoredPoint>> {
-        let segments = self.segments();
+        let scoring = match request.strategy {
+            RecommendStrategy::AverageVector => None,
+            RecommendStrategy::Weighted => Some(ExampleScoring::Weighted),
+            RecommendStrategy::BestScore => Some(ExampleScoring::BestScore),
+        };
+        if let Some(scoring) = scoring {
+            return self
+                .recommend_by_examples(
+                    &request,
+                    scoring,
+                    lookup_collection,
+                    segment_searcher,
+                    search_runtime_handle,
+                )
+                .await;
+        }
+
+        let search_request = self
+            .recommend_search_request(&request, lookup_collection, segment_searcher)
+            .await?;
+
+        segment_searcher
+            .search(
+                self.segments(),
+                Arc::new(search_request),
+                search_runtime_handle,
+            )
+            .await
+    }
+
+    /// Recommend points by scoring candidates against each example separately.
+    /// Candidates are the closest points to each of the positive examples
+    async fn recommend_by_examples(
+        &self,
+        request: &RecommendRequest,
+        scoring: ExampleScoring,
+        lookup_collection: Option<&Collection>,
+        segment_searcher: &(dyn CollectionSearcher),
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let (vector_name, examples) = self
+            .recommend_examples(request, lookup_collection, segment_searcher)
+            .await?;
+        let metric = mertic_object(&self.vector_distance(&vector_name).await);
+
+        let filter = Self::recommend_filter(request, lookup_collection);
+        let searches = examples
+            .positive
+            .iter()
+            .map(|(vector, _weight)| SearchRequest {
+                vector: Self::recommend_vector(&vector_name, vector.clone()),
+                filter: filter.clone(),
+                with_payload: None,
+                params: request.params,
+                top: request.top,
+                offset: 0,
+                score_threshold: None,
+            })
+            .collect();
+        let candidates = segment_searcher
+            .search_batch(
+                self.segments(),
+                Arc::new(SearchRequestBatch { searches }),
+                search_runtime_handle,
+            )
+            .await?
+            .into_iter()
+            .flatten()
+            .unique_by(|point| point.id)
+            .collect_vec();
+
+        let candidate_ids = candidates.iter().map(|point| point.id).collect_vec();
+        let candidate_vectors = self
+            .retrieve_vectors(&candidate_ids, &vector_name, segment_searcher)
+            .await?;
+
+        let mut scored_points = candidates
+            .into_iter()
+            .filter_map(|mut point| {
+                let vector = candidate_vectors.get(&point.id)?;
+                point.score = score_by_examples(scoring, metric.as_ref(), &examples, vector);
+                Some(point)
+            })
+            .collect_vec();
+        scored_points.sort_unstable_by(|a, b| b.cmp(a));
+        scored_points.truncate(request.top);
+        Ok(scored_points)
+    }
+
+    /// Recommend points, grouped by the value of the payload field
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - grouped recommendation request
+    /// * `lookup_collection` - collection, resolved from `lookup_from` of the request.
+    ///    Point ids of the examples are looked up in the current collection, if not provided
+    pub async fn recommend_groups(
+        &self,
+        request: RecommendGroupsRequest,
+        lookup_collection: Option<&Collection>,
+        segment_searcher: &(dyn CollectionSearcher),
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<GroupsResult> {
+        let group_request = GroupRequest::new(
+            request.group_by,
+            request.group_size,
+            request.groups,
+            request.with_payload,
+        )?;
+        // Filter and limit are replaced on each search iteration
+        let recommend_request = RecommendRequest {
+            positive: request.positive,
+            negative: request.negative,
+            filter: None,
+            params: request.params,
+            top: group_request.search_limit(),
+            using: request.using,
+            strategy: request.strategy,
+            positive_weights: request.positive_weights,
+            negative_weights: request.negative_weights,
+            lookup_from: request.lookup_from,
+        };
+
+        group_by(
+            self.segments(),
+            segment_searcher,
+            request.filter,
+            group_request,
+            |filter, top| {
+                let iteration_request = RecommendRequest {
+                    filter: Some(filter),
+                    top,
+                    ..recommend_request.clone()
+                };
+                self.recommend_by(
+                    Arc::new(iteration_request),
+                    lookup_collection,
+                    segment_searcher,
+                    search_runtime_handle,
+                )
+            },
+        )
+        .await
+    }
+
+    /// Search for the closest points, grouped by the value of the payload field
+    pub async fn search_groups(
+        &self,
+        request: SearchGroupsRequest,
+        segment_searcher: &(dyn CollectionSearcher),
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<GroupsResult> {
+        let group_request = GroupRequest::new(
+            request.group_by,
+            request.group_size,
+            request.groups,
+            request.with_payload,
+        )?;
+        let vector = request.vector;
+        let params = request.params;
+        let score_threshold = request.score_threshold;
+
+        group_by(
+            self.segments(),
+            segment_searcher,
+            request.filter,
+            group_request,
+            |filter, top| {
+                let search_request = SearchRequest {
+                    vector: vector.clone(),
+                    filter: Some(filter),
+                    params,
+                    top,
+                    offset: 0,
+                    score_threshold,
+                    with_payload: None,
+                };
+                segment_searcher.search(
+                    self.segments(),
+                    Arc::new(search_request),
+                    search_runtime_handle,
+                )
+            },
+        )
+        .await
+    }
+
+    /// Distance of the vector with given name, which is known to exist
+    async fn vector_distance(&self, vector_name: &str) -> Distance {
+        self.config
+            .read()
+            .await
+            .params
+            .vectors
+            .get_params(vector_name)
+            .map(|params| params.distance)
+            .unwrap()
+    }
+
+    /// Retrieve vectors with given name of the points of the collection.
+    /// Points without such vector are skipped
+    async fn retrieve_vectors(
+        &self,
+        point_ids: &[PointIdType],
+        vector_name: &str,
+        segment_searcher: &(dyn CollectionSearcher),
+    ) -> CollectionResult<HashMap<PointIdType, Vec<VectorElementType>>> {
+        let records = segment_searcher
+            .retrieve(self.segments(), point_ids, &WithPayload::from(false), true)
+            .await?;
+        Ok(records
+            .into_iter()
+            .filter_map(|rec| {
+                let mut vectors = rec.vector?.into_all_vectors();
+                match vectors.remove(vector_name) {
+                    Some(Vector::Dense(vector)) => Some((rec.id, vector)),
+                    _ => None,
+                }
+            })
+            .collect())
+    }
+
+    /// Validate recommendation request and resolve vectors of all its examples.
+    /// Point ids are looked up in the `lookup_collection`, if provided
+    ///
+    /// # Result
+    ///
+    /// Name of the vector space to use and preprocessed vectors of the examples with their weights
+    async fn recommend_examples(
+        &self,
+        request: &RecommendRequest,
+        lookup_collection: Option<&Collection>,
+        segment_searcher: &(dyn CollectionSearcher),
+    ) -> CollectionResult<(String, RecommendExamples)> {
         if request.positive.is_empty() {
             return Err(CollectionError::BadRequest {
-                description: "At least one positive vector ID required".to_owned(),
+                description: "At least one positive example required".to_owned(),
             });
         }
+        let positive_weights = example_weights(
+            request.strategy,
+            request.positive_weights.as_deref(),
+            request.positive.len(),
+        )?;
+        let negative_weights = example_weights(
+            request.strategy,
+            request.negative_weights.as_deref(),
+            request.negative.len(),
+        )?;
+
+        let vector_name = request
+            .using
+            .clone()
+            .unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_owned());
 
+        let vector_params = {
+            let config = self.config.read().await;
+            match config.params.vectors.get_params(&vector_name) {
+                Some(params) => params.clone(),
+                None => {
+                    let is_sparse = config
+                        .params
+                        .sparse_vectors
+                        .as_ref()
+                        .map(|sparse_vectors| sparse_vectors.contains_key(&vector_name))
+                        .unwrap_or(false);
+                    let description = if is_sparse {
+                        format!(
+                            "Recommendation by sparse vector \"{}\" is not supported",
+                            vector_name
+                        )
+                    } else {
+                        format!("Vector with name \"{}\" does not exist", vector_name)
+                    };
+                    return Err(CollectionError::BadInput { description });
+                }
+            }
+        };
+
+        let lookup_vector_name = request
+            .lookup_from
+            .as_ref()
+            .and_then(|lookup_from| lookup_from.vector.clone())
+            .unwrap_or_else(|| vector_name.clone());
         let reference_vectors_ids = request
             .positive
             .iter()
             .chain(&request.negative)
-            .cloned()
+            .filter_map(|example| match example {
+                RecommendExample::PointId(point_id) => Some(*point_id),
+                RecommendExample::Vector(_) => None,
+            })
             .collect_vec();
-
-        let vectors = segment_searcher
-            .retrieve(
-                segments,
+        let vectors_map = lookup_collection
+            .unwrap_or(self)
+            .retrieve_vectors(
                 &reference_vectors_ids,
-                &WithPayload::from(true),
-                true,
+                &lookup_vector_name,
+                segment_searcher,
             )
             .await?;
-        let vectors_map: HashMap<PointIdType, Vec<VectorElementType>> = vectors
-            .into_iter()
-            .map(|rec| (rec.id, rec.vector.unwrap()))
-            .collect();
 
-        for &point_id in &reference_vectors_ids {
-            if !vectors_map.contains_key(&point_id) {
-                return Err(CollectionError::NotFound {
-                    missed_point_id: point_id,
-                });
-            }
-        }
+        let metric = mertic_object(&vector_params.distance);
+        let resolve = |examples: &[RecommendExample],
+                       weights: Vec<ScoreType>|
+         -> CollectionResult<Vec<(Vec<VectorElementType>, ScoreType)>> {
+            examples
+                .iter()
+                .zip(weights)
+                .map(|(example, weight)| {
+                    let vector = match example {
+                        RecommendExample::PointId(point_id) => {
+                            vectors_map.get(point_id).ok_or(CollectionError::NotFound {
+                                missed_point_id: *point_id,
+                            })?
+                        }
+                        RecommendExample::Vector(vector) => vector,
+                    };
+                    if vector.len() != vector_params.size {
+                        return Err(CollectionError::BadRequest {
+                            description: format!(
+                                "Example dimension {} does not match vector dimension {}",
+                                vector.len(),
+                                vector_params.size
+                            ),
+                        });
+                    }
+                    let vector = metric.preprocess(vector).unwrap_or_else(|| vector.clone());
+                    Ok((vector, weight))
+                })
+                .collect()
+        };
+        let examples = RecommendExamples {
+            positive: resolve(&request.positive, positive_weights)?,
+            negative: resolve(&request.negative, negative_weights)?,
+        };
 
-        let avg_positive = Collection::avg_vectors(
+        Ok((vector_name, examples))
+    }
+
+    /// Filter of the request, which also excludes the example points from the result.
+    /// Examples from the lookup collection are different points, so they are not excluded
+    fn recommend_filter(
+        request: &RecommendRequest,
+        lookup_collection: Option<&Collection>,
+    ) -> Option<Filter> {
+        let example_ids: HashSet<PointIdType> = if lookup_collection.is_none() {
             request
                 .positive
                 .iter()
-                .map(|vid| vectors_map.get(vid).unwrap()),
-        );
+                .chain(&request.negative)
+                .filter_map(|example| match example {
+                    RecommendExample::PointId(point_id) => Some(*point_id),
+                    RecommendExample::Vector(_) => None,
+                })
+                .collect()
+        } else {
+            HashSet::new()
+        };
+        if example_ids.is_empty() {
+            return request.filter.clone();
+        }
+
+        Some(Filter {
+            should: None,
+            min_should: None,
+            must: request
+                .filter
+                .clone()
+                .map(|filter| vec![Condition::Filter(filter)]),
+            must_not: Some(vec![Condition::HasId(HasIdCondition {
+                has_id: example_ids,
+            })]),
+        })
+    }
 
-        let search_vector = if request.negative.is_empty() {
+    fn recommend_vector(vector_name: &str, vector: Vec<VectorElementType>) -> NamedVectorStruct {
+        if vector_name == DEFAULT_VECTOR_NAME {
+            NamedVectorStruct::Default(vector)
+        } else {
+            NamedVectorStruct::Named(NamedVector {
+                name: vector_name.to_owned(),
+                vector,
+            })
+        }
+    }
+
+    /// Build a search request, which looks for the points similar to the positive examples
+    /// of the recommendation request and dissimilar to the negative ones
+    async fn recommend_search_request(
+        &self,
+        request: &RecommendRequest,
+        lookup_collection: Option<&Collection>,
+        segment_searcher: &(dyn CollectionSearcher),
+    ) -> CollectionResult<SearchRequest> {
+        let (vector_name, examples) = self
+            .recommend_examples(request, lookup_collection, segment_searcher)
+            .await?;
+
+        let avg_positive =
+            Collection::avg_vectors(examples.positive.iter().map(|(vector, _)| vector));
+
+        let search_vector = if examples.negative.is_empty() {
             avg_positive
         } else {
-            let avg_negative = Collection::avg_vectors(
-                request
-                    .negative
-                    .iter()
-                    .map(|vid| vectors_map.get(vid).unwrap()),
-            );
+            let avg_negative =
+                Collection::avg_vectors(examples.negative.iter().map(|(vector, _)| vector));
 
             avg_positive
                 .iter()
@@ -240,25 +748,44 @@ impl Collection {
         };
 
         let search_request = SearchRequest {
-            vector: search_vector,
-            filter: Some(Filter {
-                should: None,
-                must: request
-                    .filter
-                    .clone()
-                    .map(|filter| vec![Condition::Filter(filter)]),
-                must_not: Some(vec![Condition::HasId(HasIdCondition {
-                    has_id: reference_vectors_ids.iter().cloned().collect(),
-                })]),
-            }),
+            vector: Self::recommend_vector(&vector_name, search_vector),
+            filter: Self::recommend_filter(request, lookup_collection),
             with_payload: None,
             params: request.params,
             top: request.top,
+            offset: 0,
+            score_threshold: None,
         };
 
-        segment_searcher
-            .search(segments, Arc::new(search_request), search_runtime_handle)
-            .await
+        Ok(search_request)
+    }
+
+    /// Count points which satisfy the filter of the request.
+    /// Exact count reads ids of all matching points, estimation is taken from the payload indexes.
+    pub async fn count(&self, request: CountRequest) -> CollectionResult<CountResult> {
+        let segments = self.segments.read();
+        let filter = request.filter.as_ref();
+
+        let count = if request.exact {
+            // Same point may temporary be stored in multiple segments, so ids are deduplicated
+            segments
+                .iter()
+                .flat_map(|(_, segment)| {
+                    segment
+                        .get()
+                        .read()
+                        .read_filtered(PointIdType::MIN, usize::MAX, filter)
+                })
+                .collect::<HashSet<_>>()
+                .len()
+        } else {
+            segments
+                .iter()
+                .map(|(_, segment)| segment.get().read().estimate_points_count(filter).exp)
+                .sum()
+        };
+
+        Ok(CountResult { count })
     }
 
     /// Collect overview information about the collection
@@ -356,30 +883,136 @@ impl Collection {
         Ok(())
     }
 
+    /// Check, that declared types of the fields match types of the values, already stored in segments
+    fn check_payload_schema(&self, payload_schema: &PayloadSchema) -> CollectionResult<()> {
+        let segments = self.segments.read();
+        for (_idx, segment) in segments.iter() {
+            let stored_schema = segment.get().read().info().schema;
+            for (field_name, field_schema) in payload_schema {
+                let stored_type = stored_schema.get(field_name).map(|info| info.data_type);
+                if let Some(stored_type) = stored_type {
+                    if stored_type != field_schema.field_type {
+                        return Err(CollectionError::BadInput {
+                            description: format!(
+                                "Can't declare payload field \"{}\" as {:?}, it already has values of {:?} type",
+                                field_name, field_schema.field_type, stored_type
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces declared payload schema of the collection and creates indexes
+    /// of the newly declared indexed fields.
+    /// Declared types should match types of the values, which are already stored
+    pub async fn update_payload_schema(
+        &self,
+        payload_schema: Option<PayloadSchema>,
+    ) -> CollectionResult<()> {
+        {
+            let mut config = self.config.write().await;
+            if let Some(payload_schema) = &payload_schema {
+                self.check_payload_schema(payload_schema)?;
+            }
+            config.params.payload_schema = payload_schema;
+            config.save(&self.path)?;
+        }
+        self.create_payload_schema_indexes().await
+    }
+
+    /// Creates indexes of the fields, declared as indexed in the payload schema
+    pub async fn create_payload_schema_indexes(&self) -> CollectionResult<()> {
+        let payload_schema = self.config.read().await.params.payload_schema.clone();
+        let indexed_fields = payload_schema
+            .into_iter()
+            .flatten()
+            .filter(|(_, field_schema)| field_schema.index);
+        for (field_name, field_schema) in indexed_fields {
+            let create_index = match field_schema.index_params {
+                None => CreateFieldIndex::FieldName(field_name),
+                Some(field_index_params) => CreateFieldIndex::WithParams {
+                    field_name,
+                    field_index_params,
+                },
+            };
+            let operation = CollectionUpdateOperations::FieldIndexOperation(
+                FieldIndexOperations::CreateIndex(create_index),
+            );
+            self.update(operation, true).await?;
+        }
+        Ok(())
+    }
+
+    /// Creates snapshot of the collection in a single archive file.
+    ///
+    /// Update and optimization workers are stopped while the snapshot is being written,
+    /// so segments and WAL are flushed and stay consistent with each other.
+    /// Update operations, received during the snapshot, are applied right after it.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot_path` - path of the archive to create
+    /// * `aliases` - aliases of the collection, stored in the snapshot
+    pub async fn create_snapshot(
+        &self,
+        snapshot_path: &Path,
+        aliases: &[String],
+    ) -> CollectionResult<()> {
+        let mut update_handler = self.update_handler.lock().await;
+        self.stop().await?;
+        // Workers are restarted even if they were not stopped cleanly
+        let stop_res = update_handler.wait_workers_stops().await;
+
+        let snapshot_res = match stop_res {
+            Ok(()) => {
+                update_handler.wait_optimizations_finish().await;
+                // Prevent new operations from being written into WAL while it is archived
+                let _wal_lock = self.wal.lock().await;
+                self.segments
+                    .read()
+                    .flush_all()
+                    .map_err(CollectionError::from)
+                    .and_then(|_| pack_snapshot(&self.path, snapshot_path, aliases))
+            }
+            Err(err) => Err(err),
+        };
+
+        update_handler.run_workers();
+        self.update_sender.send(UpdateSignal::Nop).await?;
+
+        snapshot_res
+    }
+
     pub async fn wait_update_workers_stop(&self) -> CollectionResult<()> {
         let mut update_handler = self.update_handler.lock().await;
         update_handler.wait_workers_stops().await
     }
 
     /// Loads latest collection operations from WAL
-    pub async fn load_from_wal(&self) {
+    pub async fn load_from_wal(&self) -> CollectionResult<()> {
         let wal = self.wal.lock().await;
         let bar = ProgressBar::new(wal.len());
         bar.set_message("Recovering collection");
         let segments = self.segments();
         // ToDo: Start from minimal applied version
         for (op_num, update) in wal.read_all() {
-            // Panic only in case of internal error. If wrong formatting - skip
+            // Fail only in case of internal error. If wrong formatting - skip
             if let Err(CollectionError::ServiceError { error }) =
                 CollectionUpdater::update(segments, op_num, update)
             {
-                panic!("Can't apply WAL operation: {}", error)
+                return Err(CollectionError::ServiceError {
+                    error: format!("Can't apply WAL operation: {}", error),
+                });
             }
             bar.inc(1);
         }
 
-        self.segments.read().flush_all().unwrap();
+        self.segments.read().flush_all()?;
         bar.finish();
+        Ok(())
     }
 }
 
@@ -8,8 +8,8 @@ use parking_lot::RwLock;
 use tokio::runtime;
 use tokio::sync::Mutex;
 
-use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
-use segment::types::HnswConfig;
+use segment::segment_constructor::build_segment;
+use segment::types::{HnswConfig, Indexes, SegmentConfig, StorageType};
 
 use crate::collection::Collection;
 use crate::collection_builder::optimizers_builder::build_optimizers;
@@ -87,12 +87,17 @@ pub fn build_collection(
 
     let mut segment_holder = SegmentHolder::default();
 
+    let segment_config = SegmentConfig {
+        vector_data: collection_params.get_all_vector_params(hnsw_config)?,
+        sparse_vector_data: collection_params.get_all_sparse_vector_params(),
+        index: Indexes::Plain {},
+        payload_index: None,
+        storage_type: StorageType::InMemory,
+        quantization_config: None,
+    };
+
     for _sid in 0..optimizers_config.max_segment_number {
-        let segment = build_simple_segment(
-            &segments_path,
-            collection_params.vector_size,
-            collection_params.distance,
-        )?;
+        let segment = build_segment(&segments_path, &segment_config)?;
         segment_holder.add(segment);
     }
 
@@ -9,55 +9,61 @@ use crate::collection_builder::construct_collection;
 use crate::collection_builder::optimizers_builder::build_optimizers;
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
 use crate::config::CollectionConfig;
+use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::CollectionUpdateOperations;
 use crate::wal::SerdeWal;
 
 pub fn load_collection(collection_path: &Path) -> Collection {
-    let wal_path = collection_path.join("wal");
-    let segments_path = collection_path.join("segments");
-    let mut segment_holder = SegmentHolder::default();
-
-    let collection_config = CollectionConfig::load(collection_path).unwrap_or_else(|err| {
+    try_load_collection(collection_path).unwrap_or_else(|err| {
         panic!(
-            "Can't read collection config due to {}\nat {}",
+            "Can't load collection due to {}\nat {}",
             err,
             collection_path.to_str().unwrap()
         )
-    });
+    })
+}
+
+/// Loads collection, which files might be broken, e.g. unpacked from the user-provided snapshot.
+/// Errors are returned instead of panics, so the caller could discard the collection
+pub fn try_load_collection(collection_path: &Path) -> CollectionResult<Collection> {
+    let wal_path = collection_path.join("wal");
+    let segments_path = collection_path.join("segments");
+    let mut segment_holder = SegmentHolder::default();
+
+    let collection_config =
+        CollectionConfig::load(collection_path).map_err(|err| CollectionError::ServiceError {
+            error: format!("Can't read collection config due to {}", err),
+        })?;
 
     let wal: SerdeWal<CollectionUpdateOperations> = SerdeWal::new(
         wal_path.to_str().unwrap(),
         &(&collection_config.wal_config).into(),
-    )
-    .expect("Can't read WAL");
+    )?;
 
-    let segment_dirs = read_dir(&segments_path).unwrap_or_else(|err| {
-        panic!(
-            "Can't read segments directory due to {}\nat {}",
-            err,
-            segments_path.to_str().unwrap()
-        )
-    });
+    let segment_dirs = read_dir(&segments_path).map_err(|err| CollectionError::ServiceError {
+        error: format!("Can't read segments directory due to {}", err),
+    })?;
 
     for entry in segment_dirs {
-        let segments_path = entry.unwrap().path();
+        let segments_path = entry?.path();
         if segments_path.ends_with("deleted") {
-            remove_dir_all(&segments_path).unwrap_or_else(|_| {
-                panic!(
-                    "Can't remove marked-for-remove segment {}",
-                    segments_path.to_str().unwrap()
-                )
-            });
+            remove_dir_all(&segments_path).map_err(|err| CollectionError::ServiceError {
+                error: format!(
+                    "Can't remove marked-for-remove segment {}, error: {}",
+                    segments_path.to_str().unwrap(),
+                    err
+                ),
+            })?;
             continue;
         }
-        let segment = match load_segment(&segments_path) {
-            Ok(x) => x,
-            Err(err) => panic!(
-                "Can't load segments from {}, error: {}",
-                segments_path.to_str().unwrap(),
-                err
-            ),
-        };
+        let segment =
+            load_segment(&segments_path).map_err(|err| CollectionError::ServiceError {
+                error: format!(
+                    "Can't load segments from {}, error: {}",
+                    segments_path.to_str().unwrap(),
+                    err
+                ),
+            })?;
         segment_holder.add(segment);
     }
 
@@ -76,7 +82,7 @@ pub fn load_collection(collection_path: &Path) -> Collection {
         collection_path,
     );
 
-    block_on(collection.load_from_wal());
+    block_on(collection.load_from_wal())?;
 
-    collection
+    Ok(collection)
 }
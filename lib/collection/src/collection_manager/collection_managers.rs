@@ -6,7 +6,7 @@ use tokio::runtime::Handle;
 use segment::types::{PointIdType, ScoredPoint, WithPayload};
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
-use crate::operations::types::{CollectionResult, Record, SearchRequest};
+use crate::operations::types::{CollectionResult, Record, SearchRequest, SearchRequestBatch};
 
 /// Interface for implementing search and retrieve functions
 #[async_trait::async_trait]
@@ -19,6 +19,14 @@ pub trait CollectionSearcher {
         runtime_handle: &Handle,
     ) -> CollectionResult<Vec<ScoredPoint>>;
 
+    /// Perform multiple searches at once, results are returned in the order of requests
+    async fn search_batch(
+        &self,
+        segments: &RwLock<SegmentHolder>,
+        request: Arc<SearchRequestBatch>,
+        runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>>;
+
     async fn retrieve(
         &self,
         segments: &RwLock<SegmentHolder>,
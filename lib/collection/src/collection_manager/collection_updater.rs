@@ -74,7 +74,10 @@ impl CollectionUpdater {
 mod tests {
     use tempdir::TempDir;
 
-    use segment::types::{PayloadInterface, PayloadKeyType, PayloadVariant, WithPayload};
+    use segment::types::{
+        Condition, FieldCondition, Filter, Match, PayloadInterface, PayloadKeyType, PayloadVariant,
+        PointIdType, WithPayload,
+    };
 
     use crate::collection_manager::collection_managers::CollectionSearcher;
     use crate::collection_manager::fixtures::build_test_holder;
@@ -93,15 +96,20 @@ mod tests {
         let segments = build_test_holder(dir.path());
         let searcher = SimpleCollectionSearcher::new();
 
-        let points = vec![1, 500];
+        let points: Vec<PointIdType> = vec![1.into(), 500.into()];
 
-        let vectors = vec![vec![2., 2., 2., 2.], vec![2., 0., 2., 0.]];
+        let vectors = vec![vec![2., 2., 2., 2.].into(), vec![2., 0., 2., 0.].into()];
 
         let res = upsert_points(&segments, 100, &points, &vectors, &None);
         assert!(matches!(res, Ok(1)));
 
         let records = searcher
-            .retrieve(&segments, &[1, 2, 500], &WithPayload::from(true), true)
+            .retrieve(
+                &segments,
+                &[1.into(), 2.into(), 500.into()],
+                &WithPayload::from(true),
+                true,
+            )
             .await
             .unwrap();
 
@@ -110,29 +118,36 @@ mod tests {
         for record in records {
             let v = record.vector.unwrap();
 
-            if record.id == 1 {
-                assert_eq!(&v, &vec![2., 2., 2., 2.])
+            if record.id == 1.into() {
+                assert_eq!(v, vec![2., 2., 2., 2.].into())
             }
-            if record.id == 500 {
-                assert_eq!(&v, &vec![2., 0., 2., 0.])
+            if record.id == 500.into() {
+                assert_eq!(v, vec![2., 0., 2., 0.].into())
             }
         }
 
         process_point_operation(
             &segments,
             101,
-            PointOperations::DeletePoints { ids: vec![500] },
+            PointOperations::DeletePoints {
+                ids: vec![500.into()],
+            },
         )
         .unwrap();
 
         let records = searcher
-            .retrieve(&segments, &[1, 2, 500], &WithPayload::from(true), true)
+            .retrieve(
+                &segments,
+                &[1.into(), 2.into(), 500.into()],
+                &WithPayload::from(true),
+                true,
+            )
             .await
             .unwrap();
 
         for record in records {
             let _v = record.vector.unwrap();
-            assert_ne!(record.id, 500);
+            assert_ne!(record.id, 500.into());
         }
     }
 
@@ -149,7 +164,7 @@ mod tests {
             PayloadInterface::KeywordShortcut(PayloadVariant::Value("red".to_string())),
         );
 
-        let points = vec![1, 2, 3];
+        let points: Vec<PointIdType> = vec![1.into(), 2.into(), 3.into()];
 
         process_payload_operation(
             &segments,
@@ -183,14 +198,14 @@ mod tests {
             &segments,
             101,
             &PayloadOps::DeletePayload {
-                points: vec![3],
+                points: vec![3.into()],
                 keys: vec!["color".to_string(), "empty".to_string()],
             },
         )
         .unwrap();
 
         let res = searcher
-            .retrieve(&segments, &[3], &WithPayload::from(true), false)
+            .retrieve(&segments, &[3.into()], &WithPayload::from(true), false)
             .await
             .unwrap();
         assert_eq!(res.len(), 1);
@@ -199,7 +214,7 @@ mod tests {
         // Test clear payload
 
         let res = searcher
-            .retrieve(&segments, &[2], &WithPayload::from(true), false)
+            .retrieve(&segments, &[2.into()], &WithPayload::from(true), false)
             .await
             .unwrap();
         assert_eq!(res.len(), 1);
@@ -208,14 +223,95 @@ mod tests {
         process_payload_operation(
             &segments,
             102,
-            &PayloadOps::ClearPayload { points: vec![2] },
+            &PayloadOps::ClearPayload {
+                points: vec![2.into()],
+            },
         )
         .unwrap();
         let res = searcher
-            .retrieve(&segments, &[2], &WithPayload::from(true), false)
+            .retrieve(&segments, &[2.into()], &WithPayload::from(true), false)
             .await
             .unwrap();
         assert_eq!(res.len(), 1);
         assert!(!res[0].payload.as_ref().unwrap().contains_key("color"));
     }
+
+    fn keyword_filter(key: &str, value: &str) -> Filter {
+        Filter::new_must(Condition::Field(FieldCondition {
+            key: key.to_string(),
+            r#match: Some(Match {
+                keyword: Some(value.to_string()),
+                integer: None,
+                boolean: None,
+                text: None,
+                any: None,
+                except: None,
+            }),
+            range: None,
+            geo_bounding_box: None,
+            geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_ops_by_filter() {
+        let dir = TempDir::new("segment_dir").unwrap();
+        let segments = build_test_holder(dir.path());
+        let searcher = SimpleCollectionSearcher::new();
+
+        let mut payload: HashMap<PayloadKeyType, PayloadInterface> = Default::default();
+        payload.insert(
+            "size".to_string(),
+            PayloadInterface::KeywordShortcut(PayloadVariant::Value("big".to_string())),
+        );
+
+        process_payload_operation(
+            &segments,
+            100,
+            &PayloadOps::SetPayloadByFilter {
+                payload,
+                filter: keyword_filter("color", "blue"),
+            },
+        )
+        .unwrap();
+
+        let res = searcher
+            .retrieve(
+                &segments,
+                &[1.into(), 3.into()],
+                &WithPayload::from(true),
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.len(), 2);
+        for record in res {
+            let has_size = record.payload.as_ref().unwrap().contains_key("size");
+            // Point 1 is "red", point 3 is "blue"
+            assert_eq!(has_size, record.id == 3.into());
+        }
+
+        process_point_operation(
+            &segments,
+            101,
+            PointOperations::DeletePointsByFilter {
+                filter: keyword_filter("size", "big"),
+            },
+        )
+        .unwrap();
+
+        let res = searcher
+            .retrieve(
+                &segments,
+                &[1.into(), 3.into()],
+                &WithPayload::from(false),
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].id, 1.into());
+    }
 }
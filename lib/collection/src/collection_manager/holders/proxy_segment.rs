@@ -1,17 +1,20 @@
 use crate::collection_manager::holders::segment_holder::LockedSegment;
 use parking_lot::RwLock;
 use segment::entry::entry_point::{OperationResult, SegmentEntry, SegmentFailedState};
+use segment::index::CardinalityEstimation;
 use segment::types::{
-    Condition, Filter, PayloadKeyType, PayloadKeyTypeRef, PayloadType, PointIdType, ScoredPoint,
-    SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, TheMap,
+    Condition, Filter, NamedVectors, OrderBy, PayloadIndexParams, PayloadKeyType,
+    PayloadKeyTypeRef, PayloadType, PointIdType, RangeValue, ScoreType, ScoredPoint, SearchParams,
+    SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, SparseVector, TheMap,
     VectorElementType, WithPayload,
 };
 use std::cmp::max;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 type LockedRmSet = Arc<RwLock<HashSet<PointIdType>>>;
 type LockedFieldsSet = Arc<RwLock<HashSet<PayloadKeyType>>>;
+pub type LockedFieldsMap = Arc<RwLock<HashMap<PayloadKeyType, Option<PayloadIndexParams>>>>;
 
 /// This object is a wrapper around read-only segment.
 /// It could be used to provide all read and write operations while wrapped segment is being optimized (i.e. not available for writing)
@@ -22,7 +25,8 @@ pub struct ProxySegment {
     /// Points which should not longer used from wrapped_segment
     deleted_points: LockedRmSet,
     deleted_indexes: LockedFieldsSet,
-    created_indexes: LockedFieldsSet,
+    /// Created indexes with their explicit parameters, if any
+    created_indexes: LockedFieldsMap,
 }
 
 impl ProxySegment {
@@ -30,8 +34,8 @@ impl ProxySegment {
         segment: LockedSegment,
         write_segment: LockedSegment,
         deleted_points: LockedRmSet,
-        created_indexes: LockedFieldsSet,
         deleted_indexes: LockedFieldsSet,
+        created_indexes: LockedFieldsMap,
     ) -> Self {
         ProxySegment {
             write_segment,
@@ -43,10 +47,10 @@ impl ProxySegment {
     }
 
     fn move_point(&self, op_num: SeqNumberType, point_id: PointIdType) -> OperationResult<bool> {
-        let (vector, payload) = {
+        let (vectors, payload) = {
             let segment_arc = self.wrapped_segment.get();
             let segment = segment_arc.read();
-            (segment.vector(point_id)?, segment.payload(point_id)?)
+            (segment.all_vectors(point_id)?, segment.payload(point_id)?)
         };
 
         let mut deleted_points = self.deleted_points.write();
@@ -55,7 +59,7 @@ impl ProxySegment {
         let segment_arc = self.write_segment.get();
         let mut write_segment = segment_arc.write();
 
-        write_segment.upsert_point(op_num, point_id, &vector)?;
+        write_segment.upsert_vectors(op_num, point_id, &vectors)?;
         write_segment.set_full_payload(op_num, point_id, payload)?;
 
         Ok(true)
@@ -116,11 +120,13 @@ impl SegmentEntry for ProxySegment {
 
     fn search(
         &self,
+        vector_name: &str,
         vector: &[VectorElementType],
         with_payload: &WithPayload,
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        score_threshold: Option<ScoreType>,
     ) -> OperationResult<Vec<ScoredPoint>> {
         let deleted_points = self.deleted_points.read();
 
@@ -135,40 +141,97 @@ impl SegmentEntry for ProxySegment {
             let wrapped_filter = self.add_deleted_points_condition_to_filter(filter);
 
             self.wrapped_segment.get().read().search(
+                vector_name,
                 vector,
                 with_payload,
                 Some(&wrapped_filter),
                 top,
                 params,
+                score_threshold,
             )?
         } else {
-            self.wrapped_segment
-                .get()
-                .read()
-                .search(vector, with_payload, filter, top, params)?
+            self.wrapped_segment.get().read().search(
+                vector_name,
+                vector,
+                with_payload,
+                filter,
+                top,
+                params,
+                score_threshold,
+            )?
         };
 
-        let mut write_result =
-            self.write_segment
-                .get()
-                .read()
-                .search(vector, with_payload, filter, top, params)?;
+        let mut write_result = self.write_segment.get().read().search(
+            vector_name,
+            vector,
+            with_payload,
+            filter,
+            top,
+            params,
+            score_threshold,
+        )?;
 
         wrapped_result.append(&mut write_result);
         Ok(wrapped_result)
     }
 
-    fn upsert_point(
+    fn search_sparse(
+        &self,
+        vector_name: &str,
+        vector: &SparseVector,
+        with_payload: &WithPayload,
+        filter: Option<&Filter>,
+        top: usize,
+        score_threshold: Option<ScoreType>,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let deleted_points = self.deleted_points.read();
+
+        // Same as for dense search: exclude points, deleted after temporary segment creation
+        let mut wrapped_result = if !deleted_points.is_empty() {
+            let wrapped_filter = self.add_deleted_points_condition_to_filter(filter);
+            self.wrapped_segment.get().read().search_sparse(
+                vector_name,
+                vector,
+                with_payload,
+                Some(&wrapped_filter),
+                top,
+                score_threshold,
+            )?
+        } else {
+            self.wrapped_segment.get().read().search_sparse(
+                vector_name,
+                vector,
+                with_payload,
+                filter,
+                top,
+                score_threshold,
+            )?
+        };
+
+        let mut write_result = self.write_segment.get().read().search_sparse(
+            vector_name,
+            vector,
+            with_payload,
+            filter,
+            top,
+            score_threshold,
+        )?;
+
+        wrapped_result.append(&mut write_result);
+        Ok(wrapped_result)
+    }
+
+    fn upsert_vectors(
         &mut self,
         op_num: SeqNumberType,
         point_id: PointIdType,
-        vector: &[VectorElementType],
+        vectors: &NamedVectors,
     ) -> OperationResult<bool> {
         self.move_if_exists(op_num, point_id)?;
         self.write_segment
             .get()
             .write()
-            .upsert_point(op_num, point_id, vector)
+            .upsert_vectors(op_num, point_id, vectors)
     }
 
     fn delete_point(
@@ -205,8 +268,8 @@ impl SegmentEntry for ProxySegment {
 
     fn set_full_payload_with_json(
         &mut self,
-        op_num: u64,
-        point_id: u64,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
         full_payload: &str,
     ) -> OperationResult<bool> {
         self.move_if_exists(op_num, point_id)?;
@@ -255,18 +318,43 @@ impl SegmentEntry for ProxySegment {
             .clear_payload(op_num, point_id)
     }
 
-    fn vector(&self, point_id: PointIdType) -> OperationResult<Vec<VectorElementType>> {
+    fn vector(
+        &self,
+        vector_name: &str,
+        point_id: PointIdType,
+    ) -> OperationResult<Vec<VectorElementType>> {
         return if self.deleted_points.read().contains(&point_id) {
-            self.write_segment.get().read().vector(point_id)
+            self.write_segment
+                .get()
+                .read()
+                .vector(vector_name, point_id)
         } else {
             {
                 let write_segment = self.write_segment.get();
                 let segment_guard = write_segment.read();
                 if segment_guard.has_point(point_id) {
-                    return segment_guard.vector(point_id);
+                    return segment_guard.vector(vector_name, point_id);
                 }
             }
-            self.wrapped_segment.get().read().vector(point_id)
+            self.wrapped_segment
+                .get()
+                .read()
+                .vector(vector_name, point_id)
+        };
+    }
+
+    fn all_vectors(&self, point_id: PointIdType) -> OperationResult<NamedVectors> {
+        return if self.deleted_points.read().contains(&point_id) {
+            self.write_segment.get().read().all_vectors(point_id)
+        } else {
+            {
+                let write_segment = self.write_segment.get();
+                let segment_guard = write_segment.read();
+                if segment_guard.has_point(point_id) {
+                    return segment_guard.all_vectors(point_id);
+                }
+            }
+            self.wrapped_segment.get().read().all_vectors(point_id)
         };
     }
 
@@ -324,6 +412,65 @@ impl SegmentEntry for ProxySegment {
         read_points
     }
 
+    fn read_ordered_filtered<'a>(
+        &'a self,
+        offset: PointIdType,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        order_by: &'a OrderBy,
+    ) -> Vec<(RangeValue, PointIdType)> {
+        let deleted_points = self.deleted_points.read();
+        let mut read_points = if deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .read_ordered_filtered(offset, limit, filter, order_by)
+        } else {
+            let wrapped_filter = self.add_deleted_points_condition_to_filter(filter);
+            self.wrapped_segment.get().read().read_ordered_filtered(
+                offset,
+                limit,
+                Some(&wrapped_filter),
+                order_by,
+            )
+        };
+        let mut write_segment_points = self
+            .write_segment
+            .get()
+            .read()
+            .read_ordered_filtered(offset, limit, filter, order_by);
+        read_points.append(&mut write_segment_points);
+        read_points.sort_by(|(value, point_id), (other_value, other_id)| {
+            order_by
+                .cmp_values(value, other_value)
+                .then(point_id.cmp(other_id))
+        });
+        read_points.truncate(limit);
+        read_points
+    }
+
+    fn estimate_points_count(&self, filter: Option<&Filter>) -> CardinalityEstimation {
+        let deleted_points_count = self.deleted_points.read().len();
+        let wrapped_estimation = self
+            .wrapped_segment
+            .get()
+            .read()
+            .estimate_points_count(filter);
+        let write_estimation = self
+            .write_segment
+            .get()
+            .read()
+            .estimate_points_count(filter);
+
+        // Points, removed from the wrapped segment, may or may not satisfy the filter
+        CardinalityEstimation {
+            primary_clauses: vec![],
+            min: wrapped_estimation.min.saturating_sub(deleted_points_count) + write_estimation.min,
+            exp: wrapped_estimation.exp.saturating_sub(deleted_points_count) + write_estimation.exp,
+            max: wrapped_estimation.max + write_estimation.max,
+        }
+    }
+
     fn has_point(&self, point_id: PointIdType) -> bool {
         return if self.deleted_points.read().contains(&point_id) {
             self.write_segment.get().read().has_point(point_id)
@@ -361,6 +508,7 @@ impl SegmentEntry for ProxySegment {
             disk_usage_bytes: wrapped_info.disk_usage_bytes + write_info.disk_usage_bytes,
             is_appendable: false,
             schema: wrapped_info.schema,
+            vector_compression: wrapped_info.vector_compression,
         }
     }
 
@@ -393,23 +541,30 @@ impl SegmentEntry for ProxySegment {
             .delete_field_index(op_num, key)
     }
 
-    fn create_field_index(&mut self, op_num: u64, key: PayloadKeyTypeRef) -> OperationResult<bool> {
+    fn create_field_index(
+        &mut self,
+        op_num: u64,
+        key: PayloadKeyTypeRef,
+        params: Option<&PayloadIndexParams>,
+    ) -> OperationResult<bool> {
         if self.version() > op_num {
             return Ok(false);
         }
-        self.created_indexes.write().insert(key.into());
+        self.created_indexes
+            .write()
+            .insert(key.into(), params.cloned());
         self.deleted_indexes.write().remove(key);
         self.write_segment
             .get()
             .write()
-            .create_field_index(op_num, key)
+            .create_field_index(op_num, key, params)
     }
 
     fn get_indexed_fields(&self) -> Vec<PayloadKeyType> {
         let indexed_fields = self.wrapped_segment.get().read().get_indexed_fields();
         indexed_fields
             .into_iter()
-            .chain(self.created_indexes.read().iter().cloned())
+            .chain(self.created_indexes.read().keys().cloned())
             .filter(|x| !self.deleted_indexes.read().contains(x))
             .collect()
     }
@@ -423,7 +578,7 @@ impl SegmentEntry for ProxySegment {
 mod tests {
     use super::*;
     use crate::collection_manager::fixtures::{build_segment_1, empty_segment};
-    use segment::types::{FieldCondition, Match};
+    use segment::types::{FieldCondition, Match, DEFAULT_VECTOR_NAME};
     use tempdir::TempDir;
 
     #[test]
@@ -434,7 +589,7 @@ mod tests {
         let deleted_points = Arc::new(RwLock::new(HashSet::<PointIdType>::new()));
 
         let deleted_indexes = Arc::new(RwLock::new(HashSet::<PayloadKeyType>::new()));
-        let created_indexes = Arc::new(RwLock::new(HashSet::<PayloadKeyType>::new()));
+        let created_indexes: LockedFieldsMap = Default::default();
 
         let mut proxy_segment = ProxySegment::new(
             original_segment,
@@ -445,14 +600,22 @@ mod tests {
         );
 
         let vec4 = vec![1.1, 1.0, 0.0, 1.0];
-        proxy_segment.upsert_point(100, 4, &vec4).unwrap();
+        proxy_segment.upsert_point(100, 4.into(), &vec4).unwrap();
         let vec6 = vec![1.0, 1.0, 0.5, 1.0];
-        proxy_segment.upsert_point(101, 6, &vec6).unwrap();
-        proxy_segment.delete_point(102, 1).unwrap();
+        proxy_segment.upsert_point(101, 6.into(), &vec6).unwrap();
+        proxy_segment.delete_point(102, 1.into()).unwrap();
 
         let query_vector = vec![1.0, 1.0, 1.0, 1.0];
         let search_result = proxy_segment
-            .search(&query_vector, &WithPayload::default(), None, 10, None)
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &query_vector,
+                &WithPayload::default(),
+                None,
+                10,
+                None,
+                None,
+            )
             .unwrap();
 
         eprintln!("search_result = {:#?}", search_result);
@@ -465,16 +628,18 @@ mod tests {
             seen_points.insert(res.id);
         }
 
-        assert!(seen_points.contains(&4));
-        assert!(seen_points.contains(&6));
-        assert!(!seen_points.contains(&1));
+        assert!(seen_points.contains(&PointIdType::from(4)));
+        assert!(seen_points.contains(&PointIdType::from(6)));
+        assert!(!seen_points.contains(&PointIdType::from(1)));
 
-        assert!(!proxy_segment.write_segment.get().read().has_point(2));
+        assert!(!proxy_segment.write_segment.get().read().has_point(2.into()));
 
         let payload_key = "color".to_owned();
-        proxy_segment.delete_payload(103, 2, &payload_key).unwrap();
+        proxy_segment
+            .delete_payload(103, 2.into(), &payload_key)
+            .unwrap();
 
-        assert!(proxy_segment.write_segment.get().read().has_point(2))
+        assert!(proxy_segment.write_segment.get().read().has_point(2.into()))
     }
 
     #[test]
@@ -487,25 +652,34 @@ mod tests {
             r#match: Some(Match {
                 keyword: Some("blue".to_string()),
                 integer: None,
+                boolean: None,
+                text: None,
+                any: None,
+                except: None,
             }),
             range: None,
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
         }));
 
-        let original_points = original_segment.get().read().read_filtered(0, 100, None);
+        let original_points = original_segment
+            .get()
+            .read()
+            .read_filtered(0.into(), 100, None);
 
         let original_points_filtered =
             original_segment
                 .get()
                 .read()
-                .read_filtered(0, 100, Some(&filter));
+                .read_filtered(0.into(), 100, Some(&filter));
 
         let write_segment = LockedSegment::new(empty_segment(dir.path()));
         let deleted_points = Arc::new(RwLock::new(HashSet::<PointIdType>::new()));
 
         let deleted_indexes = Arc::new(RwLock::new(HashSet::<PayloadKeyType>::new()));
-        let created_indexes = Arc::new(RwLock::new(HashSet::<PayloadKeyType>::new()));
+        let created_indexes: LockedFieldsMap = Default::default();
 
         let mut proxy_segment = ProxySegment::new(
             original_segment,
@@ -515,10 +689,10 @@ mod tests {
             created_indexes.clone(),
         );
 
-        proxy_segment.delete_point(100, 2).unwrap();
+        proxy_segment.delete_point(100, 2.into()).unwrap();
 
-        let proxy_res = proxy_segment.read_filtered(0, 100, None);
-        let proxy_res_filtered = proxy_segment.read_filtered(0, 100, Some(&filter));
+        let proxy_res = proxy_segment.read_filtered(0.into(), 100, None);
+        let proxy_res_filtered = proxy_segment.read_filtered(0.into(), 100, Some(&filter));
 
         assert_eq!(original_points_filtered.len() - 1, proxy_res_filtered.len());
         assert_eq!(original_points.len() - 1, proxy_res.len());
@@ -305,10 +305,10 @@ impl<'s> SegmentHolder {
                 self.aloha_random_write(
                     &appendable_segments,
                     |_appendable_idx, appendable_write_segment| {
-                        let vector = write_segment.vector(point_id)?;
+                        let vectors = write_segment.all_vectors(point_id)?;
                         let payload = write_segment.payload(point_id)?;
 
-                        appendable_write_segment.upsert_point(op_num, point_id, &vector)?;
+                        appendable_write_segment.upsert_vectors(op_num, point_id, &vectors)?;
                         appendable_write_segment.set_full_payload(op_num, point_id, payload)?;
 
                         write_segment.delete_point(op_num, point_id)?;
@@ -449,11 +449,15 @@ mod tests {
 
         let mut processed_points: Vec<PointIdType> = vec![];
         holder
-            .apply_points_to_appendable(100, &[1, 2, 11, 12], |point_id, segment| {
-                processed_points.push(point_id);
-                assert!(segment.has_point(point_id));
-                Ok(true)
-            })
+            .apply_points_to_appendable(
+                100,
+                &[1.into(), 2.into(), 11.into(), 12.into()],
+                |point_id, segment| {
+                    processed_points.push(point_id);
+                    assert!(segment.has_point(point_id));
+                    Ok(true)
+                },
+            )
             .unwrap();
 
         assert_eq!(4, processed_points.len());
@@ -461,11 +465,11 @@ mod tests {
         let locked_segment_1 = holder.get(sid1).unwrap().get();
         let read_segment_1 = locked_segment_1.read();
 
-        assert!(read_segment_1.has_point(1));
-        assert!(read_segment_1.has_point(2));
+        assert!(read_segment_1.has_point(1.into()));
+        assert!(read_segment_1.has_point(2.into()));
 
         // Points moved on apply
-        assert!(read_segment_1.has_point(11));
-        assert!(read_segment_1.has_point(12));
+        assert!(read_segment_1.has_point(11.into()));
+        assert!(read_segment_1.has_point(12.into()));
     }
 }
@@ -65,7 +65,7 @@ impl IndexingOptimizer {
 
                 let is_memmaped = match segment_config.storage_type {
                     StorageType::InMemory => false,
-                    StorageType::Mmap => true,
+                    StorageType::Mmap | StorageType::ProductQuantized(_) => true,
                 };
 
                 let big_for_mmap = vector_count >= self.thresholds_config.memmap_threshold;
@@ -127,10 +127,10 @@ mod tests {
     use parking_lot::lock_api::RwLock;
     use tempdir::TempDir;
 
-    use segment::types::StorageType;
+    use segment::types::{StorageType, DEFAULT_VECTOR_NAME};
 
     use crate::operations::point_ops::{PointInsertOperations, PointOperations};
-    use crate::operations::FieldIndexOperations;
+    use crate::operations::{CreateFieldIndex, FieldIndexOperations};
 
     use super::*;
     use crate::collection_manager::fixtures::random_segment;
@@ -138,6 +138,7 @@ mod tests {
     use crate::collection_manager::segments_updater::{
         process_field_index_operation, process_point_operation,
     };
+    use crate::config::{VectorParams, VectorsConfig};
 
     fn init() {
         let _ = env_logger::builder().is_test(true).try_init();
@@ -176,8 +177,15 @@ mod tests {
             segments_dir.path().to_owned(),
             segments_temp_dir.path().to_owned(),
             CollectionParams {
-                vector_size: segment_config.vector_size,
-                distance: segment_config.distance,
+                vectors: VectorsConfig::Single(VectorParams {
+                    size: segment_config.vector_data[DEFAULT_VECTOR_NAME].size,
+                    distance: segment_config.vector_data[DEFAULT_VECTOR_NAME].distance,
+                    hnsw_config: None,
+                }),
+                quantization_config: None,
+                product_quantization_config: None,
+                sparse_vectors: None,
+                payload_schema: None,
             },
             Default::default(),
         );
@@ -198,7 +206,7 @@ mod tests {
         process_field_index_operation(
             locked_holder.deref(),
             opnum.next().unwrap(),
-            &FieldIndexOperations::CreateIndex(payload_field.clone()),
+            &FieldIndexOperations::CreateIndex(CreateFieldIndex::FieldName(payload_field.clone())),
         )
         .unwrap();
 
@@ -276,11 +284,11 @@ mod tests {
         }
 
         let insert_point_ops = PointOperations::UpsertPoints(PointInsertOperations::BatchPoints {
-            ids: vec![501, 502, 503],
+            ids: vec![501.into(), 502.into(), 503.into()],
             vectors: vec![
-                vec![1.0, 0.0, 0.5, 0.0],
-                vec![1.0, 0.0, 0.5, 0.5],
-                vec![1.0, 0.0, 0.5, 1.0],
+                vec![1.0, 0.0, 0.5, 0.0].into(),
+                vec![1.0, 0.0, 0.5, 0.5].into(),
+                vec![1.0, 0.0, 0.5, 1.0].into(),
             ],
             payloads: None,
         });
@@ -337,11 +345,11 @@ mod tests {
         );
 
         let insert_point_ops = PointOperations::UpsertPoints(PointInsertOperations::BatchPoints {
-            ids: vec![601, 602, 603],
+            ids: vec![601.into(), 602.into(), 603.into()],
             vectors: vec![
-                vec![0.0, 1.0, 0.5, 0.0],
-                vec![0.0, 1.0, 0.5, 0.5],
-                vec![0.0, 1.0, 0.5, 1.0],
+                vec![0.0, 1.0, 0.5, 0.0].into(),
+                vec![0.0, 1.0, 0.5, 0.5].into(),
+                vec![0.0, 1.0, 0.5, 1.0].into(),
             ],
             payloads: None,
         });
@@ -93,6 +93,7 @@ mod tests {
     use super::*;
     use crate::collection_manager::fixtures::random_segment;
     use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
+    use crate::config::{VectorParams, VectorsConfig};
     use parking_lot::RwLock;
     use segment::types::Distance;
     use std::sync::Arc;
@@ -128,8 +129,15 @@ mod tests {
             dir.path().to_owned(),
             temp_dir.path().to_owned(),
             CollectionParams {
-                vector_size: 4,
-                distance: Distance::Dot,
+                vectors: VectorsConfig::Single(VectorParams {
+                    size: 4,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                }),
+                quantization_config: None,
+                product_quantization_config: None,
+                sparse_vectors: None,
+                payload_schema: None,
             },
             Default::default(),
         );
@@ -1,4 +1,4 @@
-use crate::collection_manager::holders::proxy_segment::ProxySegment;
+use crate::collection_manager::holders::proxy_segment::{LockedFieldsMap, ProxySegment};
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentId,
 };
@@ -8,8 +8,8 @@ use itertools::Itertools;
 use parking_lot::RwLock;
 use segment::entry::entry_point::SegmentEntry;
 use segment::segment::Segment;
+use segment::segment_constructor::build_segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
-use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
 use segment::types::{
     HnswConfig, Indexes, PayloadIndexType, PayloadKeyType, PointIdType, SegmentConfig, StorageType,
 };
@@ -56,16 +56,16 @@ pub trait SegmentOptimizer {
     fn temp_segment(&self) -> CollectionResult<LockedSegment> {
         let collection_params = self.collection_params();
         let config = SegmentConfig {
-            vector_size: collection_params.vector_size,
-            distance: collection_params.distance,
+            vector_data: collection_params.get_all_vector_params(&self.hnsw_config())?,
+            sparse_vector_data: collection_params.get_all_sparse_vector_params(),
             index: Indexes::Plain {},
             payload_index: Some(PayloadIndexType::Plain),
             storage_type: StorageType::InMemory,
+            quantization_config: None,
         };
-        Ok(LockedSegment::new(build_simple_segment(
+        Ok(LockedSegment::new(build_segment(
             self.collection_path(),
-            config.vector_size,
-            config.distance,
+            &config,
         )?))
     }
 
@@ -94,9 +94,19 @@ pub trait SegmentOptimizer {
 
         let is_on_disk = total_vectors >= thresholds.memmap_threshold;
 
+        let storage_type = match (is_on_disk, collection_params.product_quantization_config) {
+            (false, _) => StorageType::InMemory,
+            (true, None) => StorageType::Mmap,
+            (true, Some(pq_config)) => StorageType::ProductQuantized(pq_config),
+        };
+
+        // Scalar quantization is only useful for large segments, so build it together with the index.
+        // Product quantized storage is already compressed and does not require it
+        let is_quantized = is_indexed && !matches!(storage_type, StorageType::ProductQuantized(_));
+
         let optimized_config = SegmentConfig {
-            vector_size: collection_params.vector_size,
-            distance: collection_params.distance,
+            vector_data: collection_params.get_all_vector_params(&self.hnsw_config())?,
+            sparse_vector_data: collection_params.get_all_sparse_vector_params(),
             index: if is_indexed {
                 Indexes::Hnsw(self.hnsw_config())
             } else {
@@ -107,10 +117,11 @@ pub trait SegmentOptimizer {
             } else {
                 PayloadIndexType::Plain
             }),
-            storage_type: if is_on_disk {
-                StorageType::Mmap
+            storage_type,
+            quantization_config: if is_quantized {
+                collection_params.quantization_config
             } else {
-                StorageType::InMemory
+                None
             },
         };
 
@@ -133,7 +144,7 @@ pub trait SegmentOptimizer {
 
         let proxy_deleted_points = Arc::new(RwLock::new(HashSet::<PointIdType>::new()));
         let proxy_deleted_indexes = Arc::new(RwLock::new(HashSet::<PayloadKeyType>::new()));
-        let proxy_created_indexes = Arc::new(RwLock::new(HashSet::<PayloadKeyType>::new()));
+        let proxy_created_indexes: LockedFieldsMap = Default::default();
 
         // Exclusive lock for the segments operations
         let mut write_segments = segments.write();
@@ -179,8 +190,10 @@ pub trait SegmentOptimizer {
         for field in proxy_deleted_indexes.read().iter() {
             segment_builder.indexed_fields.remove(field);
         }
-        for field in proxy_created_indexes.read().iter().cloned() {
-            segment_builder.indexed_fields.insert(field);
+        for (field, params) in proxy_created_indexes.read().iter() {
+            segment_builder
+                .indexed_fields
+                .insert(field.clone(), params.clone());
         }
 
         let mut optimized_segment: Segment = segment_builder.try_into()?;
@@ -198,14 +211,18 @@ pub trait SegmentOptimizer {
         }
 
         let deleted_indexes = proxy_deleted_indexes.read().iter().cloned().collect_vec();
-        let create_indexes = proxy_created_indexes.read().iter().cloned().collect_vec();
+        let create_indexes = proxy_created_indexes.read().clone();
 
         for delete_field_name in &deleted_indexes {
             optimized_segment.delete_field_index(optimized_segment.version(), delete_field_name)?;
         }
 
-        for create_field_name in &create_indexes {
-            optimized_segment.create_field_index(optimized_segment.version(), create_field_name)?;
+        for (create_field_name, params) in &create_indexes {
+            optimized_segment.create_field_index(
+                optimized_segment.version(),
+                create_field_name,
+                params.as_ref(),
+            )?;
         }
         // ---- SLOW PART ENDS HERE -----
 
@@ -225,9 +242,12 @@ pub trait SegmentOptimizer {
                     .delete_field_index(optimized_segment.version(), deleted_field_name)?;
             }
 
-            for created_field_name in proxy_created_indexes.read().iter() {
-                optimized_segment
-                    .create_field_index(optimized_segment.version(), created_field_name)?;
+            for (created_field_name, params) in proxy_created_indexes.read().iter() {
+                optimized_segment.create_field_index(
+                    optimized_segment.version(),
+                    created_field_name,
+                    params.as_ref(),
+                )?;
             }
 
             write_segments.swap(optimized_segment, &proxy_ids, true)?;
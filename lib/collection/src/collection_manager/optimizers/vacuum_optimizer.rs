@@ -101,6 +101,7 @@ mod tests {
     use super::*;
     use crate::collection_manager::fixtures::random_segment;
     use crate::collection_manager::holders::segment_holder::SegmentHolder;
+    use crate::config::{VectorParams, VectorsConfig};
     use itertools::Itertools;
     use parking_lot::RwLock;
     use rand::Rng;
@@ -188,8 +189,15 @@ mod tests {
             dir.path().to_owned(),
             temp_dir.path().to_owned(),
             CollectionParams {
-                vector_size: 4,
-                distance: Distance::Dot,
+                vectors: VectorsConfig::Single(VectorParams {
+                    size: 4,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                }),
+                quantization_config: None,
+                product_quantization_config: None,
+                sparse_vectors: None,
+                payload_schema: None,
             },
             Default::default(),
         );
@@ -237,6 +245,9 @@ mod tests {
                 PayloadType::Integer(_) => assert!(false),
                 PayloadType::Float(_) => assert!(false),
                 PayloadType::Geo(_) => assert!(false),
+                PayloadType::Bool(_) => assert!(false),
+                PayloadType::Datetime(_) => assert!(false),
+                PayloadType::Object(_) => assert!(false),
             }
         }
 
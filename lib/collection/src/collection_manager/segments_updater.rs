@@ -3,14 +3,14 @@ use std::collections::{HashMap, HashSet};
 use parking_lot::{RwLock, RwLockWriteGuard};
 
 use segment::types::{
-    PayloadInterface, PayloadKeyType, PayloadKeyTypeRef, PointIdType, SeqNumberType,
-    VectorElementType,
+    Filter, NamedVectors, PayloadIndexParams, PayloadInterface, PayloadKeyType, PayloadKeyTypeRef,
+    PointIdType, SeqNumberType,
 };
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
 use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::{PointInsertOperations, PointOperations};
-use crate::operations::types::{CollectionError, CollectionResult, VectorType};
+use crate::operations::types::{CollectionError, CollectionResult, VectorStruct};
 use crate::operations::FieldIndexOperations;
 use itertools::Itertools;
 use segment::entry::entry_point::{OperationResult, SegmentEntry};
@@ -38,6 +38,17 @@ pub(crate) fn check_unprocessed_points(
     }
 }
 
+/// Collect ids of all points across the segments, which satisfy the filter
+fn points_by_filter(segments: &SegmentHolder, filter: &Filter) -> Vec<PointIdType> {
+    let mut points: HashSet<PointIdType> = Default::default();
+    for (_idx, segment) in segments.iter() {
+        let segment_arc = segment.get();
+        let read_segment = segment_arc.read();
+        points.extend(read_segment.read_filtered(PointIdType::MIN, usize::MAX, Some(filter)));
+    }
+    points.into_iter().collect()
+}
+
 /// Tries to delete points from all segments, returns number of actually deleted points
 pub(crate) fn delete_points(
     segments: &SegmentHolder,
@@ -106,9 +117,11 @@ pub(crate) fn create_field_index(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
     field_name: PayloadKeyTypeRef,
+    field_index_params: Option<&PayloadIndexParams>,
 ) -> CollectionResult<usize> {
-    let res = segments
-        .apply_segments(|write_segment| write_segment.create_field_index(op_num, field_name))?;
+    let res = segments.apply_segments(|write_segment| {
+        write_segment.create_field_index(op_num, field_name, field_index_params)
+    })?;
     Ok(res)
 }
 
@@ -126,10 +139,10 @@ fn upsert_with_payload(
     segment: &mut RwLockWriteGuard<dyn SegmentEntry>,
     op_num: SeqNumberType,
     point_id: PointIdType,
-    vector: &[VectorElementType],
+    vectors: &NamedVectors,
     payload: Option<&HashMap<PayloadKeyType, PayloadInterface>>,
 ) -> OperationResult<bool> {
-    let mut res = segment.upsert_point(op_num, point_id, vector)?;
+    let mut res = segment.upsert_vectors(op_num, point_id, vectors)?;
     if let Some(full_payload) = payload {
         for (key, payload_value) in full_payload {
             res &= segment.set_payload(op_num, point_id, key, payload_value.into())?;
@@ -145,7 +158,7 @@ pub(crate) fn upsert_points(
     segments: &RwLock<SegmentHolder>,
     op_num: SeqNumberType,
     ids: &[PointIdType],
-    vectors: &[VectorType],
+    vectors: &[VectorStruct],
     payloads: &Option<Vec<Option<HashMap<PayloadKeyType, PayloadInterface>>>>,
 ) -> CollectionResult<usize> {
     if ids.len() != vectors.len() {
@@ -173,7 +186,11 @@ pub(crate) fn upsert_points(
         }
     }
 
-    let vectors_map: HashMap<PointIdType, &VectorType> = ids.iter().cloned().zip(vectors).collect();
+    let vectors_map: HashMap<PointIdType, NamedVectors> = ids
+        .iter()
+        .cloned()
+        .zip(vectors.iter().cloned().map(VectorStruct::into_all_vectors))
+        .collect();
     let payloads_map: HashMap<PointIdType, &HashMap<PayloadKeyType, PayloadInterface>> =
         match payloads {
             None => Default::default(),
@@ -195,7 +212,7 @@ pub(crate) fn upsert_points(
                 write_segment,
                 op_num,
                 id,
-                vectors_map[&id],
+                &vectors_map[&id],
                 payloads_map.get(&id).cloned(),
             )
         })?;
@@ -222,7 +239,7 @@ pub(crate) fn upsert_points(
                 &mut write_segment,
                 op_num,
                 point_id,
-                vectors_map[&point_id],
+                &vectors_map[&point_id],
                 payloads_map.get(&point_id).cloned(),
             )? as usize;
         }
@@ -238,6 +255,11 @@ pub(crate) fn process_point_operation(
 ) -> CollectionResult<usize> {
     match point_operation {
         PointOperations::DeletePoints { ids, .. } => delete_points(&segments.read(), op_num, &ids),
+        PointOperations::DeletePointsByFilter { filter } => {
+            let segments = segments.read();
+            let ids = points_by_filter(&segments, &filter);
+            delete_points(&segments, op_num, &ids)
+        }
         PointOperations::UpsertPoints(operation) => {
             let (ids, vectors, payloads) = match operation {
                 PointInsertOperations::BatchPoints {
@@ -277,6 +299,21 @@ pub(crate) fn process_payload_operation(
             delete_payload(&segments.read(), op_num, points, keys)
         }
         PayloadOps::ClearPayload { points, .. } => clear_payload(&segments.read(), op_num, points),
+        PayloadOps::SetPayloadByFilter { payload, filter } => {
+            let segments = segments.read();
+            let points = points_by_filter(&segments, filter);
+            set_payload(&segments, op_num, payload, &points)
+        }
+        PayloadOps::DeletePayloadByFilter { keys, filter } => {
+            let segments = segments.read();
+            let points = points_by_filter(&segments, filter);
+            delete_payload(&segments, op_num, &points, keys)
+        }
+        PayloadOps::ClearPayloadByFilter { filter } => {
+            let segments = segments.read();
+            let points = points_by_filter(&segments, filter);
+            clear_payload(&segments, op_num, &points)
+        }
     }
 }
 
@@ -286,9 +323,12 @@ pub(crate) fn process_field_index_operation(
     field_index_operation: &FieldIndexOperations,
 ) -> CollectionResult<usize> {
     match field_index_operation {
-        FieldIndexOperations::CreateIndex(field_name) => {
-            create_field_index(&segments.read(), op_num, field_name)
-        }
+        FieldIndexOperations::CreateIndex(create_index) => create_field_index(
+            &segments.read(),
+            op_num,
+            create_index.field_name(),
+            create_index.field_index_params(),
+        ),
         FieldIndexOperations::DeleteIndex(field_name) => {
             delete_field_index(&segments.read(), op_num, field_name)
         }
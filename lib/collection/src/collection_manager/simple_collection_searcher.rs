@@ -4,7 +4,7 @@ use std::sync::Arc;
 use futures::future::try_join_all;
 use itertools::Itertools;
 use parking_lot::RwLock;
-use segment::entry::entry_point::OperationError;
+use segment::entry::entry_point::{OperationError, SegmentEntry};
 use tokio::runtime::Handle;
 
 use segment::spaces::tools::peek_top_scores_iterable;
@@ -13,7 +13,7 @@ use segment::types::{PointIdType, ScoredPoint, SeqNumberType, WithPayload, WithP
 use crate::collection_manager::collection_managers::CollectionSearcher;
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
 use crate::operations::types::CollectionResult;
-use crate::operations::types::{Record, SearchRequest};
+use crate::operations::types::{NamedVectorStruct, Record, SearchRequest, SearchRequestBatch};
 
 /// Simple implementation of segment manager
 ///  - rebuild segment for memory optimization purposes
@@ -34,6 +34,23 @@ impl CollectionSearcher for SimpleCollectionSearcher {
         request: Arc<SearchRequest>,
         runtime_handle: &Handle,
     ) -> CollectionResult<Vec<ScoredPoint>> {
+        let batch = SearchRequestBatch {
+            searches: vec![request.as_ref().clone()],
+        };
+        let mut results = self
+            .search_batch(segments, Arc::new(batch), runtime_handle)
+            .await?;
+        Ok(results.pop().unwrap_or_default())
+    }
+
+    async fn search_batch(
+        &self,
+        segments: &RwLock<SegmentHolder>,
+        request: Arc<SearchRequestBatch>,
+        runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let mut requests_results: Vec<Vec<ScoredPoint>> = vec![vec![]; request.searches.len()];
+
         // Using { } block to ensure segments variable is dropped in the end of it
         // and is not transferred across the all_searches.await? boundary as it
         // does not impl Send trait
@@ -43,7 +60,7 @@ impl CollectionSearcher for SimpleCollectionSearcher {
             let some_segment = segments.iter().next();
 
             if some_segment.is_none() {
-                return Ok(vec![]);
+                return Ok(requests_results);
             }
 
             segments
@@ -56,30 +73,19 @@ impl CollectionSearcher for SimpleCollectionSearcher {
         let all_searches = try_join_all(searches);
         let all_search_results = all_searches.await?;
 
-        match all_search_results
-            .iter()
-            .filter_map(|res| res.to_owned().err())
-            .next()
-        {
-            None => {}
-            Some(error) => return Err(error),
+        // Each segment returns a list of results for every request of the batch
+        for segment_results in all_search_results {
+            let segment_results = segment_results?;
+            for (results, segment_result) in requests_results.iter_mut().zip(segment_results) {
+                results.extend(segment_result);
+            }
         }
 
-        let mut seen_idx: HashSet<PointIdType> = HashSet::new();
-
-        let top_scores = peek_top_scores_iterable(
-            all_search_results
-                .into_iter()
-                .flat_map(Result::unwrap) // already checked for errors
-                .sorted_by_key(|a| (a.id, 1 - a.version as i64)) // Prefer higher version first
-                .dedup_by(|a, b| a.id == b.id) // Keep only highest version
-                .filter(|scored| {
-                    let res = seen_idx.contains(&scored.id);
-                    seen_idx.insert(scored.id);
-                    !res
-                }),
-            request.top,
-        );
+        let top_scores = requests_results
+            .into_iter()
+            .zip(request.searches.iter())
+            .map(|(results, search)| merge_segments_results(results, search))
+            .collect();
 
         Ok(top_scores)
     }
@@ -116,7 +122,7 @@ impl CollectionSearcher for SimpleCollectionSearcher {
                             None
                         },
                         vector: if with_vector {
-                            Some(segment.vector(id)?)
+                            Some(segment.all_vectors(id)?.into())
                         } else {
                             None
                         },
@@ -130,22 +136,81 @@ impl CollectionSearcher for SimpleCollectionSearcher {
     }
 }
 
+/// Merge results of a single search from all segments.
+/// Only the latest version of each point is kept, first `offset` results are skipped.
+/// Score threshold is already applied by the segments.
+fn merge_segments_results(results: Vec<ScoredPoint>, request: &SearchRequest) -> Vec<ScoredPoint> {
+    let mut seen_idx: HashSet<PointIdType> = HashSet::new();
+
+    let top_scores = peek_top_scores_iterable(
+        results
+            .into_iter()
+            .sorted_by_key(|a| (a.id, 1 - a.version as i64)) // Prefer higher version first
+            .dedup_by(|a, b| a.id == b.id) // Keep only highest version
+            .filter(|scored| {
+                let res = seen_idx.contains(&scored.id);
+                seen_idx.insert(scored.id);
+                !res
+            }),
+        request.top + request.offset,
+    );
+
+    top_scores.into_iter().skip(request.offset).collect()
+}
+
+/// Run all searches of the batch in a segment, holding its read lock once
 async fn search_in_segment(
     segment: LockedSegment,
-    request: Arc<SearchRequest>,
+    request: Arc<SearchRequestBatch>,
+) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+    let segment = segment.get();
+    let segment = segment.read();
+    request
+        .searches
+        .iter()
+        .map(|search| execute_search(&*segment, search))
+        .collect()
+}
+
+fn execute_search(
+    segment: &dyn SegmentEntry,
+    request: &SearchRequest,
 ) -> CollectionResult<Vec<ScoredPoint>> {
     let with_payload_interface = request
         .with_payload
         .as_ref()
         .unwrap_or(&WithPayloadInterface::Bool(false));
     let with_payload = WithPayload::from(with_payload_interface);
-    let res = segment.get().read().search(
-        &request.vector,
-        &with_payload,
-        request.filter.as_ref(),
-        request.top,
-        request.params.as_ref(),
-    )?;
+    // Each segment should return enough points to skip `offset` of them after the merge
+    let top = request.top + request.offset;
+    let res = match &request.vector {
+        NamedVectorStruct::Default(vector) => segment.search(
+            request.vector.get_name(),
+            vector,
+            &with_payload,
+            request.filter.as_ref(),
+            top,
+            request.params.as_ref(),
+            request.score_threshold,
+        )?,
+        NamedVectorStruct::Named(named) => segment.search(
+            &named.name,
+            &named.vector,
+            &with_payload,
+            request.filter.as_ref(),
+            top,
+            request.params.as_ref(),
+            request.score_threshold,
+        )?,
+        NamedVectorStruct::Sparse(named) => segment.search_sparse(
+            &named.name,
+            &named.vector,
+            &with_payload,
+            request.filter.as_ref(),
+            top,
+            request.score_threshold,
+        )?,
+    };
 
     Ok(res)
 }
@@ -154,6 +219,8 @@ async fn search_in_segment(
 mod tests {
     use tempdir::TempDir;
 
+    use segment::types::{Condition, FieldCondition, Filter, Match};
+
     use crate::collection_manager::fixtures::build_test_holder;
 
     use super::*;
@@ -169,11 +236,13 @@ mod tests {
         let query = vec![1.0, 1.0, 1.0, 1.0];
 
         let req = Arc::new(SearchRequest {
-            vector: query,
+            vector: query.into(),
             with_payload: None,
             filter: None,
             params: None,
             top: 5,
+            offset: 0,
+            score_threshold: None,
         });
 
         let result = searcher
@@ -185,8 +254,105 @@ mod tests {
 
         assert_eq!(result.len(), 5);
 
-        assert!(result[0].id == 3 || result[0].id == 11);
-        assert!(result[1].id == 3 || result[1].id == 11);
+        assert!(result[0].id == 3.into() || result[0].id == 11.into());
+        assert!(result[1].id == 3.into() || result[1].id == 11.into());
+    }
+
+    #[tokio::test]
+    async fn test_segments_search_batch() {
+        let dir = TempDir::new("segment_dir").unwrap();
+
+        let segment_holder = build_test_holder(dir.path());
+
+        let searcher = SimpleCollectionSearcher::new();
+
+        let blue_filter = Filter::new_must(Condition::Field(FieldCondition {
+            key: "color".to_string(),
+            r#match: Some(Match {
+                keyword: Some("blue".to_string()),
+                integer: None,
+                boolean: None,
+                text: None,
+                any: None,
+                except: None,
+            }),
+            range: None,
+            geo_bounding_box: None,
+            geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
+        }));
+
+        let batch = Arc::new(SearchRequestBatch {
+            searches: vec![
+                SearchRequest {
+                    vector: vec![1.0, 1.0, 1.0, 1.0].into(),
+                    with_payload: None,
+                    filter: None,
+                    params: None,
+                    top: 5,
+                    offset: 0,
+                    score_threshold: None,
+                },
+                SearchRequest {
+                    vector: vec![1.0, 1.0, 1.0, 1.0].into(),
+                    with_payload: None,
+                    filter: Some(blue_filter),
+                    params: None,
+                    top: 2,
+                    offset: 0,
+                    score_threshold: None,
+                },
+            ],
+        });
+
+        let result = searcher
+            .search_batch(&segment_holder, batch, &Handle::current())
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 2);
+
+        assert_eq!(result[0].len(), 5);
+        assert!(result[0][0].id == 3.into() || result[0][0].id == 11.into());
+
+        assert_eq!(result[1].len(), 2);
+        assert_eq!(result[1][0].id, 3.into());
+    }
+
+    #[tokio::test]
+    async fn test_segments_search_offset() {
+        let dir = TempDir::new("segment_dir").unwrap();
+
+        let segment_holder = build_test_holder(dir.path());
+
+        let searcher = SimpleCollectionSearcher::new();
+
+        let request = |top, offset| {
+            Arc::new(SearchRequest {
+                vector: vec![1.0, 1.0, 1.0, 1.0].into(),
+                with_payload: None,
+                filter: None,
+                params: None,
+                top,
+                offset,
+                score_threshold: None,
+            })
+        };
+
+        let all_results = searcher
+            .search(&segment_holder, request(5, 0), &Handle::current())
+            .await
+            .unwrap();
+
+        let page = searcher
+            .search(&segment_holder, request(2, 3), &Handle::current())
+            .await
+            .unwrap();
+
+        let scores = |points: &[ScoredPoint]| points.iter().map(|p| p.score).collect_vec();
+        assert_eq!(page.len(), 2);
+        assert_eq!(scores(&page), scores(&all_results[3..5]));
     }
 
     #[tokio::test]
@@ -197,7 +363,12 @@ mod tests {
         let searcher = SimpleCollectionSearcher::new();
 
         let records = searcher
-            .retrieve(&segment_holder, &[1, 2, 3], &WithPayload::from(true), true)
+            .retrieve(
+                &segment_holder,
+                &[1.into(), 2.into(), 3.into()],
+                &WithPayload::from(true),
+                true,
+            )
             .await
             .unwrap();
         assert_eq!(records.len(), 3);
@@ -8,7 +8,7 @@ use itertools::Itertools;
 use parking_lot::RwLock;
 use segment::entry::entry_point::SegmentEntry;
 use segment::types::{PayloadKeyType, PointIdType};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::Arc;
 use tempdir::TempDir;
@@ -22,7 +22,7 @@ fn wrap_proxy(segments: LockedSegmentHolder, sid: SegmentId, path: &Path) -> Seg
 
     let proxy_deleted_points = Arc::new(RwLock::new(HashSet::<PointIdType>::new()));
     let proxy_deleted_indexes = Arc::new(RwLock::new(HashSet::<PayloadKeyType>::new()));
-    let proxy_created_indexes = Arc::new(RwLock::new(HashSet::<PayloadKeyType>::new()));
+    let proxy_created_indexes = Arc::new(RwLock::new(HashMap::new()));
 
     let proxy = ProxySegment::new(
         optimizing_segment.clone(),
@@ -51,22 +51,25 @@ fn test_update_proxy_segments() {
 
     let _proxy_id = wrap_proxy(segments.clone(), sid1, dir.path());
 
-    let vectors = vec![vec![0.0, 0.0, 0.0, 0.0], vec![0.0, 0.0, 0.0, 0.0]];
+    let vectors = vec![
+        vec![0.0, 0.0, 0.0, 0.0].into(),
+        vec![0.0, 0.0, 0.0, 0.0].into(),
+    ];
 
     for i in 1..10 {
-        let ids = vec![100 * i + 1, 100 * i + 2];
+        let ids: Vec<PointIdType> = vec![(100 * i + 1).into(), (100 * i + 2).into()];
         upsert_points(&segments, 1000 + i, &ids, &vectors, &None).unwrap();
     }
 
     let all_ids = segments
         .read()
         .iter()
-        .flat_map(|(_id, segment)| segment.get().read().read_filtered(0, 100, None))
+        .flat_map(|(_id, segment)| segment.get().read().read_filtered(0.into(), 100, None))
         .sorted()
         .collect_vec();
 
     for i in 1..10 {
-        let idx = 100 * i + 1;
+        let idx: PointIdType = (100 * i + 1).into();
         assert!(all_ids.contains(&idx), "Not found {}", idx)
     }
 }
@@ -87,11 +90,17 @@ fn test_move_points_to_copy_on_write() {
 
     let proxy_id = wrap_proxy(segments.clone(), sid1, dir.path());
 
-    let vectors = vec![vec![0.0, 0.0, 0.0, 0.0], vec![0.0, 0.0, 0.0, 0.0]];
-    upsert_points(&segments, 1001, &[1, 2], &vectors, &None).unwrap();
+    let vectors = vec![
+        vec![0.0, 0.0, 0.0, 0.0].into(),
+        vec![0.0, 0.0, 0.0, 0.0].into(),
+    ];
+    upsert_points(&segments, 1001, &[1.into(), 2.into()], &vectors, &None).unwrap();
 
-    let vectors = vec![vec![0.0, 0.0, 0.0, 0.0], vec![0.0, 0.0, 0.0, 0.0]];
-    upsert_points(&segments, 1002, &[2, 3], &vectors, &None).unwrap();
+    let vectors = vec![
+        vec![0.0, 0.0, 0.0, 0.0].into(),
+        vec![0.0, 0.0, 0.0, 0.0].into(),
+    ];
+    upsert_points(&segments, 1002, &[2.into(), 3.into()], &vectors, &None).unwrap();
 
     let segments_write = segments.write();
 
@@ -111,7 +120,7 @@ fn test_move_points_to_copy_on_write() {
 
     let copy_on_write_points = copy_on_write_segment_read.iter_points().collect_vec();
 
-    let vector_storage = copy_on_write_segment_read.vector_storage.clone();
+    let points_storage = copy_on_write_segment_read.points_storage.clone();
     let id_mapper = copy_on_write_segment_read.id_tracker.clone();
 
     eprintln!("copy_on_write_points = {:#?}", copy_on_write_points);
@@ -121,7 +130,7 @@ fn test_move_points_to_copy_on_write() {
         eprintln!("{} -> {}", idx, internal);
     }
 
-    let internal_ids = vector_storage.borrow().iter_ids().collect_vec();
+    let internal_ids = points_storage.borrow().iter_ids().collect_vec();
 
     eprintln!("internal_ids = {:#?}", internal_ids);
 
@@ -8,10 +8,17 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use wal::WalOptions;
 
-use segment::types::{Distance, HnswConfig};
+use segment::types::{
+    Distance, HnswConfig, PayloadIndexParams, PayloadInterface, PayloadInterfaceStrict,
+    PayloadKeyType, PayloadSchemaType, PayloadType, PayloadVariant, ProductQuantizationConfig,
+    ScalarQuantizationConfig, SparseVectorDataConfig, VectorDataConfig, DEFAULT_VECTOR_NAME,
+    PAYLOAD_PATH_SEPARATOR,
+};
 
 use crate::collection_builder::optimizers_builder::OptimizersConfig;
+use crate::operations::config_diff::{DiffConfig, HnswConfigDiff};
 use crate::operations::types::{CollectionError, CollectionResult};
+use std::collections::{BTreeMap, HashMap};
 
 pub const COLLECTION_CONFIG_FILE: &str = "config.json";
 
@@ -41,13 +48,221 @@ impl Default for WalConfig {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+/// Params of a single vector space of the collection
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
-pub struct CollectionParams {
+pub struct VectorParams {
     /// Size of a vectors used
-    pub vector_size: usize,
+    pub size: usize,
     /// Type of distance function used for measuring distance between vectors
     pub distance: Distance,
+    /// Custom params of the HNSW index for this vector. If none - collection `hnsw_config` is used
+    #[serde(default)]
+    pub hnsw_config: Option<HnswConfigDiff>,
+}
+
+/// Params of a single sparse vector space of the collection
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SparseVectorParams {
+    /// Filtered search with the number of matched points below this threshold scores
+    /// the matched points directly, instead of using the inverted index
+    #[serde(default)]
+    pub full_scan_threshold: Option<usize>,
+}
+
+/// Vector params of the collection: either a single unnamed vector,
+/// or several named vectors, each with its own params
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[serde(untagged)]
+pub enum VectorsConfig {
+    Single(VectorParams),
+    Multi(BTreeMap<String, VectorParams>),
+}
+
+impl VectorsConfig {
+    /// Params of the vector with a given name.
+    /// Unnamed vector is available under the `DEFAULT_VECTOR_NAME`
+    pub fn get_params(&self, vector_name: &str) -> Option<&VectorParams> {
+        match self {
+            VectorsConfig::Single(params) => {
+                if vector_name == DEFAULT_VECTOR_NAME {
+                    Some(params)
+                } else {
+                    None
+                }
+            }
+            VectorsConfig::Multi(params) => params.get(vector_name),
+        }
+    }
+
+    /// Iterate over params of all vectors with their names
+    pub fn params_iter(&self) -> Box<dyn Iterator<Item = (&str, &VectorParams)> + '_> {
+        match self {
+            VectorsConfig::Single(params) => {
+                Box::new(std::iter::once((DEFAULT_VECTOR_NAME, params)))
+            }
+            VectorsConfig::Multi(params) => {
+                Box::new(params.iter().map(|(name, params)| (name.as_str(), params)))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionParams {
+    /// Params of the vectors, stored in each point of the collection
+    pub vectors: VectorsConfig,
+    /// If set - vectors of the optimized segments are quantized,
+    /// quantized vectors are used for search and original vectors only for rescoring
+    #[serde(default)]
+    pub quantization_config: Option<ScalarQuantizationConfig>,
+    /// If set - segments, large enough to be stored on disk, use product quantized storage
+    /// instead of plain memmap. Search in such segments is approximate, but requires much less RAM
+    #[serde(default)]
+    pub product_quantization_config: Option<ProductQuantizationConfig>,
+    /// Params of the named sparse vectors, stored in each point of the collection
+    #[serde(default)]
+    pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// Declared types of the payload fields. If none - types are inferred from the stored values
+    #[serde(default)]
+    pub payload_schema: Option<PayloadSchema>,
+}
+
+/// Declared type of the payload field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadFieldSchema {
+    /// Type of the field values. Payloads with values of another type are rejected
+    #[serde(flatten)]
+    pub field_type: PayloadSchemaType,
+    /// If true - index of the field is created automatically
+    #[serde(default)]
+    pub index: bool,
+    /// Explicitly selected type of the field index, e.g. full-text index of the keyword field
+    #[serde(default)]
+    pub index_params: Option<PayloadIndexParams>,
+}
+
+/// Declared payload fields of the collection, nested fields are addressed by dotted paths.
+/// Fields, which are not declared, could have values of any type
+pub type PayloadSchema = BTreeMap<PayloadKeyType, PayloadFieldSchema>;
+
+fn variant_values_mut<T>(variant: &mut PayloadVariant<T>) -> std::slice::IterMut<T> {
+    match variant {
+        PayloadVariant::Value(value) => std::slice::from_mut(value).iter_mut(),
+        PayloadVariant::List(values) => values.iter_mut(),
+    }
+}
+
+fn integers_to_floats(integers: &PayloadVariant<i64>) -> PayloadVariant<f64> {
+    match integers {
+        PayloadVariant::Value(value) => PayloadVariant::Value(*value as f64),
+        PayloadVariant::List(values) => {
+            PayloadVariant::List(values.iter().map(|value| *value as f64).collect())
+        }
+    }
+}
+
+/// JSON numbers without fraction are parsed as integers, but are valid values of the float field
+fn convert_integers_to_floats(value: &mut PayloadInterface) {
+    let converted = match value {
+        PayloadInterface::IntShortcut(integers)
+        | PayloadInterface::Payload(PayloadInterfaceStrict::Integer(integers)) => {
+            PayloadInterface::FloatShortcut(integers_to_floats(integers))
+        }
+        _ => return,
+    };
+    *value = converted;
+}
+
+fn check_payload_value(
+    payload_schema: &PayloadSchema,
+    path: &str,
+    value: &mut PayloadInterface,
+) -> CollectionResult<()> {
+    if let Some(field_schema) = payload_schema.get(path) {
+        if field_schema.field_type == PayloadSchemaType::Float {
+            convert_integers_to_floats(value);
+        }
+        let value_type = PayloadSchemaType::from(&PayloadType::from(&*value));
+        if value_type != field_schema.field_type {
+            return Err(CollectionError::BadInput {
+                description: format!(
+                    "Wrong type of payload field \"{}\": expected {:?}, got {:?}",
+                    path, field_schema.field_type, value_type
+                ),
+            });
+        }
+    }
+    if let PayloadInterface::ObjectShortcut(objects)
+    | PayloadInterface::Payload(PayloadInterfaceStrict::Object(objects)) = value
+    {
+        for object in variant_values_mut(objects) {
+            for (nested_key, nested_value) in object.iter_mut() {
+                let nested_path = format!("{}{}{}", path, PAYLOAD_PATH_SEPARATOR, nested_key);
+                check_payload_value(payload_schema, &nested_path, nested_value)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check that values of the declared fields have declared types.
+/// Integer values of the float fields are converted into floats
+pub fn check_payload(
+    payload_schema: &PayloadSchema,
+    payload: &mut HashMap<PayloadKeyType, PayloadInterface>,
+) -> CollectionResult<()> {
+    for (key, value) in payload.iter_mut() {
+        check_payload_value(payload_schema, key, value)?;
+    }
+    Ok(())
+}
+
+impl CollectionParams {
+    /// Config of the segment vector storages for each vector of the collection.
+    /// Custom HNSW params of the vector are applied on top of the collection `hnsw_config`
+    pub fn get_all_vector_params(
+        &self,
+        hnsw_config: &HnswConfig,
+    ) -> CollectionResult<HashMap<String, VectorDataConfig>> {
+        self.vectors
+            .params_iter()
+            .map(|(vector_name, params)| {
+                let vector_hnsw_config = params
+                    .hnsw_config
+                    .map(|diff| diff.update(hnsw_config))
+                    .transpose()?;
+                Ok((
+                    vector_name.to_owned(),
+                    VectorDataConfig {
+                        size: params.size,
+                        distance: params.distance,
+                        hnsw_config: vector_hnsw_config,
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    /// Config of the segment sparse vector storages for each sparse vector of the collection
+    pub fn get_all_sparse_vector_params(&self) -> HashMap<String, SparseVectorDataConfig> {
+        self.sparse_vectors
+            .iter()
+            .flatten()
+            .map(|(vector_name, params)| {
+                (
+                    vector_name.to_owned(),
+                    SparseVectorDataConfig {
+                        full_scan_threshold: params.full_scan_threshold,
+                    },
+                )
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
@@ -58,6 +273,46 @@ pub struct CollectionConfig {
     pub wal_config: WalConfig,
 }
 
+/// Collection params, used before named vectors were introduced
+#[derive(Debug, Deserialize)]
+struct CollectionParamsV0 {
+    vector_size: usize,
+    distance: Distance,
+    #[serde(default)]
+    quantization_config: Option<ScalarQuantizationConfig>,
+    #[serde(default)]
+    product_quantization_config: Option<ProductQuantizationConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollectionConfigV0 {
+    params: CollectionParamsV0,
+    hnsw_config: HnswConfig,
+    optimizer_config: OptimizersConfig,
+    wal_config: WalConfig,
+}
+
+impl From<CollectionConfigV0> for CollectionConfig {
+    fn from(old: CollectionConfigV0) -> Self {
+        CollectionConfig {
+            params: CollectionParams {
+                vectors: VectorsConfig::Single(VectorParams {
+                    size: old.params.vector_size,
+                    distance: old.params.distance,
+                    hnsw_config: None,
+                }),
+                quantization_config: old.params.quantization_config,
+                product_quantization_config: old.params.product_quantization_config,
+                sparse_vectors: None,
+                payload_schema: None,
+            },
+            hnsw_config: old.hnsw_config,
+            optimizer_config: old.optimizer_config,
+            wal_config: old.wal_config,
+        }
+    }
+}
+
 impl CollectionConfig {
     pub fn save(&self, path: &Path) -> CollectionResult<()> {
         let config_path = path.join(COLLECTION_CONFIG_FILE);
@@ -75,6 +330,12 @@ impl CollectionConfig {
         let mut contents = String::new();
         let mut file = File::open(config_path)?;
         file.read_to_string(&mut contents)?;
-        Ok(serde_json::from_str(&contents)?)
+        let config = serde_json::from_str(&contents).or_else(|err| {
+            // Collection might be created before named vectors were introduced
+            serde_json::from_str::<CollectionConfigV0>(&contents)
+                .map(CollectionConfig::from)
+                .map_err(|_| err)
+        })?;
+        Ok(config)
     }
 }
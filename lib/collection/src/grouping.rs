@@ -0,0 +1,272 @@
+//! Search of the points, grouped by the value of a payload field
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+
+use itertools::Itertools;
+use parking_lot::RwLock;
+
+use segment::types::{
+    get_value_by_path, Condition, FieldCondition, Filter, HasIdCondition, Match, PayloadKeyType,
+    PayloadType, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
+};
+
+use crate::collection_manager::collection_managers::CollectionSearcher;
+use crate::collection_manager::holders::segment_holder::SegmentHolder;
+use crate::operations::types::{
+    CollectionError, CollectionResult, GroupId, GroupsResult, PointGroup,
+};
+
+/// Max number of search requests performed to fill the groups.
+/// Limits the amount of work in case of a few dominating groups.
+const MAX_GROUP_SEARCH_ITERATIONS: usize = 10;
+
+/// Parameters of the grouping, shared by grouped search and recommendation
+#[derive(Debug, Clone)]
+pub struct GroupRequest {
+    /// Payload field to group by
+    pub group_by: PayloadKeyType,
+    /// Max number of points in each group
+    pub group_size: usize,
+    /// Max number of groups
+    pub groups: usize,
+    /// Payload to return with the grouped points
+    pub with_payload: WithPayload,
+}
+
+impl GroupRequest {
+    pub fn new(
+        group_by: PayloadKeyType,
+        group_size: usize,
+        groups: usize,
+        with_payload: Option<WithPayloadInterface>,
+    ) -> CollectionResult<Self> {
+        if group_size == 0 || groups == 0 {
+            return Err(CollectionError::BadRequest {
+                description: "Both `group_size` and `groups` should be greater than 0".to_owned(),
+            });
+        }
+
+        let with_payload = with_payload
+            .as_ref()
+            .map(WithPayload::from)
+            .unwrap_or_else(|| WithPayload::from(false));
+
+        Ok(GroupRequest {
+            group_by,
+            group_size,
+            groups,
+            with_payload,
+        })
+    }
+
+    /// Number of points requested by each search iteration
+    pub fn search_limit(&self) -> usize {
+        self.group_size * self.groups
+    }
+}
+
+/// Extract group identifiers from the value of the grouping field.
+/// Point with multiple values belongs to multiple groups.
+fn group_ids(value: &PayloadType) -> Vec<GroupId> {
+    match value {
+        PayloadType::Keyword(values) => values.iter().cloned().map(GroupId::Keyword).collect(),
+        PayloadType::Integer(values) => values.iter().cloned().map(GroupId::Integer).collect(),
+        PayloadType::Float(_)
+        | PayloadType::Geo(_)
+        | PayloadType::Bool(_)
+        | PayloadType::Datetime(_)
+        | PayloadType::Object(_) => vec![],
+    }
+}
+
+/// Condition, which matches all points of the group
+fn group_condition(key: &PayloadKeyType, group_id: &GroupId) -> Condition {
+    let r#match = match group_id {
+        GroupId::Keyword(keyword) => Match {
+            keyword: Some(keyword.clone()),
+            integer: None,
+            boolean: None,
+            text: None,
+            any: None,
+            except: None,
+        },
+        GroupId::Integer(integer) => Match {
+            keyword: None,
+            integer: Some(*integer),
+            boolean: None,
+            text: None,
+            any: None,
+            except: None,
+        },
+    };
+    Condition::Field(FieldCondition {
+        key: key.clone(),
+        r#match: Some(r#match),
+        range: None,
+        geo_bounding_box: None,
+        geo_radius: None,
+        geo_polygon: None,
+        values_count: None,
+    })
+}
+
+/// Collects search results into groups
+struct GroupsAggregator<'a> {
+    request: &'a GroupRequest,
+    groups: Vec<PointGroup>,
+    group_positions: HashMap<GroupId, usize>,
+    seen_points: HashSet<PointIdType>,
+}
+
+impl<'a> GroupsAggregator<'a> {
+    fn new(request: &'a GroupRequest) -> Self {
+        GroupsAggregator {
+            request,
+            groups: vec![],
+            group_positions: HashMap::new(),
+            seen_points: HashSet::new(),
+        }
+    }
+
+    fn add_point(&mut self, point: ScoredPoint) {
+        self.seen_points.insert(point.id);
+
+        let ids = point
+            .payload
+            .as_ref()
+            .and_then(|payload| get_value_by_path(payload, &self.request.group_by))
+            .map(|value| group_ids(&value))
+            .unwrap_or_default();
+
+        for group_id in ids {
+            let position = match self.group_positions.get(&group_id) {
+                Some(&position) => position,
+                None if self.groups.len() < self.request.groups => {
+                    self.groups.push(PointGroup {
+                        id: group_id.clone(),
+                        hits: vec![],
+                    });
+                    self.group_positions.insert(group_id, self.groups.len() - 1);
+                    self.groups.len() - 1
+                }
+                // No more groups are accepted
+                None => continue,
+            };
+            let group = &mut self.groups[position];
+            if group.hits.len() < self.request.group_size {
+                group.hits.push(point.clone());
+            }
+        }
+    }
+
+    fn is_full(&self, group: &PointGroup) -> bool {
+        group.hits.len() >= self.request.group_size
+    }
+
+    fn is_complete(&self) -> bool {
+        self.groups.len() >= self.request.groups
+            && self.groups.iter().all(|group| self.is_full(group))
+    }
+
+    /// Filter, which excludes already processed points and points of the full groups
+    fn next_filter(&self, base_filter: &Option<Filter>) -> Filter {
+        let mut must_not: Vec<Condition> = self
+            .groups
+            .iter()
+            .filter(|group| self.is_full(group))
+            .map(|group| group_condition(&self.request.group_by, &group.id))
+            .collect();
+        if !self.seen_points.is_empty() {
+            must_not.push(Condition::HasId(HasIdCondition {
+                has_id: self.seen_points.clone(),
+            }));
+        }
+
+        Filter {
+            should: None,
+            min_should: None,
+            must: base_filter
+                .clone()
+                .map(|filter| vec![Condition::Filter(filter)]),
+            must_not: if must_not.is_empty() {
+                None
+            } else {
+                Some(must_not)
+            },
+        }
+    }
+
+    fn into_groups(self) -> Vec<PointGroup> {
+        let with_payload = &self.request.with_payload;
+        let mut groups = self.groups;
+        for group in groups.iter_mut() {
+            group.hits.sort_by(|a, b| b.cmp(a));
+            for hit in group.hits.iter_mut() {
+                hit.payload = if with_payload.enable {
+                    match (&with_payload.payload_selector, hit.payload.take()) {
+                        (Some(selector), Some(payload)) => Some(selector.process(payload)),
+                        (_, payload) => payload,
+                    }
+                } else {
+                    None
+                };
+            }
+        }
+        // Groups are never empty, the first hit is the best one
+        groups.sort_by(|a, b| b.hits[0].cmp(&a.hits[0]));
+        groups
+    }
+}
+
+/// Perform the search and group its results by the value of the payload field.
+///
+/// Search is repeated with already processed points and full groups excluded,
+/// until all groups are filled or there are no more points to search for.
+///
+/// # Arguments
+///
+/// * `filter` - filter of the original request
+/// * `search` - performs a single search iteration with the given filter and limit,
+///    e.g. vector search or recommendation
+pub async fn group_by<F, Fut>(
+    segments: &RwLock<SegmentHolder>,
+    segment_searcher: &(dyn CollectionSearcher),
+    filter: Option<Filter>,
+    request: GroupRequest,
+    search: F,
+) -> CollectionResult<GroupsResult>
+where
+    F: Fn(Filter, usize) -> Fut,
+    Fut: Future<Output = CollectionResult<Vec<ScoredPoint>>>,
+{
+    let limit = request.search_limit();
+    let mut aggregator = GroupsAggregator::new(&request);
+
+    for _ in 0..MAX_GROUP_SEARCH_ITERATIONS {
+        let points = search(aggregator.next_filter(&filter), limit).await?;
+        let is_exhausted = points.len() < limit;
+
+        // Full payload is required to find the groups of the points
+        let point_ids = points.iter().map(|point| point.id).collect_vec();
+        let mut payloads: HashMap<_, _> = segment_searcher
+            .retrieve(segments, &point_ids, &WithPayload::from(true), false)
+            .await?
+            .into_iter()
+            .map(|record| (record.id, record.payload))
+            .collect();
+
+        for mut point in points {
+            point.payload = payloads.remove(&point.id).flatten();
+            aggregator.add_point(point);
+        }
+
+        if is_exhausted || aggregator.is_complete() {
+            break;
+        }
+    }
+
+    Ok(GroupsResult {
+        groups: aggregator.into_groups(),
+    })
+}
@@ -4,6 +4,9 @@ pub mod collection;
 pub mod collection_builder;
 pub mod collection_manager;
 pub mod config;
+pub mod grouping;
 pub mod operations;
+pub mod recommendations;
+pub mod snapshot;
 mod update_handler;
 mod wal;
@@ -4,13 +4,50 @@ pub mod point_ops;
 pub mod types;
 
 use schemars::JsonSchema;
+use segment::types::{PayloadIndexParams, PayloadInterface, PayloadKeyType};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::iter;
+
+use crate::operations::payload_ops::PayloadOps;
+use crate::operations::point_ops::{PointInsertOperations, PointOperations};
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[serde(untagged)]
+pub enum CreateFieldIndex {
+    /// Name of the field, index type is selected by the type of field values
+    FieldName(String),
+    /// Field index with explicitly selected type
+    WithParams {
+        field_name: String,
+        field_index_params: PayloadIndexParams,
+    },
+}
+
+impl CreateFieldIndex {
+    pub fn field_name(&self) -> &str {
+        match self {
+            CreateFieldIndex::FieldName(field_name) => field_name,
+            CreateFieldIndex::WithParams { field_name, .. } => field_name,
+        }
+    }
+
+    pub fn field_index_params(&self) -> Option<&PayloadIndexParams> {
+        match self {
+            CreateFieldIndex::FieldName(_) => None,
+            CreateFieldIndex::WithParams {
+                field_index_params, ..
+            } => Some(field_index_params),
+        }
+    }
+}
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum FieldIndexOperations {
     /// Create index for payload field
-    CreateIndex(String),
+    CreateIndex(CreateFieldIndex),
     /// Delete index for the field
     DeleteIndex(String),
 }
@@ -24,6 +61,31 @@ pub enum CollectionUpdateOperations {
     FieldIndexOperation(FieldIndexOperations),
 }
 
+type PayloadRef<'a> = &'a mut HashMap<PayloadKeyType, PayloadInterface>;
+
+impl CollectionUpdateOperations {
+    /// Payloads, which are assigned to the points by this operation
+    pub fn payloads_mut(&mut self) -> Box<dyn Iterator<Item = PayloadRef> + '_> {
+        match self {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperations::BatchPoints { payloads, .. },
+            )) => Box::new(payloads.iter_mut().flatten().flatten()),
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperations::PointsList(points),
+            )) => Box::new(points.iter_mut().filter_map(|point| point.payload.as_mut())),
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload {
+                payload,
+                ..
+            })
+            | CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayloadByFilter {
+                payload,
+                ..
+            }) => Box::new(iter::once(payload)),
+            _ => Box::new(iter::empty()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -33,7 +95,7 @@ mod tests {
     fn test_deserialize() {
         let op =
             CollectionUpdateOperations::PayloadOperation(payload_ops::PayloadOps::ClearPayload {
-                points: vec![1, 2, 3],
+                points: vec![1.into(), 2.into(), 3.into()],
             });
 
         let json = serde_json::to_string_pretty(&op).unwrap();
@@ -1,5 +1,5 @@
 use schemars::JsonSchema;
-use segment::types::{PayloadInterface, PayloadKeyType, PointIdType};
+use segment::types::{Filter, PayloadInterface, PayloadKeyType, PointIdType};
 use serde;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -22,6 +22,18 @@ pub enum PayloadOps {
     },
     /// Drops all Payload values associated with given points.
     ClearPayload { points: Vec<PointIdType> },
+    /// Set payload value to all points which satisfy the filter
+    SetPayloadByFilter {
+        payload: HashMap<PayloadKeyType, PayloadInterface>,
+        filter: Filter,
+    },
+    /// Deletes specified payload values from all points which satisfy the filter
+    DeletePayloadByFilter {
+        keys: Vec<PayloadKeyType>,
+        filter: Filter,
+    },
+    /// Drops all Payload values of the points which satisfy the filter
+    ClearPayloadByFilter { filter: Filter },
 }
 
 #[cfg(test)]
@@ -1,6 +1,6 @@
-use crate::operations::types::VectorType;
+use crate::operations::types::VectorStruct;
 use schemars::JsonSchema;
-use segment::types::{PayloadInterface, PayloadKeyType, PointIdType};
+use segment::types::{Filter, PayloadInterface, PayloadKeyType, PointIdType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -9,8 +9,8 @@ use std::collections::HashMap;
 pub struct PointStruct {
     /// Point id
     pub id: PointIdType,
-    /// Vector
-    pub vector: VectorType,
+    /// Vector or named vectors of the point
+    pub vector: VectorStruct,
     /// Payload values (optional)
     pub payload: Option<HashMap<PayloadKeyType, PayloadInterface>>,
 }
@@ -22,7 +22,7 @@ pub enum PointInsertOperations {
     /// Inset points from a batch.
     BatchPoints {
         ids: Vec<PointIdType>,
-        vectors: Vec<VectorType>,
+        vectors: Vec<VectorStruct>,
         payloads: Option<Vec<Option<HashMap<PayloadKeyType, PayloadInterface>>>>,
     },
     #[serde(rename = "points")]
@@ -37,4 +37,6 @@ pub enum PointOperations {
     UpsertPoints(PointInsertOperations),
     /// Delete point if exists
     DeletePoints { ids: Vec<PointIdType> },
+    /// Delete all points which satisfy the filter
+    DeletePointsByFilter { filter: Filter },
 }
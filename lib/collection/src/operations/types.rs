@@ -9,8 +9,9 @@ use tokio::task::JoinError;
 
 use segment::entry::entry_point::OperationError;
 use segment::types::{
-    Filter, PayloadKeyType, PayloadSchemaInfo, PayloadType, PointIdType, SearchParams,
-    SeqNumberType, TheMap, VectorElementType, WithPayloadInterface,
+    Filter, IntPayloadType, NamedVectors, OrderBy, PayloadKeyType, PayloadSchemaInfo, PayloadType,
+    PointIdType, RangeValue, ScoreType, ScoredPoint, SearchParams, SeqNumberType, SparseVector,
+    TheMap, Vector, VectorElementType, WithPayloadInterface, DEFAULT_VECTOR_NAME,
 };
 
 use crate::config::CollectionConfig;
@@ -21,6 +22,92 @@ use std::collections::HashMap;
 /// Type of vector in API
 pub type VectorType = Vec<VectorElementType>;
 
+/// Vectors of the point in API.
+/// Either a single unnamed vector or a set of named dense and sparse vectors
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(untagged, rename_all = "snake_case")]
+pub enum VectorStruct {
+    Single(VectorType),
+    Multi(HashMap<String, Vector>),
+}
+
+impl From<VectorType> for VectorStruct {
+    fn from(vector: VectorType) -> Self {
+        VectorStruct::Single(vector)
+    }
+}
+
+impl From<NamedVectors> for VectorStruct {
+    fn from(mut vectors: NamedVectors) -> Self {
+        if vectors.len() == 1 {
+            if let Some(Vector::Dense(vector)) = vectors.remove(DEFAULT_VECTOR_NAME) {
+                return VectorStruct::Single(vector);
+            }
+        }
+        VectorStruct::Multi(vectors)
+    }
+}
+
+impl VectorStruct {
+    pub fn into_all_vectors(self) -> NamedVectors {
+        match self {
+            VectorStruct::Single(vector) => {
+                let mut vectors = NamedVectors::new();
+                vectors.insert(DEFAULT_VECTOR_NAME.to_owned(), vector.into());
+                vectors
+            }
+            VectorStruct::Multi(vectors) => vectors,
+        }
+    }
+}
+
+/// Vector used for search.
+/// Either an unnamed vector, or a dense or sparse vector with the name of the vector space
+/// to search in
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(untagged, rename_all = "snake_case")]
+pub enum NamedVectorStruct {
+    Default(VectorType),
+    Named(NamedVector),
+    Sparse(NamedSparseVector),
+}
+
+/// Vector with the name of the vector space it belongs to
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct NamedVector {
+    /// Name of the vector
+    pub name: String,
+    /// Vector data
+    pub vector: VectorType,
+}
+
+/// Sparse vector with the name of the vector space it belongs to
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct NamedSparseVector {
+    /// Name of the sparse vector
+    pub name: String,
+    /// Sparse vector data
+    pub vector: SparseVector,
+}
+
+impl From<VectorType> for NamedVectorStruct {
+    fn from(vector: VectorType) -> Self {
+        NamedVectorStruct::Default(vector)
+    }
+}
+
+impl NamedVectorStruct {
+    pub fn get_name(&self) -> &str {
+        match self {
+            NamedVectorStruct::Default(_) => DEFAULT_VECTOR_NAME,
+            NamedVectorStruct::Named(named) => &named.name,
+            NamedVectorStruct::Sparse(named) => &named.name,
+        }
+    }
+}
+
 /// Current state of the collection
 #[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -43,7 +130,7 @@ pub struct Record {
     /// Payload - values assigned to the point
     pub payload: Option<TheMap<PayloadKeyType, PayloadType>>,
     /// Vector of the point
-    pub vector: Option<Vec<VectorElementType>>,
+    pub vector: Option<VectorStruct>,
 }
 
 /// Current statistics and configuration of the collection
@@ -97,16 +184,21 @@ pub struct ScrollRequest {
     pub with_payload: Option<WithPayloadInterface>,
     /// Return point vector with the result. Default: false
     pub with_vector: Option<bool>,
+    /// Order points by the value of the indexed numeric payload field instead of the id.
+    /// `offset` is used to start from the point among points with the `start_from` value
+    #[serde(default)]
+    pub order_by: Option<OrderBy>,
 }
 
 impl Default for ScrollRequest {
     fn default() -> Self {
         ScrollRequest {
-            offset: Some(0),
+            offset: Some(PointIdType::MIN),
             limit: Some(10),
             filter: None,
             with_payload: Some(WithPayloadInterface::Bool(true)),
             with_vector: Some(false),
+            order_by: None,
         }
     }
 }
@@ -119,46 +211,267 @@ pub struct ScrollResult {
     pub points: Vec<Record>,
     /// Offset which should be used to retrieve a next page result
     pub next_page_offset: Option<PointIdType>,
+    /// Value of the `order_by` field, which should be used as `start_from` to retrieve
+    /// a next page result. Only set for the ordered scroll
+    pub next_page_order_value: Option<RangeValue>,
+}
+
+/// Count request
+/// Counts the number of points which satisfy the given filter
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct CountRequest {
+    /// Look only for points which satisfies this conditions. If not provided - all points.
+    pub filter: Option<Filter>,
+    /// If true, count exact number of points. If false, return an approximate estimation,
+    /// which is much faster to obtain. Default: true
+    #[serde(default = "default_exact_count")]
+    pub exact: bool,
+}
+
+pub const fn default_exact_count() -> bool {
+    true
+}
+
+/// Result of the count request
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CountResult {
+    /// Number of points which satisfy the conditions
+    pub count: usize,
 }
 
 /// Search request
 /// Holds all conditions and parameters for the search of most similar points by vector similarity
 /// given the filtering restrictions.
-#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct SearchRequest {
     /// Look for vectors closest to this
-    pub vector: Vec<VectorElementType>,
+    pub vector: NamedVectorStruct,
     /// Look only for points which satisfies this conditions
     pub filter: Option<Filter>,
     /// Additional search params
     pub params: Option<SearchParams>,
     /// Max number of result to return
     pub top: usize,
+    /// Offset of the first result to return, may be used to paginate results.
+    /// Note: large offset values may cause performance issues
+    #[serde(default)]
+    pub offset: usize,
+    /// Define a minimal score threshold for the result: less similar results are not returned.
+    /// For distances where smaller is better (Euclid) - max distance to the query vector
+    pub score_threshold: Option<ScoreType>,
     /// Payload interface
     pub with_payload: Option<WithPayloadInterface>,
 }
 
+/// Batch of search requests
+/// All searches are performed in a single pass over the segments of the collection.
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchRequestBatch {
+    /// Search requests, results are returned in the same order
+    pub searches: Vec<SearchRequest>,
+}
+
+/// How to use positive and negative examples to look for the recommended points
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendStrategy {
+    /// Look for the points closest to the average of positive examples,
+    /// moved away from the average of negative examples.
+    /// Fastest strategy, but diverse positive examples may cancel each other out
+    AverageVector,
+    /// Score each candidate against every example separately.
+    /// If the candidate is closer to the best positive example than to the best negative one,
+    /// score is the sigmoid of similarity to the best positive example.
+    /// Otherwise - negative sigmoid of similarity to the best negative example
+    BestScore,
+    /// Score is a weighted sum of similarities to positive examples minus the weighted sum of
+    /// similarities to negative examples. Weights are defined by `positive_weights` and
+    /// `negative_weights`, default weight of each example is 1
+    Weighted,
+}
+
+impl Default for RecommendStrategy {
+    fn default() -> Self {
+        RecommendStrategy::AverageVector
+    }
+}
+
+/// Example of the recommendation request
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum RecommendExample {
+    /// Id of the stored point, which vector is used as an example
+    PointId(PointIdType),
+    /// Raw vector, e.g. embedding of the current user session
+    Vector(VectorType),
+}
+
+impl From<u64> for RecommendExample {
+    fn from(id: u64) -> Self {
+        RecommendExample::PointId(id.into())
+    }
+}
+
+impl From<PointIdType> for RecommendExample {
+    fn from(id: PointIdType) -> Self {
+        RecommendExample::PointId(id)
+    }
+}
+
+impl From<VectorType> for RecommendExample {
+    fn from(vector: VectorType) -> Self {
+        RecommendExample::Vector(vector)
+    }
+}
+
+/// Location of the points, which are used as examples of the recommendation request
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct LookupLocation {
+    /// Name of the collection to look up the example points in
+    pub collection: String,
+    /// Name of the vector of the lookup collection to use.
+    /// If not provided - the same vector name as in `using` is used
+    #[serde(default)]
+    pub vector: Option<String>,
+}
+
 /// Recommendation request
 /// Provides positive and negative examples of the vectors, which
-/// are already stored in the collection.
+/// are either stored in the collection or given as raw vectors.
 ///
 /// Service should look for the points which are closer to positive examples and at the same time
 /// further to negative examples. The concrete way of how to compare negative and positive distances
 /// is up to implementation in `segment` crate.
-#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct RecommendRequest {
-    /// Look for vectors closest to those
-    pub positive: Vec<PointIdType>,
-    /// Try to avoid vectors like this
-    pub negative: Vec<PointIdType>,
+    /// Look for vectors closest to those. Either point ids or raw vectors
+    pub positive: Vec<RecommendExample>,
+    /// Try to avoid vectors like this. Either point ids or raw vectors
+    pub negative: Vec<RecommendExample>,
     /// Look only for points which satisfies this conditions
     pub filter: Option<Filter>,
     /// Additional search params
     pub params: Option<SearchParams>,
     /// Max number of result to return
     pub top: usize,
+    /// Name of the vector space to use for recommendations.
+    /// If not provided - the default (unnamed) vector is used
+    #[serde(default)]
+    pub using: Option<String>,
+    /// How to use positive and negative examples. Default: `average_vector`
+    #[serde(default)]
+    pub strategy: RecommendStrategy,
+    /// Weights of the positive examples, in the same order as `positive`.
+    /// Only used by the `weighted` strategy
+    #[serde(default)]
+    pub positive_weights: Option<Vec<ScoreType>>,
+    /// Weights of the negative examples, in the same order as `negative`.
+    /// Only used by the `weighted` strategy
+    #[serde(default)]
+    pub negative_weights: Option<Vec<ScoreType>>,
+    /// Collection to look up the example point ids in.
+    /// If not provided - ids are looked up in the current collection
+    #[serde(default)]
+    pub lookup_from: Option<LookupLocation>,
+}
+
+/// Search request with grouping of the results by the value of the payload field.
+/// Search goes deeper until enough groups are filled or no more suitable points are found.
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchGroupsRequest {
+    /// Look for vectors closest to this
+    pub vector: NamedVectorStruct,
+    /// Look only for points which satisfies this conditions
+    pub filter: Option<Filter>,
+    /// Additional search params
+    pub params: Option<SearchParams>,
+    /// Define a minimal score threshold for the result: less similar results are not returned.
+    /// For distances where smaller is better (Euclid) - max distance to the query vector
+    pub score_threshold: Option<ScoreType>,
+    /// Payload interface
+    pub with_payload: Option<WithPayloadInterface>,
+    /// Payload field to group by, must be a keyword or an integer field.
+    /// Points without this field are not included into the result
+    pub group_by: PayloadKeyType,
+    /// Max number of points to return in each group
+    pub group_size: usize,
+    /// Max number of groups to return
+    pub groups: usize,
+}
+
+/// Recommendation request with grouping of the results by the value of the payload field
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RecommendGroupsRequest {
+    /// Look for vectors closest to those. Either point ids or raw vectors
+    pub positive: Vec<RecommendExample>,
+    /// Try to avoid vectors like this. Either point ids or raw vectors
+    pub negative: Vec<RecommendExample>,
+    /// Look only for points which satisfies this conditions
+    pub filter: Option<Filter>,
+    /// Additional search params
+    pub params: Option<SearchParams>,
+    /// Name of the vector space to use for recommendations.
+    /// If not provided - the default (unnamed) vector is used
+    #[serde(default)]
+    pub using: Option<String>,
+    /// How to use positive and negative examples. Default: `average_vector`
+    #[serde(default)]
+    pub strategy: RecommendStrategy,
+    /// Weights of the positive examples, in the same order as `positive`.
+    /// Only used by the `weighted` strategy
+    #[serde(default)]
+    pub positive_weights: Option<Vec<ScoreType>>,
+    /// Weights of the negative examples, in the same order as `negative`.
+    /// Only used by the `weighted` strategy
+    #[serde(default)]
+    pub negative_weights: Option<Vec<ScoreType>>,
+    /// Collection to look up the example point ids in.
+    /// If not provided - ids are looked up in the current collection
+    #[serde(default)]
+    pub lookup_from: Option<LookupLocation>,
+    /// Payload interface
+    pub with_payload: Option<WithPayloadInterface>,
+    /// Payload field to group by, must be a keyword or an integer field.
+    /// Points without this field are not included into the result
+    pub group_by: PayloadKeyType,
+    /// Max number of points to return in each group
+    pub group_size: usize,
+    /// Max number of groups to return
+    pub groups: usize,
+}
+
+/// Value of the payload field, which identifies a group
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum GroupId {
+    Keyword(String),
+    Integer(IntPayloadType),
+}
+
+/// Points which share the same value of the grouping field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct PointGroup {
+    /// Value of the grouping field
+    pub id: GroupId,
+    /// Points of the group, ordered by score
+    pub hits: Vec<ScoredPoint>,
+}
+
+/// Result of the grouped search
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct GroupsResult {
+    /// Groups, ordered by the score of the best point in the group
+    pub groups: Vec<PointGroup>,
 }
 
 #[derive(Error, Debug, Clone)]
@@ -180,6 +493,18 @@ impl From<OperationError> for CollectionError {
             OperationError::WrongVector { .. } => Self::BadInput {
                 description: format!("{}", err),
             },
+            OperationError::VectorNameNotExists { .. } => Self::BadInput {
+                description: format!("{}", err),
+            },
+            OperationError::MissedVectorName { .. } => Self::BadInput {
+                description: format!("{}", err),
+            },
+            OperationError::WrongVectorType { .. } => Self::BadInput {
+                description: format!("{}", err),
+            },
+            OperationError::WrongSparseVector { .. } => Self::BadInput {
+                description: format!("{}", err),
+            },
             OperationError::PointIdError { missed_point_id } => Self::NotFound { missed_point_id },
             OperationError::ServiceError { description } => {
                 Self::ServiceError { error: description }
@@ -0,0 +1,198 @@
+use segment::spaces::metric::Metric;
+use segment::types::{ScoreType, VectorElementType};
+
+use crate::operations::types::{CollectionError, CollectionResult, RecommendStrategy};
+
+/// Example vectors of the recommendation request together with their weights
+pub struct RecommendExamples {
+    pub positive: Vec<(Vec<VectorElementType>, ScoreType)>,
+    pub negative: Vec<(Vec<VectorElementType>, ScoreType)>,
+}
+
+/// Check, that weights are only provided for the `weighted` strategy and match the examples
+///
+/// # Result
+///
+/// Weights of the examples, 1 for each example without explicit weight
+pub fn example_weights(
+    strategy: RecommendStrategy,
+    weights: Option<&[ScoreType]>,
+    examples_count: usize,
+) -> CollectionResult<Vec<ScoreType>> {
+    match weights {
+        None => Ok(vec![1.0; examples_count]),
+        Some(_) if strategy != RecommendStrategy::Weighted => Err(CollectionError::BadRequest {
+            description: "Weights of the examples are only supported by `weighted` strategy"
+                .to_owned(),
+        }),
+        Some(weights) if weights.len() != examples_count => Err(CollectionError::BadRequest {
+            description: format!(
+                "Number of weights ({}) should be equal to the number of examples ({})",
+                weights.len(),
+                examples_count
+            ),
+        }),
+        Some(weights) => Ok(weights.to_vec()),
+    }
+}
+
+/// Scoring of the candidates against each example separately.
+/// Recommend strategies, which are not listed, don't score examples separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExampleScoring {
+    /// Weighted sum of the similarities to the examples
+    Weighted,
+    /// Similarity to the closest example
+    BestScore,
+}
+
+fn sigmoid(x: ScoreType) -> ScoreType {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Score the candidate vector against every example separately
+/// Larger score is always better, regardless of the distance metric
+pub fn score_by_examples(
+    scoring: ExampleScoring,
+    metric: &dyn Metric,
+    examples: &RecommendExamples,
+    vector: &[VectorElementType],
+) -> ScoreType {
+    let similarities = |examples: &[(Vec<VectorElementType>, ScoreType)]| {
+        examples
+            .iter()
+            .map(|(example, weight)| (metric.similarity(example, vector), *weight))
+            .collect::<Vec<_>>()
+    };
+    let positive = similarities(&examples.positive);
+    let negative = similarities(&examples.negative);
+
+    match scoring {
+        ExampleScoring::Weighted => {
+            let weighted_sum = |scores: &[(ScoreType, ScoreType)]| {
+                scores
+                    .iter()
+                    .map(|(score, weight)| score * weight)
+                    .sum::<ScoreType>()
+            };
+            weighted_sum(&positive) - weighted_sum(&negative)
+        }
+        ExampleScoring::BestScore => {
+            let best = |scores: &[(ScoreType, ScoreType)]| {
+                scores
+                    .iter()
+                    .map(|(score, _)| *score)
+                    .fold(ScoreType::NEG_INFINITY, ScoreType::max)
+            };
+            let best_positive = best(&positive);
+            let best_negative = best(&negative);
+            if best_positive > best_negative {
+                sigmoid(best_positive)
+            } else {
+                -sigmoid(best_negative)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::spaces::simple::{DotProductMetric, EuclidMetric};
+
+    use super::*;
+
+    fn examples(
+        positive: Vec<Vec<VectorElementType>>,
+        negative: Vec<Vec<VectorElementType>>,
+    ) -> RecommendExamples {
+        RecommendExamples {
+            positive: positive.into_iter().map(|x| (x, 1.0)).collect(),
+            negative: negative.into_iter().map(|x| (x, 1.0)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_best_score() {
+        let examples = examples(
+            vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0]],
+            vec![vec![0.0, 0.0, 1.0]],
+        );
+
+        // Diverse positive examples do not cancel each other out
+        let close_to_first = score_by_examples(
+            ExampleScoring::BestScore,
+            &DotProductMetric {},
+            &examples,
+            &[0.9, 0.0, 0.1],
+        );
+        let close_to_second = score_by_examples(
+            ExampleScoring::BestScore,
+            &DotProductMetric {},
+            &examples,
+            &[0.0, 0.8, 0.1],
+        );
+        let close_to_negative = score_by_examples(
+            ExampleScoring::BestScore,
+            &DotProductMetric {},
+            &examples,
+            &[0.1, 0.1, 0.9],
+        );
+        let closer_to_negative = score_by_examples(
+            ExampleScoring::BestScore,
+            &DotProductMetric {},
+            &examples,
+            &[0.1, 0.1, 1.0],
+        );
+        assert!(close_to_first > close_to_second);
+        assert!(close_to_second > 0.0);
+        assert!(close_to_negative < 0.0);
+        assert!(closer_to_negative < close_to_negative);
+
+        // Larger similarity is better for Euclid as well
+        let euclid_close = score_by_examples(
+            ExampleScoring::BestScore,
+            &EuclidMetric {},
+            &examples,
+            &[0.9, 0.1, 0.0],
+        );
+        let euclid_far = score_by_examples(
+            ExampleScoring::BestScore,
+            &EuclidMetric {},
+            &examples,
+            &[0.6, 0.3, 0.0],
+        );
+        assert!(euclid_close > euclid_far);
+    }
+
+    #[test]
+    fn test_weighted_score() {
+        let mut examples = examples(
+            vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0]],
+            vec![vec![0.0, 0.0, 1.0]],
+        );
+        examples.positive[1].1 = 2.0;
+        examples.negative[0].1 = 0.5;
+
+        let score = score_by_examples(
+            ExampleScoring::Weighted,
+            &DotProductMetric {},
+            &examples,
+            &[1.0, 1.0, 1.0],
+        );
+        assert!((score - (1.0 + 2.0 - 0.5)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_example_weights() {
+        assert_eq!(
+            example_weights(RecommendStrategy::BestScore, None, 2).unwrap(),
+            vec![1.0, 1.0]
+        );
+        assert_eq!(
+            example_weights(RecommendStrategy::Weighted, Some(&[0.5, 2.0][..]), 2).unwrap(),
+            vec![0.5, 2.0]
+        );
+        assert!(example_weights(RecommendStrategy::Weighted, Some(&[0.5][..]), 2).is_err());
+        assert!(example_weights(RecommendStrategy::BestScore, Some(&[0.5][..]), 1).is_err());
+    }
+}
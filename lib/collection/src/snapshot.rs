@@ -0,0 +1,181 @@
+use std::fs::{remove_file, rename, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{CollectionConfig, COLLECTION_CONFIG_FILE};
+use crate::operations::types::{CollectionError, CollectionResult};
+
+pub const SNAPSHOT_ALIASES_FILE: &str = "aliases.json";
+pub const SNAPSHOT_FILE_EXTENSION: &str = "snapshot";
+
+/// Directories of the collection, which are required to restore it
+const SNAPSHOT_DIRS: [&str; 2] = ["segments", "wal"];
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct SnapshotDescription {
+    /// Name of the snapshot file
+    pub name: String,
+    /// Size of the snapshot file in bytes
+    pub size: u64,
+}
+
+/// Packs persisted data of the collection into a single tar archive.
+/// Collection should not be modified while packing, see [`crate::collection::Collection::create_snapshot`]
+///
+/// # Arguments
+///
+/// * `collection_path` - directory of the collection
+/// * `snapshot_path` - path of the archive to create. Archive is written into a temporary file first,
+///    so the incomplete snapshot is never visible by this path
+/// * `aliases` - aliases of the collection, which should be restored together with it
+pub fn pack_snapshot(
+    collection_path: &Path,
+    snapshot_path: &Path,
+    aliases: &[String],
+) -> CollectionResult<()> {
+    let tmp_path = snapshot_path.with_extension("tmp");
+    let pack_res = (|| -> CollectionResult<()> {
+        let mut builder = tar::Builder::new(BufWriter::new(File::create(&tmp_path)?));
+        builder.append_path_with_name(
+            collection_path.join(COLLECTION_CONFIG_FILE),
+            COLLECTION_CONFIG_FILE,
+        )?;
+        for dir in SNAPSHOT_DIRS {
+            builder.append_dir_all(dir, collection_path.join(dir))?;
+        }
+        let aliases_bytes = serde_json::to_vec(aliases)?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(aliases_bytes.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, SNAPSHOT_ALIASES_FILE, aliases_bytes.as_slice())?;
+        builder.into_inner()?.flush()?;
+        Ok(())
+    })();
+
+    match pack_res {
+        Ok(()) => rename(&tmp_path, snapshot_path).map_err(|err| CollectionError::ServiceError {
+            error: format!("Can't write snapshot {:?}, error: {}", snapshot_path, err),
+        }),
+        Err(err) => {
+            remove_file(&tmp_path).ok();
+            Err(err)
+        }
+    }
+}
+
+/// Unpacks snapshot archive into the directory of the collection
+///
+/// # Result
+///
+/// Aliases of the collection, stored in the snapshot
+pub fn unpack_snapshot(
+    snapshot_path: &Path,
+    collection_path: &Path,
+) -> CollectionResult<Vec<String>> {
+    let snapshot_file = File::open(snapshot_path).map_err(|err| CollectionError::BadInput {
+        description: format!("Can't open snapshot {:?}, error: {}", snapshot_path, err),
+    })?;
+    let mut archive = tar::Archive::new(BufReader::new(snapshot_file));
+    archive
+        .unpack(collection_path)
+        .map_err(|err| CollectionError::BadInput {
+            description: format!("Can't unpack snapshot {:?}, error: {}", snapshot_path, err),
+        })?;
+
+    CollectionConfig::load(collection_path).map_err(|err| CollectionError::BadInput {
+        description: format!(
+            "Snapshot {:?} has no valid collection config, error: {}",
+            snapshot_path, err
+        ),
+    })?;
+
+    let aliases_path = collection_path.join(SNAPSHOT_ALIASES_FILE);
+    let aliases = if aliases_path.exists() {
+        let aliases = serde_json::from_reader(BufReader::new(File::open(&aliases_path)?))?;
+        remove_file(&aliases_path)?;
+        aliases
+    } else {
+        vec![]
+    };
+    Ok(aliases)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{create_dir_all, read_to_string, write};
+
+    use segment::types::Distance;
+    use tempdir::TempDir;
+
+    use crate::collection_builder::optimizers_builder::OptimizersConfig;
+    use crate::config::{CollectionParams, VectorParams, VectorsConfig};
+
+    use super::*;
+
+    #[test]
+    fn test_pack_unpack_snapshot() {
+        let collection_dir = TempDir::new("collection").unwrap();
+        let snapshot_dir = TempDir::new("snapshots").unwrap();
+        let restored_dir = TempDir::new("restored").unwrap();
+
+        let config = CollectionConfig {
+            params: CollectionParams {
+                vectors: VectorsConfig::Single(VectorParams {
+                    size: 4,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                }),
+                quantization_config: None,
+                product_quantization_config: None,
+                sparse_vectors: None,
+                payload_schema: None,
+            },
+            hnsw_config: Default::default(),
+            optimizer_config: OptimizersConfig {
+                deleted_threshold: 0.2,
+                vacuum_min_vector_number: 1000,
+                max_segment_number: 5,
+                memmap_threshold: 50000,
+                indexing_threshold: 20000,
+                payload_indexing_threshold: 10000,
+                flush_interval_sec: 1,
+                max_optimization_threads: 1,
+            },
+            wal_config: Default::default(),
+        };
+        config.save(collection_dir.path()).unwrap();
+        for dir in SNAPSHOT_DIRS {
+            create_dir_all(collection_dir.path().join(dir)).unwrap();
+        }
+        write(collection_dir.path().join("wal").join("open-1"), "wal data").unwrap();
+        create_dir_all(collection_dir.path().join("temp_segments")).unwrap();
+
+        let snapshot_path = snapshot_dir.path().join("test.snapshot");
+        let aliases = vec!["alias1".to_string(), "alias2".to_string()];
+        pack_snapshot(collection_dir.path(), &snapshot_path, &aliases).unwrap();
+        assert!(snapshot_path.exists());
+        assert!(!snapshot_path.with_extension("tmp").exists());
+
+        let restored_aliases = unpack_snapshot(&snapshot_path, restored_dir.path()).unwrap();
+        assert_eq!(restored_aliases, aliases);
+        assert!(restored_dir.path().join("segments").is_dir());
+        assert!(!restored_dir.path().join("temp_segments").exists());
+        assert!(!restored_dir.path().join(SNAPSHOT_ALIASES_FILE).exists());
+        assert_eq!(
+            read_to_string(restored_dir.path().join("wal").join("open-1")).unwrap(),
+            "wal data"
+        );
+    }
+
+    #[test]
+    fn test_unpack_invalid_snapshot() {
+        let restored_dir = TempDir::new("restored").unwrap();
+        let res = unpack_snapshot(Path::new("/not/existing.snapshot"), restored_dir.path());
+        assert!(matches!(res, Err(CollectionError::BadInput { .. })));
+    }
+}
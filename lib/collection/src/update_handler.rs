@@ -124,6 +124,16 @@ impl UpdateHandler {
         Ok(())
     }
 
+    /// Wait for the already running optimizations to finish.
+    /// Should be called after [`Self::wait_workers_stops`], so no new optimizations are scheduled.
+    pub async fn wait_optimizations_finish(&mut self) {
+        for handle in self.optimization_handles.lock().await.drain(..) {
+            handle.await.unwrap_or_else(|err| {
+                debug!("Optimization is not finished: {}", err);
+            });
+        }
+    }
+
     /// Checks if there are any failed operations.
     /// If so - attempts to re-apply all failed operations.
     async fn try_recover(
@@ -22,8 +22,11 @@ async fn test_collection_reloading() {
         let collection = load_collection(collection_dir.path());
         let insert_points = CollectionUpdateOperations::PointOperation(
             PointOperations::UpsertPoints(PointInsertOperations::BatchPoints {
-                ids: vec![0, 1],
-                vectors: vec![vec![1.0, 0.0, 1.0, 1.0], vec![1.0, 0.0, 1.0, 0.0]],
+                ids: vec![0.into(), 1.into()],
+                vectors: vec![
+                    vec![1.0, 0.0, 1.0, 1.0].into(),
+                    vec![1.0, 0.0, 1.0, 0.0].into(),
+                ],
                 payloads: None,
             }),
         );
@@ -41,8 +44,11 @@ async fn test_collection_payload_reloading() {
         let collection = simple_collection_fixture(collection_dir.path()).await;
         let insert_points = CollectionUpdateOperations::PointOperation(
             PointOperations::UpsertPoints(PointInsertOperations::BatchPoints {
-                ids: vec![0, 1],
-                vectors: vec![vec![1.0, 0.0, 1.0, 1.0], vec![1.0, 0.0, 1.0, 0.0]],
+                ids: vec![0.into(), 1.into()],
+                vectors: vec![
+                    vec![1.0, 0.0, 1.0, 1.0].into(),
+                    vec![1.0, 0.0, 1.0, 0.0].into(),
+                ],
                 payloads: serde_json::from_str(
                     r#"[{ "k": { "type": "keyword", "value": "v1" } }, { "k": "v2"}]"#,
                 )
@@ -58,11 +64,12 @@ async fn test_collection_payload_reloading() {
     let res = collection
         .scroll_by(
             ScrollRequest {
-                offset: Some(0),
+                offset: Some(0.into()),
                 limit: Some(10),
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: Some(true),
+                order_by: None,
             },
             &searcher,
         )
@@ -95,8 +102,8 @@ async fn test_collection_payload_custom_payload() {
         let collection = simple_collection_fixture(collection_dir.path()).await;
         let insert_points = CollectionUpdateOperations::PointOperation(
             PointOperations::UpsertPoints(PointInsertOperations::BatchPoints {
-                ids: vec![0, 1],
-                vectors: vec![vec![1.0, 0.0, 1.0, 1.0], vec![1.0, 0.0, 1.0, 0.0]],
+                ids: vec![0.into(), 1.into()],
+                vectors: vec![vec![1.0, 0.0, 1.0, 1.0].into(), vec![1.0, 0.0, 1.0, 0.0].into()],
                 payloads: serde_json::from_str(
                     r#"[{ "k": { "type": "keyword", "value": "v1" } }, { "k": "v2" , "v": "v3", "v2": "v4"}]"#,
                 )
@@ -113,11 +120,12 @@ async fn test_collection_payload_custom_payload() {
     let res_with_custom_payload = collection
         .scroll_by(
             ScrollRequest {
-                offset: Some(0),
+                offset: Some(0.into()),
                 limit: Some(10),
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Fields(vec![String::from("v")])),
                 with_vector: Some(true),
+                order_by: None,
             },
             &searcher,
         )
@@ -149,7 +157,7 @@ async fn test_collection_payload_custom_payload() {
     let res_with_custom_payload = collection
         .scroll_by(
             ScrollRequest {
-                offset: Some(0),
+                offset: Some(0.into()),
                 limit: Some(10),
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Selector(PayloadSelector {
@@ -157,6 +165,7 @@ async fn test_collection_payload_custom_payload() {
                     exclude: vec![String::from("v")],
                 })),
                 with_vector: Some(false),
+                order_by: None,
             },
             &searcher,
         )
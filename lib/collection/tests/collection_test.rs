@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+use itertools::Itertools;
 use tempdir::TempDir;
 use tokio::runtime::Handle;
 
@@ -8,10 +9,15 @@ use collection::collection_builder::collection_loader::load_collection;
 use collection::operations::payload_ops::PayloadOps;
 use collection::operations::point_ops::PointInsertOperations::{BatchPoints, PointsList};
 use collection::operations::point_ops::{PointOperations, PointStruct};
-use collection::operations::types::{RecommendRequest, ScrollRequest, SearchRequest, UpdateStatus};
-use collection::operations::CollectionUpdateOperations;
+use collection::operations::types::{
+    CollectionError, CountRequest, GroupId, LookupLocation, NamedVector, NamedVectorStruct,
+    RecommendExample, RecommendGroupsRequest, RecommendRequest, RecommendStrategy, ScrollRequest,
+    ScrollResult, SearchGroupsRequest, SearchRequest, UpdateStatus, VectorStruct,
+};
+use collection::operations::{CollectionUpdateOperations, CreateFieldIndex, FieldIndexOperations};
 use segment::types::{
-    PayloadInterface, PayloadKeyType, PayloadVariant, WithPayload, WithPayloadInterface,
+    Direction, OrderBy, PayloadInterface, PayloadKeyType, PayloadType, PayloadVariant, PointIdType,
+    RangeValue, SparseVector, Vector, WithPayload, WithPayloadInterface,
 };
 
 use crate::common::simple_collection_fixture;
@@ -28,13 +34,13 @@ async fn test_collection_updater() {
 
     let insert_points =
         CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
-            ids: vec![0, 1, 2, 3, 4],
+            ids: vec![0.into(), 1.into(), 2.into(), 3.into(), 4.into()],
             vectors: vec![
-                vec![1.0, 0.0, 1.0, 1.0],
-                vec![1.0, 0.0, 1.0, 0.0],
-                vec![1.0, 1.0, 1.0, 1.0],
-                vec![1.0, 1.0, 0.0, 1.0],
-                vec![1.0, 0.0, 0.0, 0.0],
+                vec![1.0, 0.0, 1.0, 1.0].into(),
+                vec![1.0, 0.0, 1.0, 0.0].into(),
+                vec![1.0, 1.0, 1.0, 1.0].into(),
+                vec![1.0, 1.0, 0.0, 1.0].into(),
+                vec![1.0, 0.0, 0.0, 0.0].into(),
             ],
             payloads: None,
         }));
@@ -49,11 +55,13 @@ async fn test_collection_updater() {
     }
 
     let search_request = SearchRequest {
-        vector: vec![1.0, 1.0, 1.0, 1.0],
+        vector: vec![1.0, 1.0, 1.0, 1.0].into(),
         with_payload: None,
         filter: None,
         params: None,
         top: 3,
+        offset: 0,
+        score_threshold: None,
     };
 
     let segment_searcher = SimpleCollectionSearcher::new();
@@ -68,7 +76,7 @@ async fn test_collection_updater() {
     match search_res {
         Ok(res) => {
             assert_eq!(res.len(), 3);
-            assert_eq!(res[0].id, 2);
+            assert_eq!(res[0].id, 2.into());
             assert!(res[0].payload.is_none());
         }
         Err(err) => panic!("search failed: {:?}", err),
@@ -83,8 +91,11 @@ async fn test_collection_search_with_payload() {
 
     let insert_points =
         CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
-            ids: vec![0, 1],
-            vectors: vec![vec![1.0, 0.0, 1.0, 1.0], vec![1.0, 0.0, 1.0, 0.0]],
+            ids: vec![0.into(), 1.into()],
+            vectors: vec![
+                vec![1.0, 0.0, 1.0, 1.0].into(),
+                vec![1.0, 0.0, 1.0, 0.0].into(),
+            ],
             payloads: serde_json::from_str(
                 r#"[{ "k": { "type": "keyword", "value": "v1" } }, { "k": "v2" , "v": "v3"}]"#,
             )
@@ -101,11 +112,13 @@ async fn test_collection_search_with_payload() {
     }
 
     let search_request = SearchRequest {
-        vector: vec![1.0, 0.0, 1.0, 1.0],
+        vector: vec![1.0, 0.0, 1.0, 1.0].into(),
         with_payload: Some(WithPayloadInterface::Bool(true)),
         filter: None,
         params: None,
         top: 3,
+        offset: 0,
+        score_threshold: None,
     };
 
     let segment_searcher = SimpleCollectionSearcher::new();
@@ -120,7 +133,7 @@ async fn test_collection_search_with_payload() {
     match search_res {
         Ok(res) => {
             assert_eq!(res.len(), 2);
-            assert_eq!(res[0].id, 0);
+            assert_eq!(res[0].id, 0.into());
             if let Some(payload) = &res[0].payload {
                 assert_eq!(payload.len(), 1)
             } else {
@@ -139,13 +152,13 @@ async fn test_collection_loading() {
         let collection = simple_collection_fixture(collection_dir.path()).await;
         let insert_points = CollectionUpdateOperations::PointOperation(
             PointOperations::UpsertPoints(BatchPoints {
-                ids: vec![0, 1, 2, 3, 4],
+                ids: vec![0.into(), 1.into(), 2.into(), 3.into(), 4.into()],
                 vectors: vec![
-                    vec![1.0, 0.0, 1.0, 1.0],
-                    vec![1.0, 0.0, 1.0, 0.0],
-                    vec![1.0, 1.0, 1.0, 1.0],
-                    vec![1.0, 1.0, 0.0, 1.0],
-                    vec![1.0, 0.0, 0.0, 0.0],
+                    vec![1.0, 0.0, 1.0, 1.0].into(),
+                    vec![1.0, 0.0, 1.0, 0.0].into(),
+                    vec![1.0, 1.0, 1.0, 1.0].into(),
+                    vec![1.0, 1.0, 0.0, 1.0].into(),
+                    vec![1.0, 0.0, 0.0, 0.0].into(),
                 ],
                 payloads: None,
             }),
@@ -162,7 +175,7 @@ async fn test_collection_loading() {
 
         let assign_payload = CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload {
             payload,
-            points: vec![2, 3],
+            points: vec![2.into(), 3.into()],
         });
 
         collection.update(assign_payload, true).await.unwrap();
@@ -173,7 +186,7 @@ async fn test_collection_loading() {
     let retrieved = segment_searcher
         .retrieve(
             loaded_collection.segments(),
-            &[1, 2],
+            &[1.into(), 2.into()],
             &WithPayload::from(true),
             true,
         )
@@ -183,7 +196,7 @@ async fn test_collection_loading() {
     assert_eq!(retrieved.len(), 2);
 
     for record in retrieved {
-        if record.id == 2 {
+        if record.id == 2.into() {
             let non_empty_payload = record.payload.unwrap();
 
             assert_eq!(non_empty_payload.len(), 1)
@@ -195,8 +208,11 @@ async fn test_collection_loading() {
 fn test_deserialization() {
     let insert_points =
         CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
-            ids: vec![0, 1],
-            vectors: vec![vec![1.0, 0.0, 1.0, 1.0], vec![1.0, 0.0, 1.0, 0.0]],
+            ids: vec![0.into(), 1.into()],
+            vectors: vec![
+                vec![1.0, 0.0, 1.0, 1.0].into(),
+                vec![1.0, 0.0, 1.0, 0.0].into(),
+            ],
             payloads: None,
         }));
     let json_str = serde_json::to_string_pretty(&insert_points).unwrap();
@@ -213,13 +229,13 @@ fn test_deserialization2() {
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
         PointsList(vec![
             PointStruct {
-                id: 0,
-                vector: vec![1.0, 0.0, 1.0, 1.0],
+                id: 0.into(),
+                vector: vec![1.0, 0.0, 1.0, 1.0].into(),
                 payload: None,
             },
             PointStruct {
-                id: 1,
-                vector: vec![1.0, 0.0, 1.0, 0.0],
+                id: 1.into(),
+                vector: vec![1.0, 0.0, 1.0, 0.0].into(),
                 payload: None,
             },
         ]),
@@ -234,6 +250,57 @@ fn test_deserialization2() {
     let _read_obj2: CollectionUpdateOperations = rmp_serde::from_read_ref(&raw_bytes).unwrap();
 }
 
+#[test]
+fn test_named_vectors_deserialization() {
+    let single: PointStruct =
+        serde_json::from_str(r#"{ "id": 1, "vector": [1.0, 0.0, 1.0, 1.0] }"#).unwrap();
+    assert_eq!(
+        single.vector,
+        VectorStruct::Single(vec![1.0, 0.0, 1.0, 1.0])
+    );
+
+    let multi: PointStruct = serde_json::from_str(
+        r#"{ "id": 2, "vector": { "image": [1.0, 0.0], "text": [0.0, 1.0, 1.0] } }"#,
+    )
+    .unwrap();
+    let vectors = multi.vector.into_all_vectors();
+    assert_eq!(vectors.len(), 2);
+    assert_eq!(vectors["text"], Vector::Dense(vec![0.0, 1.0, 1.0]));
+
+    let search_request: SearchRequest = serde_json::from_str(
+        r#"{ "vector": { "name": "image", "vector": [1.0, 0.0] }, "top": 3 }"#,
+    )
+    .unwrap();
+    assert_eq!(search_request.vector.get_name(), "image");
+    assert!(matches!(
+        search_request.vector,
+        NamedVectorStruct::Named(NamedVector { vector, .. }) if vector == vec![1.0, 0.0]
+    ));
+
+    let sparse: PointStruct = serde_json::from_str(
+        r#"{ "id": 3, "vector": { "text": { "indices": [7, 2], "values": [0.5, 1.0] } } }"#,
+    )
+    .unwrap();
+    let vectors = sparse.vector.into_all_vectors();
+    assert_eq!(
+        vectors["text"],
+        Vector::Sparse(SparseVector {
+            indices: vec![7, 2],
+            values: vec![0.5, 1.0],
+        })
+    );
+
+    let sparse_request: SearchRequest = serde_json::from_str(
+        r#"{ "vector": { "name": "text", "vector": { "indices": [2], "values": [1] } }, "top": 3}"#,
+    )
+    .unwrap();
+    assert_eq!(sparse_request.vector.get_name(), "text");
+    assert!(matches!(
+        sparse_request.vector,
+        NamedVectorStruct::Sparse(_)
+    ));
+}
+
 #[tokio::test]
 async fn test_recommendation_api() {
     let collection_dir = TempDir::new("collection").unwrap();
@@ -241,17 +308,17 @@ async fn test_recommendation_api() {
 
     let insert_points =
         CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
-            ids: vec![0, 1, 2, 3, 4, 5, 6, 7, 8],
+            ids: (0..9).map(|x| x.into()).collect(),
             vectors: vec![
-                vec![0.0, 0.0, 1.0, 1.0],
-                vec![1.0, 0.0, 0.0, 0.0],
-                vec![1.0, 0.0, 0.0, 0.0],
-                vec![0.0, 1.0, 0.0, 0.0],
-                vec![0.0, 1.0, 0.0, 0.0],
-                vec![0.0, 0.0, 1.0, 0.0],
-                vec![0.0, 0.0, 1.0, 0.0],
-                vec![0.0, 0.0, 0.0, 1.0],
-                vec![0.0, 0.0, 0.0, 1.0],
+                vec![0.0, 0.0, 1.0, 1.0].into(),
+                vec![1.0, 0.0, 0.0, 0.0].into(),
+                vec![1.0, 0.0, 0.0, 0.0].into(),
+                vec![0.0, 1.0, 0.0, 0.0].into(),
+                vec![0.0, 1.0, 0.0, 0.0].into(),
+                vec![0.0, 0.0, 1.0, 0.0].into(),
+                vec![0.0, 0.0, 1.0, 0.0].into(),
+                vec![0.0, 0.0, 0.0, 1.0].into(),
+                vec![0.0, 0.0, 0.0, 1.0].into(),
             ],
             payloads: None,
         }));
@@ -261,12 +328,18 @@ async fn test_recommendation_api() {
     let result = collection
         .recommend_by(
             Arc::new(RecommendRequest {
-                positive: vec![0],
-                negative: vec![8],
+                positive: vec![0.into()],
+                negative: vec![8.into()],
                 filter: None,
                 params: None,
                 top: 5,
+                using: None,
+                strategy: Default::default(),
+                positive_weights: None,
+                negative_weights: None,
+                lookup_from: None,
             }),
+            None,
             &segment_searcher,
             &Handle::current(),
         )
@@ -275,7 +348,256 @@ async fn test_recommendation_api() {
     assert!(!result.is_empty());
     let top1 = &result[0];
 
-    assert!(top1.id == 5 || top1.id == 6);
+    assert!(top1.id == 5.into() || top1.id == 6.into());
+}
+
+#[tokio::test]
+async fn test_recommendation_strategies() {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let collection = simple_collection_fixture(collection_dir.path()).await;
+
+    let insert_points =
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
+            ids: (0..9).map(|x| x.into()).collect(),
+            vectors: vec![
+                vec![0.0, 0.0, 1.0, 1.0].into(),
+                vec![1.0, 0.0, 0.0, 0.0].into(),
+                vec![1.0, 0.0, 0.0, 0.0].into(),
+                vec![0.0, 1.0, 0.0, 0.0].into(),
+                vec![0.0, 1.0, 0.0, 0.0].into(),
+                vec![0.0, 0.0, 1.0, 0.0].into(),
+                vec![0.0, 0.0, 1.0, 0.0].into(),
+                vec![0.0, 0.0, 0.0, 1.0].into(),
+                vec![0.0, 0.0, 0.0, 1.0].into(),
+            ],
+            payloads: None,
+        }));
+
+    collection.update(insert_points, true).await.unwrap();
+    let segment_searcher = SimpleCollectionSearcher::new();
+    let handle = Handle::current();
+    let (collection, segment_searcher, handle) = (&collection, &segment_searcher, &handle);
+    let recommend = move |strategy: RecommendStrategy, positive_weights: Option<Vec<f32>>| {
+        collection.recommend_by(
+            Arc::new(RecommendRequest {
+                positive: vec![1.into(), 3.into()],
+                negative: vec![8.into()],
+                filter: None,
+                params: None,
+                top: 2,
+                using: None,
+                strategy,
+                positive_weights,
+                negative_weights: None,
+                lookup_from: None,
+            }),
+            None,
+            segment_searcher,
+            handle,
+        )
+    };
+
+    // Both diverse positive examples are represented in the result
+    let result = recommend(RecommendStrategy::BestScore, None).await.unwrap();
+    let ids: HashSet<_> = result.iter().map(|point| point.id).collect();
+    assert_eq!(
+        ids,
+        HashSet::from([PointIdType::from(2), PointIdType::from(4)])
+    );
+
+    let result = recommend(RecommendStrategy::Weighted, Some(vec![1.0, 3.0]))
+        .await
+        .unwrap();
+    assert_eq!(result[0].id, 4.into());
+    assert!(result[0].score > result[1].score);
+
+    // Weights are not supported by other strategies
+    let result = recommend(RecommendStrategy::AverageVector, Some(vec![1.0, 3.0])).await;
+    assert!(matches!(result, Err(CollectionError::BadRequest { .. })));
+}
+
+#[tokio::test]
+async fn test_recommendation_by_vectors_and_lookup() {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let collection = simple_collection_fixture(collection_dir.path()).await;
+    let catalog_dir = TempDir::new("catalog").unwrap();
+    let catalog = simple_collection_fixture(catalog_dir.path()).await;
+
+    let insert_points =
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
+            ids: (0..9).map(|x| x.into()).collect(),
+            vectors: vec![
+                vec![0.0, 0.0, 1.0, 1.0].into(),
+                vec![1.0, 0.0, 0.0, 0.0].into(),
+                vec![1.0, 0.0, 0.0, 0.0].into(),
+                vec![0.0, 1.0, 0.0, 0.0].into(),
+                vec![0.0, 1.0, 0.0, 0.0].into(),
+                vec![0.0, 0.0, 1.0, 0.0].into(),
+                vec![0.0, 0.0, 1.0, 0.0].into(),
+                vec![0.0, 0.0, 0.0, 1.0].into(),
+                vec![0.0, 0.0, 0.0, 1.0].into(),
+            ],
+            payloads: None,
+        }));
+    collection.update(insert_points, true).await.unwrap();
+
+    let insert_catalog_points =
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
+            ids: vec![1.into()],
+            vectors: vec![vec![0.0, 0.0, 1.0, -1.0].into()],
+            payloads: None,
+        }));
+    catalog.update(insert_catalog_points, true).await.unwrap();
+
+    let segment_searcher = SimpleCollectionSearcher::new();
+    let handle = Handle::current();
+    let (collection, catalog, segment_searcher, handle) =
+        (&collection, &catalog, &segment_searcher, &handle);
+    let recommend = move |positive: Vec<RecommendExample>,
+                          lookup_collection: Option<&'static str>| {
+        let lookup = lookup_collection.map(|_| catalog);
+        async move {
+            collection
+                .recommend_by(
+                    Arc::new(RecommendRequest {
+                        positive,
+                        negative: vec![],
+                        filter: None,
+                        params: None,
+                        top: 2,
+                        using: None,
+                        strategy: Default::default(),
+                        positive_weights: None,
+                        negative_weights: None,
+                        lookup_from: lookup_collection.map(|name| LookupLocation {
+                            collection: name.to_string(),
+                            vector: None,
+                        }),
+                    }),
+                    lookup,
+                    segment_searcher,
+                    handle,
+                )
+                .await
+        }
+    };
+
+    // Raw vector is not excluded from the result, unlike the stored example point
+    let result = recommend(vec![vec![0.0, 1.0, 0.0, 0.0].into(), 3.into()], None)
+        .await
+        .unwrap();
+    let ids: HashSet<_> = result.iter().map(|point| point.id).collect();
+    assert!(ids.contains(&4.into()));
+    assert!(!ids.contains(&3.into()));
+
+    // Point 1 of the catalog is an example, point 1 of the collection is a candidate
+    let result = recommend(vec![1.into()], Some("catalog")).await.unwrap();
+    let ids: HashSet<_> = result.iter().map(|point| point.id).collect();
+    assert_eq!(
+        ids,
+        HashSet::from([PointIdType::from(5), PointIdType::from(6)])
+    );
+
+    let result = recommend(vec![vec![1.0, 0.0].into()], None).await;
+    assert!(matches!(result, Err(CollectionError::BadRequest { .. })));
+
+    let result = recommend(vec![100.into()], Some("catalog")).await;
+    assert!(matches!(result, Err(CollectionError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_scroll_order_by() {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let collection = simple_collection_fixture(collection_dir.path()).await;
+    let segment_searcher = SimpleCollectionSearcher::new();
+
+    let insert_points =
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
+            ids: (0..6).map(|x| x.into()).collect(),
+            vectors: (0..6).map(|_| vec![1.0, 0.0, 1.0, 1.0].into()).collect(),
+            payloads: serde_json::from_str(
+                r#"[
+                    { "created_at": 3 },
+                    { "created_at": 1 },
+                    { "created_at": 3 },
+                    { "created_at": 5 },
+                    { "title": "no value" },
+                    { "created_at": [2, 7] }
+                ]"#,
+            )
+            .unwrap(),
+        }));
+    collection.update(insert_points, true).await.unwrap();
+
+    let scroll_request =
+        |start_from: Option<RangeValue>, offset: Option<PointIdType>| ScrollRequest {
+            offset,
+            limit: Some(2),
+            filter: None,
+            with_payload: Some(WithPayloadInterface::Bool(false)),
+            with_vector: None,
+            order_by: Some(OrderBy {
+                key: "created_at".to_string(),
+                direction: Direction::Desc,
+                start_from,
+            }),
+        };
+
+    // Field should be indexed
+    let result = collection
+        .scroll_by(scroll_request(None, None), &segment_searcher)
+        .await;
+    assert!(matches!(result, Err(CollectionError::BadInput { .. })));
+
+    let create_index = CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::CreateIndex(CreateFieldIndex::FieldName("created_at".to_string())),
+    );
+    collection.update(create_index, true).await.unwrap();
+
+    let page_ids = |page: &ScrollResult| page.points.iter().map(|point| point.id).collect_vec();
+
+    let page = collection
+        .scroll_by(scroll_request(None, None), &segment_searcher)
+        .await
+        .unwrap();
+    assert_eq!(page_ids(&page), vec![5.into(), 3.into()]);
+    assert_eq!(page.next_page_order_value, Some(RangeValue::Integer(3)));
+    assert_eq!(page.next_page_offset, Some(0.into()));
+
+    // Points with the same value are ordered by id
+    let page = collection
+        .scroll_by(
+            scroll_request(page.next_page_order_value, page.next_page_offset),
+            &segment_searcher,
+        )
+        .await
+        .unwrap();
+    assert_eq!(page_ids(&page), vec![0.into(), 2.into()]);
+    assert_eq!(page.next_page_order_value, Some(RangeValue::Integer(1)));
+
+    // Point without the field is not returned
+    let page = collection
+        .scroll_by(
+            scroll_request(page.next_page_order_value, page.next_page_offset),
+            &segment_searcher,
+        )
+        .await
+        .unwrap();
+    assert_eq!(page_ids(&page), vec![1.into()]);
+    assert_eq!(page.next_page_offset, None);
+    assert_eq!(page.next_page_order_value, None);
+
+    // Declared field should be numeric
+    let payload_schema =
+        serde_json::from_str(r#"{ "title": { "type": "keyword", "index": true } }"#).unwrap();
+    collection
+        .update_payload_schema(Some(payload_schema))
+        .await
+        .unwrap();
+    let mut request = scroll_request(None, None);
+    request.order_by.as_mut().unwrap().key = "title".to_string();
+    let result = collection.scroll_by(request, &segment_searcher).await;
+    assert!(matches!(result, Err(CollectionError::BadInput { .. })));
 }
 
 #[tokio::test]
@@ -285,17 +607,17 @@ async fn test_read_api() {
 
     let insert_points =
         CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
-            ids: vec![0, 1, 2, 3, 4, 5, 6, 7, 8],
+            ids: (0..9).map(|x| x.into()).collect(),
             vectors: vec![
-                vec![0.0, 0.0, 1.0, 1.0],
-                vec![1.0, 0.0, 0.0, 0.0],
-                vec![1.0, 0.0, 0.0, 0.0],
-                vec![0.0, 1.0, 0.0, 0.0],
-                vec![0.0, 1.0, 0.0, 0.0],
-                vec![0.0, 0.0, 1.0, 0.0],
-                vec![0.0, 0.0, 1.0, 0.0],
-                vec![0.0, 0.0, 0.0, 1.0],
-                vec![0.0, 0.0, 0.0, 1.0],
+                vec![0.0, 0.0, 1.0, 1.0].into(),
+                vec![1.0, 0.0, 0.0, 0.0].into(),
+                vec![1.0, 0.0, 0.0, 0.0].into(),
+                vec![0.0, 1.0, 0.0, 0.0].into(),
+                vec![0.0, 1.0, 0.0, 0.0].into(),
+                vec![0.0, 0.0, 1.0, 0.0].into(),
+                vec![0.0, 0.0, 1.0, 0.0].into(),
+                vec![0.0, 0.0, 0.0, 1.0].into(),
+                vec![0.0, 0.0, 0.0, 1.0].into(),
             ],
             payloads: None,
         }));
@@ -306,17 +628,374 @@ async fn test_read_api() {
     let result = collection
         .scroll_by(
             ScrollRequest {
-                offset: Some(0),
+                offset: Some(0.into()),
                 limit: Some(2),
                 filter: None,
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: None,
+                order_by: None,
             },
             &segment_searcher,
         )
         .await
         .unwrap();
 
-    assert_eq!(result.next_page_offset, Some(2));
+    assert_eq!(result.next_page_offset, Some(2.into()));
     assert_eq!(result.points.len(), 2);
 }
+
+#[tokio::test]
+async fn test_uuid_point_ids() {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let collection = simple_collection_fixture(collection_dir.path()).await;
+
+    let uuid_id: PointIdType = "5c56c793-69f3-4fbf-87e6-c4bf54c28c26".parse().unwrap();
+
+    let insert_points =
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
+            ids: vec![uuid_id, 10.into()],
+            vectors: vec![
+                vec![1.0, 0.0, 1.0, 1.0].into(),
+                vec![1.0, 0.0, 1.0, 0.0].into(),
+            ],
+            payloads: None,
+        }));
+
+    collection.update(insert_points, true).await.unwrap();
+
+    let segment_searcher = SimpleCollectionSearcher::new();
+    let retrieved = segment_searcher
+        .retrieve(
+            collection.segments(),
+            &[uuid_id],
+            &WithPayload::from(false),
+            true,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(retrieved.len(), 1);
+    assert_eq!(retrieved[0].id, uuid_id);
+
+    let scroll_request = |offset: Option<PointIdType>| ScrollRequest {
+        offset,
+        limit: Some(1),
+        filter: None,
+        with_payload: Some(WithPayloadInterface::Bool(false)),
+        with_vector: None,
+        order_by: None,
+    };
+
+    // Numeric ids are ordered before UUIDs
+    let first_page = collection
+        .scroll_by(scroll_request(None), &segment_searcher)
+        .await
+        .unwrap();
+    assert_eq!(first_page.points[0].id, 10.into());
+    assert_eq!(first_page.next_page_offset, Some(uuid_id));
+
+    let second_page = collection
+        .scroll_by(
+            scroll_request(first_page.next_page_offset),
+            &segment_searcher,
+        )
+        .await
+        .unwrap();
+    assert_eq!(second_page.points[0].id, uuid_id);
+    assert_eq!(second_page.next_page_offset, None);
+}
+
+#[tokio::test]
+async fn test_search_groups() {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let collection = simple_collection_fixture(collection_dir.path()).await;
+
+    let colors = [
+        "red", "red", "red", "red", "red", "red", "green", "red", "blue", "green",
+    ];
+    let payloads = colors
+        .iter()
+        .map(|color| format!(r#"{{ "color": "{}" }}"#, color))
+        .join(", ");
+
+    let insert_points =
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
+            ids: (0..10).map(|x| x.into()).collect(),
+            vectors: (0..10)
+                .map(|x| vec![10.0 - x as f32, 0.0, 0.0, 0.0].into())
+                .collect(),
+            payloads: serde_json::from_str(&format!("[{}]", payloads)).unwrap(),
+        }));
+
+    collection.update(insert_points, true).await.unwrap();
+
+    let segment_searcher = SimpleCollectionSearcher::new();
+    let result = collection
+        .search_groups(
+            SearchGroupsRequest {
+                vector: vec![1.0, 0.0, 0.0, 0.0].into(),
+                filter: None,
+                params: None,
+                score_threshold: None,
+                with_payload: None,
+                group_by: "color".to_string(),
+                group_size: 2,
+                groups: 2,
+            },
+            &segment_searcher,
+            &Handle::current(),
+        )
+        .await
+        .unwrap();
+
+    // First search iteration fills the "red" group only, the "green" one is found by going deeper
+    assert_eq!(result.groups.len(), 2);
+
+    assert_eq!(result.groups[0].id, GroupId::Keyword("red".to_string()));
+    let red_ids = result.groups[0].hits.iter().map(|hit| hit.id).collect_vec();
+    assert_eq!(red_ids, vec![0.into(), 1.into()]);
+
+    assert_eq!(result.groups[1].id, GroupId::Keyword("green".to_string()));
+    let green_ids = result.groups[1].hits.iter().map(|hit| hit.id).collect_vec();
+    assert_eq!(green_ids, vec![6.into(), 9.into()]);
+
+    assert!(result.groups[1].hits[0].payload.is_none());
+}
+
+#[tokio::test]
+async fn test_recommend_groups() {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let collection = simple_collection_fixture(collection_dir.path()).await;
+    let catalog_dir = TempDir::new("catalog").unwrap();
+    let catalog = simple_collection_fixture(catalog_dir.path()).await;
+
+    let colors = [
+        "red", "red", "red", "red", "red", "red", "green", "red", "blue", "green",
+    ];
+    let payloads = colors
+        .iter()
+        .map(|color| format!(r#"{{ "color": "{}" }}"#, color))
+        .join(", ");
+
+    let insert_points =
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
+            ids: (0..10).map(|x| x.into()).collect(),
+            vectors: (0..10)
+                .map(|x| vec![10.0 - x as f32, 0.0, 0.0, 0.0].into())
+                .collect(),
+            payloads: serde_json::from_str(&format!("[{}]", payloads)).unwrap(),
+        }));
+    collection.update(insert_points, true).await.unwrap();
+
+    let insert_catalog_points =
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
+            ids: vec![100.into()],
+            vectors: vec![vec![1.0, 0.0, 0.0, 0.0].into()],
+            payloads: None,
+        }));
+    catalog.update(insert_catalog_points, true).await.unwrap();
+
+    let segment_searcher = SimpleCollectionSearcher::new();
+    let request = || RecommendGroupsRequest {
+        positive: vec![100.into()],
+        negative: vec![],
+        filter: None,
+        params: None,
+        using: None,
+        strategy: RecommendStrategy::BestScore,
+        positive_weights: None,
+        negative_weights: None,
+        lookup_from: Some(LookupLocation {
+            collection: "catalog".to_string(),
+            vector: None,
+        }),
+        with_payload: None,
+        group_by: "color".to_string(),
+        group_size: 2,
+        groups: 2,
+    };
+
+    // Example point is looked up in the catalog on every search iteration
+    let result = collection
+        .recommend_groups(
+            request(),
+            Some(&catalog),
+            &segment_searcher,
+            &Handle::current(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.groups.len(), 2);
+    assert_eq!(result.groups[0].id, GroupId::Keyword("red".to_string()));
+    let red_ids = result.groups[0].hits.iter().map(|hit| hit.id).collect_vec();
+    assert_eq!(red_ids, vec![0.into(), 1.into()]);
+    assert_eq!(result.groups[1].id, GroupId::Keyword("green".to_string()));
+    let green_ids = result.groups[1].hits.iter().map(|hit| hit.id).collect_vec();
+    assert_eq!(green_ids, vec![6.into(), 9.into()]);
+
+    let result = collection
+        .recommend_groups(request(), None, &segment_searcher, &Handle::current())
+        .await;
+    assert!(matches!(result, Err(CollectionError::NotFound { .. })));
+}
+
+#[tokio::test]
+async fn test_count_points() {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let collection = simple_collection_fixture(collection_dir.path()).await;
+
+    let insert_points =
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
+            ids: vec![0.into(), 1.into(), 2.into()],
+            vectors: vec![
+                vec![1.0, 0.0, 1.0, 1.0].into(),
+                vec![1.0, 0.0, 1.0, 0.0].into(),
+                vec![0.0, 1.0, 1.0, 0.0].into(),
+            ],
+            payloads: serde_json::from_str(r#"[{ "k": "v1" }, { "k": "v2" }, { "k": "v1" }]"#)
+                .unwrap(),
+        }));
+
+    collection.update(insert_points, true).await.unwrap();
+
+    let total = collection
+        .count(CountRequest {
+            filter: None,
+            exact: true,
+        })
+        .await
+        .unwrap();
+    assert_eq!(total.count, 3);
+
+    // Without filter the estimation is exact
+    let estimated_total = collection
+        .count(CountRequest {
+            filter: None,
+            exact: false,
+        })
+        .await
+        .unwrap();
+    assert_eq!(estimated_total.count, 3);
+
+    let filter = r#"{ "must": [{ "key": "k", "match": { "keyword": "v1" } }] }"#;
+    let filter = serde_json::from_str(filter).unwrap();
+    let filtered = collection
+        .count(CountRequest {
+            filter: Some(filter),
+            exact: true,
+        })
+        .await
+        .unwrap();
+    assert_eq!(filtered.count, 2);
+}
+
+#[tokio::test]
+async fn test_payload_schema() {
+    let collection_dir = TempDir::new("collection").unwrap();
+    let collection = simple_collection_fixture(collection_dir.path()).await;
+
+    let payload_schema = serde_json::from_str(
+        r#"{
+            "price": { "type": "float", "index": true },
+            "seller.country": { "type": "keyword" }
+        }"#,
+    )
+    .unwrap();
+    collection
+        .update_payload_schema(Some(payload_schema))
+        .await
+        .unwrap();
+
+    let indexed_fields = collection
+        .segments()
+        .read()
+        .iter()
+        .flat_map(|(_, segment)| segment.get().read().get_indexed_fields())
+        .collect_vec();
+    assert!(indexed_fields.contains(&"price".to_string()));
+    assert!(!indexed_fields.contains(&"seller.country".to_string()));
+
+    let upsert_points = |payloads: &str| {
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(BatchPoints {
+            ids: vec![0.into(), 1.into()],
+            vectors: vec![
+                vec![1.0, 0.0, 1.0, 1.0].into(),
+                vec![1.0, 0.0, 1.0, 0.0].into(),
+            ],
+            payloads: serde_json::from_str(payloads).unwrap(),
+        }))
+    };
+
+    let valid_points = upsert_points(
+        r#"[{ "price": 10.5, "seller": { "country": "DE" } }, { "title": "undeclared", "price": [1.0, 2.0] }]"#,
+    );
+    collection.update(valid_points, true).await.unwrap();
+
+    // Integer values of the float field are stored as floats
+    let integer_prices =
+        upsert_points(r#"[{ "price": 10 }, { "price": { "type": "integer", "value": [1, 2] } }]"#);
+    collection.update(integer_prices, true).await.unwrap();
+    let retrieved = SimpleCollectionSearcher::new()
+        .retrieve(
+            collection.segments(),
+            &[0.into(), 1.into()],
+            &WithPayload::from(true),
+            false,
+        )
+        .await
+        .unwrap();
+    let prices = retrieved
+        .into_iter()
+        .sorted_by_key(|record| record.id)
+        .map(|record| match record.payload.unwrap().get("price") {
+            Some(PayloadType::Float(prices)) => prices.clone(),
+            price => panic!("Unexpected price: {:?}", price),
+        })
+        .collect_vec();
+    assert_eq!(prices, vec![vec![10.0], vec![1.0, 2.0]]);
+
+    let invalid_points = [
+        r#"[{ "price": "10" }, null]"#,
+        r#"[{ "price": 10.0 }, { "seller": { "country": 49 } }]"#,
+    ];
+    for payloads in invalid_points {
+        let result = collection.update(upsert_points(payloads), true).await;
+        assert!(
+            matches!(result, Err(CollectionError::BadInput { .. })),
+            "{:?}",
+            result
+        );
+    }
+
+    let mut payload: HashMap<PayloadKeyType, PayloadInterface> = Default::default();
+    payload.insert(
+        "price".to_string(),
+        PayloadInterface::KeywordShortcut(PayloadVariant::Value("free".to_string())),
+    );
+    let assign_payload = CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload {
+        payload,
+        points: vec![0.into()],
+    });
+    let result = collection.update(assign_payload, true).await;
+    assert!(matches!(result, Err(CollectionError::BadInput { .. })));
+
+    // Declaration is removed, so updates are not checked against it
+    collection.update_payload_schema(None).await.unwrap();
+    collection
+        .update(upsert_points(r#"[{ "stock": 10 }, null]"#), true)
+        .await
+        .unwrap();
+
+    // Declared type should match the type of already stored values
+    let conflicting_schema = serde_json::from_str(r#"{ "price": { "type": "keyword" } }"#).unwrap();
+    let result = collection
+        .update_payload_schema(Some(conflicting_schema))
+        .await;
+    assert!(matches!(result, Err(CollectionError::BadInput { .. })));
+
+    let matching_schema = serde_json::from_str(r#"{ "price": { "type": "float" } }"#).unwrap();
+    collection
+        .update_payload_schema(Some(matching_schema))
+        .await
+        .unwrap();
+}
@@ -1,7 +1,7 @@
 use collection::collection::Collection;
 use collection::collection_builder::build_collection;
 use collection::collection_builder::optimizers_builder::OptimizersConfig;
-use collection::config::{CollectionParams, WalConfig};
+use collection::config::{CollectionParams, VectorParams, VectorsConfig, WalConfig};
 use segment::types::Distance;
 use std::path::Path;
 
@@ -24,8 +24,15 @@ pub async fn simple_collection_fixture(collection_path: &Path) -> Collection {
     };
 
     let collection_params = CollectionParams {
-        vector_size: 4,
-        distance: Distance::Dot,
+        vectors: VectorsConfig::Single(VectorParams {
+            size: 4,
+            distance: Distance::Dot,
+            hnsw_config: None,
+        }),
+        quantization_config: None,
+        product_quantization_config: None,
+        sparse_vectors: None,
+        payload_schema: None,
     };
 
     build_collection(
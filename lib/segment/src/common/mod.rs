@@ -1,3 +1,4 @@
 pub mod error_logging;
 pub mod file_operations;
+pub mod tokenizer;
 pub mod utils;
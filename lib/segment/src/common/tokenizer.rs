@@ -0,0 +1,17 @@
+/// Split text into lowercase words, separated by whitespaces and punctuation
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let tokens: Vec<_> = tokenize("Hello, World! It's  a-test_42").collect();
+        assert_eq!(tokens, vec!["hello", "world", "it", "s", "a", "test", "42"]);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::types::{
+    Distance, Indexes, PayloadIndexType, ScalarQuantizationConfig, SegmentConfig, SegmentState,
+    SeqNumberType, StorageType, VectorDataConfig, DEFAULT_VECTOR_NAME,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Segment configuration, used before named vectors were introduced
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct SegmentConfigV0 {
+    pub vector_size: usize,
+    pub distance: Distance,
+    pub index: Indexes,
+    pub payload_index: Option<PayloadIndexType>,
+    pub storage_type: StorageType,
+    #[serde(default)]
+    pub quantization_config: Option<ScalarQuantizationConfig>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub(crate) struct SegmentStateV0 {
+    pub version: SeqNumberType,
+    pub config: SegmentConfigV0,
+}
+
+impl From<SegmentConfigV0> for SegmentConfig {
+    fn from(old: SegmentConfigV0) -> Self {
+        let vector_data = HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: old.vector_size,
+                distance: old.distance,
+                hnsw_config: None,
+            },
+        )]);
+
+        SegmentConfig {
+            vector_data,
+            sparse_vector_data: HashMap::new(),
+            index: old.index,
+            payload_index: old.payload_index,
+            storage_type: old.storage_type,
+            quantization_config: old.quantization_config,
+        }
+    }
+}
+
+impl From<SegmentStateV0> for SegmentState {
+    fn from(old: SegmentStateV0) -> Self {
+        SegmentState {
+            version: old.version,
+            config: old.config.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_old_segment_state() {
+        let old_state = r#"{
+            "version": 42,
+            "config": {
+                "vector_size": 4,
+                "distance": "Dot",
+                "index": {"type": "plain", "options": {}},
+                "payload_index": null,
+                "storage_type": {"type": "in_memory"}
+            }
+        }"#;
+
+        let state: SegmentState = serde_json::from_str::<SegmentStateV0>(old_state)
+            .unwrap()
+            .into();
+
+        assert_eq!(state.version, 42);
+        let vector_config = state.config.vector_data[DEFAULT_VECTOR_NAME];
+        assert_eq!(vector_config.size, 4);
+        assert_eq!(vector_config.distance, Distance::Dot);
+    }
+}
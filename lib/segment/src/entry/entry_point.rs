@@ -1,6 +1,9 @@
+use crate::index::CardinalityEstimation;
 use crate::types::{
-    Filter, PayloadKeyType, PayloadKeyTypeRef, PayloadType, PointIdType, ScoredPoint, SearchParams,
-    SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, TheMap, VectorElementType, WithPayload,
+    only_default_vector, Filter, NamedVectors, OrderBy, PayloadIndexParams, PayloadKeyType,
+    PayloadKeyTypeRef, PayloadType, PointIdType, RangeValue, ScoreType, ScoredPoint, SearchParams,
+    SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, SparseVector, TheMap,
+    VectorElementType, WithPayload,
 };
 use atomicwrites::Error as AtomicIoError;
 use rocksdb::Error;
@@ -16,6 +19,20 @@ pub enum OperationError {
         expected_dim: usize,
         received_dim: usize,
     },
+    #[error("Not existing vector name error: {received_name}")]
+    VectorNameNotExists { received_name: String },
+    #[error("Missed vector name error: {received_name}")]
+    MissedVectorName { received_name: String },
+    #[error("Wrong type of vector {vector_name}: expected {expected_type} vector")]
+    WrongVectorType {
+        vector_name: String,
+        expected_type: String,
+    },
+    #[error("Wrong sparse vector {vector_name}: {description}")]
+    WrongSparseVector {
+        vector_name: String,
+        description: String,
+    },
     #[error("No point with id {missed_point_id} found")]
     PointIdError { missed_point_id: PointIdType },
     #[error("Payload type does not match with previously given for field {field_name}. Expected: {expected_type}")]
@@ -92,21 +109,50 @@ pub trait SegmentEntry {
     /// Get version of specified point
     fn point_version(&self, point_id: PointIdType) -> Option<SeqNumberType>;
 
+    /// Search for the closest points by the vector with a given name.
+    /// If `score_threshold` is set, only points with a better score are returned
+    #[allow(clippy::too_many_arguments)]
     fn search(
         &self,
+        vector_name: &str,
         vector: &[VectorElementType],
         with_payload: &WithPayload,
         filter: Option<&Filter>,
         top: usize,
         params: Option<&SearchParams>,
+        score_threshold: Option<ScoreType>,
     ) -> OperationResult<Vec<ScoredPoint>>;
 
+    /// Search for the closest points by the sparse vector with a given name.
+    /// Score is a dot product of sparse vectors
+    fn search_sparse(
+        &self,
+        vector_name: &str,
+        vector: &SparseVector,
+        with_payload: &WithPayload,
+        filter: Option<&Filter>,
+        top: usize,
+        score_threshold: Option<ScoreType>,
+    ) -> OperationResult<Vec<ScoredPoint>>;
+
+    /// Insert or update all named vectors of the point.
+    /// Vectors for every vector name of the segment are required.
+    fn upsert_vectors(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        vectors: &NamedVectors,
+    ) -> OperationResult<bool>;
+
+    /// Insert or update point, which only has a vector with the default name
     fn upsert_point(
         &mut self,
         op_num: SeqNumberType,
         point_id: PointIdType,
         vector: &[VectorElementType],
-    ) -> OperationResult<bool>;
+    ) -> OperationResult<bool> {
+        self.upsert_vectors(op_num, point_id, &only_default_vector(vector))
+    }
 
     fn delete_point(
         &mut self,
@@ -149,7 +195,15 @@ pub trait SegmentEntry {
         point_id: PointIdType,
     ) -> OperationResult<bool>;
 
-    fn vector(&self, point_id: PointIdType) -> OperationResult<Vec<VectorElementType>>;
+    /// Get dense vector of the point with a given name
+    fn vector(
+        &self,
+        vector_name: &str,
+        point_id: PointIdType,
+    ) -> OperationResult<Vec<VectorElementType>>;
+
+    /// Get all named vectors of the point, both dense and sparse
+    fn all_vectors(&self, point_id: PointIdType) -> OperationResult<NamedVectors>;
 
     fn payload(
         &self,
@@ -166,6 +220,24 @@ pub trait SegmentEntry {
         filter: Option<&'a Filter>,
     ) -> Vec<PointIdType>;
 
+    /// Paginate over points which satisfies filtering condition in the order of the payload field.
+    /// Starts with `order_by.start_from` value and `offset` id including.
+    ///
+    /// # Result
+    ///
+    /// Points with their order values, sorted by value in the requested direction and then by id
+    fn read_ordered_filtered<'a>(
+        &'a self,
+        offset: PointIdType,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        order_by: &'a OrderBy,
+    ) -> Vec<(RangeValue, PointIdType)>;
+
+    /// Estimate number of points, which satisfy filtering condition.
+    /// Estimation is exact if no filter is given.
+    fn estimate_points_count(&self, filter: Option<&Filter>) -> CardinalityEstimation;
+
     /// Check if there is point with `point_id` in this segment.
     fn has_point(&self, point_id: PointIdType) -> bool;
 
@@ -201,11 +273,13 @@ pub trait SegmentEntry {
         key: PayloadKeyTypeRef,
     ) -> OperationResult<bool>;
 
-    /// Create index for a payload field, if not exists
+    /// Create index for a payload field, if not exists.
+    /// Index type is selected by the field type, unless `params` are given
     fn create_field_index(
         &mut self,
         op_num: SeqNumberType,
         key: PayloadKeyTypeRef,
+        params: Option<&PayloadIndexParams>,
     ) -> OperationResult<bool>;
 
     /// Get indexed fields
@@ -1,5 +1,5 @@
 use crate::types::{
-    Condition, FieldCondition, Filter, Match, PayloadType, Range as RangeCondition,
+    Condition, FieldCondition, Filter, Match, PayloadType, Range as RangeCondition, RangeValue,
     VectorElementType,
 };
 use itertools::Itertools;
@@ -69,10 +69,16 @@ pub fn random_field_condition(rnd_gen: &mut ThreadRng) -> Condition {
             r#match: Some(Match {
                 keyword: Some(random_keyword(rnd_gen)),
                 integer: None,
+                boolean: None,
+                text: None,
+                any: None,
+                except: None,
             }),
             range: None,
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
         }),
         false => Condition::Field(FieldCondition {
             key: "int".to_string(),
@@ -80,11 +86,13 @@ pub fn random_field_condition(rnd_gen: &mut ThreadRng) -> Condition {
             range: Some(RangeCondition {
                 lt: None,
                 gt: None,
-                gte: Some(rnd_gen.gen_range(INT_RANGE) as f64),
-                lte: Some(rnd_gen.gen_range(INT_RANGE) as f64),
+                gte: Some(RangeValue::Integer(rnd_gen.gen_range(INT_RANGE))),
+                lte: Some(RangeValue::Integer(rnd_gen.gen_range(INT_RANGE))),
             }),
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
         }),
     }
 }
@@ -114,6 +122,7 @@ pub fn random_filter(rnd_gen: &mut ThreadRng) -> Filter {
 
     Filter {
         should: should_conditions_opt,
+        min_should: None,
         must: must_conditions_opt,
         must_not: None,
     }
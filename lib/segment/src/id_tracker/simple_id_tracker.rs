@@ -1,10 +1,11 @@
 use crate::entry::entry_point::OperationResult;
 use crate::id_tracker::IdTracker;
-use crate::types::{PointIdType, PointOffsetType, SeqNumberType};
+use crate::types::{ExtendedPointId, PointIdType, PointOffsetType, SeqNumberType};
 use bincode;
 use rocksdb::{IteratorMode, Options, DB};
 use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
+use uuid::Uuid;
 
 /// Since sled is used for reading only during the initialization, large read cache is not required
 const DB_CACHE_SIZE: usize = 10 * 1024 * 1024; // 10 mb
@@ -12,6 +13,24 @@ const DB_CACHE_SIZE: usize = 10 * 1024 * 1024; // 10 mb
 const MAPPING_CF: &str = "mapping";
 const VERSIONS_CF: &str = "versions";
 
+/// Numeric ids are stored the same way as before UUID ids were introduced (8 bytes),
+/// UUIDs are stored as raw 16 bytes. So the type of the stored id is defined by the key length
+fn external_id_to_stored(external_id: PointIdType) -> Vec<u8> {
+    match external_id {
+        ExtendedPointId::NumId(id) => bincode::serialize(&id).unwrap(),
+        ExtendedPointId::Uuid(uuid) => uuid.as_bytes().to_vec(),
+    }
+}
+
+fn stored_to_external_id(key: &[u8]) -> PointIdType {
+    if key.len() == 16 {
+        Uuid::from_slice(key).unwrap().into()
+    } else {
+        let id: u64 = bincode::deserialize(key).unwrap();
+        id.into()
+    }
+}
+
 pub struct SimpleIdTracker {
     internal_to_external: HashMap<PointOffsetType, PointIdType>,
     external_to_internal: BTreeMap<PointIdType, PointOffsetType>,
@@ -34,7 +53,7 @@ impl SimpleIdTracker {
         for (key, val) in
             store.iterator_cf(store.cf_handle(MAPPING_CF).unwrap(), IteratorMode::Start)
         {
-            let external_id: PointIdType = bincode::deserialize(&key).unwrap();
+            let external_id = stored_to_external_id(&key);
             let internal_id: PointOffsetType = bincode::deserialize(&val).unwrap();
             internal_to_external.insert(internal_id, external_id);
             external_to_internal.insert(external_id, internal_id);
@@ -43,7 +62,7 @@ impl SimpleIdTracker {
         for (key, val) in
             store.iterator_cf(store.cf_handle(VERSIONS_CF).unwrap(), IteratorMode::Start)
         {
-            let external_id: PointIdType = bincode::deserialize(&key).unwrap();
+            let external_id = stored_to_external_id(&key);
             let version: SeqNumberType = bincode::deserialize(&val).unwrap();
             external_to_version.insert(external_id, version);
         }
@@ -70,7 +89,7 @@ impl IdTracker for SimpleIdTracker {
         self.external_to_version.insert(external_id, version);
         self.store.put_cf(
             self.store.cf_handle(VERSIONS_CF).unwrap(),
-            bincode::serialize(&external_id).unwrap(),
+            external_id_to_stored(external_id),
             bincode::serialize(&version).unwrap(),
         )?;
         Ok(())
@@ -94,7 +113,7 @@ impl IdTracker for SimpleIdTracker {
 
         self.store.put_cf(
             self.store.cf_handle(MAPPING_CF).unwrap(),
-            bincode::serialize(&external_id).unwrap(),
+            external_id_to_stored(external_id),
             bincode::serialize(&internal_id).unwrap(),
         )?;
         Ok(())
@@ -110,11 +129,11 @@ impl IdTracker for SimpleIdTracker {
         };
         self.store.delete_cf(
             self.store.cf_handle(MAPPING_CF).unwrap(),
-            bincode::serialize(&external_id).unwrap(),
+            external_id_to_stored(external_id),
         )?;
         self.store.delete_cf(
             self.store.cf_handle(VERSIONS_CF).unwrap(),
-            bincode::serialize(&external_id).unwrap(),
+            external_id_to_stored(external_id),
         )?;
         Ok(())
     }
@@ -133,7 +152,7 @@ impl IdTracker for SimpleIdTracker {
     ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
         Box::new(
             self.external_to_internal
-                .range(external_id..PointIdType::MAX)
+                .range(external_id..)
                 .map(|(key, value)| (*key, *value)),
         )
     }
@@ -159,23 +178,49 @@ mod tests {
 
         let mut id_tracker = SimpleIdTracker::open(dir.path()).unwrap();
 
-        id_tracker.set_link(200, 0).unwrap();
-        id_tracker.set_link(100, 1).unwrap();
-        id_tracker.set_link(150, 2).unwrap();
-        id_tracker.set_link(120, 3).unwrap();
-        id_tracker.set_link(180, 4).unwrap();
-        id_tracker.set_link(110, 5).unwrap();
-        id_tracker.set_link(115, 6).unwrap();
-        id_tracker.set_link(190, 7).unwrap();
-        id_tracker.set_link(177, 8).unwrap();
-        id_tracker.set_link(118, 9).unwrap();
+        id_tracker.set_link(200.into(), 0).unwrap();
+        id_tracker.set_link(100.into(), 1).unwrap();
+        id_tracker.set_link(150.into(), 2).unwrap();
+        id_tracker.set_link(120.into(), 3).unwrap();
+        id_tracker.set_link(180.into(), 4).unwrap();
+        id_tracker.set_link(110.into(), 5).unwrap();
+        id_tracker.set_link(115.into(), 6).unwrap();
+        id_tracker.set_link(190.into(), 7).unwrap();
+        id_tracker.set_link(177.into(), 8).unwrap();
+        id_tracker.set_link(118.into(), 9).unwrap();
 
-        let first_four = id_tracker.iter_from(0).take(4).collect_vec();
+        let first_four = id_tracker.iter_from(PointIdType::MIN).take(4).collect_vec();
 
         assert_eq!(first_four.len(), 4);
-        assert_eq!(first_four[0].0, 100);
+        assert_eq!(first_four[0].0, 100.into());
 
-        let last = id_tracker.iter_from(first_four[3].0 + 1).collect_vec();
+        let last = id_tracker.iter_from(118.into()).skip(1).collect_vec();
         assert_eq!(last.len(), 6);
     }
+
+    #[test]
+    fn test_uuid_ids_persistence() {
+        let dir = TempDir::new("storage_dir").unwrap();
+        let uuid: PointIdType = Uuid::parse_str("4a1d2d62-9bb4-4e4a-9c4f-9f0f5b6fa81f")
+            .unwrap()
+            .into();
+
+        {
+            let mut id_tracker = SimpleIdTracker::open(dir.path()).unwrap();
+            id_tracker.set_link(uuid, 0).unwrap();
+            id_tracker.set_version(uuid, 10).unwrap();
+            id_tracker.set_link(7.into(), 1).unwrap();
+            id_tracker.set_version(7.into(), 11).unwrap();
+            id_tracker.flush().unwrap();
+        }
+
+        let id_tracker = SimpleIdTracker::open(dir.path()).unwrap();
+        assert_eq!(id_tracker.internal_id(uuid), Some(0));
+        assert_eq!(id_tracker.external_id(1), Some(7.into()));
+        assert_eq!(id_tracker.version(uuid), Some(10));
+
+        // Numeric ids go first
+        let all_ids = id_tracker.iter_from(PointIdType::MIN).collect_vec();
+        assert_eq!(all_ids, vec![(7.into(), 1), (uuid, 0)]);
+    }
 }
@@ -1,8 +1,12 @@
+use crate::index::field_index::full_text_index::FullTextIndex;
+use crate::index::field_index::geo_index::GeoMapIndex;
 use crate::index::field_index::map_index::PersistedMapIndex;
 use crate::index::field_index::numeric_index::PersistedNumericIndex;
+use crate::index::field_index::values_count_index::PersistedValuesCountIndex;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::types::{
-    FieldCondition, FloatPayloadType, IntPayloadType, PayloadKeyType, PayloadType, PointOffsetType,
+    Direction, FieldCondition, FloatPayloadType, IntPayloadType, PayloadKeyType, PayloadType,
+    PointOffsetType, RangeValue,
 };
 use serde::{Deserialize, Serialize};
 
@@ -16,6 +20,9 @@ pub trait PayloadFieldIndex {
     /// Return estimation of points amount which satisfy given condition
     fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation>;
 
+    /// Number of points, which have the indexed field, even if it has no values
+    fn count_indexed_points(&self) -> usize;
+
     /// Iterate conditions for payload blocks with minimum size of `threshold`
     /// Required for building HNSW index
     fn payload_blocks(
@@ -41,6 +48,10 @@ pub enum FieldIndex {
     IntMapIndex(PersistedMapIndex<IntPayloadType>),
     KeywordIndex(PersistedMapIndex<String>),
     FloatIndex(PersistedNumericIndex<FloatPayloadType>),
+    BoolIndex(PersistedMapIndex<bool>),
+    ValuesCountIndex(PersistedValuesCountIndex),
+    FullTextIndex(FullTextIndex),
+    GeoIndex(GeoMapIndex),
 }
 
 impl FieldIndex {
@@ -50,6 +61,24 @@ impl FieldIndex {
             FieldIndex::IntMapIndex(payload_field_index) => payload_field_index,
             FieldIndex::KeywordIndex(payload_field_index) => payload_field_index,
             FieldIndex::FloatIndex(payload_field_index) => payload_field_index,
+            FieldIndex::BoolIndex(payload_field_index) => payload_field_index,
+            FieldIndex::ValuesCountIndex(payload_field_index) => payload_field_index,
+            FieldIndex::FullTextIndex(payload_field_index) => payload_field_index,
+            FieldIndex::GeoIndex(payload_field_index) => payload_field_index,
+        }
+    }
+
+    /// Iterate over values of the field with their points in the given direction.
+    /// `None` if the index can't order points by the values
+    pub fn ordered_points(
+        &self,
+        direction: Direction,
+        start_from: Option<RangeValue>,
+    ) -> Option<Box<dyn Iterator<Item = (RangeValue, PointOffsetType)> + '_>> {
+        match self {
+            FieldIndex::IntIndex(index) => Some(index.ordered_points(direction, start_from)),
+            FieldIndex::FloatIndex(index) => Some(index.ordered_points(direction, start_from)),
+            _ => None,
         }
     }
 }
@@ -67,6 +96,10 @@ impl PayloadFieldIndex for FieldIndex {
             .estimate_cardinality(condition)
     }
 
+    fn count_indexed_points(&self) -> usize {
+        self.get_payload_field_index().count_indexed_points()
+    }
+
     fn payload_blocks(
         &self,
         threshold: usize,
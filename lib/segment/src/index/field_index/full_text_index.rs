@@ -0,0 +1,269 @@
+use std::cmp::min;
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::tokenizer::tokenize;
+use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition, PrimaryCondition};
+use crate::index::field_index::{FieldIndex, PayloadFieldIndex, PayloadFieldIndexBuilder};
+use crate::types::{
+    FieldCondition, Match, PayloadKeyType, PayloadType, PointOffsetType, TextIndexParams,
+};
+
+/// Inverted index of the words in keyword values, used for full-text matching
+#[derive(Serialize, Deserialize, Default)]
+pub struct FullTextIndex {
+    /// Sorted ids of the points, which contain the token
+    postings: HashMap<String, Vec<PointOffsetType>>,
+    points_count: usize,
+    params: TextIndexParams,
+}
+
+impl FullTextIndex {
+    pub fn new(params: TextIndexParams) -> Self {
+        FullTextIndex {
+            postings: Default::default(),
+            points_count: 0,
+            params,
+        }
+    }
+
+    fn is_indexed_token(&self, token: &str) -> bool {
+        let TextIndexParams {
+            min_token_len,
+            max_token_len,
+        } = &self.params;
+        let token_len = token.chars().count();
+        min_token_len.map_or(true, |min_len| token_len >= min_len)
+            && max_token_len.map_or(true, |max_len| token_len <= max_len)
+    }
+
+    /// Postings of the query tokens, which could be found in the index.
+    /// `None` if the query has no indexed tokens, so index can't be used for it
+    fn query_postings(&self, query: &str) -> Option<Vec<&[PointOffsetType]>> {
+        let tokens: HashSet<String> = tokenize(query)
+            .filter(|token| self.is_indexed_token(token))
+            .collect();
+        if tokens.is_empty() {
+            return None;
+        }
+        let postings = tokens
+            .iter()
+            .map(|token| match self.postings.get(token) {
+                Some(points) => points.as_slice(),
+                None => &[],
+            })
+            .collect();
+        Some(postings)
+    }
+
+    /// Iterate over points, which contain all of the query tokens
+    fn query_iterator(
+        &self,
+        query: &str,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        let mut postings = self.query_postings(query)?;
+        postings.sort_by_key(|points| points.len());
+        let shortest = postings.remove(0);
+        let matched_points = shortest.iter().cloned().filter(move |point_id| {
+            postings
+                .iter()
+                .all(|points| points.binary_search(point_id).is_ok())
+        });
+        Some(Box::new(matched_points))
+    }
+
+    pub fn text_cardinality(&self, query: &str) -> Option<CardinalityEstimation> {
+        let postings = self.query_postings(query)?;
+        // Tokens, skipped by the index, are only checked on the found points
+        let all_indexed = tokenize(query).all(|token| self.is_indexed_token(&token));
+        if postings.len() == 1 && all_indexed {
+            return Some(CardinalityEstimation::exact(postings[0].len()));
+        }
+        let max_count = postings
+            .iter()
+            .map(|points| points.len())
+            .min()
+            .unwrap_or(0);
+        // Assume that tokens are independent
+        let selectivity: f64 = postings
+            .iter()
+            .map(|points| points.len() as f64 / self.points_count.max(1) as f64)
+            .product();
+        let expected_count = (selectivity * self.points_count as f64).round() as usize;
+        Some(CardinalityEstimation {
+            primary_clauses: vec![],
+            min: 0,
+            exp: min(expected_count, max_count),
+            max: max_count,
+        })
+    }
+
+    fn add_texts(&mut self, idx: PointOffsetType, texts: &[String]) {
+        self.points_count += 1;
+        let tokens: HashSet<String> = texts
+            .iter()
+            .flat_map(|text| tokenize(text))
+            .filter(|token| self.is_indexed_token(token))
+            .collect();
+        for token in tokens {
+            self.postings.entry(token).or_default().push(idx);
+        }
+    }
+}
+
+impl PayloadFieldIndex for FullTextIndex {
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        condition
+            .r#match
+            .as_ref()
+            .and_then(|match_condition| match_condition.text.as_ref())
+            .and_then(|text| self.query_iterator(text))
+    }
+
+    fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        condition
+            .r#match
+            .as_ref()
+            .and_then(|match_condition| match_condition.text.as_ref())
+            .and_then(|text| self.text_cardinality(text))
+            .map(|mut estimation| {
+                estimation
+                    .primary_clauses
+                    .push(PrimaryCondition::Condition(condition.clone()));
+                estimation
+            })
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        self.points_count
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        let iter = self
+            .postings
+            .iter()
+            .filter(move |(_token, point_ids)| point_ids.len() >= threshold)
+            .map(move |(token, point_ids)| PayloadBlockCondition {
+                condition: FieldCondition {
+                    key: key.clone(),
+                    r#match: Some(Match {
+                        keyword: None,
+                        integer: None,
+                        boolean: None,
+                        text: Some(token.to_owned()),
+                        any: None,
+                        except: None,
+                    }),
+                    range: None,
+                    geo_bounding_box: None,
+                    geo_radius: None,
+                    geo_polygon: None,
+                    values_count: None,
+                },
+                cardinality: point_ids.len(),
+            });
+        Box::new(iter)
+    }
+}
+
+impl PayloadFieldIndexBuilder for FullTextIndex {
+    fn add(&mut self, id: PointOffsetType, value: &PayloadType) {
+        match value {
+            PayloadType::Keyword(texts) => self.add_texts(id, texts),
+            _ => panic!("Unexpected payload type: {:?}", value),
+        }
+    }
+
+    fn build(&mut self) -> FieldIndex {
+        let mut postings = mem::take(&mut self.postings);
+        for points in postings.values_mut() {
+            points.sort_unstable();
+        }
+        FieldIndex::FullTextIndex(FullTextIndex {
+            postings,
+            points_count: mem::take(&mut self.points_count),
+            params: self.params.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_condition(text: &str) -> FieldCondition {
+        FieldCondition {
+            key: "description".to_owned(),
+            r#match: Some(Match {
+                keyword: None,
+                integer: None,
+                boolean: None,
+                text: Some(text.to_owned()),
+                any: None,
+                except: None,
+            }),
+            range: None,
+            geo_bounding_box: None,
+            geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
+        }
+    }
+
+    #[test]
+    fn test_full_text_filter() {
+        let mut builder = FullTextIndex::new(TextIndexParams {
+            min_token_len: Some(2),
+            max_token_len: None,
+        });
+        let texts = [
+            "Red apple, green pear",
+            "A green apple",
+            "Green tea",
+            "red wine",
+        ];
+        for (idx, text) in texts.iter().enumerate().rev() {
+            let value = PayloadType::Keyword(vec![text.to_string()]);
+            builder.add(idx as PointOffsetType, &value);
+        }
+        let index = builder.build();
+
+        let filtered = |text: &str| {
+            index
+                .filter(&text_condition(text))
+                .map(|points| points.collect::<Vec<_>>())
+        };
+
+        assert_eq!(filtered("apple"), Some(vec![0, 1]));
+        assert_eq!(filtered("GREEN apple"), Some(vec![0, 1]));
+        assert_eq!(filtered("red tea"), Some(vec![]));
+        // Short tokens are not indexed, so index returns all points with the other tokens
+        assert_eq!(filtered("a green"), Some(vec![0, 1, 2]));
+        assert_eq!(filtered("a"), None);
+
+        let estimation = index
+            .estimate_cardinality(&text_condition("green"))
+            .unwrap();
+        assert_eq!(estimation.exp, 3);
+        assert_eq!(estimation.primary_clauses.len(), 1);
+
+        let estimation = index
+            .estimate_cardinality(&text_condition("green apple"))
+            .unwrap();
+        assert!(estimation.min <= 2 && 2 <= estimation.max);
+
+        let estimation = index
+            .estimate_cardinality(&text_condition("a green"))
+            .unwrap();
+        assert!(estimation.min <= 1 && 3 <= estimation.max);
+    }
+}
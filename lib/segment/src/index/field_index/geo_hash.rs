@@ -0,0 +1,368 @@
+//! Geohash encoding of geo points and covering of geo areas with geohash regions
+
+use crate::types::{GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius};
+
+/// Geohash string, each char encodes 5 bits of interleaved longitude and latitude
+pub type GeoHash = String;
+
+/// Length of the most precise geohash, size of the region is a few centimeters
+pub const GEOHASH_MAX_LENGTH: usize = 12;
+
+const BASE32_CODES: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// Mean Earth radius in meters, same as used for the haversine distance of the `geo` crate
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+fn lon_bits(length: usize) -> usize {
+    (length * 5 + 1) / 2
+}
+
+fn lat_bits(length: usize) -> usize {
+    length * 5 / 2
+}
+
+/// Index of the grid column or row, which contains the coordinate.
+/// Coordinates out of bounds are assigned to the border cells
+fn grid_index(value: f64, min: f64, max: f64, bits: usize) -> u64 {
+    let cells = 1u64 << bits;
+    (((value - min) / (max - min) * cells as f64) as u64).min(cells - 1)
+}
+
+/// Position of the coordinates in the grid of the most precise geohashes
+fn max_precision_cell(lon: f64, lat: f64) -> (u64, u64) {
+    (
+        grid_index(lon, -180.0, 180.0, lon_bits(GEOHASH_MAX_LENGTH)),
+        grid_index(lat, -90.0, 90.0, lat_bits(GEOHASH_MAX_LENGTH)),
+    )
+}
+
+/// Position of the most precise cell in the grid of geohashes with the given `length`
+fn cell_at_length((lon_index, lat_index): (u64, u64), length: usize) -> (u64, u64) {
+    (
+        lon_index >> (lon_bits(GEOHASH_MAX_LENGTH) - lon_bits(length)),
+        lat_index >> (lat_bits(GEOHASH_MAX_LENGTH) - lat_bits(length)),
+    )
+}
+
+fn encode_cell((lon_index, lat_index): (u64, u64), length: usize) -> GeoHash {
+    let (lon_bits, lat_bits) = (lon_bits(length), lat_bits(length));
+    let mut hash = GeoHash::with_capacity(length);
+    let mut code = 0;
+    // Bits of longitude and latitude are interleaved, starting with the longitude
+    for bit in 0..length * 5 {
+        let value = if bit % 2 == 0 {
+            (lon_index >> (lon_bits - 1 - bit / 2)) & 1
+        } else {
+            (lat_index >> (lat_bits - 1 - bit / 2)) & 1
+        };
+        code = (code << 1) | value as usize;
+        if bit % 5 == 4 {
+            hash.push(BASE32_CODES[code] as char);
+            code = 0;
+        }
+    }
+    hash
+}
+
+/// Geohash of the given `length`, which contains the point
+pub fn encode(point: &GeoPoint, length: usize) -> GeoHash {
+    let cell = max_precision_cell(point.lon, point.lat);
+    encode_cell(cell_at_length(cell, length), length)
+}
+
+/// Rectangle of geo coordinates, all bounds are inclusive
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoRect {
+    pub min_lon: f64,
+    pub min_lat: f64,
+    pub max_lon: f64,
+    pub max_lat: f64,
+}
+
+impl GeoRect {
+    fn is_empty(&self) -> bool {
+        self.min_lon > self.max_lon || self.min_lat > self.max_lat
+    }
+
+    /// Area in square degrees, only useful to compare rectangles with each other
+    pub fn area(&self) -> f64 {
+        if self.is_empty() {
+            0.0
+        } else {
+            (self.max_lon - self.min_lon) * (self.max_lat - self.min_lat)
+        }
+    }
+
+    pub fn to_bounding_box(self) -> GeoBoundingBox {
+        GeoBoundingBox {
+            top_left: GeoPoint {
+                lon: self.min_lon,
+                lat: self.max_lat,
+            },
+            bottom_right: GeoPoint {
+                lon: self.max_lon,
+                lat: self.min_lat,
+            },
+        }
+    }
+}
+
+impl From<&GeoBoundingBox> for GeoRect {
+    fn from(bounding_box: &GeoBoundingBox) -> Self {
+        GeoRect {
+            min_lon: bounding_box.top_left.lon,
+            min_lat: bounding_box.bottom_right.lat,
+            max_lon: bounding_box.bottom_right.lon,
+            max_lat: bounding_box.top_left.lat,
+        }
+    }
+}
+
+/// Rectangle of the region, covered by the geohash
+pub fn decode(hash: &str) -> GeoRect {
+    let (lon_bits, lat_bits) = (lon_bits(hash.len()), lat_bits(hash.len()));
+    let (mut lon_index, mut lat_index) = (0u64, 0u64);
+    let bits = hash.bytes().flat_map(|byte| {
+        let code = BASE32_CODES
+            .iter()
+            .position(|&base32_byte| base32_byte == byte)
+            .unwrap_or_else(|| panic!("Invalid geohash: {}", hash));
+        (0..5).rev().map(move |bit| (code >> bit) as u64 & 1)
+    });
+    for (bit, value) in bits.enumerate() {
+        if bit % 2 == 0 {
+            lon_index = (lon_index << 1) | value;
+        } else {
+            lat_index = (lat_index << 1) | value;
+        }
+    }
+    let lon_size = 360.0 / (1u64 << lon_bits) as f64;
+    let lat_size = 180.0 / (1u64 << lat_bits) as f64;
+    GeoRect {
+        min_lon: -180.0 + lon_index as f64 * lon_size,
+        min_lat: -90.0 + lat_index as f64 * lat_size,
+        max_lon: -180.0 + (lon_index + 1) as f64 * lon_size,
+        max_lat: -90.0 + (lat_index + 1) as f64 * lat_size,
+    }
+}
+
+/// Rectangles, which contain the whole circle. Circles, crossing the 180th meridian, are split in two
+pub fn radius_rects(geo_radius: &GeoRadius) -> Vec<GeoRect> {
+    let GeoRadius { center, radius } = geo_radius;
+    let angular_radius = radius / EARTH_RADIUS;
+    let lat_delta = angular_radius.to_degrees();
+    let min_lat = center.lat - lat_delta;
+    let max_lat = center.lat + lat_delta;
+
+    if min_lat <= -90.0 || max_lat >= 90.0 {
+        // Circle contains the pole, so it contains all longitudes
+        return vec![GeoRect {
+            min_lon: -180.0,
+            min_lat: min_lat.max(-90.0),
+            max_lon: 180.0,
+            max_lat: max_lat.min(90.0),
+        }];
+    }
+
+    let lon_delta = (angular_radius.sin() / center.lat.to_radians().cos())
+        .min(1.0)
+        .asin()
+        .to_degrees();
+    let min_lon = center.lon - lon_delta;
+    let max_lon = center.lon + lon_delta;
+    let rect = |min_lon, max_lon| GeoRect {
+        min_lon,
+        min_lat,
+        max_lon,
+        max_lat,
+    };
+
+    if min_lon < -180.0 {
+        vec![rect(min_lon + 360.0, 180.0), rect(-180.0, max_lon)]
+    } else if max_lon > 180.0 {
+        vec![rect(min_lon, 180.0), rect(-180.0, max_lon - 360.0)]
+    } else {
+        vec![rect(min_lon, max_lon)]
+    }
+}
+
+/// Rectangle, which contains all points of the exterior ring of the polygon
+pub fn polygon_rects(geo_polygon: &GeoPolygon) -> Vec<GeoRect> {
+    let points = &geo_polygon.exterior.points;
+    if points.is_empty() {
+        return vec![];
+    }
+    let rect = points.iter().fold(
+        GeoRect {
+            min_lon: f64::INFINITY,
+            min_lat: f64::INFINITY,
+            max_lon: f64::NEG_INFINITY,
+            max_lat: f64::NEG_INFINITY,
+        },
+        |rect, point| GeoRect {
+            min_lon: rect.min_lon.min(point.lon),
+            min_lat: rect.min_lat.min(point.lat),
+            max_lon: rect.max_lon.max(point.lon),
+            max_lat: rect.max_lat.max(point.lat),
+        },
+    );
+    vec![rect]
+}
+
+/// Geohash regions of the same length, which together contain all points of the rectangles.
+/// The longest geohashes are selected, as long as the number of regions doesn't exceed `max_regions`
+pub fn cover_rects(rects: &[GeoRect], max_regions: usize) -> Vec<GeoHash> {
+    let cells: Vec<_> = rects
+        .iter()
+        .filter(|rect| !rect.is_empty())
+        .map(|rect| {
+            (
+                max_precision_cell(rect.min_lon, rect.min_lat),
+                max_precision_cell(rect.max_lon, rect.max_lat),
+            )
+        })
+        .collect();
+
+    let cell_ranges = |length| {
+        cells.iter().map(move |&(min_cell, max_cell)| {
+            (
+                cell_at_length(min_cell, length),
+                cell_at_length(max_cell, length),
+            )
+        })
+    };
+    let regions_count = |length| -> u64 {
+        cell_ranges(length)
+            .map(|((min_lon, min_lat), (max_lon, max_lat))| {
+                (max_lon - min_lon + 1) * (max_lat - min_lat + 1)
+            })
+            .sum()
+    };
+
+    // Number of regions only grows with the length of geohash
+    let length = (2..=GEOHASH_MAX_LENGTH)
+        .take_while(|&length| regions_count(length) <= max_regions as u64)
+        .last()
+        .unwrap_or(1);
+
+    let mut hashes: Vec<GeoHash> = cell_ranges(length)
+        .flat_map(|((min_lon, min_lat), (max_lon, max_lat))| {
+            (min_lon..=max_lon).flat_map(move |lon_index| {
+                (min_lat..=max_lat)
+                    .map(move |lat_index| encode_cell((lon_index, lat_index), length))
+            })
+        })
+        .collect();
+    // Split rectangles may share regions
+    hashes.sort_unstable();
+    hashes.dedup();
+    hashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BERLIN: GeoPoint = GeoPoint {
+        lon: 13.41053,
+        lat: 52.52437,
+    };
+
+    #[test]
+    fn test_encode_and_decode() {
+        assert_eq!(encode(&BERLIN, GEOHASH_MAX_LENGTH), "u33dc1v0xupz");
+        assert_eq!(encode(&BERLIN, 5), "u33dc");
+        assert_eq!(
+            encode(&GeoPoint { lon: 0.0, lat: 0.0 }, 3),
+            "s00".to_string()
+        );
+
+        let rect = decode("u33dc");
+        assert!(rect.min_lon <= BERLIN.lon && BERLIN.lon <= rect.max_lon);
+        assert!(rect.min_lat <= BERLIN.lat && BERLIN.lat <= rect.max_lat);
+        assert!((rect.max_lon - rect.min_lon - 360.0 / 8192.0).abs() < 1e-9);
+        assert!((rect.max_lat - rect.min_lat - 180.0 / 4096.0).abs() < 1e-9);
+
+        // Points on the borders of the world belong to the border regions
+        assert_eq!(
+            encode(
+                &GeoPoint {
+                    lon: 180.0,
+                    lat: 90.0
+                },
+                1
+            ),
+            "z"
+        );
+        assert_eq!(
+            encode(
+                &GeoPoint {
+                    lon: -180.0,
+                    lat: -90.0
+                },
+                1
+            ),
+            "0"
+        );
+    }
+
+    #[test]
+    fn test_cover_rects() {
+        let rect = GeoRect {
+            min_lon: 13.0,
+            min_lat: 52.3,
+            max_lon: 13.8,
+            max_lat: 52.7,
+        };
+        let hashes = cover_rects(&[rect], 16);
+        assert!(!hashes.is_empty() && hashes.len() <= 16);
+        assert!(hashes.iter().all(|hash| hash.len() == hashes[0].len()));
+        assert!(hashes
+            .iter()
+            .any(|hash| encode(&BERLIN, hash.len()) == *hash));
+
+        // The whole world is covered by all the shortest regions
+        let world = GeoRect {
+            min_lon: -180.0,
+            min_lat: -90.0,
+            max_lon: 180.0,
+            max_lat: 90.0,
+        };
+        assert_eq!(cover_rects(&[world], 16).len(), 32);
+
+        let empty = GeoRect {
+            min_lon: 1.0,
+            min_lat: 0.0,
+            max_lon: 0.0,
+            max_lat: 1.0,
+        };
+        assert!(cover_rects(&[empty], 16).is_empty());
+    }
+
+    #[test]
+    fn test_radius_rects() {
+        let near_meridian = GeoRadius {
+            center: GeoPoint {
+                lon: 179.99,
+                lat: 0.0,
+            },
+            radius: 10_000.0,
+        };
+        let rects = radius_rects(&near_meridian);
+        assert_eq!(rects.len(), 2);
+        assert_eq!(rects[0].max_lon, 180.0);
+        assert_eq!(rects[1].min_lon, -180.0);
+        assert!(rects[1].max_lon > -180.0 && rects[1].max_lon < -179.9);
+
+        let near_pole = GeoRadius {
+            center: GeoPoint {
+                lon: 10.0,
+                lat: 89.99,
+            },
+            radius: 10_000.0,
+        };
+        let rects = radius_rects(&near_pole);
+        assert_eq!(rects.len(), 1);
+        assert_eq!((rects[0].min_lon, rects[0].max_lon), (-180.0, 180.0));
+    }
+}
@@ -1 +1,343 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::mem;
 
+use serde::{Deserialize, Serialize};
+
+use crate::index::field_index::geo_hash::{
+    cover_rects, decode, encode, polygon_rects, radius_rects, GeoHash, GeoRect, GEOHASH_MAX_LENGTH,
+};
+use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition, PrimaryCondition};
+use crate::index::field_index::{FieldIndex, PayloadFieldIndex, PayloadFieldIndexBuilder};
+use crate::types::{FieldCondition, PayloadKeyType, PayloadType, PointOffsetType};
+
+/// Max number of geohash regions, which are used to select points of a single condition
+const GEOHASH_MAX_REGIONS: usize = 16;
+
+/// Geohash-based index of geo points.
+/// Points are stored by the most precise geohash of their values, so points of any geohash region
+/// are selected with the range of the hashes, starting with the region hash
+#[derive(Serialize, Deserialize, Default)]
+pub struct GeoMapIndex {
+    /// Points, grouped by the most precise geohash of their values
+    points_map: BTreeMap<GeoHash, Vec<PointOffsetType>>,
+    /// Number of points, which have values in the region, for geohashes of every length
+    points_per_hash: HashMap<GeoHash, usize>,
+    points_count: usize,
+}
+
+/// Rectangles, which contain all points matched by the geo conditions of the field condition.
+/// `None` if there are no geo conditions
+fn condition_rects(condition: &FieldCondition) -> Option<Vec<GeoRect>> {
+    if condition.geo_bounding_box.is_none()
+        && condition.geo_radius.is_none()
+        && condition.geo_polygon.is_none()
+    {
+        return None;
+    }
+    // Geo conditions of the same field condition are alternatives, so all areas are covered
+    let mut rects = vec![];
+    rects.extend(condition.geo_bounding_box.iter().map(GeoRect::from));
+    rects.extend(condition.geo_radius.iter().flat_map(radius_rects));
+    rects.extend(condition.geo_polygon.iter().flat_map(polygon_rects));
+    Some(rects)
+}
+
+impl GeoMapIndex {
+    fn hash_points(&self, hash: GeoHash) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.points_map
+            .range(hash.clone()..)
+            .take_while(move |(point_hash, _)| point_hash.starts_with(hash.as_str()))
+            .flat_map(|(_, point_ids)| point_ids.iter().cloned())
+    }
+
+    fn hash_points_count(&self, hash: &str) -> usize {
+        self.points_per_hash.get(hash).copied().unwrap_or(0)
+    }
+
+    /// Estimation of points within the rectangles, assuming points are spread evenly across
+    /// the covering regions
+    pub fn rects_cardinality(&self, rects: &[GeoRect]) -> CardinalityEstimation {
+        let hashes = cover_rects(rects, GEOHASH_MAX_REGIONS);
+        // Points with multiple values may be counted in several regions
+        let max = hashes
+            .iter()
+            .map(|hash| self.hash_points_count(hash))
+            .sum::<usize>()
+            .min(self.points_count);
+
+        let rects_area: f64 = rects.iter().map(GeoRect::area).sum();
+        let covered_area: f64 = hashes.iter().map(|hash| decode(hash).area()).sum();
+        let exp = if covered_area > 0.0 {
+            (max as f64 * (rects_area / covered_area).min(1.0)).round() as usize
+        } else {
+            0
+        };
+
+        CardinalityEstimation {
+            primary_clauses: vec![],
+            min: 0,
+            exp,
+            max,
+        }
+    }
+
+    /// The most precise regions with at least `threshold` points, regions don't overlap
+    fn large_hashes(&self, threshold: usize) -> Vec<(&GeoHash, usize)> {
+        let mut large_hashes: Vec<_> = self
+            .points_per_hash
+            .iter()
+            .filter(|(_, &count)| count >= threshold)
+            .map(|(hash, &count)| (hash, count))
+            .collect();
+        large_hashes.sort_by(|(hash_a, _), (hash_b, _)| {
+            hash_b.len().cmp(&hash_a.len()).then(hash_a.cmp(hash_b))
+        });
+
+        let mut selected: Vec<(&GeoHash, usize)> = vec![];
+        for (hash, count) in large_hashes {
+            if !selected
+                .iter()
+                .any(|(selected_hash, _)| selected_hash.starts_with(hash.as_str()))
+            {
+                selected.push((hash, count));
+            }
+        }
+        selected
+    }
+}
+
+impl PayloadFieldIndex for GeoMapIndex {
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        let hashes = cover_rects(&condition_rects(condition)?, GEOHASH_MAX_REGIONS);
+        // Regions contain points outside of the condition area, so points are checked afterwards.
+        // Same point may be returned multiple times
+        Some(Box::new(
+            hashes
+                .into_iter()
+                .flat_map(move |hash| self.hash_points(hash)),
+        ))
+    }
+
+    fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        condition_rects(condition).map(|rects| {
+            let mut estimation = self.rects_cardinality(&rects);
+            estimation
+                .primary_clauses
+                .push(PrimaryCondition::Condition(condition.clone()));
+            estimation
+        })
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        self.points_count
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        let iter = self
+            .large_hashes(threshold)
+            .into_iter()
+            .map(move |(hash, count)| PayloadBlockCondition {
+                condition: FieldCondition {
+                    key: key.clone(),
+                    r#match: None,
+                    range: None,
+                    geo_bounding_box: Some(decode(hash).to_bounding_box()),
+                    geo_radius: None,
+                    geo_polygon: None,
+                    values_count: None,
+                },
+                cardinality: count,
+            });
+        Box::new(iter)
+    }
+}
+
+impl PayloadFieldIndexBuilder for GeoMapIndex {
+    fn add(&mut self, id: PointOffsetType, value: &PayloadType) {
+        let geo_points = match value {
+            PayloadType::Geo(geo_points) => geo_points,
+            _ => panic!("Unexpected payload type: {:?}", value),
+        };
+
+        let mut point_hashes: HashSet<GeoHash> = HashSet::new();
+        for geo_point in geo_points {
+            let hash = encode(geo_point, GEOHASH_MAX_LENGTH);
+            let point_ids = self.points_map.entry(hash.clone()).or_default();
+            // Same location might be repeated in the values of the point
+            if point_ids.last() != Some(&id) {
+                point_ids.push(id);
+            }
+            point_hashes.extend((1..=GEOHASH_MAX_LENGTH).map(|length| hash[..length].to_owned()));
+        }
+        for hash in point_hashes {
+            *self.points_per_hash.entry(hash).or_default() += 1;
+        }
+        self.points_count += 1;
+    }
+
+    fn build(&mut self) -> FieldIndex {
+        FieldIndex::GeoIndex(mem::take(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{GeoBoundingBox, GeoLineString, GeoPoint, GeoPolygon, GeoRadius};
+
+    const BERLIN: GeoPoint = GeoPoint {
+        lon: 13.41053,
+        lat: 52.52437,
+    };
+    const POTSDAM: GeoPoint = GeoPoint {
+        lon: 13.06566,
+        lat: 52.39886,
+    };
+    const MOSCOW: GeoPoint = GeoPoint {
+        lon: 37.61556,
+        lat: 55.75222,
+    };
+
+    fn geo_condition(
+        geo_bounding_box: Option<GeoBoundingBox>,
+        geo_radius: Option<GeoRadius>,
+        geo_polygon: Option<GeoPolygon>,
+    ) -> FieldCondition {
+        FieldCondition {
+            key: "location".to_owned(),
+            r#match: None,
+            range: None,
+            geo_bounding_box,
+            geo_radius,
+            geo_polygon,
+            values_count: None,
+        }
+    }
+
+    fn build_index() -> FieldIndex {
+        let mut builder = GeoMapIndex::default();
+        builder.add(0, &PayloadType::Geo(vec![BERLIN]));
+        builder.add(1, &PayloadType::Geo(vec![POTSDAM, BERLIN]));
+        builder.add(2, &PayloadType::Geo(vec![MOSCOW]));
+        builder.add(3, &PayloadType::Geo(vec![]));
+        builder.build()
+    }
+
+    fn filtered(index: &FieldIndex, condition: &FieldCondition) -> Vec<PointOffsetType> {
+        let mut point_ids: Vec<_> = index.filter(condition).unwrap().collect();
+        point_ids.sort_unstable();
+        point_ids.dedup();
+        point_ids
+    }
+
+    #[test]
+    fn test_geo_index_filter() {
+        let index = build_index();
+
+        let near_berlin = geo_condition(
+            None,
+            Some(GeoRadius {
+                center: BERLIN,
+                radius: 5000.0,
+            }),
+            None,
+        );
+        assert_eq!(filtered(&index, &near_berlin), vec![0, 1]);
+
+        let around_moscow = geo_condition(
+            Some(GeoBoundingBox {
+                top_left: GeoPoint {
+                    lon: 37.0366,
+                    lat: 56.1859,
+                },
+                bottom_right: GeoPoint {
+                    lon: 38.2532,
+                    lat: 55.317,
+                },
+            }),
+            None,
+            None,
+        );
+        assert_eq!(filtered(&index, &around_moscow), vec![2]);
+
+        let ring = |points: &[(f64, f64)]| GeoLineString {
+            points: points
+                .iter()
+                .map(|&(lon, lat)| GeoPoint { lon, lat })
+                .collect(),
+        };
+        let around_potsdam = geo_condition(
+            None,
+            None,
+            Some(GeoPolygon::new(
+                ring(&[(13.0, 52.3), (13.2, 52.3), (13.2, 52.5), (13.0, 52.5)]),
+                None,
+            )),
+        );
+        assert_eq!(filtered(&index, &around_potsdam), vec![1]);
+
+        assert!(index.filter(&geo_condition(None, None, None)).is_none());
+        assert_eq!(index.count_indexed_points(), 4);
+    }
+
+    #[test]
+    fn test_geo_index_cardinality() {
+        let index = build_index();
+
+        let near_moscow = geo_condition(
+            None,
+            Some(GeoRadius {
+                center: MOSCOW,
+                radius: 1000.0,
+            }),
+            None,
+        );
+        let estimation = index.estimate_cardinality(&near_moscow).unwrap();
+        assert_eq!(estimation.primary_clauses.len(), 1);
+        assert_eq!(estimation.min, 0);
+        assert!(estimation.exp <= estimation.max);
+        assert_eq!(estimation.max, 1);
+
+        let whole_world = geo_condition(
+            Some(GeoBoundingBox {
+                top_left: GeoPoint {
+                    lon: -180.0,
+                    lat: 90.0,
+                },
+                bottom_right: GeoPoint {
+                    lon: 180.0,
+                    lat: -90.0,
+                },
+            }),
+            None,
+            None,
+        );
+        let estimation = index.estimate_cardinality(&whole_world).unwrap();
+        assert_eq!(estimation.exp, 3);
+        assert_eq!(estimation.max, 3);
+    }
+
+    #[test]
+    fn test_geo_index_payload_blocks() {
+        let index = build_index();
+
+        let blocks: Vec<_> = index.payload_blocks(2, "location".to_owned()).collect();
+        // Both points in Berlin share the most precise region
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].cardinality, 2);
+        let bounding_box = blocks[0].condition.geo_bounding_box.as_ref().unwrap();
+        assert!(
+            bounding_box.top_left.lon < BERLIN.lon && BERLIN.lon < bounding_box.bottom_right.lon
+        );
+        assert!(
+            bounding_box.bottom_right.lat < BERLIN.lat && BERLIN.lat < bounding_box.top_left.lat
+        );
+    }
+}
@@ -1,21 +1,38 @@
+use crate::index::field_index::full_text_index::FullTextIndex;
+use crate::index::field_index::geo_index::GeoMapIndex;
 use crate::index::field_index::map_index::PersistedMapIndex;
 use crate::index::field_index::numeric_index::PersistedNumericIndex;
+use crate::index::field_index::values_count_index::PersistedValuesCountIndex;
 use crate::index::field_index::PayloadFieldIndexBuilder;
-use crate::types::{FloatPayloadType, IntPayloadType, PayloadSchemaType};
+use crate::types::{FloatPayloadType, IntPayloadType, PayloadIndexParams, PayloadSchemaType};
 
-/// Selects index types based on field type
-pub fn index_selector(payload_type: &PayloadSchemaType) -> Vec<Box<dyn PayloadFieldIndexBuilder>> {
-    match payload_type {
-        PayloadSchemaType::Keyword => vec![Box::new(PersistedMapIndex::<String>::default())],
-        PayloadSchemaType::Integer => vec![
-            Box::new(PersistedMapIndex::<IntPayloadType>::default()),
-            Box::new(PersistedNumericIndex::<IntPayloadType>::default()),
-        ],
-        PayloadSchemaType::Float => {
+/// Selects index types based on field type and explicitly requested index parameters
+pub fn index_selector(
+    payload_type: &PayloadSchemaType,
+    index_params: Option<&PayloadIndexParams>,
+) -> Vec<Box<dyn PayloadFieldIndexBuilder>> {
+    let mut builders: Vec<Box<dyn PayloadFieldIndexBuilder>> = match (payload_type, index_params) {
+        (PayloadSchemaType::Keyword, Some(PayloadIndexParams::Text(text_params))) => {
+            vec![Box::new(FullTextIndex::new(text_params.clone()))]
+        }
+        (PayloadSchemaType::Keyword, _) => vec![Box::new(PersistedMapIndex::<String>::default())],
+        // Numeric index serves both exact match and range conditions of the integer field
+        (PayloadSchemaType::Integer, _) => {
+            vec![Box::new(PersistedNumericIndex::<IntPayloadType>::default())]
+        }
+        (PayloadSchemaType::Float, _) => {
             vec![Box::new(
                 PersistedNumericIndex::<FloatPayloadType>::default(),
             )]
         }
-        PayloadSchemaType::Geo => vec![],
-    }
+        (PayloadSchemaType::Geo, _) => vec![Box::new(GeoMapIndex::default())],
+        (PayloadSchemaType::Bool, _) => vec![Box::new(PersistedMapIndex::<bool>::default())],
+        (PayloadSchemaType::Datetime, _) => {
+            vec![Box::new(PersistedNumericIndex::<IntPayloadType>::default())]
+        }
+        (PayloadSchemaType::Object, _) => vec![],
+    };
+    // Number of values could be indexed for any type of the field
+    builders.push(Box::new(PersistedValuesCountIndex::default()));
+    builders
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use std::{iter, mem};
 
@@ -7,13 +7,18 @@ use serde::{Deserialize, Serialize};
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition, PrimaryCondition};
 use crate::index::field_index::{FieldIndex, PayloadFieldIndex, PayloadFieldIndexBuilder};
 use crate::types::{
-    FieldCondition, IntPayloadType, Match, PayloadKeyType, PayloadType, PointOffsetType,
+    AnyVariants, FieldCondition, IntPayloadType, Match, PayloadKeyType, PayloadType,
+    PointOffsetType,
 };
 
 /// HashMap-based type of index
 #[derive(Serialize, Deserialize, Default)]
 pub struct PersistedMapIndex<N: Hash + Eq + Clone> {
     map: HashMap<N, Vec<PointOffsetType>>,
+    /// Number of points, which have the indexed field.
+    /// Could be missing in the indexes, stored by the older versions
+    #[serde(default)]
+    points_count: usize,
 }
 
 impl<N: Hash + Eq + Clone> PersistedMapIndex<N> {
@@ -31,7 +36,45 @@ impl<N: Hash + Eq + Clone> PersistedMapIndex<N> {
         }
     }
 
+    /// Estimation of points, which have any of the given values
+    pub fn any_cardinality(&self, values: &[N]) -> CardinalityEstimation {
+        let counts: Vec<usize> = values
+            .iter()
+            .map(|value| self.map.get(value).map_or(0, |points| points.len()))
+            .collect();
+        let max_count = counts.iter().cloned().max().unwrap_or(0);
+        let total_count = counts.iter().sum();
+
+        // Points with multiple values may be counted several times
+        CardinalityEstimation {
+            primary_clauses: vec![],
+            min: max_count,
+            exp: total_count,
+            max: total_count,
+        }
+    }
+
+    /// Estimation of points, which have none of the given values
+    pub fn except_cardinality(&self, except: &[N]) -> CardinalityEstimation {
+        let except: HashSet<&N> = except.iter().collect();
+        let total_count = self
+            .map
+            .iter()
+            .filter(|(value, _)| !except.contains(value))
+            .map(|(_, points)| points.len())
+            .sum();
+
+        // Points with multiple values may be excluded by any of them
+        CardinalityEstimation {
+            primary_clauses: vec![],
+            min: 0,
+            exp: total_count,
+            max: total_count,
+        }
+    }
+
     fn add_many(&mut self, idx: PointOffsetType, values: &[N]) {
+        self.points_count += 1;
         for value in values {
             let vec = match self.map.get_mut(value) {
                 None => {
@@ -41,7 +84,10 @@ impl<N: Hash + Eq + Clone> PersistedMapIndex<N> {
                 }
                 Some(vec) => vec,
             };
-            vec.push(idx);
+            // Same value might be repeated, e.g. in several nested objects of the point
+            if vec.last() != Some(&idx) {
+                vec.push(idx);
+            }
         }
     }
 
@@ -51,6 +97,64 @@ impl<N: Hash + Eq + Clone> PersistedMapIndex<N> {
             .map(|ids| Box::new(ids.iter().cloned()) as Box<dyn Iterator<Item = PointOffsetType>>)
             .unwrap_or_else(|| Box::new(iter::empty::<PointOffsetType>()))
     }
+
+    /// Iterate over points, which have any of the given values.
+    /// Same point may be returned multiple times.
+    fn get_any_iterator(&self, values: &[N]) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let values = values.to_vec();
+        Box::new(
+            values
+                .into_iter()
+                .flat_map(move |value| self.get_iterator(&value)),
+        )
+    }
+
+    /// Iterate over points, which have at least one value not in the `except` list.
+    /// Might include points, which also have excluded values.
+    fn get_except_iterator(&self, except: &[N]) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let except: HashSet<N> = except.iter().cloned().collect();
+        Box::new(
+            self.map
+                .iter()
+                .filter(move |(value, _)| !except.contains(*value))
+                .flat_map(|(_, points)| points.iter().cloned()),
+        )
+    }
+
+    /// Select points for the match condition, values are checked in order of priority.
+    /// Resulting points should still be checked against the full condition.
+    fn match_filter(
+        &self,
+        value: Option<&N>,
+        any: Option<&[N]>,
+        except: Option<&[N]>,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        match (value, any, except) {
+            (Some(value), _, _) => Some(self.get_iterator(value)),
+            (None, Some(any), _) => Some(self.get_any_iterator(any)),
+            (None, None, Some(except)) => Some(self.get_except_iterator(except)),
+            (None, None, None) => None,
+        }
+    }
+
+    fn match_estimation(
+        &self,
+        condition: &FieldCondition,
+        value: Option<&N>,
+        any: Option<&[N]>,
+        except: Option<&[N]>,
+    ) -> Option<CardinalityEstimation> {
+        let mut estimation = match (value, any, except) {
+            (Some(value), _, _) => self.match_cardinality(value),
+            (None, Some(any), _) => self.any_cardinality(any),
+            (None, None, Some(except)) => self.except_cardinality(except),
+            (None, None, None) => return None,
+        };
+        estimation
+            .primary_clauses
+            .push(PrimaryCondition::Condition(condition.clone()));
+        Some(estimation)
+    }
 }
 
 impl PayloadFieldIndex for PersistedMapIndex<String> {
@@ -59,25 +163,35 @@ impl PayloadFieldIndex for PersistedMapIndex<String> {
         condition: &FieldCondition,
     ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
         condition.r#match.as_ref().and_then(|match_condition| {
-            match_condition
-                .keyword
-                .as_ref()
-                .map(|keyword| self.get_iterator(keyword))
+            self.match_filter(
+                match_condition.keyword.as_ref(),
+                match_condition.any.as_ref().and_then(AnyVariants::keywords),
+                match_condition
+                    .except
+                    .as_ref()
+                    .and_then(AnyVariants::keywords),
+            )
         })
     }
 
     fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
         condition.r#match.as_ref().and_then(|match_condition| {
-            match_condition.keyword.as_ref().map(|keyword| {
-                let mut estimation = self.match_cardinality(keyword);
-                estimation
-                    .primary_clauses
-                    .push(PrimaryCondition::Condition(condition.clone()));
-                estimation
-            })
+            self.match_estimation(
+                condition,
+                match_condition.keyword.as_ref(),
+                match_condition.any.as_ref().and_then(AnyVariants::keywords),
+                match_condition
+                    .except
+                    .as_ref()
+                    .and_then(AnyVariants::keywords),
+            )
         })
     }
 
+    fn count_indexed_points(&self) -> usize {
+        self.points_count
+    }
+
     fn payload_blocks(
         &self,
         threshold: usize,
@@ -93,10 +207,16 @@ impl PayloadFieldIndex for PersistedMapIndex<String> {
                     r#match: Some(Match {
                         keyword: Some(value.to_owned()),
                         integer: None,
+                        boolean: None,
+                        text: None,
+                        any: None,
+                        except: None,
                     }),
                     range: None,
                     geo_bounding_box: None,
                     geo_radius: None,
+                    geo_polygon: None,
+                    values_count: None,
                 },
                 cardinality: point_ids.len(),
             });
@@ -110,25 +230,35 @@ impl PayloadFieldIndex for PersistedMapIndex<IntPayloadType> {
         condition: &FieldCondition,
     ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
         condition.r#match.as_ref().and_then(|match_condition| {
-            match_condition
-                .integer
-                .as_ref()
-                .map(|int| self.get_iterator(int))
+            self.match_filter(
+                match_condition.integer.as_ref(),
+                match_condition.any.as_ref().and_then(AnyVariants::integers),
+                match_condition
+                    .except
+                    .as_ref()
+                    .and_then(AnyVariants::integers),
+            )
         })
     }
 
     fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
         condition.r#match.as_ref().and_then(|match_condition| {
-            match_condition.integer.as_ref().map(|number| {
-                let mut estimation = self.match_cardinality(number);
-                estimation
-                    .primary_clauses
-                    .push(PrimaryCondition::Condition(condition.clone()));
-                estimation
-            })
+            self.match_estimation(
+                condition,
+                match_condition.integer.as_ref(),
+                match_condition.any.as_ref().and_then(AnyVariants::integers),
+                match_condition
+                    .except
+                    .as_ref()
+                    .and_then(AnyVariants::integers),
+            )
         })
     }
 
+    fn count_indexed_points(&self) -> usize {
+        self.points_count
+    }
+
     fn payload_blocks(
         &self,
         threshold: usize,
@@ -144,10 +274,68 @@ impl PayloadFieldIndex for PersistedMapIndex<IntPayloadType> {
                     r#match: Some(Match {
                         keyword: None,
                         integer: Some(*value),
+                        boolean: None,
+                        text: None,
+                        any: None,
+                        except: None,
                     }),
                     range: None,
                     geo_bounding_box: None,
                     geo_radius: None,
+                    geo_polygon: None,
+                    values_count: None,
+                },
+                cardinality: point_ids.len(),
+            });
+        Box::new(iter)
+    }
+}
+
+impl PayloadFieldIndex for PersistedMapIndex<bool> {
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        condition.r#match.as_ref().and_then(|match_condition| {
+            self.match_filter(match_condition.boolean.as_ref(), None, None)
+        })
+    }
+
+    fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        condition.r#match.as_ref().and_then(|match_condition| {
+            self.match_estimation(condition, match_condition.boolean.as_ref(), None, None)
+        })
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        self.points_count
+    }
+
+    fn payload_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        let iter = self
+            .map
+            .iter()
+            .filter(move |(_value, point_ids)| point_ids.len() >= threshold)
+            .map(move |(value, point_ids)| PayloadBlockCondition {
+                condition: FieldCondition {
+                    key: key.clone(),
+                    r#match: Some(Match {
+                        keyword: None,
+                        integer: None,
+                        boolean: Some(*value),
+                        text: None,
+                        any: None,
+                        except: None,
+                    }),
+                    range: None,
+                    geo_bounding_box: None,
+                    geo_radius: None,
+                    geo_polygon: None,
+                    values_count: None,
                 },
                 cardinality: point_ids.len(),
             });
@@ -165,8 +353,12 @@ impl PayloadFieldIndexBuilder for PersistedMapIndex<String> {
 
     fn build(&mut self) -> FieldIndex {
         let data = mem::take(&mut self.map);
+        let points_count = mem::take(&mut self.points_count);
 
-        FieldIndex::KeywordIndex(PersistedMapIndex { map: data })
+        FieldIndex::KeywordIndex(PersistedMapIndex {
+            map: data,
+            points_count,
+        })
     }
 }
 
@@ -180,7 +372,30 @@ impl PayloadFieldIndexBuilder for PersistedMapIndex<IntPayloadType> {
 
     fn build(&mut self) -> FieldIndex {
         let data = mem::take(&mut self.map);
+        let points_count = mem::take(&mut self.points_count);
+
+        FieldIndex::IntMapIndex(PersistedMapIndex {
+            map: data,
+            points_count,
+        })
+    }
+}
+
+impl PayloadFieldIndexBuilder for PersistedMapIndex<bool> {
+    fn add(&mut self, id: PointOffsetType, value: &PayloadType) {
+        match value {
+            PayloadType::Bool(bools) => self.add_many(id, bools),
+            _ => panic!("Unexpected payload type: {:?}", value),
+        }
+    }
+
+    fn build(&mut self) -> FieldIndex {
+        let data = mem::take(&mut self.map);
+        let points_count = mem::take(&mut self.points_count);
 
-        FieldIndex::IntMapIndex(PersistedMapIndex { map: data })
+        FieldIndex::BoolIndex(PersistedMapIndex {
+            map: data,
+            points_count,
+        })
     }
 }
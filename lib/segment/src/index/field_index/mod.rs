@@ -2,10 +2,13 @@ use crate::types::{FieldCondition, PointOffsetType};
 use std::collections::HashSet;
 
 mod field_index_base;
+pub mod full_text_index;
+pub mod geo_hash;
 pub mod geo_index;
 pub mod index_selector;
 pub mod map_index;
 pub mod numeric_index;
+pub mod values_count_index;
 
 pub use field_index_base::*;
 
@@ -34,7 +37,6 @@ pub struct CardinalityEstimation {
 }
 
 impl CardinalityEstimation {
-    #[allow(dead_code)]
     pub fn exact(count: usize) -> Self {
         CardinalityEstimation {
             primary_clauses: vec![],
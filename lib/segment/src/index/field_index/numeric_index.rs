@@ -1,19 +1,80 @@
-use std::cmp::Ordering::{Greater, Less};
+use std::cmp::Ordering::{self, Equal, Greater, Less};
 use std::cmp::{max, min};
-use std::mem;
+use std::{iter, mem};
 
-use num_traits::ToPrimitive;
 use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
 
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition, PrimaryCondition};
 use crate::index::field_index::{FieldIndex, PayloadFieldIndex, PayloadFieldIndexBuilder};
 use crate::types::{
-    FieldCondition, FloatPayloadType, IntPayloadType, PayloadKeyType, PayloadType, PointOffsetType,
-    Range,
+    datetime_timestamp, AnyVariants, Direction, FieldCondition, FloatPayloadType, IntPayloadType,
+    Match, PayloadKeyType, PayloadType, PointOffsetType, Range, RangeValue,
 };
 use itertools::Itertools;
 
+/// Type of the values, which could be stored in the numeric index
+pub trait Numericable: Copy {
+    /// Ordering of the value relative to the range bound
+    fn cmp_bound(&self, bound: &RangeValue) -> Option<Ordering>;
+
+    /// Ranges of values, which should be selected by the match condition.
+    /// `None` if the index can't serve the condition
+    fn match_ranges(_condition_match: &Match) -> Option<Vec<Range>> {
+        None
+    }
+}
+
+impl Numericable for IntPayloadType {
+    fn cmp_bound(&self, bound: &RangeValue) -> Option<Ordering> {
+        bound.cmp_int(*self)
+    }
+
+    fn match_ranges(condition_match: &Match) -> Option<Vec<Range>> {
+        let exact_range = |value: &IntPayloadType| Range {
+            lt: None,
+            gt: None,
+            gte: Some(RangeValue::Integer(*value)),
+            lte: Some(RangeValue::Integer(*value)),
+        };
+        if let Some(value) = &condition_match.integer {
+            return Some(vec![exact_range(value)]);
+        }
+        if let Some(any) = condition_match.any.as_ref().and_then(AnyVariants::integers) {
+            return Some(any.iter().map(exact_range).collect());
+        }
+        let except = condition_match
+            .except
+            .as_ref()
+            .and_then(AnyVariants::integers)?;
+        // Gaps between the excluded values
+        let bounds = except
+            .iter()
+            .cloned()
+            .sorted()
+            .dedup()
+            .map(Some)
+            .collect_vec();
+        let ranges = iter::once(None)
+            .chain(bounds.iter().cloned())
+            .zip(bounds.iter().cloned().chain(iter::once(None)))
+            .map(|(lower, upper)| Range {
+                lt: upper.map(RangeValue::Integer),
+                gt: lower.map(RangeValue::Integer),
+                gte: None,
+                lte: None,
+            })
+            .collect();
+        Some(ranges)
+    }
+}
+
+impl Numericable for FloatPayloadType {
+    fn cmp_bound(&self, bound: &RangeValue) -> Option<Ordering> {
+        bound.cmp_float(*self)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Element<N> {
     pub id: PointOffsetType,
@@ -22,14 +83,14 @@ pub struct Element<N> {
 
 /// Sorting-based type of index
 #[derive(Serialize, Deserialize, Default)]
-pub struct PersistedNumericIndex<N: ToPrimitive + Clone> {
+pub struct PersistedNumericIndex<N: Numericable> {
     /// Number of unique element ids.
     /// Each point can have several values
     points_count: usize,
     elements: Vec<Element<N>>,
 }
 
-impl<N: ToPrimitive + Clone> PersistedNumericIndex<N> {
+impl<N: Numericable + Into<RangeValue>> PersistedNumericIndex<N> {
     fn search_range(&self, range: &Range) -> (usize, usize) {
         let mut lower_index = 0;
         let mut upper_index = self.elements.len();
@@ -38,10 +99,10 @@ impl<N: ToPrimitive + Clone> PersistedNumericIndex<N> {
             let index = self
                 .elements
                 .binary_search_by(|x| {
-                    if x.value.to_f64().unwrap() <= thr {
-                        Less
-                    } else {
+                    if matches!(x.value.cmp_bound(&thr), Some(Greater)) {
                         Greater
+                    } else {
+                        Less
                     }
                 })
                 .err()
@@ -53,10 +114,10 @@ impl<N: ToPrimitive + Clone> PersistedNumericIndex<N> {
             let index = self
                 .elements
                 .binary_search_by(|x| {
-                    if x.value.to_f64().unwrap() < thr {
-                        Less
-                    } else {
+                    if matches!(x.value.cmp_bound(&thr), Some(Greater | Equal)) {
                         Greater
+                    } else {
+                        Less
                     }
                 })
                 .err()
@@ -68,7 +129,7 @@ impl<N: ToPrimitive + Clone> PersistedNumericIndex<N> {
             let index = self
                 .elements
                 .binary_search_by(|x| {
-                    if x.value.to_f64().unwrap() < thr {
+                    if matches!(x.value.cmp_bound(&thr), Some(Less)) {
                         Less
                     } else {
                         Greater
@@ -83,7 +144,7 @@ impl<N: ToPrimitive + Clone> PersistedNumericIndex<N> {
             let index = self
                 .elements
                 .binary_search_by(|x| {
-                    if x.value.to_f64().unwrap() <= thr {
+                    if matches!(x.value.cmp_bound(&thr), Some(Less | Equal)) {
                         Less
                     } else {
                         Greater
@@ -136,6 +197,21 @@ impl<N: ToPrimitive + Clone> PersistedNumericIndex<N> {
         self.points_count += 1
     }
 
+    /// Estimation of points, which have values in any of the disjoint ranges
+    fn ranges_cardinality(&self, ranges: &[Range]) -> CardinalityEstimation {
+        let estimations = ranges
+            .iter()
+            .map(|range| self.range_cardinality(range))
+            .collect_vec();
+        // Points with multiple values may fall into several ranges
+        CardinalityEstimation {
+            primary_clauses: vec![],
+            min: estimations.iter().map(|x| x.min).max().unwrap_or(0),
+            exp: min(self.points_count, estimations.iter().map(|x| x.exp).sum()),
+            max: min(self.points_count, estimations.iter().map(|x| x.max).sum()),
+        }
+    }
+
     fn condition_iter(&self, range: &Range) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         let (lower_index, upper_index) = self.search_range(range);
         Box::new(
@@ -144,27 +220,80 @@ impl<N: ToPrimitive + Clone> PersistedNumericIndex<N> {
                 .map(|element| element.id),
         )
     }
+
+    /// Iterate over values in the given direction, starting from `start_from` value including.
+    /// Point with multiple values is returned once for each value
+    pub fn ordered_points(
+        &self,
+        direction: Direction,
+        start_from: Option<RangeValue>,
+    ) -> Box<dyn Iterator<Item = (RangeValue, PointOffsetType)> + '_> {
+        let element_value = |element: &Element<N>| -> (RangeValue, PointOffsetType) {
+            (element.value.into(), element.id)
+        };
+        match direction {
+            Direction::Asc => {
+                let (lower_index, _) = self.search_range(&Range {
+                    lt: None,
+                    gt: None,
+                    gte: start_from,
+                    lte: None,
+                });
+                Box::new(self.elements[lower_index..].iter().map(element_value))
+            }
+            Direction::Desc => {
+                let (_, upper_index) = self.search_range(&Range {
+                    lt: None,
+                    gt: None,
+                    gte: None,
+                    lte: start_from,
+                });
+                Box::new(self.elements[..upper_index].iter().rev().map(element_value))
+            }
+        }
+    }
+
+    /// Same point may be returned multiple times
+    fn ranges_iter(&self, ranges: Vec<Range>) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        Box::new(
+            ranges
+                .into_iter()
+                .flat_map(move |range| self.condition_iter(&range)),
+        )
+    }
 }
 
-impl<N: ToPrimitive + Clone> PayloadFieldIndex for PersistedNumericIndex<N> {
+impl<N: Numericable + Into<RangeValue>> PayloadFieldIndex for PersistedNumericIndex<N> {
     fn filter(
         &self,
         condition: &FieldCondition,
     ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        if let Some(range) = &condition.range {
+            return Some(self.condition_iter(range));
+        }
         condition
-            .range
+            .r#match
             .as_ref()
-            .map(|range| self.condition_iter(range))
+            .and_then(N::match_ranges)
+            .map(|ranges| self.ranges_iter(ranges))
     }
 
     fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
-        condition.range.as_ref().map(|range| {
-            let mut cardinality = self.range_cardinality(range);
-            cardinality
-                .primary_clauses
-                .push(PrimaryCondition::Condition(condition.clone()));
-            cardinality
-        })
+        let mut cardinality = match &condition.range {
+            Some(range) => self.range_cardinality(range),
+            None => {
+                let ranges = condition.r#match.as_ref().and_then(N::match_ranges)?;
+                self.ranges_cardinality(&ranges)
+            }
+        };
+        cardinality
+            .primary_clauses
+            .push(PrimaryCondition::Condition(condition.clone()));
+        Some(cardinality)
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        self.points_count
     }
 
     fn payload_blocks(
@@ -182,8 +311,8 @@ impl<N: ToPrimitive + Clone> PayloadFieldIndex for PersistedNumericIndex<N> {
             .filter_map(move |init_offset| {
                 let upper_index = min(num_elements - 1, init_offset + effective_threshold);
 
-                let upper_value = self.elements[upper_index].value.to_f64();
-                let lower_value = self.elements[init_offset].value.to_f64();
+                let upper_value: RangeValue = self.elements[upper_index].value.into();
+                let lower_value: RangeValue = self.elements[init_offset].value.into();
 
                 if upper_value == lower_value {
                     return None; // Range blocks makes no sense within a single value
@@ -191,8 +320,8 @@ impl<N: ToPrimitive + Clone> PayloadFieldIndex for PersistedNumericIndex<N> {
                 Some(Range {
                     lt: None,
                     gt: None,
-                    gte: lower_value,
-                    lte: upper_value,
+                    gte: Some(lower_value),
+                    lte: Some(upper_value),
                 })
             })
             .dedup()
@@ -206,6 +335,8 @@ impl<N: ToPrimitive + Clone> PayloadFieldIndex for PersistedNumericIndex<N> {
                         range: Some(range),
                         geo_bounding_box: None,
                         geo_radius: None,
+                        geo_polygon: None,
+                        values_count: None,
                     },
                     cardinality: cardinality.exp,
                 }
@@ -237,6 +368,11 @@ impl PayloadFieldIndexBuilder for PersistedNumericIndex<IntPayloadType> {
     fn add(&mut self, id: PointOffsetType, value: &PayloadType) {
         match value {
             PayloadType::Integer(number) => self.add_many(id, number),
+            // Datetimes are indexed as timestamps, so range filters work the same way
+            PayloadType::Datetime(datetimes) => {
+                let timestamps = datetimes.iter().map(datetime_timestamp).collect_vec();
+                self.add_many(id, &timestamps)
+            }
             _ => panic!("Unexpected payload type: {:?}", value),
         }
     }
@@ -284,7 +420,7 @@ mod tests {
                 .expect("range condition")
                 .gte
                 .expect("gte"),
-            1.0
+            RangeValue::Float(1.0)
         );
         assert_eq!(
             blocks[0]
@@ -293,7 +429,7 @@ mod tests {
                 .expect("range condition")
                 .lte
                 .expect("lte"),
-            2.0
+            RangeValue::Float(2.0)
         );
     }
 
@@ -323,9 +459,9 @@ mod tests {
         assert_eq!(res, (0, index.elements.len()));
 
         let res = index.search_range(&Range {
-            lt: Some(15.0),
+            lt: Some(RangeValue::Float(15.0)),
             gt: None,
-            gte: Some(6.0),
+            gte: Some(RangeValue::Float(6.0)),
             lte: None,
         });
         let elements = &index.elements[res.0..res.1];
@@ -333,6 +469,36 @@ mod tests {
         assert_eq!(elements[elements.len() - 1].id, 7);
     }
 
+    #[test]
+    fn test_ordered_points() {
+        let index = PersistedNumericIndex {
+            points_count: 4,
+            elements: vec![
+                Element { id: 1, value: 1 },
+                Element { id: 2, value: 3 },
+                Element { id: 3, value: 3 },
+                Element { id: 4, value: 9 },
+            ],
+        };
+
+        let ids = |direction, start_from| {
+            index
+                .ordered_points(direction, start_from)
+                .map(|(_, id)| id)
+                .collect_vec()
+        };
+        assert_eq!(ids(Direction::Asc, None), vec![1, 2, 3, 4]);
+        assert_eq!(
+            ids(Direction::Asc, Some(RangeValue::Float(2.5))),
+            vec![2, 3, 4]
+        );
+        assert_eq!(ids(Direction::Desc, None), vec![4, 3, 2, 1]);
+        assert_eq!(
+            ids(Direction::Desc, Some(RangeValue::Integer(3))),
+            vec![3, 2, 1]
+        );
+    }
+
     #[test]
     fn test_cardinality() {
         let index = PersistedNumericIndex {
@@ -351,9 +517,9 @@ mod tests {
         };
 
         let estimation = index.range_cardinality(&Range {
-            lt: Some(15.0),
+            lt: Some(RangeValue::Float(15.0)),
             gt: None,
-            gte: Some(6.0),
+            gte: Some(RangeValue::Float(6.0)),
             lte: None,
         });
         eprintln!("estimation = {:#?}", estimation);
@@ -361,9 +527,9 @@ mod tests {
         assert!(estimation.exp <= estimation.max);
 
         let estimation = index.range_cardinality(&Range {
-            lt: Some(6.0),
+            lt: Some(RangeValue::Float(6.0)),
             gt: None,
-            gte: Some(16.0),
+            gte: Some(RangeValue::Float(16.0)),
             lte: None,
         });
         eprintln!("estimation = {:#?}", estimation);
@@ -381,4 +547,69 @@ mod tests {
         let json = serde_json::to_string_pretty(&index).unwrap();
         println!("{}", json)
     }
+
+    #[test]
+    fn test_int_match_and_exact_range() {
+        let big = 9_007_199_254_740_993; // 2^53 + 1, not representable as f64
+        let mut builder = PersistedNumericIndex::<IntPayloadType>::default();
+        builder.add(1, &PayloadType::Integer(vec![big - 1]));
+        builder.add(2, &PayloadType::Integer(vec![big, 5]));
+        builder.add(3, &PayloadType::Integer(vec![5]));
+        builder.add(4, &PayloadType::Integer(vec![7]));
+        let index = builder.build();
+
+        let condition = |range, condition_match| FieldCondition {
+            key: "test".to_owned(),
+            r#match: condition_match,
+            range,
+            geo_bounding_box: None,
+            geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
+        };
+        let integer_match = |integer, any, except| {
+            Some(Match {
+                keyword: None,
+                integer,
+                boolean: None,
+                text: None,
+                any,
+                except,
+            })
+        };
+        let filtered = |condition: &FieldCondition| {
+            index
+                .filter(condition)
+                .unwrap()
+                .sorted()
+                .dedup()
+                .collect_vec()
+        };
+
+        let range = Range {
+            lt: None,
+            gt: Some(RangeValue::Integer(big - 1)),
+            gte: None,
+            lte: None,
+        };
+        assert_eq!(filtered(&condition(Some(range), None)), vec![2]);
+
+        let match_five = condition(None, integer_match(Some(5), None, None));
+        assert_eq!(filtered(&match_five), vec![2, 3]);
+        let estimation = index.estimate_cardinality(&match_five).unwrap();
+        assert!(estimation.min <= 2 && 2 <= estimation.max);
+        assert_eq!(estimation.primary_clauses.len(), 1);
+
+        let any = AnyVariants::Integers(vec![7, big - 1]);
+        let match_any = condition(None, integer_match(None, Some(any), None));
+        assert_eq!(filtered(&match_any), vec![1, 4]);
+
+        let except = AnyVariants::Integers(vec![5]);
+        let match_except = condition(None, integer_match(None, None, Some(except)));
+        assert_eq!(filtered(&match_except), vec![1, 2, 4]);
+
+        let except = AnyVariants::Integers(vec![big, 5, 5]);
+        let match_except = condition(None, integer_match(None, None, Some(except)));
+        assert_eq!(filtered(&match_except), vec![1, 4]);
+    }
 }
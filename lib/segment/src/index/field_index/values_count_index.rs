@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+use std::{iter, mem};
+
+use serde::{Deserialize, Serialize};
+
+use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition, PrimaryCondition};
+use crate::index::field_index::{FieldIndex, PayloadFieldIndex, PayloadFieldIndexBuilder};
+use crate::types::{FieldCondition, PayloadKeyType, PayloadType, PointOffsetType, ValuesCount};
+
+/// Index of the number of values, which points have in the field.
+/// Could be built for any type of the field
+#[derive(Serialize, Deserialize, Default)]
+pub struct PersistedValuesCountIndex {
+    /// Points, grouped by the number of values
+    counts: BTreeMap<usize, Vec<PointOffsetType>>,
+    points_count: usize,
+}
+
+impl PersistedValuesCountIndex {
+    fn matched_points(
+        &self,
+        values_count: &ValuesCount,
+    ) -> Box<dyn Iterator<Item = &Vec<PointOffsetType>> + '_> {
+        match values_count.bounds() {
+            None => Box::new(iter::empty()),
+            Some((lower, upper)) => Box::new(
+                self.counts
+                    .range(lower..=upper)
+                    .map(|(_count, point_ids)| point_ids),
+            ),
+        }
+    }
+
+    pub fn values_count_cardinality(&self, values_count: &ValuesCount) -> CardinalityEstimation {
+        let count = self
+            .matched_points(values_count)
+            .map(|point_ids| point_ids.len())
+            .sum();
+        CardinalityEstimation::exact(count)
+    }
+}
+
+impl PayloadFieldIndex for PersistedValuesCountIndex {
+    fn filter(
+        &self,
+        condition: &FieldCondition,
+    ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
+        condition.values_count.as_ref().map(|values_count| {
+            Box::new(
+                self.matched_points(values_count)
+                    .flat_map(|point_ids| point_ids.iter().cloned()),
+            ) as Box<dyn Iterator<Item = PointOffsetType>>
+        })
+    }
+
+    fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
+        condition.values_count.as_ref().map(|values_count| {
+            let mut cardinality = self.values_count_cardinality(values_count);
+            cardinality
+                .primary_clauses
+                .push(PrimaryCondition::Condition(condition.clone()));
+            cardinality
+        })
+    }
+
+    fn count_indexed_points(&self) -> usize {
+        self.points_count
+    }
+
+    fn payload_blocks(
+        &self,
+        _threshold: usize,
+        _key: PayloadKeyType,
+    ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_> {
+        // Number of values is rarely used for filtering, no need to build extra HNSW links for it
+        Box::new(iter::empty())
+    }
+}
+
+impl PayloadFieldIndexBuilder for PersistedValuesCountIndex {
+    fn add(&mut self, id: PointOffsetType, value: &PayloadType) {
+        self.counts.entry(value.len()).or_default().push(id);
+        self.points_count += 1;
+    }
+
+    fn build(&mut self) -> FieldIndex {
+        FieldIndex::ValuesCountIndex(mem::take(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_values_count_filter() {
+        let mut builder = PersistedValuesCountIndex::default();
+        builder.add(1, &PayloadType::Keyword(vec![]));
+        builder.add(2, &PayloadType::Integer(vec![1, 2]));
+        builder.add(3, &PayloadType::Integer(vec![1, 2, 3]));
+        builder.add(4, &PayloadType::Float(vec![1.0, 2.0, 3.0, 4.0]));
+        let index = builder.build();
+
+        let condition = |gt, lte| FieldCondition {
+            key: "test".to_owned(),
+            r#match: None,
+            range: None,
+            geo_bounding_box: None,
+            geo_radius: None,
+            geo_polygon: None,
+            values_count: Some(ValuesCount {
+                lt: None,
+                gt,
+                gte: None,
+                lte,
+            }),
+        };
+
+        let filtered = |condition: &FieldCondition| {
+            let mut point_ids: Vec<_> = index.filter(condition).unwrap().collect();
+            point_ids.sort_unstable();
+            point_ids
+        };
+
+        assert_eq!(filtered(&condition(Some(1), Some(3))), vec![2, 3]);
+        assert_eq!(filtered(&condition(None, Some(0))), vec![1]);
+        assert!(filtered(&condition(Some(3), Some(2))).is_empty());
+
+        let estimation = index
+            .estimate_cardinality(&condition(Some(2), None))
+            .unwrap();
+        assert_eq!(estimation.exp, 2);
+        assert_eq!(estimation.primary_clauses.len(), 1);
+        assert_eq!(index.count_indexed_points(), 4);
+    }
+}
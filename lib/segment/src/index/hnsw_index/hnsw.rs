@@ -160,7 +160,22 @@ impl HNSWIndex {
             filter,
         };
 
-        self.graph.search(top, ef, &points_scorer)
+        match vector_storage.quantization_config() {
+            None => self.graph.search(top, ef, &points_scorer),
+            Some(quantization_config) => {
+                // Graph is traversed with approximate scores of quantized vectors,
+                // so more candidates are selected and rescored with original vectors
+                let rescore_limit = quantization_config.rescore_limit(top);
+                let candidates =
+                    self.graph
+                        .search(rescore_limit, max(ef, rescore_limit), &points_scorer);
+                vector_storage.score_points(
+                    vector,
+                    &mut candidates.iter().map(|candidate| candidate.idx),
+                    top,
+                )
+            }
+        }
     }
 }
 
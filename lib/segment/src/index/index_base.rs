@@ -1,7 +1,8 @@
 use crate::entry::entry_point::OperationResult;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::types::{
-    Filter, PayloadKeyType, PayloadKeyTypeRef, PointOffsetType, SearchParams, VectorElementType,
+    Direction, Filter, PayloadIndexParams, PayloadKeyType, PayloadKeyTypeRef, PointOffsetType,
+    RangeValue, SearchParams, VectorElementType,
 };
 use crate::vector_storage::ScoredPointOffset;
 
@@ -24,8 +25,16 @@ pub trait PayloadIndex {
     /// Get indexed fields
     fn indexed_fields(&self) -> Vec<PayloadKeyType>;
 
-    /// Mark field as one which should be indexed
-    fn set_indexed(&mut self, field: PayloadKeyTypeRef) -> OperationResult<()>;
+    /// Get explicit parameters of the field index, if any
+    fn index_params(&self, field: PayloadKeyTypeRef) -> Option<PayloadIndexParams>;
+
+    /// Mark field as one which should be indexed.
+    /// Index is rebuilt, if parameters of the existing index are changed
+    fn set_indexed(
+        &mut self,
+        field: PayloadKeyTypeRef,
+        params: Option<&PayloadIndexParams>,
+    ) -> OperationResult<()>;
 
     /// Remove index
     fn drop_index(&mut self, field: PayloadKeyTypeRef) -> OperationResult<()>;
@@ -46,4 +55,14 @@ pub trait PayloadIndex {
         field: PayloadKeyTypeRef,
         threshold: usize,
     ) -> Box<dyn Iterator<Item = PayloadBlockCondition> + '_>;
+
+    /// Iterate over values of the numeric field with their points in the given direction,
+    /// starting from the `start_from` value. Point with multiple values is returned for each value.
+    /// `None` if there is no index, which could order points by the field
+    fn ordered_points(
+        &self,
+        field: PayloadKeyTypeRef,
+        direction: Direction,
+        start_from: Option<RangeValue>,
+    ) -> Option<Box<dyn Iterator<Item = (RangeValue, PointOffsetType)> + '_>>;
 }
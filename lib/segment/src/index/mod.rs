@@ -5,7 +5,9 @@ mod payload_config;
 pub mod plain_payload_index;
 pub mod query_estimator;
 mod sample_estimation;
+pub mod sparse_index;
 pub mod struct_payload_index;
 mod visited_pool;
 
+pub use field_index::CardinalityEstimation;
 pub use index_base::*;
@@ -1,7 +1,8 @@
 use crate::common::file_operations::{atomic_save_json, read_json};
 use crate::entry::entry_point::OperationResult;
-use crate::types::PayloadKeyType;
+use crate::types::{PayloadIndexParams, PayloadKeyType, PayloadKeyTypeRef};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 pub const PAYLOAD_INDEX_CONFIG_FILE: &str = "config.json";
@@ -10,6 +11,9 @@ pub const PAYLOAD_INDEX_CONFIG_FILE: &str = "config.json";
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
 pub struct PayloadConfig {
     pub indexed_fields: Vec<PayloadKeyType>,
+    /// Explicit parameters of the field indexes, default index is used for other fields
+    #[serde(default)]
+    pub index_params: HashMap<PayloadKeyType, PayloadIndexParams>,
 }
 
 impl PayloadConfig {
@@ -24,4 +28,30 @@ impl PayloadConfig {
     pub fn save(&self, path: &Path) -> OperationResult<()> {
         atomic_save_json(path, self)
     }
+
+    /// Mark field as indexed with given parameters.
+    /// Returns `true` if the field was not indexed before or index parameters are changed
+    pub fn set_indexed(
+        &mut self,
+        field: PayloadKeyTypeRef,
+        params: Option<&PayloadIndexParams>,
+    ) -> bool {
+        let is_new = !self.indexed_fields.iter().any(|x| x == field);
+        if is_new {
+            self.indexed_fields.push(field.into());
+        }
+        let params_changed = self.index_params.get(field) != params;
+        if params_changed {
+            match params {
+                Some(params) => self.index_params.insert(field.into(), params.clone()),
+                None => self.index_params.remove(field),
+            };
+        }
+        is_new || params_changed
+    }
+
+    pub fn drop_indexed(&mut self, field: PayloadKeyTypeRef) {
+        self.indexed_fields.retain(|x| x != field);
+        self.index_params.remove(field);
+    }
 }
@@ -1,9 +1,10 @@
 use crate::index::{PayloadIndex, VectorIndex};
 use crate::payload_storage::ConditionChecker;
 use crate::types::{
-    Filter, PayloadKeyType, PayloadKeyTypeRef, PointOffsetType, SearchParams, VectorElementType,
+    Direction, Filter, PayloadIndexParams, PayloadKeyType, PayloadKeyTypeRef, PointOffsetType,
+    RangeValue, SearchParams, VectorElementType,
 };
-use crate::vector_storage::{ScoredPointOffset, VectorStorage};
+use crate::vector_storage::{PointsStorage, ScoredPointOffset, VectorStorage};
 
 use crate::entry::entry_point::OperationResult;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
@@ -19,7 +20,7 @@ use std::sync::Arc;
 /// rather than spend time for index re-building
 pub struct PlainPayloadIndex {
     condition_checker: Arc<dyn ConditionChecker>,
-    vector_storage: Arc<AtomicRefCell<dyn VectorStorage>>,
+    points_storage: Arc<AtomicRefCell<dyn PointsStorage>>,
     config: PayloadConfig,
     path: PathBuf,
 }
@@ -36,7 +37,7 @@ impl PlainPayloadIndex {
 
     pub fn open(
         condition_checker: Arc<dyn ConditionChecker>,
-        vector_storage: Arc<AtomicRefCell<dyn VectorStorage>>,
+        points_storage: Arc<AtomicRefCell<dyn PointsStorage>>,
         path: &Path,
     ) -> OperationResult<Self> {
         create_dir_all(path)?;
@@ -49,7 +50,7 @@ impl PlainPayloadIndex {
 
         let index = PlainPayloadIndex {
             condition_checker,
-            vector_storage,
+            points_storage,
             config,
             path: path.to_owned(),
         };
@@ -67,27 +68,28 @@ impl PayloadIndex for PlainPayloadIndex {
         self.config.indexed_fields.clone()
     }
 
-    fn set_indexed(&mut self, field: PayloadKeyTypeRef) -> OperationResult<()> {
-        if !self.config.indexed_fields.iter().any(|x| x == field) {
-            self.config.indexed_fields.push(field.into());
+    fn index_params(&self, field: PayloadKeyTypeRef) -> Option<PayloadIndexParams> {
+        self.config.index_params.get(field).cloned()
+    }
+
+    fn set_indexed(
+        &mut self,
+        field: PayloadKeyTypeRef,
+        params: Option<&PayloadIndexParams>,
+    ) -> OperationResult<()> {
+        if self.config.set_indexed(field, params) {
             return self.save_config();
         }
         Ok(())
     }
 
     fn drop_index(&mut self, field: PayloadKeyTypeRef) -> OperationResult<()> {
-        self.config.indexed_fields = self
-            .config
-            .indexed_fields
-            .iter()
-            .cloned()
-            .filter(|x| x != field)
-            .collect();
+        self.config.drop_indexed(field);
         self.save_config()
     }
 
     fn estimate_cardinality(&self, _query: &Filter) -> CardinalityEstimation {
-        let total_points = self.vector_storage.borrow().vector_count();
+        let total_points = self.points_storage.borrow().vector_count();
         CardinalityEstimation {
             primary_clauses: vec![],
             min: 0,
@@ -101,7 +103,7 @@ impl PayloadIndex for PlainPayloadIndex {
         query: &'a Filter,
     ) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
         let mut matched_points = vec![];
-        for i in self.vector_storage.borrow().iter_ids() {
+        for i in self.points_storage.borrow().iter_ids() {
             if self.condition_checker.check(i, query) {
                 matched_points.push(i);
             }
@@ -117,6 +119,15 @@ impl PayloadIndex for PlainPayloadIndex {
         // No blocks for un-indexed payload
         Box::new(vec![].into_iter())
     }
+
+    fn ordered_points(
+        &self,
+        _field: PayloadKeyTypeRef,
+        _direction: Direction,
+        _start_from: Option<RangeValue>,
+    ) -> Option<Box<dyn Iterator<Item = (RangeValue, PointOffsetType)> + '_>> {
+        None
+    }
 }
 
 pub struct PlainIndex {
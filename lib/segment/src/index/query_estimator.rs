@@ -4,7 +4,7 @@
 //! - use vector index or payload index first
 
 use crate::index::field_index::{CardinalityEstimation, PrimaryCondition};
-use crate::types::{Condition, Filter};
+use crate::types::{Condition, Filter, MinShould};
 use itertools::Itertools;
 use std::cmp::{max, min};
 
@@ -79,6 +79,12 @@ where
             }
         }
     }
+    match &filter.min_should {
+        None => {}
+        Some(min_should) => {
+            filter_estimations.push(estimate_min_should(estimator, min_should, total))
+        }
+    }
     match &filter.must_not {
         None => {}
         Some(conditions) => {
@@ -91,6 +97,79 @@ where
     combine_must_estimations(&filter_estimations, total)
 }
 
+fn estimate_min_should<F>(
+    estimator: &F,
+    min_should: &MinShould,
+    total: usize,
+) -> CardinalityEstimation
+where
+    F: Fn(&Condition) -> CardinalityEstimation,
+{
+    let MinShould {
+        conditions,
+        min_count,
+    } = min_should;
+    let min_count = *min_count;
+    let conditions_count = conditions.len();
+
+    if min_count == 0 {
+        return CardinalityEstimation::exact(total);
+    }
+    if min_count > conditions_count {
+        return CardinalityEstimation::exact(0);
+    }
+
+    let estimate = |x| estimate_condition(estimator, x, total);
+    let estimations = conditions.iter().map(estimate).collect_vec();
+
+    // Matched point could miss at most `conditions_count - min_count` conditions,
+    // so it satisfies at least one of any `conditions_count - min_count + 1` conditions
+    let required_clauses = conditions_count - min_count + 1;
+    let indexed_estimations = estimations
+        .iter()
+        .filter(|x| !x.primary_clauses.is_empty())
+        .sorted_by_key(|x| x.exp)
+        .collect_vec();
+    let clauses = if indexed_estimations.len() >= required_clauses {
+        indexed_estimations
+            .iter()
+            .take(required_clauses)
+            .flat_map(|x| x.primary_clauses.iter().cloned())
+            .collect()
+    } else {
+        vec![]
+    };
+
+    // Assuming conditions are independent, probability of `k` hits is computed
+    // incrementally, adding conditions one by one
+    let mut hits_prob = vec![0.0; conditions_count + 1];
+    hits_prob[0] = 1.0;
+    for (added, estimation) in estimations.iter().enumerate() {
+        let hit_prob = estimation.exp as f64 / total as f64;
+        for hits in (1..=added + 1).rev() {
+            hits_prob[hits] = hits_prob[hits] * (1.0 - hit_prob) + hits_prob[hits - 1] * hit_prob;
+        }
+        hits_prob[0] *= 1.0 - hit_prob;
+    }
+    let element_hit_prob: f64 = hits_prob[min_count..].iter().sum();
+    let expected_count = (element_hit_prob * (total as f64)) as usize;
+
+    // Each matched point is counted at least `min_count` times in the sum of hits,
+    // and each not matched point is counted at most `min_count - 1` times
+    let max_hits: usize = estimations.iter().map(|x| x.max).sum();
+    let min_hits: usize = estimations.iter().map(|x| x.min).sum();
+    let min_estimation =
+        min_hits.saturating_sub((min_count - 1) * total) / (conditions_count - min_count + 1);
+    let max_estimation = min(max_hits / min_count, total);
+
+    CardinalityEstimation {
+        primary_clauses: clauses,
+        min: min_estimation,
+        exp: min(max(expected_count, min_estimation), max_estimation),
+        max: max_estimation,
+    }
+}
+
 fn estimate_should<F>(
     estimator: &F,
     conditions: &[Condition],
@@ -162,7 +241,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{FieldCondition, HasIdCondition, PointOffsetType};
+    use crate::types::{ExtendedPointId, FieldCondition, HasIdCondition, PointOffsetType};
     use std::collections::HashSet;
     use std::iter::FromIterator;
 
@@ -175,6 +254,8 @@ mod tests {
             range: None,
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
         })
     }
 
@@ -202,12 +283,16 @@ mod tests {
                 },
                 _ => CardinalityEstimation::unknown(TOTAL),
             },
+            Condition::IsEmpty(_) | Condition::HasField(_) => CardinalityEstimation::unknown(TOTAL),
             Condition::HasId(has_id) => CardinalityEstimation {
                 primary_clauses: vec![PrimaryCondition::Ids(
                     has_id
                         .has_id
                         .iter()
-                        .map(|&x| x as PointOffsetType)
+                        .map(|x| match x {
+                            ExtendedPointId::NumId(id) => *id as PointOffsetType,
+                            ExtendedPointId::Uuid(_) => panic!("unexpected uuid"),
+                        })
                         .collect(),
                 )],
                 min: has_id.has_id.len(),
@@ -229,6 +314,7 @@ mod tests {
     fn must_estimation_query_test() {
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![
                 test_condition("color".to_owned()),
                 test_condition("size".to_owned()),
@@ -255,6 +341,7 @@ mod tests {
                 test_condition("color".to_owned()),
                 test_condition("size".to_owned()),
             ]),
+            min_should: None,
             must: None,
             must_not: None,
         };
@@ -274,6 +361,7 @@ mod tests {
                 test_condition("size".to_owned()),
                 test_condition("un-indexed".to_owned()),
             ]),
+            min_should: None,
             must: None,
             must_not: None,
         };
@@ -292,6 +380,7 @@ mod tests {
             should: Some(vec![
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![
                         test_condition("color".to_owned()),
                         test_condition("size".to_owned()),
@@ -300,6 +389,7 @@ mod tests {
                 }),
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![
                         test_condition("price".to_owned()),
                         test_condition("size".to_owned()),
@@ -307,9 +397,10 @@ mod tests {
                     must_not: None,
                 }),
             ]),
+            min_should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(HasIdCondition {
-                has_id: HashSet::from_iter([1, 2, 3, 4, 5]),
+                has_id: HashSet::from_iter([1, 2, 3, 4, 5].iter().map(|&x| x.into())),
             })]),
         };
 
@@ -324,8 +415,10 @@ mod tests {
     fn another_complex_estimation_query_test() {
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![
                 Condition::Filter(Filter {
+                    min_should: None,
                     must: None,
                     should: Some(vec![
                         test_condition("color".to_owned()),
@@ -334,6 +427,7 @@ mod tests {
                     must_not: None,
                 }),
                 Condition::Filter(Filter {
+                    min_should: None,
                     must: None,
                     should: Some(vec![
                         test_condition("price".to_owned()),
@@ -343,7 +437,7 @@ mod tests {
                 }),
             ]),
             must_not: Some(vec![Condition::HasId(HasIdCondition {
-                has_id: HashSet::from_iter([1, 2, 3, 4, 5]),
+                has_id: HashSet::from_iter([1, 2, 3, 4, 5].iter().map(|&x| x.into())),
             })]),
         };
 
@@ -359,4 +453,50 @@ mod tests {
         assert!(estimation.exp <= estimation.max);
         assert!(estimation.min <= estimation.exp);
     }
+
+    #[test]
+    fn min_should_estimation_query_test() {
+        let query = |min_count| Filter {
+            should: None,
+            min_should: Some(MinShould {
+                conditions: vec![
+                    test_condition("color".to_owned()),
+                    test_condition("size".to_owned()),
+                    test_condition("price".to_owned()),
+                    test_condition("un-indexed".to_owned()),
+                ],
+                min_count,
+            }),
+            must: None,
+            must_not: None,
+        };
+
+        let estimation = estimate_filter(&test_estimator, &query(0), TOTAL);
+        assert_eq!(estimation.exp, TOTAL);
+
+        let estimation = estimate_filter(&test_estimator, &query(5), TOTAL);
+        assert_eq!(estimation.max, 0);
+
+        // Un-indexed condition could be the missed one
+        let estimation = estimate_filter(&test_estimator, &query(1), TOTAL);
+        assert!(estimation.primary_clauses.is_empty());
+
+        // Any 2 of the 3 indexed conditions are enough to cover matched points
+        let estimation = estimate_filter(&test_estimator, &query(3), TOTAL);
+        assert_eq!(estimation.primary_clauses.len(), 2);
+        match &estimation.primary_clauses[0] {
+            PrimaryCondition::Condition(field) => assert_eq!(&field.key, "price"),
+            PrimaryCondition::Ids(_) => assert!(false),
+        }
+
+        let mut previous_exp = TOTAL;
+        for min_count in 1..=4 {
+            let estimation = estimate_filter(&test_estimator, &query(min_count), TOTAL);
+            assert!(estimation.max <= TOTAL);
+            assert!(estimation.exp <= estimation.max);
+            assert!(estimation.min <= estimation.exp);
+            assert!(estimation.exp <= previous_exp);
+            previous_exp = estimation.exp;
+        }
+    }
 }
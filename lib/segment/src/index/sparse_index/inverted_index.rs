@@ -0,0 +1,99 @@
+use crate::types::{PointOffsetType, ScoreType, SparseDimType, SparseVector, VectorElementType};
+use std::collections::HashMap;
+
+/// Element of the posting list: point, which has non-zero value in the dimension, and this value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PostingElement {
+    pub id: PointOffsetType,
+    pub weight: VectorElementType,
+}
+
+/// In-memory inverted index of sparse vectors.
+///
+/// For each dimension it keeps a posting list of points with non-zero value in this dimension,
+/// so only points which share at least one dimension with the query are scored.
+#[derive(Debug, Default)]
+pub struct InvertedIndex {
+    postings: HashMap<SparseDimType, Vec<PostingElement>>,
+}
+
+impl InvertedIndex {
+    /// Add all non-zero dimensions of the point into posting lists
+    pub fn insert(&mut self, id: PointOffsetType, vector: &SparseVector) {
+        for (dim, weight) in vector.indices.iter().zip(&vector.values) {
+            self.postings.entry(*dim).or_default().push(PostingElement {
+                id,
+                weight: *weight,
+            });
+        }
+    }
+
+    /// Remove point from posting lists of the dimensions of its vector
+    pub fn remove(&mut self, id: PointOffsetType, vector: &SparseVector) {
+        for dim in &vector.indices {
+            if let Some(posting) = self.postings.get_mut(dim) {
+                posting.retain(|element| element.id != id);
+                if posting.is_empty() {
+                    self.postings.remove(dim);
+                }
+            }
+        }
+    }
+
+    /// Number of points with non-zero value in the dimension
+    pub fn posting_len(&self, dim: SparseDimType) -> usize {
+        self.postings
+            .get(&dim)
+            .map(|posting| posting.len())
+            .unwrap_or(0)
+    }
+
+    /// Dot product scores of all points, which share at least one dimension with the query
+    pub fn score_candidates(&self, query: &SparseVector) -> HashMap<PointOffsetType, ScoreType> {
+        let mut scores: HashMap<PointOffsetType, ScoreType> = HashMap::new();
+        for (dim, query_weight) in query.indices.iter().zip(&query.values) {
+            if let Some(posting) = self.postings.get(dim) {
+                for element in posting {
+                    *scores.entry(element.id).or_insert(0.0) += query_weight * element.weight;
+                }
+            }
+        }
+        scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_candidates() {
+        let mut index = InvertedIndex::default();
+        let vec0 = SparseVector {
+            indices: vec![1, 3, 5],
+            values: vec![1.0, 2.0, 3.0],
+        };
+        let vec1 = SparseVector {
+            indices: vec![3, 7],
+            values: vec![1.0, 1.0],
+        };
+        index.insert(0, &vec0);
+        index.insert(1, &vec1);
+        assert_eq!(index.posting_len(3), 2);
+
+        let query = SparseVector {
+            indices: vec![3, 5, 100],
+            values: vec![1.0, 1.0, 1.0],
+        };
+        let scores = index.score_candidates(&query);
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores[&0], query.dot(&vec0));
+        assert_eq!(scores[&1], 1.0);
+
+        index.remove(0, &vec0);
+        assert_eq!(index.posting_len(5), 0);
+        let scores = index.score_candidates(&query);
+        assert_eq!(scores.len(), 1);
+        assert!(scores.contains_key(&1));
+    }
+}
@@ -0,0 +1,66 @@
+use crate::index::PayloadIndex;
+use crate::payload_storage::ConditionChecker;
+use crate::types::{
+    Filter, SparseVector, SparseVectorDataConfig, DEFAULT_SPARSE_FULL_SCAN_THRESHOLD,
+};
+use crate::vector_storage::sparse_vector_storage::SparseVectorStorage;
+use crate::vector_storage::ScoredPointOffset;
+use atomic_refcell::AtomicRefCell;
+use std::sync::Arc;
+
+/// Search over sparse vectors of the segment.
+///
+/// Uses inverted index of the storage to score only points which share dimensions with the query.
+/// Filtered search with small cardinality scores points, matched by payload index, directly.
+pub struct SparseVectorIndex {
+    config: SparseVectorDataConfig,
+    vector_storage: Arc<AtomicRefCell<SparseVectorStorage>>,
+    payload_index: Arc<AtomicRefCell<dyn PayloadIndex>>,
+    condition_checker: Arc<dyn ConditionChecker>,
+}
+
+impl SparseVectorIndex {
+    pub fn new(
+        config: SparseVectorDataConfig,
+        vector_storage: Arc<AtomicRefCell<SparseVectorStorage>>,
+        payload_index: Arc<AtomicRefCell<dyn PayloadIndex>>,
+        condition_checker: Arc<dyn ConditionChecker>,
+    ) -> Self {
+        SparseVectorIndex {
+            config,
+            vector_storage,
+            payload_index,
+            condition_checker,
+        }
+    }
+
+    pub fn search(
+        &self,
+        vector: &SparseVector,
+        filter: Option<&Filter>,
+        top: usize,
+    ) -> Vec<ScoredPointOffset> {
+        let vector_storage = self.vector_storage.borrow();
+        match filter {
+            // Deleted points are removed from the inverted index, no need to check them
+            None => vector_storage.score_all(vector, top, |_| true),
+            Some(filter) => {
+                let payload_index = self.payload_index.borrow();
+                let full_scan_threshold = self
+                    .config
+                    .full_scan_threshold
+                    .unwrap_or(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD);
+
+                let query_cardinality = payload_index.estimate_cardinality(filter);
+                if query_cardinality.max < full_scan_threshold {
+                    let mut filtered_points = payload_index.query_points(filter);
+                    vector_storage.score_points(vector, &mut filtered_points, top)
+                } else {
+                    vector_storage.score_all(vector, top, |point| {
+                        self.condition_checker.check(point, filter)
+                    })
+                }
+            }
+        }
+    }
+}
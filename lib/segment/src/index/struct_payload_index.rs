@@ -1,3 +1,4 @@
+use std::cmp::min;
 use std::collections::{HashMap, HashSet};
 use std::fs::{create_dir_all, remove_file, File};
 use std::path::{Path, PathBuf};
@@ -18,9 +19,10 @@ use crate::index::visited_pool::VisitedPool;
 use crate::index::PayloadIndex;
 use crate::payload_storage::{ConditionChecker, PayloadStorage};
 use crate::types::{
-    Condition, FieldCondition, Filter, PayloadKeyType, PayloadKeyTypeRef, PointOffsetType,
+    get_value_by_path, Condition, Direction, FieldCondition, Filter, PayloadIndexParams,
+    PayloadKeyType, PayloadKeyTypeRef, PointOffsetType, RangeValue,
 };
-use crate::vector_storage::VectorStorage;
+use crate::vector_storage::PointsStorage;
 
 pub const PAYLOAD_FIELD_INDEX_PATH: &str = "fields";
 
@@ -29,7 +31,7 @@ type IndexesMap = HashMap<PayloadKeyType, Vec<FieldIndex>>;
 /// `PayloadIndex` implementation, which actually uses index structures for providing faster search
 pub struct StructPayloadIndex {
     condition_checker: Arc<dyn ConditionChecker>,
-    vector_storage: Arc<AtomicRefCell<dyn VectorStorage>>,
+    points_storage: Arc<AtomicRefCell<dyn PointsStorage>>,
     /// Payload storage
     payload: Arc<AtomicRefCell<dyn PayloadStorage>>,
     id_tracker: Arc<AtomicRefCell<dyn IdTracker>>,
@@ -58,6 +60,14 @@ impl StructPayloadIndex {
         })
     }
 
+    /// Number of points, which have the field, known from any of its indexes
+    fn count_indexed_points(&self, key: PayloadKeyTypeRef) -> Option<usize> {
+        self.field_indexes
+            .get(key)
+            .and_then(|indexes| indexes.first())
+            .map(|index| index.count_indexed_points())
+    }
+
     fn query_field(
         &self,
         field_condition: &FieldCondition,
@@ -153,7 +163,7 @@ impl StructPayloadIndex {
 
     pub fn open(
         condition_checker: Arc<dyn ConditionChecker>,
-        vector_storage: Arc<AtomicRefCell<dyn VectorStorage>>,
+        points_storage: Arc<AtomicRefCell<dyn PointsStorage>>,
         payload: Arc<AtomicRefCell<dyn PayloadStorage>>,
         id_tracker: Arc<AtomicRefCell<dyn IdTracker>>,
         path: &Path,
@@ -168,7 +178,7 @@ impl StructPayloadIndex {
 
         let mut index = StructPayloadIndex {
             condition_checker,
-            vector_storage,
+            points_storage,
             payload,
             id_tracker,
             field_indexes: Default::default(),
@@ -200,16 +210,16 @@ impl StructPayloadIndex {
 
         let field_type = field_type_opt.unwrap();
 
-        let mut builders = index_selector(field_type);
+        let mut builders = index_selector(field_type, self.config.index_params.get(field));
 
         for point_id in payload_ref.iter_ids() {
             let point_payload = payload_ref.payload(point_id);
-            let field_value_opt = point_payload.get(field);
+            let field_value_opt = get_value_by_path(&point_payload, field);
             match field_value_opt {
                 None => {}
                 Some(field_value) => {
                     for builder in &mut builders {
-                        builder.add(point_id, field_value)
+                        builder.add(point_id, &field_value)
                     }
                 }
             }
@@ -238,7 +248,7 @@ impl StructPayloadIndex {
     }
 
     pub fn total_points(&self) -> usize {
-        self.vector_storage.borrow().vector_count()
+        self.points_storage.borrow().vector_count()
     }
 }
 
@@ -247,9 +257,16 @@ impl PayloadIndex for StructPayloadIndex {
         self.config.indexed_fields.clone()
     }
 
-    fn set_indexed(&mut self, field: PayloadKeyTypeRef) -> OperationResult<()> {
-        if !self.config.indexed_fields.iter().any(|x| x == field) {
-            self.config.indexed_fields.push(field.into());
+    fn index_params(&self, field: PayloadKeyTypeRef) -> Option<PayloadIndexParams> {
+        self.config.index_params.get(field).cloned()
+    }
+
+    fn set_indexed(
+        &mut self,
+        field: PayloadKeyTypeRef,
+        params: Option<&PayloadIndexParams>,
+    ) -> OperationResult<()> {
+        if self.config.set_indexed(field, params) {
             self.save_config()?;
             self.build_and_save(field)?;
         }
@@ -257,13 +274,7 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn drop_index(&mut self, field: PayloadKeyTypeRef) -> OperationResult<()> {
-        self.config.indexed_fields = self
-            .config
-            .indexed_fields
-            .iter()
-            .cloned()
-            .filter(|x| x != field)
-            .collect();
+        self.config.drop_indexed(field);
         self.save_config()?;
         self.field_indexes.remove(field);
 
@@ -299,6 +310,32 @@ impl PayloadIndex for StructPayloadIndex {
             Condition::Field(field_condition) => self
                 .estimate_field_condition(field_condition)
                 .unwrap_or_else(|| CardinalityEstimation::unknown(self.total_points())),
+            Condition::HasField(condition) => self
+                .count_indexed_points(&condition.has_field.key)
+                .map(|count| {
+                    // Indexes, stored before points were counted, report no indexed points
+                    let count = min(count, total_points);
+                    CardinalityEstimation {
+                        primary_clauses: vec![],
+                        min: count,
+                        exp: count,
+                        max: total_points,
+                    }
+                })
+                .unwrap_or_else(|| CardinalityEstimation::unknown(total_points)),
+            Condition::IsEmpty(condition) => {
+                let indexed_count = self.count_indexed_points(&condition.is_empty.key);
+                match indexed_count {
+                    // Points with the field might still have no values
+                    Some(count) => CardinalityEstimation {
+                        primary_clauses: vec![],
+                        min: total_points.saturating_sub(count),
+                        exp: total_points.saturating_sub(count),
+                        max: total_points,
+                    },
+                    None => CardinalityEstimation::unknown(total_points),
+                }
+            }
         };
 
         estimate_filter(&estimator, query, total_points)
@@ -309,11 +346,11 @@ impl PayloadIndex for StructPayloadIndex {
         query: &'a Filter,
     ) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
         // Assume query is already estimated to be small enough so we can iterate over all matched ids
-        let vector_storage_ref = self.vector_storage.borrow();
+        let points_storage_ref = self.points_storage.borrow();
 
         let query_cardinality = self.estimate_cardinality(query);
         return if query_cardinality.primary_clauses.is_empty() {
-            let full_scan_iterator = vector_storage_ref.iter_ids();
+            let full_scan_iterator = points_storage_ref.iter_ids();
             // Worst case: query expected to return few matches, but index can't be used
             let matched_points = full_scan_iterator
                 .filter(|i| self.condition_checker.check(*i, query))
@@ -322,10 +359,10 @@ impl PayloadIndex for StructPayloadIndex {
             Box::new(matched_points.into_iter())
         } else {
             // CPU-optimized strategy here: points are made unique before applying other filters.
-            // ToDo: Implement iterator which holds the `visited_pool` and borrowed `vector_storage_ref` to prevent `preselected` array creation
+            // ToDo: Implement iterator which holds the `visited_pool` and borrowed `points_storage_ref` to prevent `preselected` array creation
             let mut visited_list = self
                 .visited_pool
-                .get(vector_storage_ref.total_vector_count());
+                .get(points_storage_ref.total_vector_count());
 
             #[allow(clippy::needless_collect)]
             let preselected: Vec<PointOffsetType> = query_cardinality
@@ -335,7 +372,7 @@ impl PayloadIndex for StructPayloadIndex {
                     match clause {
                         PrimaryCondition::Condition(field_condition) => {
                             self.query_field(field_condition).unwrap_or_else(
-                                || vector_storage_ref.iter_ids(), /* index is not built */
+                                || points_storage_ref.iter_ids(), /* index is not built */
                             )
                         }
                         PrimaryCondition::Ids(ids) => Box::new(ids.iter().cloned()),
@@ -367,4 +404,17 @@ impl PayloadIndex for StructPayloadIndex {
             }
         }
     }
+
+    fn ordered_points(
+        &self,
+        field: PayloadKeyTypeRef,
+        direction: Direction,
+        start_from: Option<RangeValue>,
+    ) -> Option<Box<dyn Iterator<Item = (RangeValue, PointOffsetType)> + '_>> {
+        self.field_indexes.get(field).and_then(|indexes| {
+            indexes
+                .iter()
+                .find_map(|field_index| field_index.ordered_points(direction, start_from))
+        })
+    }
 }
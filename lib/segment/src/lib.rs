@@ -1,4 +1,5 @@
 mod common;
+mod compat;
 pub mod entry;
 pub mod fixtures;
 mod id_tracker;
@@ -1,43 +1,109 @@
 //! Contains functions for interpreting filter queries and defining if given points pass the conditions
 
-use crate::types::{GeoBoundingBox, GeoRadius, Match, PayloadType, Range};
+use crate::common::tokenizer::tokenize;
+use crate::types::{
+    datetime_timestamp, AnyVariants, GeoBoundingBox, GeoPolygon, GeoRadius, Match, PayloadType,
+    Range, ValuesCount,
+};
+use geo::algorithm::contains::Contains;
 use geo::algorithm::haversine_distance::HaversineDistance;
 use geo::Point;
+use std::collections::HashSet;
+
+/// Check values of the point against the match condition of a single type.
+/// If neither value nor `any` list is given, only `except` list is checked.
+fn match_values<T: PartialEq>(
+    values: &[T],
+    value: Option<&T>,
+    any: Option<&[T]>,
+    except: Option<&[T]>,
+) -> bool {
+    if value.is_none() && any.is_none() && except.is_none() {
+        return false;
+    }
+
+    let is_selected = (value.is_none() && any.is_none())
+        || values.iter().any(|payload_value| {
+            value == Some(payload_value) || any.map_or(false, |any| any.contains(payload_value))
+        });
+    let is_excluded = except.map_or(false, |except| {
+        values
+            .iter()
+            .any(|payload_value| except.contains(payload_value))
+    });
+
+    is_selected && !is_excluded
+}
+
+/// Check if the texts contain all words of the query, in any order
+fn match_text(texts: &[String], query: &str) -> bool {
+    let query_tokens: HashSet<String> = tokenize(query).collect();
+    if query_tokens.is_empty() {
+        return false;
+    }
+    let text_tokens: HashSet<String> = texts.iter().flat_map(|text| tokenize(text)).collect();
+    query_tokens.is_subset(&text_tokens)
+}
 
 pub fn match_payload(payload: &PayloadType, condition_match: &Match) -> bool {
     match payload {
-        PayloadType::Keyword(payload_kws) => payload_kws.iter().any(|payload_kw| {
-            condition_match
-                .keyword
+        PayloadType::Keyword(payload_kws) => {
+            match_values(
+                payload_kws,
+                condition_match.keyword.as_ref(),
+                condition_match.any.as_ref().and_then(AnyVariants::keywords),
+                condition_match
+                    .except
+                    .as_ref()
+                    .and_then(AnyVariants::keywords),
+            ) || condition_match
+                .text
                 .as_ref()
-                .map(|x| x == payload_kw)
-                .unwrap_or(false)
-        }),
-        PayloadType::Integer(payload_ints) => payload_ints.iter().cloned().any(|payload_int| {
+                .map_or(false, |text| match_text(payload_kws, text))
+        }
+        PayloadType::Integer(payload_ints) => match_values(
+            payload_ints,
+            condition_match.integer.as_ref(),
+            condition_match.any.as_ref().and_then(AnyVariants::integers),
             condition_match
-                .integer
-                .map(|x| x == payload_int)
-                .unwrap_or(false)
-        }),
+                .except
+                .as_ref()
+                .and_then(AnyVariants::integers),
+        ),
+        PayloadType::Bool(payload_bools) => {
+            match_values(payload_bools, condition_match.boolean.as_ref(), None, None)
+        }
         _ => false,
     }
 }
 
 pub fn match_range(payload: &PayloadType, num_range: &Range) -> bool {
-    let condition = |number| {
-        num_range.lt.map_or(true, |x| number < x)
-            && num_range.gt.map_or(true, |x| number > x)
-            && num_range.lte.map_or(true, |x| number <= x)
-            && num_range.gte.map_or(true, |x| number >= x)
-    };
+    match payload {
+        PayloadType::Float(num) => num.iter().any(|x| num_range.check_float(*x)),
+        PayloadType::Integer(num) => num.iter().any(|x| num_range.check_int(*x)),
+        PayloadType::Datetime(datetimes) => datetimes
+            .iter()
+            .any(|x| num_range.check_int(datetime_timestamp(x))),
+        _ => false,
+    }
+}
 
+pub fn match_geo_polygon(payload: &PayloadType, geo_polygon: &GeoPolygon) -> bool {
     match payload {
-        PayloadType::Float(num) => num.iter().cloned().any(condition),
-        PayloadType::Integer(num) => num.iter().cloned().any(|x| condition(x as f64)),
+        PayloadType::Geo(geo_points) => {
+            let polygon = geo_polygon.polygon();
+            geo_points
+                .iter()
+                .any(|geo_point| polygon.contains(&Point::new(geo_point.lon, geo_point.lat)))
+        }
         _ => false,
     }
 }
 
+pub fn match_values_count(payload: &PayloadType, values_count: &ValuesCount) -> bool {
+    values_count.check_count(payload.len())
+}
+
 pub fn match_geo(payload: &PayloadType, geo_bounding_box: &GeoBoundingBox) -> bool {
     return match payload {
         PayloadType::Geo(geo_points) => geo_points.iter().any(|geo_point| {
@@ -67,7 +133,7 @@ pub fn match_geo_radius(payload: &PayloadType, geo_radius_query: &GeoRadius) ->
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::GeoPoint;
+    use crate::types::{parse_datetime, GeoLineString, GeoPoint};
 
     #[test]
     fn test_geo_matching() {
@@ -99,4 +165,137 @@ mod tests {
         assert!(match_geo_radius(&berlin_and_moscow, &near_berlin_query));
         assert!(!match_geo_radius(&berlin_and_moscow, &miss_geo_query));
     }
+
+    #[test]
+    fn test_geo_polygon_matching() {
+        let ring = |points: &[(f64, f64)]| GeoLineString {
+            points: points
+                .iter()
+                .map(|&(lon, lat)| GeoPoint { lon, lat })
+                .collect(),
+        };
+        let point = |lon, lat| PayloadType::Geo(vec![GeoPoint { lon, lat }]);
+
+        // Square with the square hole in the middle, last point of the hole is omitted
+        let polygon = GeoPolygon::new(
+            ring(&[
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+                (0.0, 0.0),
+            ]),
+            Some(vec![ring(&[
+                (4.0, 4.0),
+                (6.0, 4.0),
+                (6.0, 6.0),
+                (4.0, 6.0),
+            ])]),
+        );
+
+        assert!(match_geo_polygon(&point(2.0, 2.0), &polygon));
+        assert!(match_geo_polygon(&point(8.0, 5.0), &polygon));
+        assert!(!match_geo_polygon(&point(5.0, 5.0), &polygon));
+        assert!(!match_geo_polygon(&point(12.0, 5.0), &polygon));
+        assert!(!match_geo_polygon(&PayloadType::Integer(vec![1]), &polygon));
+    }
+
+    #[test]
+    fn test_match_any_and_except() {
+        let payload = PayloadType::Keyword(vec!["red".to_owned(), "green".to_owned()]);
+        let keywords = |values: &[&str]| {
+            Some(AnyVariants::Keywords(
+                values.iter().map(|x| x.to_string()).collect(),
+            ))
+        };
+        let condition = |any, except| Match {
+            keyword: None,
+            integer: None,
+            boolean: None,
+            text: None,
+            any,
+            except,
+        };
+
+        assert!(match_payload(
+            &payload,
+            &condition(keywords(&["blue", "red"]), None)
+        ));
+        assert!(!match_payload(
+            &payload,
+            &condition(keywords(&["blue"]), None)
+        ));
+        assert!(match_payload(
+            &payload,
+            &condition(None, keywords(&["blue"]))
+        ));
+        // Point is excluded if any of its values is in the `except` list
+        assert!(!match_payload(
+            &payload,
+            &condition(None, keywords(&["green"]))
+        ));
+        assert!(!match_payload(
+            &payload,
+            &condition(keywords(&["red"]), keywords(&["green"]))
+        ));
+
+        let integers = PayloadType::Integer(vec![1, 2]);
+        let any_integers = Some(AnyVariants::Integers(vec![2, 3]));
+        assert!(match_payload(
+            &integers,
+            &condition(any_integers.clone(), None)
+        ));
+        assert!(!match_payload(&integers, &condition(None, any_integers)));
+        // Keyword values never match integer payload
+        assert!(!match_payload(
+            &integers,
+            &condition(keywords(&["1"]), None)
+        ));
+    }
+
+    #[test]
+    fn test_match_bool_and_datetime() {
+        let bools = PayloadType::Bool(vec![true]);
+        let condition = |boolean| Match {
+            keyword: None,
+            integer: None,
+            boolean: Some(boolean),
+            text: None,
+            any: None,
+            except: None,
+        };
+        assert!(match_payload(&bools, &condition(true)));
+        assert!(!match_payload(&bools, &condition(false)));
+
+        let dates = PayloadType::Datetime(vec![parse_datetime("2021-06-01T12:00:00Z").unwrap()]);
+        let june_first = r#"{"gte": "2021-06-01T00:00:00Z", "lt": "2021-06-02T00:00:00Z"}"#;
+        let range: Range = serde_json::from_str(june_first).unwrap();
+        assert!(match_range(&dates, &range));
+        let range: Range = serde_json::from_str(r#"{"gt": "2021-06-01T12:00:00Z"}"#).unwrap();
+        assert!(!match_range(&dates, &range));
+    }
+
+    #[test]
+    fn test_match_text() {
+        let payload = PayloadType::Keyword(vec![
+            "The quick brown fox".to_owned(),
+            "jumps over the lazy dog!".to_owned(),
+        ]);
+        let condition = |text: &str| Match {
+            keyword: None,
+            integer: None,
+            boolean: None,
+            text: Some(text.to_owned()),
+            any: None,
+            except: None,
+        };
+
+        assert!(match_payload(&payload, &condition("fox")));
+        // All words should be present, possibly in different values
+        assert!(match_payload(&payload, &condition("Lazy, QUICK fox")));
+        assert!(!match_payload(&payload, &condition("quick cat")));
+        // Partial words are not matched
+        assert!(!match_payload(&payload, &condition("qui")));
+        assert!(!match_payload(&payload, &condition("...")));
+    }
 }
@@ -7,47 +7,38 @@ use serde_json::value::Value;
 
 /// Trait for payload data storage. Should allow filter checks
 pub trait PayloadStorage {
+    /// Assign JSON payload to the point.
+    /// Nested objects are stored as they are and addressed by dotted paths, e.g. `author.country`.
+    /// Values, which could not be converted into payload, are skipped.
     fn assign_all_with_value(
         &mut self,
         point_id: PointOffsetType,
         payload: TheMap<PayloadKeyType, serde_json::value::Value>,
     ) -> OperationResult<()> {
-        fn _extract_payloads<'a, I>(
-            _payload: I,
-            prefix_key: Option<PayloadKeyType>,
-        ) -> Vec<(PayloadKeyType, PayloadType)>
+        fn _extract_payloads<'a, I>(_payload: I) -> TheMap<PayloadKeyType, PayloadType>
         where
             I: IntoIterator<Item = (&'a PayloadKeyType, &'a serde_json::value::Value)>,
         {
-            fn _fn(
-                prefix: &Option<PayloadKeyType>,
-                k: PayloadKeyTypeRef,
-                v: &Value,
-            ) -> Vec<(PayloadKeyType, PayloadType)> {
-                let key = match &prefix {
-                    None => k.to_string(),
-                    Some(_k) => (_k.to_owned() + "__" + k),
-                };
-
+            fn _fn(v: &Value) -> Option<PayloadType> {
                 let opt_payload_interface: Result<PayloadInterface, _> =
                     serde_json::from_value(v.to_owned());
                 match opt_payload_interface {
-                    Ok(payload_interface) => vec![(key, PayloadType::from(&payload_interface))],
+                    Ok(payload_interface) => Some(PayloadType::from(&payload_interface)),
                     _ => match v {
-                        Value::Object(x) => _extract_payloads(x, Some(key)),
-                        _ => vec![],
+                        Value::Object(x) => Some(PayloadType::Object(vec![_extract_payloads(x)])),
+                        _ => None,
                     },
                 }
             }
             _payload
                 .into_iter()
-                .flat_map(|(k, value)| _fn(&prefix_key, k, value))
+                .filter_map(|(k, value)| _fn(value).map(|payload| (k.to_owned(), payload)))
                 .collect()
         }
         self.drop(point_id)?;
-        let inner_payloads = _extract_payloads(&payload, None);
-        for (key, value) in &inner_payloads {
-            self.assign(point_id, key, value.to_owned())?;
+        let inner_payloads = _extract_payloads(&payload);
+        for (key, value) in inner_payloads {
+            self.assign(point_id, &key, value)?;
         }
         Ok(())
     }
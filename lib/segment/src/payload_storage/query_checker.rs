@@ -1,10 +1,13 @@
 use crate::id_tracker::IdTracker;
 use crate::payload_storage::condition_checker::{
-    match_geo, match_geo_radius, match_payload, match_range,
+    match_geo, match_geo_polygon, match_geo_radius, match_payload, match_range, match_values_count,
 };
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
 use crate::payload_storage::ConditionChecker;
-use crate::types::{Condition, Filter, PayloadKeyType, PayloadType, PointOffsetType, TheMap};
+use crate::types::{
+    get_value_by_path, Condition, Filter, MinShould, PayloadKeyType, PayloadType, PointOffsetType,
+    TheMap,
+};
 use atomic_refcell::AtomicRefCell;
 use std::sync::Arc;
 
@@ -23,6 +26,7 @@ where
     F: Fn(&Condition) -> bool,
 {
     check_should(checker, &filter.should)
+        && check_min_should(checker, &filter.min_should)
         && check_must(checker, &filter.must)
         && check_must_not(checker, &filter.must_not)
 }
@@ -38,6 +42,20 @@ where
     }
 }
 
+fn check_min_should<F>(checker: &F, min_should: &Option<MinShould>) -> bool
+where
+    F: Fn(&Condition) -> bool,
+{
+    let check = |x: &&Condition| check_condition(checker, x);
+    match min_should {
+        None => true,
+        Some(MinShould {
+            conditions,
+            min_count,
+        }) => conditions.iter().filter(check).take(*min_count).count() == *min_count,
+    }
+}
+
 fn check_must<F>(checker: &F, must: &Option<Vec<Condition>>) -> bool
 where
     F: Fn(&Condition) -> bool,
@@ -95,8 +113,7 @@ impl ConditionChecker for SimpleConditionChecker {
         let checker = |condition: &Condition| {
             match condition {
                 Condition::Field(field_condition) => {
-                    payload
-                        .get(&field_condition.key)
+                    get_value_by_path(payload, &field_condition.key)
                         .map(|p| {
                             let mut res = false;
                             // ToDo: Convert onto iterator over checkers, so it would be impossible to forget a condition
@@ -104,25 +121,37 @@ impl ConditionChecker for SimpleConditionChecker {
                                 || field_condition
                                     .r#match
                                     .as_ref()
-                                    .map(|condition| match_payload(p, condition))
+                                    .map(|condition| match_payload(&p, condition))
                                     .unwrap_or(false);
                             res = res
                                 || field_condition
                                     .range
                                     .as_ref()
-                                    .map(|condition| match_range(p, condition))
+                                    .map(|condition| match_range(&p, condition))
                                     .unwrap_or(false);
                             res = res
                                 || field_condition
                                     .geo_radius
                                     .as_ref()
-                                    .map(|condition| match_geo_radius(p, condition))
+                                    .map(|condition| match_geo_radius(&p, condition))
+                                    .unwrap_or(false);
+                            res = res
+                                || field_condition
+                                    .geo_polygon
+                                    .as_ref()
+                                    .map(|condition| match_geo_polygon(&p, condition))
                                     .unwrap_or(false);
                             res = res
                                 || field_condition
                                     .geo_bounding_box
                                     .as_ref()
-                                    .map(|condition| match_geo(p, condition))
+                                    .map(|condition| match_geo(&p, condition))
+                                    .unwrap_or(false);
+                            res = res
+                                || field_condition
+                                    .values_count
+                                    .as_ref()
+                                    .map(|condition| match_values_count(&p, condition))
                                     .unwrap_or(false);
                             res
                         })
@@ -135,6 +164,13 @@ impl ConditionChecker for SimpleConditionChecker {
                     };
                     has_id.has_id.contains(&external_id)
                 }
+                Condition::IsEmpty(condition) => {
+                    get_value_by_path(payload, &condition.is_empty.key)
+                        .map_or(true, |value| value.is_empty())
+                }
+                Condition::HasField(condition) => {
+                    get_value_by_path(payload, &condition.has_field.key).is_some()
+                }
                 Condition::Filter(_) => panic!("Unexpected branching!"),
             }
         };
@@ -149,7 +185,10 @@ mod tests {
     use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
     use crate::payload_storage::PayloadStorage;
     use crate::types::GeoPoint;
-    use crate::types::{FieldCondition, GeoBoundingBox, Match, PayloadType, Range};
+    use crate::types::{
+        ExtendedPointId, FieldCondition, GeoBoundingBox, HasFieldCondition, IsEmptyCondition,
+        Match, PayloadField, PayloadType, Range, RangeValue, ValuesCount,
+    };
     use std::collections::HashSet;
     use tempdir::TempDir;
 
@@ -174,6 +213,7 @@ mod tests {
                 PayloadType::Keyword(vec!["red".to_owned()]),
             ),
             ("has_delivery".to_owned(), PayloadType::Integer(vec![1])),
+            ("tags".to_owned(), PayloadType::Keyword(vec![])),
         ]
         .iter()
         .cloned()
@@ -182,10 +222,10 @@ mod tests {
         let mut payload_storage = SimplePayloadStorage::open(dir.path()).unwrap();
         let mut id_tracker = SimpleIdTracker::open(dir_id_tracker.path()).unwrap();
 
-        id_tracker.set_link(0, 0).unwrap();
-        id_tracker.set_link(1, 1).unwrap();
-        id_tracker.set_link(2, 2).unwrap();
-        id_tracker.set_link(10, 10).unwrap();
+        id_tracker.set_link(0.into(), 0).unwrap();
+        id_tracker.set_link(1.into(), 1).unwrap();
+        id_tracker.set_link(2.into(), 2).unwrap();
+        id_tracker.set_link(10.into(), 10).unwrap();
         payload_storage.assign_all(0, payload).unwrap();
 
         let payload_checker = SimpleConditionChecker::new(
@@ -198,10 +238,16 @@ mod tests {
             r#match: Some(Match {
                 keyword: Some("red".to_owned()),
                 integer: None,
+                boolean: None,
+                text: None,
+                any: None,
+                except: None,
             }),
             range: None,
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
         });
 
         let match_blue = Condition::Field(FieldCondition {
@@ -209,10 +255,16 @@ mod tests {
             r#match: Some(Match {
                 keyword: Some("blue".to_owned()),
                 integer: None,
+                boolean: None,
+                text: None,
+                any: None,
+                except: None,
             }),
             range: None,
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
         });
 
         let with_delivery = Condition::Field(FieldCondition {
@@ -220,10 +272,16 @@ mod tests {
             r#match: Some(Match {
                 keyword: None,
                 integer: Some(1),
+                boolean: None,
+                text: None,
+                any: None,
+                except: None,
             }),
             range: None,
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
         });
 
         let in_berlin = Condition::Field(FieldCondition {
@@ -241,6 +299,8 @@ mod tests {
                 },
             }),
             geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
         });
 
         let in_moscow = Condition::Field(FieldCondition {
@@ -258,6 +318,8 @@ mod tests {
                 },
             }),
             geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
         });
 
         let with_bad_rating = Condition::Field(FieldCondition {
@@ -267,14 +329,17 @@ mod tests {
                 lt: None,
                 gt: None,
                 gte: None,
-                lte: Some(5.),
+                lte: Some(RangeValue::Integer(5)),
             }),
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
         });
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![match_red.clone()]),
             must_not: None,
         };
@@ -282,6 +347,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![match_blue.clone()]),
             must_not: None,
         };
@@ -289,6 +355,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![match_blue.clone()]),
         };
@@ -296,6 +363,7 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![match_red.clone()]),
         };
@@ -303,6 +371,7 @@ mod tests {
 
         let query = Filter {
             should: Some(vec![match_red.clone(), match_blue.clone()]),
+            min_should: None,
             must: Some(vec![with_delivery.clone(), in_berlin.clone()]),
             must_not: None,
         };
@@ -310,6 +379,7 @@ mod tests {
 
         let query = Filter {
             should: Some(vec![match_red.clone(), match_blue.clone()]),
+            min_should: None,
             must: Some(vec![with_delivery, in_moscow.clone()]),
             must_not: None,
         };
@@ -319,15 +389,18 @@ mod tests {
             should: Some(vec![
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![match_red.clone(), in_moscow.clone()]),
                     must_not: None,
                 }),
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![match_blue.clone(), in_berlin.clone()]),
                     must_not: None,
                 }),
             ]),
+            min_should: None,
             must: None,
             must_not: None,
         };
@@ -337,15 +410,18 @@ mod tests {
             should: Some(vec![
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![match_blue, in_moscow]),
                     must_not: None,
                 }),
                 Condition::Filter(Filter {
                     should: None,
+                    min_should: None,
                     must: Some(vec![match_red, in_berlin]),
                     must_not: None,
                 }),
             ]),
+            min_should: None,
             must: None,
             must_not: None,
         };
@@ -353,36 +429,113 @@ mod tests {
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![with_bad_rating]),
         };
         assert!(!payload_checker.check(0, &query));
 
-        let ids: HashSet<_> = vec![1, 2, 3].into_iter().collect();
+        let ids: HashSet<ExtendedPointId> = vec![1, 2, 3].into_iter().map(|x| x.into()).collect();
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(ids.into())]),
         };
         assert!(!payload_checker.check(2, &query));
 
-        let ids: HashSet<_> = vec![1, 2, 3].into_iter().collect();
+        let ids: HashSet<ExtendedPointId> = vec![1, 2, 3].into_iter().map(|x| x.into()).collect();
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(ids.into())]),
         };
         assert!(payload_checker.check(10, &query));
 
-        let ids: HashSet<_> = vec![1, 2, 3].into_iter().collect();
+        let ids: HashSet<ExtendedPointId> = vec![1, 2, 3].into_iter().map(|x| x.into()).collect();
 
         let query = Filter {
             should: None,
+            min_should: None,
             must: Some(vec![Condition::HasId(ids.into())]),
             must_not: None,
         };
         assert!(payload_checker.check(2, &query));
+
+        let is_empty = |key: &str| {
+            Filter::new_must(Condition::IsEmpty(IsEmptyCondition {
+                is_empty: PayloadField {
+                    key: key.to_owned(),
+                },
+            }))
+        };
+        let has_field = |key: &str| {
+            Filter::new_must(Condition::HasField(HasFieldCondition {
+                has_field: PayloadField {
+                    key: key.to_owned(),
+                },
+            }))
+        };
+
+        assert!(!payload_checker.check(0, &is_empty("color")));
+        assert!(payload_checker.check(0, &is_empty("tags")));
+        assert!(payload_checker.check(0, &is_empty("missing")));
+        // Points without payload have no fields at all
+        assert!(payload_checker.check(1, &is_empty("color")));
+
+        assert!(payload_checker.check(0, &has_field("color")));
+        assert!(payload_checker.check(0, &has_field("tags")));
+        assert!(!payload_checker.check(0, &has_field("missing")));
+        assert!(!payload_checker.check(1, &has_field("color")));
+
+        let values_count = |key: &str, gte: usize| {
+            Filter::new_must(Condition::Field(FieldCondition {
+                key: key.to_owned(),
+                r#match: None,
+                range: None,
+                geo_bounding_box: None,
+                geo_radius: None,
+                geo_polygon: None,
+                values_count: Some(ValuesCount {
+                    lt: None,
+                    gt: None,
+                    gte: Some(gte),
+                    lte: None,
+                }),
+            }))
+        };
+
+        assert!(payload_checker.check(0, &values_count("rating", 4)));
+        assert!(!payload_checker.check(0, &values_count("rating", 5)));
+        assert!(payload_checker.check(0, &values_count("tags", 0)));
+        // Missing field does not match even zero values count
+        assert!(!payload_checker.check(0, &values_count("missing", 0)));
+    }
+
+    #[test]
+    fn test_min_should() {
+        let has_id = |id: u64| Condition::HasId(HashSet::from([ExtendedPointId::NumId(id)]).into());
+        // Only conditions, which select the point 1, are satisfied
+        let checker = |condition: &Condition| match condition {
+            Condition::HasId(condition) => condition.has_id.contains(&ExtendedPointId::NumId(1)),
+            _ => false,
+        };
+        let query = |min_count| Filter {
+            should: None,
+            min_should: Some(MinShould {
+                conditions: vec![has_id(1), has_id(2), has_id(1)],
+                min_count,
+            }),
+            must: None,
+            must_not: None,
+        };
+
+        assert!(check_filter(&checker, &query(0)));
+        assert!(check_filter(&checker, &query(2)));
+        assert!(!check_filter(&checker, &query(3)));
+        assert!(!check_filter(&checker, &query(4)));
     }
 }
@@ -1,5 +1,6 @@
 use crate::types::{
     PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PayloadType, PointOffsetType, TheMap,
+    PAYLOAD_PATH_SEPARATOR,
 };
 use std::collections::HashMap;
 use std::path::Path;
@@ -49,27 +50,36 @@ impl SimplePayloadStorage {
         })
     }
 
+    /// Update schema with the type of the value.
+    /// Values of nested objects are reported by their dotted paths, e.g. `author.country`
     fn update_schema_value(
         schema: &mut TheMap<PayloadKeyType, PayloadSchemaType>,
         key: PayloadKeyTypeRef,
         value: &PayloadType,
     ) -> OperationResult<()> {
-        return match schema.get(key) {
+        match schema.get(key) {
             None => {
                 schema.insert(key.to_owned(), value.into());
-                Ok(())
             }
             Some(schema_type) => {
                 if schema_type != &value.into() {
-                    Err(OperationError::TypeError {
+                    return Err(OperationError::TypeError {
                         field_name: key.to_owned(),
                         expected_type: format!("{:?}", schema_type),
-                    })
-                } else {
-                    Ok(())
+                    });
                 }
             }
         };
+
+        if let PayloadType::Object(objects) = value {
+            for object in objects {
+                for (nested_key, nested_value) in object {
+                    let path = format!("{}{}{}", key, PAYLOAD_PATH_SEPARATOR, nested_key);
+                    SimplePayloadStorage::update_schema_value(schema, &path, nested_value)?;
+                }
+            }
+        }
+        Ok(())
     }
 
     fn update_schema(
@@ -184,6 +194,7 @@ mod tests {
     use tempdir::TempDir;
 
     use super::*;
+    use crate::types::get_value_by_path;
 
     #[test]
     fn test_wipe() {
@@ -236,15 +247,21 @@ mod tests {
         assert!(keys.contains(&"age".to_string()));
         assert!(keys.contains(&"boolean".to_string()));
         assert!(keys.contains(&"floating".to_string()));
-        assert!(keys.contains(&"metadata__temperature".to_string()));
-        assert!(keys.contains(&"metadata__width".to_string()));
-        assert!(keys.contains(&"metadata__height".to_string()));
-        assert!(keys.contains(&"metadata__nested__feature".to_string()));
+        assert!(keys.contains(&"metadata".to_string()));
+        assert!(get_value_by_path(&pload, "metadata.temperature").is_some());
+        assert!(get_value_by_path(&pload, "metadata.width").is_some());
+        assert!(get_value_by_path(&pload, "metadata.height").is_some());
+        assert!(get_value_by_path(&pload, "metadata.nested.feature").is_some());
         assert!(keys.contains(&"string_array".to_string()));
         assert!(keys.contains(&"float_array".to_string()));
         assert!(keys.contains(&"integer_array".to_string()));
         assert!(keys.contains(&"boolean_array".to_string()));
-        assert!(keys.contains(&"metadata__integer_array".to_string()));
+        assert!(get_value_by_path(&pload, "metadata.integer_array").is_some());
+
+        let schema = storage.schema();
+        assert_eq!(schema["metadata"], PayloadSchemaType::Object);
+        assert_eq!(schema["metadata.width"], PayloadSchemaType::Integer);
+        assert_eq!(schema["metadata.nested.feature"], PayloadSchemaType::Float);
 
         match &pload[&"name".to_string()] {
             PayloadType::Keyword(x) => {
@@ -274,28 +291,40 @@ mod tests {
             }
             _ => assert!(false),
         }
-        match &pload[&"metadata__temperature".to_string()] {
+        match get_value_by_path(&pload, "metadata.temperature")
+            .unwrap()
+            .as_ref()
+        {
             PayloadType::Float(x) => {
                 assert_eq!(x.len(), 1);
                 assert_eq!(x[0], 60.5);
             }
             _ => assert!(false),
         }
-        match &pload[&"metadata__width".to_string()] {
+        match get_value_by_path(&pload, "metadata.width")
+            .unwrap()
+            .as_ref()
+        {
             PayloadType::Integer(x) => {
                 assert_eq!(x.len(), 1);
                 assert_eq!(x[0], 60);
             }
             _ => assert!(false),
         }
-        match &pload[&"metadata__height".to_string()] {
+        match get_value_by_path(&pload, "metadata.height")
+            .unwrap()
+            .as_ref()
+        {
             PayloadType::Integer(x) => {
                 assert_eq!(x.len(), 1);
                 assert_eq!(x[0], 50);
             }
             _ => assert!(false),
         }
-        match &pload[&"metadata__nested__feature".to_string()] {
+        match get_value_by_path(&pload, "metadata.nested.feature")
+            .unwrap()
+            .as_ref()
+        {
             PayloadType::Float(x) => {
                 assert_eq!(x.len(), 1);
                 assert_eq!(x[0], 30.5);
@@ -318,7 +347,10 @@ mod tests {
             }
             _ => assert!(false),
         }
-        match &pload[&"metadata__integer_array".to_string()] {
+        match get_value_by_path(&pload, "metadata.integer_array")
+            .unwrap()
+            .as_ref()
+        {
             PayloadType::Integer(x) => {
                 assert_eq!(x.len(), 2);
                 assert_eq!(x[0], 1);
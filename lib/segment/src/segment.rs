@@ -2,17 +2,23 @@ use crate::entry::entry_point::{
     get_service_error, OperationError, OperationResult, SegmentEntry, SegmentFailedState,
 };
 use crate::id_tracker::IdTracker;
-use crate::index::{PayloadIndex, VectorIndex};
+use crate::index::sparse_index::sparse_vector_index::SparseVectorIndex;
+use crate::index::{CardinalityEstimation, PayloadIndex, VectorIndex};
 use crate::payload_storage::{ConditionChecker, PayloadStorage};
 use crate::spaces::tools::mertic_object;
 use crate::types::{
-    Filter, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaInfo, PayloadType, PointIdType,
-    PointOffsetType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentState,
-    SegmentType, SeqNumberType, TheMap, VectorElementType, WithPayload,
+    get_value_by_path, similarity_threshold, Filter, NamedVectors, OrderBy, PayloadIndexParams,
+    PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaInfo, PayloadType, PointIdType,
+    PointOffsetType, RangeValue, ScoreType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
+    SegmentState, SegmentType, SeqNumberType, SparseVector, TheMap, Vector, VectorCompressionInfo,
+    VectorElementType, WithPayload,
 };
-use crate::vector_storage::VectorStorage;
+use crate::vector_storage::sparse_vector_storage::SparseVectorStorage;
+use crate::vector_storage::{PointsStorage, ScoredPointOffset, VectorStorage};
 use atomic_refcell::AtomicRefCell;
 use atomicwrites::{AllowOverwrite, AtomicFile};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::fs::{remove_dir_all, rename};
 use std::io::Write;
 use std::path::PathBuf;
@@ -20,6 +26,20 @@ use std::sync::{Arc, Mutex};
 
 pub const SEGMENT_STATE_FILE: &str = "segment.json";
 
+/// Storage and index of a single named vector.
+/// Storages of all vectors in the segment share the same internal ids.
+pub struct VectorData {
+    pub vector_storage: Arc<AtomicRefCell<dyn VectorStorage>>,
+    pub vector_index: Arc<AtomicRefCell<dyn VectorIndex>>,
+}
+
+/// Storage and index of a single named sparse vector.
+/// Shares internal ids with dense vector storages of the segment.
+pub struct SparseVectorData {
+    pub sparse_vector_storage: Arc<AtomicRefCell<SparseVectorStorage>>,
+    pub sparse_vector_index: Arc<AtomicRefCell<SparseVectorIndex>>,
+}
+
 /// Segment - an object which manages an independent group of points.
 ///
 /// - Provides storage, indexing and managing operations for points (vectors + payload)
@@ -35,11 +55,16 @@ pub struct Segment {
     pub current_path: PathBuf,
     /// Component for mapping external ids to internal and also keeping track of point versions
     pub id_tracker: Arc<AtomicRefCell<dyn IdTracker>>,
-    pub vector_storage: Arc<AtomicRefCell<dyn VectorStorage>>,
+    /// Storage and index of each named vector
+    pub vector_data: HashMap<String, VectorData>,
+    /// Storage and index of each named sparse vector
+    pub sparse_vector_data: HashMap<String, SparseVectorData>,
+    /// Any of the vector storages of the segment.
+    /// All storages share internal ids, so it is used to check deleted points and counts.
+    pub points_storage: Arc<AtomicRefCell<dyn PointsStorage>>,
     pub payload_storage: Arc<AtomicRefCell<dyn PayloadStorage>>,
     pub payload_index: Arc<AtomicRefCell<dyn PayloadIndex>>,
     pub condition_checker: Arc<dyn ConditionChecker>,
-    pub vector_index: Arc<AtomicRefCell<dyn VectorIndex>>,
     /// Shows if it is possible to insert more points into this segment
     pub appendable_flag: bool,
     /// Shows what kind of indexes and storages are used in this segment
@@ -51,15 +76,212 @@ pub struct Segment {
 }
 
 impl Segment {
-    fn update_vector(
+    /// Error for a vector name, which is not found among the vectors of the requested type
+    fn vector_name_error(&self, vector_name: &str) -> OperationError {
+        let expected_type = if self.vector_data.contains_key(vector_name) {
+            "dense"
+        } else if self.sparse_vector_data.contains_key(vector_name) {
+            "sparse"
+        } else {
+            return OperationError::VectorNameNotExists {
+                received_name: vector_name.to_owned(),
+            };
+        };
+        OperationError::WrongVectorType {
+            vector_name: vector_name.to_owned(),
+            expected_type: expected_type.to_owned(),
+        }
+    }
+
+    fn get_vector_data(&self, vector_name: &str) -> OperationResult<&VectorData> {
+        self.vector_data
+            .get(vector_name)
+            .ok_or_else(|| self.vector_name_error(vector_name))
+    }
+
+    fn get_sparse_vector_data(&self, vector_name: &str) -> OperationResult<&SparseVectorData> {
+        self.sparse_vector_data
+            .get(vector_name)
+            .ok_or_else(|| self.vector_name_error(vector_name))
+    }
+
+    fn check_dense_vector(
+        vector_data: &VectorData,
+        vector: &[VectorElementType],
+    ) -> OperationResult<()> {
+        let expected_dim = vector_data.vector_storage.borrow().vector_dim();
+        if expected_dim != vector.len() {
+            return Err(OperationError::WrongVector {
+                expected_dim,
+                received_dim: vector.len(),
+            });
+        }
+        Ok(())
+    }
+
+    fn check_sparse_vector(vector_name: &str, vector: &SparseVector) -> OperationResult<()> {
+        vector
+            .validate()
+            .map_err(|description| OperationError::WrongSparseVector {
+                vector_name: vector_name.to_owned(),
+                description,
+            })
+    }
+
+    /// Check that vectors for all vector names of the segment are provided
+    /// with the proper type and dimension
+    fn check_vectors(&self, vectors: &NamedVectors) -> OperationResult<()> {
+        for (vector_name, vector) in vectors {
+            match vector {
+                Vector::Dense(vector) => {
+                    Self::check_dense_vector(self.get_vector_data(vector_name)?, vector)?
+                }
+                Vector::Sparse(vector) => {
+                    self.get_sparse_vector_data(vector_name)?;
+                    Self::check_sparse_vector(vector_name, vector)?
+                }
+            }
+        }
+        let all_vector_names = self
+            .vector_data
+            .keys()
+            .chain(self.sparse_vector_data.keys());
+        for vector_name in all_vector_names {
+            if !vectors.contains_key(vector_name) {
+                return Err(OperationError::MissedVectorName {
+                    received_name: vector_name.to_owned(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply distance specific preprocessing to each of the dense vectors
+    fn preprocess_vectors(&self, vectors: &NamedVectors) -> NamedVectors {
+        vectors
+            .iter()
+            .map(|(vector_name, vector)| {
+                let processed_vector = match vector {
+                    Vector::Dense(vector) => {
+                        let distance = self.segment_config.vector_data[vector_name].distance;
+                        mertic_object(&distance)
+                            .preprocess(vector)
+                            .unwrap_or_else(|| vector.to_owned())
+                            .into()
+                    }
+                    Vector::Sparse(vector) => vector.clone().into(),
+                };
+                (vector_name.to_owned(), processed_vector)
+            })
+            .collect()
+    }
+
+    /// Check that all vector storages assigned the same internal id to the point
+    fn check_internal_ids(
+        vector_name: &str,
+        expected_id: Option<PointOffsetType>,
+        internal_id: PointOffsetType,
+    ) -> OperationResult<PointOffsetType> {
+        match expected_id {
+            Some(expected_id) if expected_id != internal_id => Err(OperationError::ServiceError {
+                description: format!(
+                    "Vector storages are out of sync: internal id {} of vector \"{}\" does not match {}",
+                    internal_id, vector_name, expected_id
+                ),
+            }),
+            _ => Ok(internal_id),
+        }
+    }
+
+    /// Mark vectors of the point, which was not inserted into all storages, as deleted.
+    /// So all storages keep the same number of stored vectors
+    fn discard_new_vectors(&self, new_internal_id: PointOffsetType) {
+        for vector_data in self.vector_data.values() {
+            let mut vector_storage = vector_data.vector_storage.borrow_mut();
+            if vector_storage.total_vector_count() > new_internal_id as usize {
+                // Storage is already failed, so the original error is reported
+                let _ = vector_storage.delete(new_internal_id);
+            }
+        }
+        for sparse_vector_data in self.sparse_vector_data.values() {
+            let mut sparse_vector_storage = sparse_vector_data.sparse_vector_storage.borrow_mut();
+            if sparse_vector_storage.total_vector_count() > new_internal_id as usize {
+                let _ = sparse_vector_storage.delete(new_internal_id);
+            }
+        }
+    }
+
+    /// Insert vectors of the new point into all storages.
+    /// Vectors should be checked with `check_vectors` beforehand,
+    /// if some storage still fails, vectors of the point are removed from the other storages
+    fn insert_new_vectors(&mut self, vectors: NamedVectors) -> OperationResult<PointOffsetType> {
+        let expected_internal_id = self
+            .vector_data
+            .values()
+            .map(|vector_data| vector_data.vector_storage.borrow().total_vector_count())
+            .chain(self.sparse_vector_data.values().map(|sparse_vector_data| {
+                sparse_vector_data
+                    .sparse_vector_storage
+                    .borrow()
+                    .total_vector_count()
+            }))
+            .max()
+            .unwrap_or(0) as PointOffsetType;
+        let result = self.put_new_vectors(vectors);
+        if result.is_err() {
+            self.discard_new_vectors(expected_internal_id);
+        }
+        result
+    }
+
+    fn put_new_vectors(&mut self, vectors: NamedVectors) -> OperationResult<PointOffsetType> {
+        let mut new_internal_index = None;
+        for (vector_name, vector) in vectors {
+            let internal_id = match vector {
+                Vector::Dense(vector) => self.vector_data[&vector_name]
+                    .vector_storage
+                    .borrow_mut()
+                    .put_vector(vector)?,
+                Vector::Sparse(vector) => self.sparse_vector_data[&vector_name]
+                    .sparse_vector_storage
+                    .borrow_mut()
+                    .put_vector(vector)?,
+            };
+            new_internal_index = Some(Self::check_internal_ids(
+                &vector_name,
+                new_internal_index,
+                internal_id,
+            )?);
+        }
+        new_internal_index.ok_or_else(|| OperationError::ServiceError {
+            description: "No vectors to insert".to_owned(),
+        })
+    }
+
+    fn update_vectors(
         &mut self,
         old_internal_id: PointOffsetType,
-        vector: Vec<VectorElementType>,
+        vectors: NamedVectors,
     ) -> OperationResult<PointOffsetType> {
-        let new_internal_index = {
-            let mut vector_storage = self.vector_storage.borrow_mut();
-            vector_storage.update_vector(old_internal_id, vector)
-        }?;
+        let mut new_internal_index = None;
+        for (vector_name, vector) in vectors {
+            let internal_id = match vector {
+                Vector::Dense(vector) => self.vector_data[&vector_name]
+                    .vector_storage
+                    .borrow_mut()
+                    .update_vector(old_internal_id, vector)?,
+                Vector::Sparse(vector) => self.sparse_vector_data[&vector_name]
+                    .sparse_vector_storage
+                    .borrow_mut()
+                    .update_vector(old_internal_id, vector)?,
+            };
+            new_internal_index = Some(Self::check_internal_ids(
+                &vector_name,
+                new_internal_index,
+                internal_id,
+            )?);
+        }
+        let new_internal_index = new_internal_index.unwrap_or(old_internal_id);
         if new_internal_index != old_internal_id {
             // If vector was moved to a new internal id, move payload to this internal id as well
             let mut payload_storage = self.payload_storage.borrow_mut();
@@ -195,40 +417,13 @@ impl Segment {
     pub fn save_current_state(&self) -> OperationResult<()> {
         self.save_state(&self.get_state())
     }
-}
-
-/// This is a basic implementation of `SegmentEntry`,
-/// meaning that it implements the _actual_ operations with data and not any kind of proxy or wrapping
-impl SegmentEntry for Segment {
-    fn version(&self) -> SeqNumberType {
-        self.version
-    }
 
-    fn point_version(&self, point_id: PointIdType) -> Option<SeqNumberType> {
-        self.id_tracker.borrow().version(point_id)
-    }
-
-    fn search(
+    /// Convert internal search result into scored points with external ids and payload
+    fn process_search_result(
         &self,
-        vector: &[VectorElementType],
+        internal_result: &[ScoredPointOffset],
         with_payload: &WithPayload,
-        filter: Option<&Filter>,
-        top: usize,
-        params: Option<&SearchParams>,
     ) -> OperationResult<Vec<ScoredPoint>> {
-        let expected_vector_dim = self.vector_storage.borrow().vector_dim();
-        if expected_vector_dim != vector.len() {
-            return Err(OperationError::WrongVector {
-                expected_dim: expected_vector_dim,
-                received_dim: vector.len(),
-            });
-        }
-
-        let internal_result = self
-            .vector_index
-            .borrow()
-            .search(vector, filter, top, params);
-
         let id_tracker = self.id_tracker.borrow();
 
         let res: OperationResult<Vec<ScoredPoint>> = internal_result
@@ -273,32 +468,98 @@ impl SegmentEntry for Segment {
         res
     }
 
-    fn upsert_point(
+    /// Total compression of all compressed vector storages of the segment
+    fn vector_compression_info(&self) -> Option<VectorCompressionInfo> {
+        self.vector_data
+            .values()
+            .filter_map(|vector_data| vector_data.vector_storage.borrow().compression_info())
+            .reduce(|total, info| VectorCompressionInfo {
+                original_size_bytes: total.original_size_bytes + info.original_size_bytes,
+                compressed_size_bytes: total.compressed_size_bytes + info.compressed_size_bytes,
+                estimated_recall: total.estimated_recall.min(info.estimated_recall),
+            })
+    }
+}
+
+/// This is a basic implementation of `SegmentEntry`,
+/// meaning that it implements the _actual_ operations with data and not any kind of proxy or wrapping
+impl SegmentEntry for Segment {
+    fn version(&self) -> SeqNumberType {
+        self.version
+    }
+
+    fn point_version(&self, point_id: PointIdType) -> Option<SeqNumberType> {
+        self.id_tracker.borrow().version(point_id)
+    }
+
+    fn search(
+        &self,
+        vector_name: &str,
+        vector: &[VectorElementType],
+        with_payload: &WithPayload,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        score_threshold: Option<ScoreType>,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let vector_data = self.get_vector_data(vector_name)?;
+        Self::check_dense_vector(vector_data, vector)?;
+
+        let mut internal_result = vector_data
+            .vector_index
+            .borrow()
+            .search(vector, filter, top, params);
+
+        if let Some(score_threshold) = score_threshold {
+            let distance = self.segment_config.vector_data[vector_name].distance;
+            let threshold = similarity_threshold(&distance, score_threshold);
+            internal_result.retain(|scored_point| scored_point.score >= threshold);
+        }
+
+        self.process_search_result(&internal_result, with_payload)
+    }
+
+    fn search_sparse(
+        &self,
+        vector_name: &str,
+        vector: &SparseVector,
+        with_payload: &WithPayload,
+        filter: Option<&Filter>,
+        top: usize,
+        score_threshold: Option<ScoreType>,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let sparse_vector_data = self.get_sparse_vector_data(vector_name)?;
+        Self::check_sparse_vector(vector_name, vector)?;
+
+        let mut internal_result = sparse_vector_data
+            .sparse_vector_index
+            .borrow()
+            .search(vector, filter, top);
+
+        // Sparse vectors are compared by dot product, larger score is better
+        if let Some(score_threshold) = score_threshold {
+            internal_result.retain(|scored_point| scored_point.score >= score_threshold);
+        }
+
+        self.process_search_result(&internal_result, with_payload)
+    }
+
+    fn upsert_vectors(
         &mut self,
         op_num: SeqNumberType,
         point_id: PointIdType,
-        vector: &[VectorElementType],
+        vectors: &NamedVectors,
     ) -> OperationResult<bool> {
         self.handle_version_and_failure(op_num, Some(point_id), |segment| {
-            let vector_dim = segment.vector_storage.borrow().vector_dim();
-            if vector_dim != vector.len() {
-                return Err(OperationError::WrongVector {
-                    expected_dim: vector_dim,
-                    received_dim: vector.len(),
-                });
-            }
-
-            let metric = mertic_object(&segment.segment_config.distance);
-            let processed_vector = metric
-                .preprocess(vector)
-                .unwrap_or_else(|| vector.to_owned());
+            segment.check_vectors(vectors)?;
+            let processed_vectors = segment.preprocess_vectors(vectors);
 
             let stored_internal_point = segment.id_tracker.borrow().internal_id(point_id);
 
             let was_replaced = match stored_internal_point {
                 Some(existing_internal_id) => {
                     let new_index =
-                        segment.update_vector(existing_internal_id, processed_vector)?;
+                        segment.update_vectors(existing_internal_id, processed_vectors)?;
                     if new_index != existing_internal_id {
                         let mut id_tracker = segment.id_tracker.borrow_mut();
                         id_tracker.drop(point_id)?;
@@ -307,10 +568,7 @@ impl SegmentEntry for Segment {
                     true
                 }
                 None => {
-                    let new_index = segment
-                        .vector_storage
-                        .borrow_mut()
-                        .put_vector(processed_vector)?;
+                    let new_index = segment.insert_new_vectors(processed_vectors)?;
                     segment
                         .id_tracker
                         .borrow_mut()
@@ -333,7 +591,18 @@ impl SegmentEntry for Segment {
             let internal_id = id_tracker.internal_id(point_id);
             match internal_id {
                 Some(internal_id) => {
-                    segment.vector_storage.borrow_mut().delete(internal_id)?;
+                    for vector_data in segment.vector_data.values() {
+                        vector_data
+                            .vector_storage
+                            .borrow_mut()
+                            .delete(internal_id)?;
+                    }
+                    for sparse_vector_data in segment.sparse_vector_data.values() {
+                        sparse_vector_data
+                            .sparse_vector_storage
+                            .borrow_mut()
+                            .delete(internal_id)?;
+                    }
                     id_tracker.drop(point_id)?;
                     Ok(true)
                 }
@@ -421,15 +690,42 @@ impl SegmentEntry for Segment {
         })
     }
 
-    fn vector(&self, point_id: PointIdType) -> OperationResult<Vec<VectorElementType>> {
+    fn vector(
+        &self,
+        vector_name: &str,
+        point_id: PointIdType,
+    ) -> OperationResult<Vec<VectorElementType>> {
         let internal_id = self.lookup_internal_id(point_id)?;
         Ok(self
+            .get_vector_data(vector_name)?
             .vector_storage
             .borrow()
             .get_vector(internal_id)
             .unwrap())
     }
 
+    fn all_vectors(&self, point_id: PointIdType) -> OperationResult<NamedVectors> {
+        let internal_id = self.lookup_internal_id(point_id)?;
+        let mut vectors = NamedVectors::new();
+        for (vector_name, vector_data) in &self.vector_data {
+            let vector = vector_data
+                .vector_storage
+                .borrow()
+                .get_vector(internal_id)
+                .unwrap();
+            vectors.insert(vector_name.to_owned(), vector.into());
+        }
+        for (vector_name, sparse_vector_data) in &self.sparse_vector_data {
+            let vector = sparse_vector_data
+                .sparse_vector_storage
+                .borrow()
+                .get_vector(internal_id)
+                .unwrap();
+            vectors.insert(vector_name.to_owned(), vector.into());
+        }
+        Ok(vectors)
+    }
+
     fn payload(
         &self,
         point_id: PointIdType,
@@ -452,7 +748,7 @@ impl SegmentEntry for Segment {
         limit: usize,
         filter: Option<&'a Filter>,
     ) -> Vec<PointIdType> {
-        let storage = self.vector_storage.borrow();
+        let storage = self.points_storage.borrow();
         match filter {
             None => self
                 .id_tracker
@@ -475,16 +771,98 @@ impl SegmentEntry for Segment {
         }
     }
 
+    fn read_ordered_filtered<'a>(
+        &'a self,
+        offset: PointIdType,
+        limit: usize,
+        filter: Option<&'a Filter>,
+        order_by: &'a OrderBy,
+    ) -> Vec<(RangeValue, PointIdType)> {
+        let storage = self.points_storage.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let payload_storage = self.payload_storage.borrow();
+        let is_selected = |internal_id: PointOffsetType| {
+            !storage.is_deleted(internal_id)
+                && filter.map_or(true, |filter| {
+                    self.condition_checker.check(internal_id, filter)
+                })
+        };
+        let point_value = |internal_id: PointOffsetType| {
+            let payload = payload_storage.payload(internal_id);
+            get_value_by_path(&payload, &order_by.key)
+                .and_then(|value| order_by.point_value(&value))
+        };
+
+        let mut points = match self.payload_index.borrow().ordered_points(
+            &order_by.key,
+            order_by.direction,
+            order_by.start_from,
+        ) {
+            Some(ordered_points) => {
+                let mut visited = HashSet::new();
+                let mut points: Vec<(RangeValue, PointIdType)> = vec![];
+                for (value, internal_id) in ordered_points {
+                    // Points with the same value are sorted by id afterwards, so they are read all
+                    let is_same_value = points.last().map_or(false, |(last_value, _)| {
+                        order_by.cmp_values(last_value, &value) == Ordering::Equal
+                    });
+                    if points.len() >= limit && !is_same_value {
+                        break;
+                    }
+                    if !visited.insert(internal_id) || !is_selected(internal_id) {
+                        continue;
+                    }
+                    // Point with multiple values is placed by the first of them,
+                    // which might be before the start of this page
+                    let is_point_value = point_value(internal_id).map_or(false, |point_value| {
+                        point_value.cmp_value(&value) == Ordering::Equal
+                    });
+                    if !is_point_value {
+                        continue;
+                    }
+                    if let Some(point_id) = id_tracker.external_id(internal_id) {
+                        if order_by.is_started(&value, point_id, offset) {
+                            points.push((value, point_id));
+                        }
+                    }
+                }
+                points
+            }
+            // Index is not built, values are read from the payload storage
+            None => id_tracker
+                .iter_from(PointIdType::MIN)
+                .filter(|(_, internal_id)| is_selected(*internal_id))
+                .filter_map(|(point_id, internal_id)| Some((point_value(internal_id)?, point_id)))
+                .filter(|(value, point_id)| order_by.is_started(value, *point_id, offset))
+                .collect(),
+        };
+
+        points.sort_by(|(value, point_id), (other_value, other_id)| {
+            order_by
+                .cmp_values(value, other_value)
+                .then(point_id.cmp(other_id))
+        });
+        points.truncate(limit);
+        points
+    }
+
+    fn estimate_points_count(&self, filter: Option<&Filter>) -> CardinalityEstimation {
+        match filter {
+            None => CardinalityEstimation::exact(self.vectors_count()),
+            Some(filter) => self.payload_index.borrow().estimate_cardinality(filter),
+        }
+    }
+
     fn has_point(&self, point_id: PointIdType) -> bool {
         self.id_tracker.borrow().internal_id(point_id).is_some()
     }
 
     fn vectors_count(&self) -> usize {
-        self.vector_storage.borrow().vector_count()
+        self.points_storage.borrow().vector_count()
     }
 
     fn deleted_count(&self) -> usize {
-        self.vector_storage.borrow().deleted_count()
+        self.points_storage.borrow().deleted_count()
     }
 
     fn segment_type(&self) -> SegmentType {
@@ -513,11 +891,12 @@ impl SegmentEntry for Segment {
         SegmentInfo {
             segment_type: self.segment_type,
             num_vectors: self.vectors_count(),
-            num_deleted_vectors: self.vector_storage.borrow().deleted_count(),
+            num_deleted_vectors: self.deleted_count(),
             ram_usage_bytes: 0,  // ToDo: Implement
             disk_usage_bytes: 0, // ToDo: Implement
             is_appendable: self.appendable_flag,
             schema,
+            vector_compression: self.vector_compression_info(),
         }
     }
 
@@ -539,7 +918,12 @@ impl SegmentEntry for Segment {
 
         self.id_tracker.borrow().flush()?;
         self.payload_storage.borrow().flush()?;
-        self.vector_storage.borrow().flush()?;
+        for vector_data in self.vector_data.values() {
+            vector_data.vector_storage.borrow().flush()?;
+        }
+        for sparse_vector_data in self.sparse_vector_data.values() {
+            sparse_vector_data.sparse_vector_storage.borrow().flush()?;
+        }
         self.save_state(&state)?;
 
         *persisted_version = state.version;
@@ -561,9 +945,17 @@ impl SegmentEntry for Segment {
         })
     }
 
-    fn create_field_index(&mut self, op_num: u64, key: PayloadKeyTypeRef) -> OperationResult<bool> {
+    fn create_field_index(
+        &mut self,
+        op_num: u64,
+        key: PayloadKeyTypeRef,
+        params: Option<&PayloadIndexParams>,
+    ) -> OperationResult<bool> {
         self.handle_version_and_failure(op_num, None, |segment| {
-            segment.payload_index.borrow_mut().set_indexed(key)?;
+            segment
+                .payload_index
+                .borrow_mut()
+                .set_indexed(key, params)?;
             Ok(true)
         })
     }
@@ -582,7 +974,10 @@ mod tests {
     use super::*;
     use crate::entry::entry_point::SegmentEntry;
     use crate::segment_constructor::build_segment;
-    use crate::types::{Distance, Indexes, PayloadIndexType, SegmentConfig, StorageType};
+    use crate::types::{
+        Distance, Indexes, PayloadIndexType, SegmentConfig, SparseVectorDataConfig, StorageType,
+        VectorDataConfig, DEFAULT_VECTOR_NAME,
+    };
     use tempdir::TempDir;
 
     #[test]
@@ -599,21 +994,29 @@ mod tests {
         let dir = TempDir::new("payload_dir").unwrap();
         let dim = 2;
         let config = SegmentConfig {
-            vector_size: dim,
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
             index: Indexes::Plain {},
             payload_index: Some(PayloadIndexType::Plain),
             storage_type: StorageType::InMemory,
-            distance: Distance::Dot,
+            quantization_config: None,
         };
 
         let mut segment = build_segment(dir.path(), &config).unwrap();
-        segment.upsert_point(0, 0, &[1.0, 1.0]).unwrap();
-        let result1 = segment.set_full_payload_with_json(0, 0, &data1.to_string());
+        segment.upsert_point(0, 0.into(), &[1.0, 1.0]).unwrap();
+        let result1 = segment.set_full_payload_with_json(0, 0.into(), &data1.to_string());
         match result1 {
             Ok(_) => assert!(false),
             Err(_) => assert!(true),
         }
-        let result2 = segment.set_full_payload_with_json(0, 0, &data2.to_string());
+        let result2 = segment.set_full_payload_with_json(0, 0.into(), &data2.to_string());
         match result2 {
             Ok(_) => assert!(false),
             Err(_) => assert!(true),
@@ -635,24 +1038,32 @@ mod tests {
         let dir = TempDir::new("payload_dir").unwrap();
         let dim = 2;
         let config = SegmentConfig {
-            vector_size: dim,
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
             index: Indexes::Plain {},
             payload_index: Some(PayloadIndexType::Plain),
             storage_type: StorageType::InMemory,
-            distance: Distance::Dot,
+            quantization_config: None,
         };
 
         let mut segment = build_segment(dir.path(), &config).unwrap();
-        segment.upsert_point(0, 0, &[1.0, 1.0]).unwrap();
+        segment.upsert_point(0, 0.into(), &[1.0, 1.0]).unwrap();
         segment
-            .set_full_payload_with_json(0, 0, &data.to_string())
+            .set_full_payload_with_json(0, 0.into(), &data.to_string())
             .unwrap();
 
         let filter_valid_str = r#"
         {
             "must": [
                 {
-                    "key": "metadata__height",
+                    "key": "metadata.height",
                     "match": {
                         "integer": 50
                     }
@@ -665,7 +1076,7 @@ mod tests {
         {
             "must": [
                 {
-                    "key": "metadata__height",
+                    "key": "metadata.height",
                     "match": {
                         "integer": 60
                     }
@@ -676,24 +1087,291 @@ mod tests {
         let filter_invalid: Filter = serde_json::from_str(filter_invalid_str).unwrap();
         let results_with_valid_filter = segment
             .search(
+                DEFAULT_VECTOR_NAME,
                 &[1.0, 1.0],
                 &WithPayload::default(),
                 Some(&filter_valid),
                 1,
                 None,
+                None,
             )
             .unwrap();
         assert_eq!(results_with_valid_filter.len(), 1);
-        assert_eq!(results_with_valid_filter.first().unwrap().id, 0);
+        assert_eq!(results_with_valid_filter.first().unwrap().id, 0.into());
         let results_with_invalid_filter = segment
             .search(
+                DEFAULT_VECTOR_NAME,
                 &[1.0, 1.0],
                 &WithPayload::default(),
                 Some(&filter_invalid),
                 1,
                 None,
+                None,
             )
             .unwrap();
         assert!(results_with_invalid_filter.is_empty());
     }
+
+    #[test]
+    fn test_named_vectors() {
+        let dir = TempDir::new("segment_dir").unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([
+                (
+                    "image".to_owned(),
+                    VectorDataConfig {
+                        size: 2,
+                        distance: Distance::Dot,
+                        hnsw_config: None,
+                    },
+                ),
+                (
+                    "text".to_owned(),
+                    VectorDataConfig {
+                        size: 3,
+                        distance: Distance::Euclid,
+                        hnsw_config: None,
+                    },
+                ),
+            ]),
+            sparse_vector_data: HashMap::new(),
+            index: Indexes::Plain {},
+            payload_index: Some(PayloadIndexType::Plain),
+            storage_type: StorageType::InMemory,
+            quantization_config: None,
+        };
+
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+
+        let point_vectors = |image: [f32; 2], text: [f32; 3]| {
+            NamedVectors::from([
+                ("image".to_owned(), image.to_vec().into()),
+                ("text".to_owned(), text.to_vec().into()),
+            ])
+        };
+
+        segment
+            .upsert_vectors(1, 1.into(), &point_vectors([1.0, 0.0], [0.0, 0.0, 1.0]))
+            .unwrap();
+        segment
+            .upsert_vectors(2, 2.into(), &point_vectors([0.0, 1.0], [1.0, 1.0, 1.0]))
+            .unwrap();
+
+        let image_result = segment
+            .search(
+                "image",
+                &[1.0, 0.0],
+                &WithPayload::default(),
+                None,
+                1,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(image_result[0].id, 1.into());
+
+        let text_result = segment
+            .search(
+                "text",
+                &[1.0, 1.0, 1.0],
+                &WithPayload::default(),
+                None,
+                1,
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(text_result[0].id, 2.into());
+
+        assert_eq!(
+            segment.vector("text", 2.into()).unwrap(),
+            vec![1.0, 1.0, 1.0]
+        );
+        assert_eq!(segment.all_vectors(1.into()).unwrap().len(), 2);
+
+        let missed_vector = segment.upsert_point(3, 3.into(), &[1.0, 1.0]);
+        assert!(matches!(
+            missed_vector,
+            Err(OperationError::VectorNameNotExists { .. })
+        ));
+
+        let partial_vectors = NamedVectors::from([("image".to_owned(), vec![1.0, 1.0].into())]);
+        let missed_name = segment.upsert_vectors(4, 4.into(), &partial_vectors);
+        assert!(matches!(
+            missed_name,
+            Err(OperationError::MissedVectorName { .. })
+        ));
+
+        let unknown_name = segment.search(
+            "audio",
+            &[1.0],
+            &WithPayload::default(),
+            None,
+            1,
+            None,
+            None,
+        );
+        assert!(matches!(
+            unknown_name,
+            Err(OperationError::VectorNameNotExists { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sparse_vectors() {
+        let dir = TempDir::new("segment_dir").unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                "image".to_owned(),
+                VectorDataConfig {
+                    size: 2,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::from([(
+                "text".to_owned(),
+                SparseVectorDataConfig::default(),
+            )]),
+            index: Indexes::Plain {},
+            payload_index: Some(PayloadIndexType::Plain),
+            storage_type: StorageType::InMemory,
+            quantization_config: None,
+        };
+
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+
+        let point_vectors = |image: [f32; 2], indices: Vec<u32>, values: Vec<f32>| {
+            NamedVectors::from([
+                ("image".to_owned(), image.to_vec().into()),
+                ("text".to_owned(), SparseVector { indices, values }.into()),
+            ])
+        };
+
+        segment
+            .upsert_vectors(
+                1,
+                1.into(),
+                &point_vectors([1.0, 0.0], vec![10, 2], vec![1.0, 2.0]),
+            )
+            .unwrap();
+        segment
+            .upsert_vectors(
+                2,
+                2.into(),
+                &point_vectors([0.0, 1.0], vec![2, 7], vec![0.5, 3.0]),
+            )
+            .unwrap();
+        segment
+            .upsert_vectors(
+                3,
+                3.into(),
+                &point_vectors([1.0, 1.0], vec![100], vec![1.0]),
+            )
+            .unwrap();
+
+        let query = SparseVector {
+            indices: vec![7, 2],
+            values: vec![1.0, 1.0],
+        };
+        let result = segment
+            .search_sparse("text", &query, &WithPayload::default(), None, 10, None)
+            .unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].id, 2.into());
+        assert_eq!(result[0].score, 3.5);
+        assert_eq!(result[1].id, 1.into());
+
+        segment.delete_point(4, 2.into()).unwrap();
+        let result = segment
+            .search_sparse("text", &query, &WithPayload::default(), None, 10, None)
+            .unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, 1.into());
+        assert_eq!(segment.vectors_count(), 2);
+
+        let stored_vectors = segment.all_vectors(1.into()).unwrap();
+        assert_eq!(
+            stored_vectors["text"],
+            Vector::Sparse(SparseVector {
+                indices: vec![2, 10],
+                values: vec![2.0, 1.0],
+            })
+        );
+
+        let wrong_type =
+            segment.search("text", &[1.0], &WithPayload::default(), None, 1, None, None);
+        assert!(matches!(
+            wrong_type,
+            Err(OperationError::WrongVectorType { .. })
+        ));
+
+        let invalid_vector = point_vectors([1.0, 1.0], vec![1, 1], vec![1.0, 2.0]);
+        let invalid_result = segment.upsert_vectors(5, 5.into(), &invalid_vector);
+        assert!(matches!(
+            invalid_result,
+            Err(OperationError::WrongSparseVector { .. })
+        ));
+    }
+
+    #[test]
+    fn test_search_score_threshold() {
+        let dir = TempDir::new("segment_dir").unwrap();
+        let vector_config = |distance| VectorDataConfig {
+            size: 2,
+            distance,
+            hnsw_config: None,
+        };
+        let config = SegmentConfig {
+            vector_data: HashMap::from([
+                ("euclid".to_owned(), vector_config(Distance::Euclid)),
+                ("dot".to_owned(), vector_config(Distance::Dot)),
+            ]),
+            sparse_vector_data: HashMap::new(),
+            index: Indexes::Plain {},
+            payload_index: Some(PayloadIndexType::Plain),
+            storage_type: StorageType::InMemory,
+            quantization_config: None,
+        };
+
+        let mut segment = build_segment(dir.path(), &config).unwrap();
+
+        let point_vectors = |euclid: [f32; 2], dot: [f32; 2]| {
+            NamedVectors::from([
+                ("euclid".to_owned(), euclid.to_vec().into()),
+                ("dot".to_owned(), dot.to_vec().into()),
+            ])
+        };
+        segment
+            .upsert_vectors(1, 1.into(), &point_vectors([0.0, 0.0], [1.0, 0.0]))
+            .unwrap();
+        segment
+            .upsert_vectors(2, 2.into(), &point_vectors([3.0, 4.0], [0.5, 0.0]))
+            .unwrap();
+
+        let search = |vector_name: &str, score_threshold: Option<ScoreType>| {
+            segment
+                .search(
+                    vector_name,
+                    &[1.0, 0.0],
+                    &WithPayload::default(),
+                    None,
+                    10,
+                    None,
+                    score_threshold,
+                )
+                .unwrap()
+        };
+
+        assert_eq!(search("euclid", None).len(), 2);
+        // Euclid threshold is a max distance to the query
+        let close_points = search("euclid", Some(2.0));
+        assert_eq!(close_points.len(), 1);
+        assert_eq!(close_points[0].id, 1.into());
+
+        assert_eq!(search("dot", None).len(), 2);
+        let similar_points = search("dot", Some(0.8));
+        assert_eq!(similar_points.len(), 1);
+        assert_eq!(similar_points[0].id, 1.into());
+    }
 }
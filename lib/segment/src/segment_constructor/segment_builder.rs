@@ -2,11 +2,12 @@ use crate::common::error_logging::LogError;
 use crate::entry::entry_point::{OperationError, OperationResult, SegmentEntry};
 use crate::segment::Segment;
 use crate::segment_constructor::{build_segment, load_segment};
-use crate::types::{PayloadKeyType, SegmentConfig};
+use crate::types::{PayloadIndexParams, PayloadKeyType, PointOffsetType, SegmentConfig};
 use core::cmp;
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::fs;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 
 /// Structure for constructing segment out of several other segments
@@ -14,7 +15,8 @@ pub struct SegmentBuilder {
     pub segment: Option<Segment>,
     pub destination_path: PathBuf,
     pub temp_path: PathBuf,
-    pub indexed_fields: HashSet<PayloadKeyType>,
+    /// Fields to index in the new segment, with explicit index parameters if any
+    pub indexed_fields: HashMap<PayloadKeyType, Option<PayloadIndexParams>>,
 }
 
 impl SegmentBuilder {
@@ -36,6 +38,22 @@ impl SegmentBuilder {
         })
     }
 
+    /// Check that all vector storages appended vectors to the same range of internal ids
+    fn check_range(
+        expected_range: Option<Range<PointOffsetType>>,
+        range: Range<PointOffsetType>,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        match expected_range {
+            Some(expected_range) if expected_range != range => Err(OperationError::ServiceError {
+                description: format!(
+                    "Segment building error: vector storages are out of sync, {:?} != {:?}",
+                    expected_range, range
+                ),
+            }),
+            _ => Ok(range),
+        }
+    }
+
     /// Update current segment builder with all (not deleted) vectors and payload form `other` segment
     /// Perform index building at the end of update
     pub fn update_from(&mut self, other: &Segment) -> OperationResult<()> {
@@ -47,17 +65,65 @@ impl SegmentBuilder {
                 self_segment.version = cmp::max(self_segment.version(), other.version());
 
                 let other_id_tracker = other.id_tracker.borrow();
-                let other_vector_storage = other.vector_storage.borrow();
+                let other_points_storage = other.points_storage.borrow();
                 let other_payload_storage = other.payload_storage.borrow();
 
                 let mut id_tracker = self_segment.id_tracker.borrow_mut();
-                let mut vector_storage = self_segment.vector_storage.borrow_mut();
                 let mut payload_storage = self_segment.payload_storage.borrow_mut();
 
-                let new_internal_range = vector_storage.update_from(&*other_vector_storage)?;
+                let mut new_internal_range = None;
+                for (vector_name, vector_data) in &self_segment.vector_data {
+                    let other_vector_data =
+                        other.vector_data.get(vector_name).ok_or_else(|| {
+                            OperationError::ServiceError {
+                                description: format!(
+                                "Segment building error: vector \"{}\" not found in source segment",
+                                vector_name
+                            ),
+                            }
+                        })?;
+                    let range = vector_data
+                        .vector_storage
+                        .borrow_mut()
+                        .update_from(&*other_vector_data.vector_storage.borrow())?;
+                    new_internal_range = Some(Self::check_range(new_internal_range, range)?);
+                }
+                for (vector_name, sparse_vector_data) in &self_segment.sparse_vector_data {
+                    let other_sparse_vector_data =
+                        other.sparse_vector_data.get(vector_name).ok_or_else(|| {
+                            OperationError::ServiceError {
+                                description: format!(
+                                    "Segment building error: sparse vector \"{}\" not found in source segment",
+                                    vector_name
+                                ),
+                            }
+                        })?;
+                    let range = sparse_vector_data
+                        .sparse_vector_storage
+                        .borrow_mut()
+                        .update_from(&*other_sparse_vector_data.sparse_vector_storage.borrow())?;
+                    new_internal_range = Some(Self::check_range(new_internal_range, range)?);
+                }
+                let new_internal_range = new_internal_range.unwrap_or_default();
+
+                let delete_vectors = |internal_id| -> OperationResult<()> {
+                    for vector_data in self_segment.vector_data.values() {
+                        vector_data
+                            .vector_storage
+                            .borrow_mut()
+                            .delete(internal_id)?;
+                    }
+                    for sparse_vector_data in self_segment.sparse_vector_data.values() {
+                        sparse_vector_data
+                            .sparse_vector_storage
+                            .borrow_mut()
+                            .delete(internal_id)?;
+                    }
+                    Ok(())
+                };
 
                 for (new_internal_id, old_internal_id) in
-                    new_internal_range.zip(other_vector_storage.iter_ids())
+                    new_internal_range.zip(other_points_storage.iter_ids())
                 {
                     let external_id = other_id_tracker.external_id(old_internal_id).unwrap();
                     let other_version = other_id_tracker.version(external_id).unwrap();
@@ -77,7 +143,7 @@ impl SegmentBuilder {
                                 // Other version is the newest, remove the existing one and replace
                                 let existing_internal_id =
                                     id_tracker.internal_id(external_id).unwrap();
-                                vector_storage.delete(existing_internal_id)?;
+                                delete_vectors(existing_internal_id)?;
                                 id_tracker.drop(external_id)?;
                                 id_tracker.set_link(external_id, new_internal_id)?;
                                 id_tracker.set_version(external_id, other_version)?;
@@ -88,14 +154,16 @@ impl SegmentBuilder {
                             } else {
                                 // Old version is still good, do not move anything else
                                 // Mark newly added vector as removed
-                                vector_storage.delete(new_internal_id)?;
+                                delete_vectors(new_internal_id)?;
                             };
                         }
                     }
                 }
 
-                for field in other.payload_index.borrow().indexed_fields().into_iter() {
-                    self.indexed_fields.insert(field);
+                let payload_index = other.payload_index.borrow();
+                for field in payload_index.indexed_fields().into_iter() {
+                    let params = payload_index.index_params(&field);
+                    self.indexed_fields.insert(field, params);
                 }
 
                 Ok(())
@@ -114,11 +182,22 @@ impl TryInto<Segment> for SegmentBuilder {
             })?;
             self.segment = None;
 
-            for field in self.indexed_fields.iter() {
-                segment.create_field_index(segment.version(), field)?;
+            for (field, params) in self.indexed_fields.iter() {
+                segment.create_field_index(segment.version(), field, params.as_ref())?;
+            }
+
+            for vector_data in segment.vector_data.values() {
+                let mut vector_storage = vector_data.vector_storage.borrow_mut();
+                vector_storage.train()?;
+
+                if let Some(quantization_config) = &segment.segment_config.quantization_config {
+                    vector_storage.quantize(quantization_config)?;
+                }
             }
 
-            segment.vector_index.borrow_mut().build_index()?;
+            for vector_data in segment.vector_data.values() {
+                vector_data.vector_index.borrow_mut().build_index()?;
+            }
 
             segment.flush()?;
             // Now segment is going to be evicted from RAM
@@ -1,22 +1,28 @@
+use crate::compat::SegmentStateV0;
 use crate::entry::entry_point::{OperationError, OperationResult};
 use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
 use crate::index::hnsw_index::hnsw::HNSWIndex;
 use crate::index::plain_payload_index::{PlainIndex, PlainPayloadIndex};
+use crate::index::sparse_index::sparse_vector_index::SparseVectorIndex;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndex};
 use crate::payload_storage::query_checker::SimpleConditionChecker;
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
-use crate::segment::{Segment, SEGMENT_STATE_FILE};
+use crate::segment::{Segment, SparseVectorData, VectorData, SEGMENT_STATE_FILE};
 use crate::types::{
-    Indexes, PayloadIndexType, SegmentConfig, SegmentState, SegmentType, SeqNumberType, StorageType,
+    Indexes, PayloadIndexType, SegmentConfig, SegmentState, SegmentType, SeqNumberType,
+    StorageType, DEFAULT_VECTOR_NAME,
 };
 use crate::vector_storage::memmap_vector_storage::MemmapVectorStorage;
+use crate::vector_storage::pq_vector_storage::PqVectorStorage;
 use crate::vector_storage::simple_vector_storage::SimpleVectorStorage;
-use crate::vector_storage::VectorStorage;
+use crate::vector_storage::sparse_vector_storage::SparseVectorStorage;
+use crate::vector_storage::{PointsStorage, VectorStorage};
 use atomic_refcell::AtomicRefCell;
+use std::collections::HashMap;
 use std::fs::{create_dir_all, File};
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
@@ -24,6 +30,27 @@ fn sp<T>(t: T) -> Arc<AtomicRefCell<T>> {
     Arc::new(AtomicRefCell::new(t))
 }
 
+/// Vectors with the default name are stored in the same directories as before named vectors were introduced
+fn get_vector_data_path(segment_path: &Path, dir_name: &str, vector_name: &str) -> PathBuf {
+    if vector_name == DEFAULT_VECTOR_NAME {
+        segment_path.join(dir_name)
+    } else {
+        segment_path.join(format!("{}-{}", dir_name, vector_name))
+    }
+}
+
+/// Same storage is used for vector search and as a source of point ids for payload index
+#[allow(clippy::type_complexity)]
+fn storage_pair<T: VectorStorage + 'static>(
+    storage: T,
+) -> (
+    Arc<AtomicRefCell<dyn VectorStorage>>,
+    Arc<AtomicRefCell<dyn PointsStorage>>,
+) {
+    let storage = sp(storage);
+    (storage.clone(), storage)
+}
+
 fn create_segment(
     version: SeqNumberType,
     segment_path: &Path,
@@ -32,23 +59,55 @@ fn create_segment(
     let tracker_path = segment_path.join("id_tracker");
     let payload_storage_path = segment_path.join("payload_storage");
     let payload_index_path = segment_path.join("payload_index");
-    let vector_storage_path = segment_path.join("vector_storage");
-    let vector_index_path = segment_path.join("vector_index");
+
+    if config.vector_data.is_empty() && config.sparse_vector_data.is_empty() {
+        return Err(OperationError::ServiceError {
+            description: "Segment should contain at least one vector".to_owned(),
+        });
+    }
 
     let id_tracker = sp(SimpleIdTracker::open(&tracker_path)?);
 
-    let vector_storage: Arc<AtomicRefCell<dyn VectorStorage>> = match config.storage_type {
-        StorageType::InMemory => sp(SimpleVectorStorage::open(
-            &vector_storage_path,
-            config.vector_size,
-            config.distance,
-        )?),
-        StorageType::Mmap => sp(MemmapVectorStorage::open(
-            &vector_storage_path,
-            config.vector_size,
-            config.distance,
-        )?),
-    };
+    let mut vector_storages: HashMap<String, Arc<AtomicRefCell<dyn VectorStorage>>> =
+        HashMap::new();
+    let mut points_storages: Vec<Arc<AtomicRefCell<dyn PointsStorage>>> = vec![];
+    for (vector_name, vector_config) in &config.vector_data {
+        let vector_storage_path = get_vector_data_path(segment_path, "vector_storage", vector_name);
+
+        let (vector_storage, points_storage) = match config.storage_type {
+            StorageType::InMemory => storage_pair(SimpleVectorStorage::open(
+                &vector_storage_path,
+                vector_config.size,
+                vector_config.distance,
+            )?),
+            StorageType::Mmap => storage_pair(MemmapVectorStorage::open(
+                &vector_storage_path,
+                vector_config.size,
+                vector_config.distance,
+            )?),
+            StorageType::ProductQuantized(pq_config) => storage_pair(PqVectorStorage::open(
+                &vector_storage_path,
+                vector_config.size,
+                vector_config.distance,
+                pq_config,
+            )?),
+        };
+        vector_storages.insert(vector_name.to_owned(), vector_storage);
+        points_storages.push(points_storage);
+    }
+
+    // Sparse vectors are always kept in memory, regardless of the storage type
+    let mut sparse_vector_storages = HashMap::new();
+    for vector_name in config.sparse_vector_data.keys() {
+        let vector_storage_path =
+            get_vector_data_path(segment_path, "sparse_vector_storage", vector_name);
+        let sparse_vector_storage = sp(SparseVectorStorage::open(&vector_storage_path)?);
+        points_storages.push(sparse_vector_storage.clone());
+        sparse_vector_storages.insert(vector_name.to_owned(), sparse_vector_storage);
+    }
+
+    // All vector storages share internal ids, so any of them could be used by payload index
+    let points_storage = points_storages.swap_remove(0);
 
     let payload_storage = sp(SimplePayloadStorage::open(&payload_storage_path)?);
 
@@ -61,31 +120,63 @@ fn create_segment(
         match config.payload_index.unwrap_or_default() {
             PayloadIndexType::Plain => sp(PlainPayloadIndex::open(
                 condition_checker.clone(),
-                vector_storage.clone(),
+                points_storage.clone(),
                 &payload_index_path,
             )?),
             PayloadIndexType::Struct => sp(StructPayloadIndex::open(
                 condition_checker.clone(),
-                vector_storage.clone(),
+                points_storage.clone(),
                 payload_storage.clone(),
                 id_tracker.clone(),
                 &payload_index_path,
             )?),
         };
 
-    let vector_index: Arc<AtomicRefCell<dyn VectorIndex>> = match config.index {
-        Indexes::Plain { .. } => sp(PlainIndex::new(
-            vector_storage.clone(),
+    let mut vector_data = HashMap::new();
+    for (vector_name, vector_storage) in vector_storages {
+        let vector_index_path = get_vector_data_path(segment_path, "vector_index", &vector_name);
+        let vector_config = &config.vector_data[&vector_name];
+
+        let vector_index: Arc<AtomicRefCell<dyn VectorIndex>> = match config.index {
+            Indexes::Plain { .. } => sp(PlainIndex::new(
+                vector_storage.clone(),
+                payload_index.clone(),
+            )),
+            Indexes::Hnsw(hnsw_config) => sp(HNSWIndex::open(
+                &vector_index_path,
+                condition_checker.clone(),
+                vector_storage.clone(),
+                payload_index.clone(),
+                vector_config.hnsw_config.unwrap_or(hnsw_config),
+            )?),
+        };
+
+        vector_data.insert(
+            vector_name,
+            VectorData {
+                vector_storage,
+                vector_index,
+            },
+        );
+    }
+
+    let mut sparse_vector_data = HashMap::new();
+    for (vector_name, sparse_vector_storage) in sparse_vector_storages {
+        let sparse_vector_index = sp(SparseVectorIndex::new(
+            config.sparse_vector_data[&vector_name],
+            sparse_vector_storage.clone(),
             payload_index.clone(),
-        )),
-        Indexes::Hnsw(hnsw_config) => sp(HNSWIndex::open(
-            &vector_index_path,
             condition_checker.clone(),
-            vector_storage.clone(),
-            payload_index.clone(),
-            hnsw_config,
-        )?),
-    };
+        ));
+
+        sparse_vector_data.insert(
+            vector_name,
+            SparseVectorData {
+                sparse_vector_storage,
+                sparse_vector_index,
+            },
+        );
+    }
 
     let segment_type = match config.index {
         Indexes::Plain { .. } => match config.payload_index.unwrap_or_default() {
@@ -103,11 +194,12 @@ fn create_segment(
         persisted_version: Arc::new(Mutex::new(version)),
         current_path: segment_path.to_owned(),
         id_tracker,
-        vector_storage,
+        vector_data,
+        sparse_vector_data,
+        points_storage,
         payload_storage,
         payload_index,
         condition_checker,
-        vector_index,
         appendable_flag,
         segment_type,
         segment_config: config.clone(),
@@ -122,8 +214,14 @@ pub fn load_segment(path: &Path) -> OperationResult<Segment> {
     let mut file = File::open(segment_config_path)?;
     file.read_to_string(&mut contents)?;
 
-    let segment_state: SegmentState =
-        serde_json::from_str(&contents).map_err(|err| OperationError::ServiceError {
+    let segment_state: SegmentState = serde_json::from_str(&contents)
+        .or_else(|err| {
+            // Segment might be created before named vectors were introduced
+            serde_json::from_str::<SegmentStateV0>(&contents)
+                .map(SegmentState::from)
+                .map_err(|_| err)
+        })
+        .map_err(|err| OperationError::ServiceError {
             description: format!(
                 "Failed to read segment {}. Error: {}",
                 path.to_str().unwrap(),
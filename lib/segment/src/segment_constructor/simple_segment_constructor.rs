@@ -1,9 +1,10 @@
 use crate::segment::Segment;
 
-use crate::types::{Distance, Indexes, SegmentConfig};
+use crate::types::{Distance, Indexes, SegmentConfig, VectorDataConfig, DEFAULT_VECTOR_NAME};
 
 use crate::entry::entry_point::OperationResult;
 use crate::segment_constructor::build_segment;
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Build new segment with plain index in given directory
@@ -20,11 +21,19 @@ pub fn build_simple_segment(
     build_segment(
         path,
         &SegmentConfig {
-            vector_size: dim,
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
             index: Indexes::Plain {},
             payload_index: None,
-            distance,
             storage_type: Default::default(),
+            quantization_config: None,
         },
     )
 }
@@ -56,7 +65,7 @@ mod tests {
         let vec4 = vec![1.0, 1.0, 0.0, 1.0];
         let vec5 = vec![1.0, 0.0, 0.0, 0.0];
 
-        match segment.upsert_point(1, 120, &wrong_vec) {
+        match segment.upsert_point(1, 120.into(), &wrong_vec) {
             Err(err) => match err {
                 OperationError::WrongVector { .. } => (),
                 _ => assert!(false, "Wrong error"),
@@ -64,18 +73,18 @@ mod tests {
             Ok(_) => assert!(false, "Operation with wrong vector should fail"),
         };
 
-        segment.upsert_point(2, 1, &vec1).unwrap();
-        segment.upsert_point(2, 2, &vec2).unwrap();
-        segment.upsert_point(2, 3, &vec3).unwrap();
-        segment.upsert_point(2, 4, &vec4).unwrap();
-        segment.upsert_point(2, 5, &vec5).unwrap();
+        segment.upsert_point(2, 1.into(), &vec1).unwrap();
+        segment.upsert_point(2, 2.into(), &vec2).unwrap();
+        segment.upsert_point(2, 3.into(), &vec3).unwrap();
+        segment.upsert_point(2, 4.into(), &vec4).unwrap();
+        segment.upsert_point(2, 5.into(), &vec5).unwrap();
 
         let payload_key = "color".to_string();
 
         segment
             .set_payload(
                 3,
-                1,
+                1.into(),
                 &payload_key,
                 PayloadType::Keyword(vec!["red".to_owned(), "green".to_owned()]),
             )
@@ -84,7 +93,7 @@ mod tests {
         segment
             .set_payload(
                 3,
-                2,
+                2.into(),
                 &payload_key,
                 PayloadType::Keyword(vec!["red".to_owned(), "blue".to_owned()]),
             )
@@ -93,7 +102,7 @@ mod tests {
         segment
             .set_payload(
                 3,
-                3,
+                3.into(),
                 &payload_key,
                 PayloadType::Keyword(vec!["red".to_owned(), "yellow".to_owned()]),
             )
@@ -102,22 +111,22 @@ mod tests {
         segment
             .set_payload(
                 3,
-                4,
+                4.into(),
                 &payload_key,
                 PayloadType::Keyword(vec!["red".to_owned(), "green".to_owned()]),
             )
             .unwrap();
 
         // Replace vectors
-        segment.upsert_point(4, 1, &vec1).unwrap();
-        segment.upsert_point(5, 2, &vec2).unwrap();
-        segment.upsert_point(6, 3, &vec3).unwrap();
-        segment.upsert_point(7, 4, &vec4).unwrap();
-        segment.upsert_point(8, 5, &vec5).unwrap();
+        segment.upsert_point(4, 1.into(), &vec1).unwrap();
+        segment.upsert_point(5, 2.into(), &vec2).unwrap();
+        segment.upsert_point(6, 3.into(), &vec3).unwrap();
+        segment.upsert_point(7, 4.into(), &vec4).unwrap();
+        segment.upsert_point(8, 5.into(), &vec5).unwrap();
 
         assert_eq!(segment.version(), 8);
 
-        let declined = segment.upsert_point(3, 5, &vec5).unwrap();
+        let declined = segment.upsert_point(3, 5.into(), &vec5).unwrap();
         // Should not be processed due to operation number
         assert!(!declined);
     }
@@ -1,10 +1,17 @@
+use chrono::{DateTime, Utc};
+use geo::{LineString, Polygon};
+use once_cell::sync::OnceCell;
 use ordered_float::OrderedFloat;
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-use std::cmp::Ordering;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Cow;
+use std::cmp::{max, min, Ordering};
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
 
-pub type PointIdType = u64;
+pub type PointIdType = ExtendedPointId;
 /// Type of point index across all segments
 pub type PointOffsetType = u32;
 /// Type of point index inside a segment
@@ -17,13 +24,81 @@ pub type ScoreType = f32;
 pub type TagType = u64;
 /// Type of vector element.
 pub type VectorElementType = f32;
+/// Type of dimension index of sparse vectors
+pub type SparseDimType = u32;
 /// Type of float point payload
 pub type FloatPayloadType = f64;
 /// Type of integer point payload
 pub type IntPayloadType = i64;
+/// Type of datetime point payload, represented as RFC 3339 string
+pub type DateTimePayloadType = DateTime<Utc>;
+
+/// Timestamp of the datetime in microseconds.
+/// Datetime payload is indexed and compared with ranges by this value
+pub fn datetime_timestamp(datetime: &DateTimePayloadType) -> IntPayloadType {
+    datetime.timestamp() * 1_000_000 + datetime.timestamp_subsec_micros() as IntPayloadType
+}
+
+/// Parse RFC 3339 datetime, e.g. `2021-06-01T12:30:00Z`, into UTC
+pub fn parse_datetime(value: &str) -> Result<DateTimePayloadType, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|datetime| datetime.with_timezone(&Utc))
+        .map_err(|err| format!("Invalid datetime {:?}: {}", value, err))
+}
+
+/// External id of the point: either an unsigned integer or a UUID.
+/// Numeric ids are ordered before UUIDs
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+#[serde(untagged)]
+pub enum ExtendedPointId {
+    NumId(u64),
+    Uuid(Uuid),
+}
+
+impl ExtendedPointId {
+    /// The smallest possible id, used as a default offset for scrolling
+    pub const MIN: ExtendedPointId = ExtendedPointId::NumId(0);
+}
+
+impl From<u64> for ExtendedPointId {
+    fn from(id: u64) -> Self {
+        ExtendedPointId::NumId(id)
+    }
+}
+
+impl From<Uuid> for ExtendedPointId {
+    fn from(uuid: Uuid) -> Self {
+        ExtendedPointId::Uuid(uuid)
+    }
+}
+
+impl fmt::Display for ExtendedPointId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtendedPointId::NumId(id) => write!(f, "{}", id),
+            ExtendedPointId::Uuid(uuid) => write!(f, "{}", uuid),
+        }
+    }
+}
+
+impl FromStr for ExtendedPointId {
+    type Err = String;
+
+    /// Parse id from a string, e.g. from URL path. Numbers are parsed as numeric ids
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(id) = s.parse::<u64>() {
+            return Ok(ExtendedPointId::NumId(id));
+        }
+        Uuid::parse_str(s)
+            .map(ExtendedPointId::Uuid)
+            .map_err(|_| format!("Invalid point id: {}", s))
+    }
+}
 
 /// Type of internal tags, build from payload
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, FromPrimitive)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, FromPrimitive)]
 /// Distance function types used to compare vectors
 pub enum Distance {
     /// https://en.wikipedia.org/wiki/Cosine_similarity
@@ -92,6 +167,18 @@ pub struct PayloadSchemaInfo {
     pub indexed: bool,
 }
 
+/// Information about compression of the stored vectors
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct VectorCompressionInfo {
+    /// Size of the original vectors in bytes
+    pub original_size_bytes: usize,
+    /// Size of the compressed vectors and auxiliary data (like codebooks) in bytes
+    pub compressed_size_bytes: usize,
+    /// Recall of the top-10 search with compressed vectors, estimated on a sample of stored vectors
+    pub estimated_recall: f32,
+}
+
 /// Aggregated information about segment
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -103,6 +190,8 @@ pub struct SegmentInfo {
     pub disk_usage_bytes: usize,
     pub is_appendable: bool,
     pub schema: HashMap<PayloadKeyType, PayloadSchemaInfo>,
+    /// Compression of the vectors, if vector storage is compressed
+    pub vector_compression: Option<VectorCompressionInfo>,
 }
 
 /// Additional parameters of the search
@@ -123,6 +212,16 @@ pub fn distance_order(distance: &Distance) -> Order {
     }
 }
 
+/// Convert score threshold of the search request into the similarity space of the metrics,
+/// in which larger score is always better.
+/// For distances where smaller is better (Euclid), the threshold is a max distance to the query
+pub fn similarity_threshold(distance: &Distance, score_threshold: ScoreType) -> ScoreType {
+    match distance_order(distance) {
+        Order::LargeBetter => score_threshold,
+        Order::SmallBetter => -score_threshold,
+    }
+}
+
 /// Vector index configuration of the segment
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -198,6 +297,9 @@ pub enum StorageType {
     InMemory,
     /// Use memmap to store vectors, a little slower than `InMemory`, but requires little RAM
     Mmap,
+    /// Keep original vectors in memmap and search only with product quantized codes in RAM.
+    /// Requires much less RAM than `InMemory`, but search results are approximate
+    ProductQuantized(ProductQuantizationConfig),
 }
 
 impl Default for StorageType {
@@ -206,24 +308,197 @@ impl Default for StorageType {
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+/// Default number of dimensions encoded by a single byte in product quantization
+pub const DEFAULT_PQ_BUCKET_SIZE: usize = 8;
+
+/// Config of product quantization (PQ) of the stored vectors
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
-pub struct SegmentConfig {
+pub struct ProductQuantizationConfig {
+    /// Number of vector dimensions encoded by a single byte code.
+    /// Larger the value - stronger the compression, but less accurate the search. Default: 8
+    pub bucket_size: Option<usize>,
+}
+
+impl ProductQuantizationConfig {
+    pub fn bucket_size(&self) -> usize {
+        self.bucket_size.unwrap_or(DEFAULT_PQ_BUCKET_SIZE).max(1)
+    }
+}
+
+/// Default quantile of vector elements used to select quantization bounds
+pub const DEFAULT_QUANTIZATION_QUANTILE: f32 = 0.99;
+
+/// Default multiplier of the number of candidates, selected with quantized vectors for rescoring
+pub const DEFAULT_RESCORE_MULTIPLIER: usize = 2;
+
+/// Config of scalar (int8) quantization of the stored vectors.
+/// Quantized vectors are used for approximate scoring during search,
+/// original vectors are only used to rescore the best candidates.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct ScalarQuantizationConfig {
+    /// Quantile of vector elements used as quantization bounds, values outside the bounds are clipped.
+    /// Should be in range (0, 1]. Default: 0.99
+    pub quantile: Option<f32>,
+    /// How many times more candidates than requested should be selected with quantized vectors
+    /// before rescoring them with original vectors. Default: 2
+    pub rescore_multiplier: Option<usize>,
+}
+
+impl ScalarQuantizationConfig {
+    pub fn quantile(&self) -> f32 {
+        self.quantile.unwrap_or(DEFAULT_QUANTIZATION_QUANTILE)
+    }
+
+    /// Number of candidates to select with quantized vectors for the requested `top`
+    pub fn rescore_limit(&self, top: usize) -> usize {
+        top * self
+            .rescore_multiplier
+            .unwrap_or(DEFAULT_RESCORE_MULTIPLIER)
+            .max(1)
+    }
+}
+
+/// Name of the vector, used if no name is specified explicitly
+pub const DEFAULT_VECTOR_NAME: &str = "";
+
+/// Sparse vector - only non-zero elements are stored, as pairs of dimension index and value
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SparseVector {
+    /// Indices of non-zero dimensions
+    pub indices: Vec<SparseDimType>,
+    /// Values of non-zero dimensions
+    pub values: Vec<VectorElementType>,
+}
+
+impl SparseVector {
+    /// Check that indices and values match and there are no duplicated indices
+    pub fn validate(&self) -> Result<(), String> {
+        if self.indices.len() != self.values.len() {
+            return Err(format!(
+                "number of indices ({}) does not match number of values ({})",
+                self.indices.len(),
+                self.values.len()
+            ));
+        }
+        let unique_indices: HashSet<_> = self.indices.iter().collect();
+        if unique_indices.len() != self.indices.len() {
+            return Err("indices should be unique".to_owned());
+        }
+        Ok(())
+    }
+
+    /// Return copy of the vector with elements sorted by dimension index
+    pub fn sorted(&self) -> SparseVector {
+        let mut pairs: Vec<_> = self.indices.iter().zip(&self.values).collect();
+        pairs.sort_by_key(|(idx, _)| **idx);
+        SparseVector {
+            indices: pairs.iter().map(|(idx, _)| **idx).collect(),
+            values: pairs.iter().map(|(_, value)| **value).collect(),
+        }
+    }
+
+    /// Dot product of two sparse vectors, both vectors should be sorted by indices
+    pub fn dot(&self, other: &SparseVector) -> ScoreType {
+        let mut score = 0.0;
+        let (mut i, mut j) = (0, 0);
+        while i < self.indices.len() && j < other.indices.len() {
+            match self.indices[i].cmp(&other.indices[j]) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    score += self.values[i] * other.values[j];
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        score
+    }
+}
+
+/// Vector of any supported kind
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(untagged, rename_all = "snake_case")]
+pub enum Vector {
+    Dense(Vec<VectorElementType>),
+    Sparse(SparseVector),
+}
+
+impl From<Vec<VectorElementType>> for Vector {
+    fn from(vector: Vec<VectorElementType>) -> Self {
+        Vector::Dense(vector)
+    }
+}
+
+impl From<SparseVector> for Vector {
+    fn from(vector: SparseVector) -> Self {
+        Vector::Sparse(vector)
+    }
+}
+
+/// Vectors of a single point, grouped by vector name
+pub type NamedVectors = HashMap<String, Vector>;
+
+/// Wrap a single vector into `NamedVectors` with the default name
+pub fn only_default_vector(vector: &[VectorElementType]) -> NamedVectors {
+    let mut vectors = NamedVectors::new();
+    vectors.insert(DEFAULT_VECTOR_NAME.to_owned(), vector.to_owned().into());
+    vectors
+}
+
+/// Config of a single vector space of the segment
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct VectorDataConfig {
     /// Size of a vectors used
-    pub vector_size: usize,
+    pub size: usize,
     /// Type of distance function used for measuring distance between vectors
     pub distance: Distance,
+    /// Params of the HNSW index of this vector.
+    /// If none - params of the segment `index` are used
+    #[serde(default)]
+    pub hnsw_config: Option<HnswConfig>,
+}
+
+/// Config of a single sparse vector space of the segment
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SparseVectorDataConfig {
+    /// If filter matches less points than this threshold, matched points are scored directly
+    /// instead of traversing the inverted index.
+    /// If none - `DEFAULT_SPARSE_FULL_SCAN_THRESHOLD` is used
+    #[serde(default)]
+    pub full_scan_threshold: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentConfig {
+    /// Config of each named vector space stored in the segment
+    pub vector_data: HashMap<String, VectorDataConfig>,
+    /// Config of each named sparse vector space stored in the segment
+    #[serde(default)]
+    pub sparse_vector_data: HashMap<String, SparseVectorDataConfig>,
     /// Type of index used for search
     pub index: Indexes,
     /// Payload Indexes
     pub payload_index: Option<PayloadIndexType>,
     /// Type of vector storage
     pub storage_type: StorageType,
+    /// Quantization of the stored vectors. If none - vectors are used with full precision
+    #[serde(default)]
+    pub quantization_config: Option<ScalarQuantizationConfig>,
 }
 
 /// Default value based on https://github.com/google-research/google-research/blob/master/scann/docs/algorithms.md
 pub const DEFAULT_FULL_SCAN_THRESHOLD: usize = 20_000;
 
+/// Filtered sparse search scores matched points directly below this cardinality
+pub const DEFAULT_SPARSE_FULL_SCAN_THRESHOLD: usize = 5_000;
+
 /// Persistable state of segment configuration
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -249,6 +524,116 @@ pub enum PayloadType {
     Integer(Vec<IntPayloadType>),
     Float(Vec<FloatPayloadType>),
     Geo(Vec<GeoPoint>),
+    Bool(Vec<bool>),
+    Datetime(Vec<DateTimePayloadType>),
+    /// Nested JSON objects, several objects are stored for arrays of objects
+    Object(Vec<TheMap<PayloadKeyType, PayloadType>>),
+}
+
+impl PayloadType {
+    /// Number of values of the payload
+    pub fn len(&self) -> usize {
+        match self {
+            PayloadType::Keyword(x) => x.len(),
+            PayloadType::Integer(x) => x.len(),
+            PayloadType::Float(x) => x.len(),
+            PayloadType::Geo(x) => x.len(),
+            PayloadType::Bool(x) => x.len(),
+            PayloadType::Datetime(x) => x.len(),
+            PayloadType::Object(x) => x.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Separator of the keys in the path to a nested payload value, e.g. `author.country`
+pub const PAYLOAD_PATH_SEPARATOR: char = '.';
+
+/// Combine values of the same type into a single value.
+/// Values of other types are skipped, schema does not allow them in the same field anyway.
+fn merge_values(mut values: impl Iterator<Item = PayloadType>) -> Option<PayloadType> {
+    let mut merged = values.next()?;
+    for value in values {
+        match (&mut merged, value) {
+            (PayloadType::Keyword(x), PayloadType::Keyword(y)) => x.extend(y),
+            (PayloadType::Integer(x), PayloadType::Integer(y)) => x.extend(y),
+            (PayloadType::Float(x), PayloadType::Float(y)) => x.extend(y),
+            (PayloadType::Geo(x), PayloadType::Geo(y)) => x.extend(y),
+            (PayloadType::Bool(x), PayloadType::Bool(y)) => x.extend(y),
+            (PayloadType::Datetime(x), PayloadType::Datetime(y)) => x.extend(y),
+            (PayloadType::Object(x), PayloadType::Object(y)) => x.extend(y),
+            _ => {}
+        }
+    }
+    Some(merged)
+}
+
+/// Get value of the payload by the dotted path to it, e.g. `author.country`.
+///
+/// If the path goes through an array of objects, values of all objects are combined,
+/// so `authors.country` selects countries of all authors.
+pub fn get_value_by_path<'a>(
+    payload: &'a TheMap<PayloadKeyType, PayloadType>,
+    path: PayloadKeyTypeRef,
+) -> Option<Cow<'a, PayloadType>> {
+    if let Some(value) = payload.get(path) {
+        return Some(Cow::Borrowed(value));
+    }
+    let (key, rest) = path.split_once(PAYLOAD_PATH_SEPARATOR)?;
+    match payload.get(key)? {
+        PayloadType::Object(objects) if objects.len() == 1 => get_value_by_path(&objects[0], rest),
+        PayloadType::Object(objects) => merge_values(
+            objects
+                .iter()
+                .filter_map(|object| get_value_by_path(object, rest))
+                .map(Cow::into_owned),
+        )
+        .map(Cow::Owned),
+        _ => None,
+    }
+}
+
+/// Copy value by the dotted `path` from `source` into `target`, preserving the nested objects
+fn copy_by_path(
+    source: &TheMap<PayloadKeyType, PayloadType>,
+    target: &mut TheMap<PayloadKeyType, PayloadType>,
+    path: PayloadKeyTypeRef,
+) {
+    if let Some(value) = source.get(path) {
+        target.insert(path.to_owned(), value.clone());
+        return;
+    }
+    let (key, rest) = match path.split_once(PAYLOAD_PATH_SEPARATOR) {
+        Some(split) => split,
+        None => return,
+    };
+    if let Some(PayloadType::Object(objects)) = source.get(key) {
+        let target_value = target
+            .entry(key.to_owned())
+            .or_insert_with(|| PayloadType::Object(vec![TheMap::new(); objects.len()]));
+        if let PayloadType::Object(target_objects) = target_value {
+            for (object, target_object) in objects.iter().zip(target_objects.iter_mut()) {
+                copy_by_path(object, target_object, rest);
+            }
+        }
+    }
+}
+
+/// Remove value by the dotted `path` from each of the nested objects
+fn remove_by_path(payload: &mut TheMap<PayloadKeyType, PayloadType>, path: PayloadKeyTypeRef) {
+    if payload.remove(path).is_some() {
+        return;
+    }
+    if let Some((key, rest)) = path.split_once(PAYLOAD_PATH_SEPARATOR) {
+        if let Some(PayloadType::Object(objects)) = payload.get_mut(key) {
+            for object in objects.iter_mut() {
+                remove_by_path(object, rest);
+            }
+        }
+    }
 }
 
 /// All possible names of payload types
@@ -260,6 +645,9 @@ pub enum PayloadSchemaType {
     Integer,
     Float,
     Geo,
+    Bool,
+    Datetime,
+    Object,
 }
 
 impl From<&PayloadType> for PayloadSchemaType {
@@ -269,10 +657,33 @@ impl From<&PayloadType> for PayloadSchemaType {
             PayloadType::Integer(_) => PayloadSchemaType::Integer,
             PayloadType::Float(_) => PayloadSchemaType::Float,
             PayloadType::Geo(_) => PayloadSchemaType::Geo,
+            PayloadType::Bool(_) => PayloadSchemaType::Bool,
+            PayloadType::Datetime(_) => PayloadSchemaType::Datetime,
+            PayloadType::Object(_) => PayloadSchemaType::Object,
         }
     }
 }
 
+/// Parameters of the full-text index
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct TextIndexParams {
+    /// Shorter tokens are not indexed
+    pub min_token_len: Option<usize>,
+    /// Longer tokens are not indexed
+    pub max_token_len: Option<usize>,
+}
+
+/// Explicitly selected type of the payload field index.
+/// If not specified, index is selected by the type of field values
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "type")]
+pub enum PayloadIndexParams {
+    /// Full-text index of the keyword field
+    Text(TextIndexParams),
+}
+
 /// Payload interface structure which ensures that user is allowed to pass payload in
 /// both - array and single element forms.
 ///
@@ -320,7 +731,19 @@ pub enum PayloadInterface {
     KeywordShortcut(PayloadVariant<String>),
     IntShortcut(PayloadVariant<i64>),
     FloatShortcut(PayloadVariant<f64>),
+    BoolShortcut(PayloadVariant<bool>),
     Payload(PayloadInterfaceStrict),
+    ObjectShortcut(PayloadVariant<PayloadObjectInterface>),
+}
+
+/// Nested JSON object of the payload, each value of the object is a payload itself
+pub type PayloadObjectInterface = TheMap<PayloadKeyType, PayloadInterface>;
+
+fn object_payload(object: &PayloadObjectInterface) -> TheMap<PayloadKeyType, PayloadType> {
+    object
+        .iter()
+        .map(|(key, value)| (key.clone(), value.into()))
+        .collect()
 }
 
 /// Fallback for PayloadInterface which is used if user explicitly specifies type of payload
@@ -340,6 +763,9 @@ pub enum PayloadInterfaceStrict {
     Integer(PayloadVariant<i64>),
     Float(PayloadVariant<f64>),
     Geo(PayloadVariant<GeoPoint>),
+    Bool(PayloadVariant<bool>),
+    Datetime(PayloadVariant<DateTimePayloadType>),
+    Object(PayloadVariant<PayloadObjectInterface>),
 }
 
 // For tests
@@ -356,6 +782,11 @@ impl From<&PayloadInterfaceStrict> for PayloadType {
             PayloadInterfaceStrict::Integer(x) => PayloadType::Integer(x.to_list()),
             PayloadInterfaceStrict::Float(x) => PayloadType::Float(x.to_list()),
             PayloadInterfaceStrict::Geo(x) => PayloadType::Geo(x.to_list()),
+            PayloadInterfaceStrict::Bool(x) => PayloadType::Bool(x.to_list()),
+            PayloadInterfaceStrict::Datetime(x) => PayloadType::Datetime(x.to_list()),
+            PayloadInterfaceStrict::Object(x) => {
+                PayloadType::Object(x.to_list().iter().map(object_payload).collect())
+            }
         }
     }
 }
@@ -367,6 +798,34 @@ impl From<&PayloadInterface> for PayloadType {
             PayloadInterface::KeywordShortcut(x) => PayloadType::Keyword(x.to_list()),
             PayloadInterface::FloatShortcut(x) => PayloadType::Float(x.to_list()),
             PayloadInterface::IntShortcut(x) => PayloadType::Integer(x.to_list()),
+            PayloadInterface::BoolShortcut(x) => PayloadType::Bool(x.to_list()),
+            PayloadInterface::ObjectShortcut(x) => {
+                PayloadType::Object(x.to_list().iter().map(object_payload).collect())
+            }
+        }
+    }
+}
+
+/// List of keyword or integer values to match against
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum AnyVariants {
+    Keywords(Vec<String>),
+    Integers(Vec<IntPayloadType>),
+}
+
+impl AnyVariants {
+    pub fn keywords(&self) -> Option<&[String]> {
+        match self {
+            AnyVariants::Keywords(keywords) => Some(keywords),
+            AnyVariants::Integers(_) => None,
+        }
+    }
+
+    pub fn integers(&self) -> Option<&[IntPayloadType]> {
+        match self {
+            AnyVariants::Keywords(_) => None,
+            AnyVariants::Integers(integers) => Some(integers),
         }
     }
 }
@@ -379,20 +838,246 @@ pub struct Match {
     pub keyword: Option<String>,
     /// Integer value to match
     pub integer: Option<IntPayloadType>,
+    /// Boolean value to match
+    pub boolean: Option<bool>,
+    /// Match if the keyword value contains all words of the given text.
+    /// Words are compared case-insensitively, punctuation is ignored
+    pub text: Option<String>,
+    /// Match if any of the point values is in the given list
+    pub any: Option<AnyVariants>,
+    /// Match if none of the point values is in the given list
+    pub except: Option<AnyVariants>,
+}
+
+/// Bound of the range filter: either a number or RFC 3339 datetime string
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum RangeBound {
+    Integer(IntPayloadType),
+    Float(FloatPayloadType),
+    Datetime(DateTimePayloadType),
+}
+
+/// Datetime bounds are converted into timestamps, see [`datetime_timestamp`]
+fn deserialize_range_bound<'de, D>(deserializer: D) -> Result<Option<RangeValue>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let bound = Option::<RangeBound>::deserialize(deserializer)?;
+    Ok(bound.map(|bound| match bound {
+        RangeBound::Integer(number) => RangeValue::Integer(number),
+        RangeBound::Float(number) => RangeValue::Float(number),
+        RangeBound::Datetime(datetime) => RangeValue::Integer(datetime_timestamp(&datetime)),
+    }))
+}
+
+/// Ordering of the integer `value` relative to the float `bound`, computed without rounding.
+/// `None` if the bound is NaN
+fn cmp_int_float(value: IntPayloadType, bound: FloatPayloadType) -> Option<Ordering> {
+    // Both limits are powers of two, so they are exactly representable as floats
+    const INT_UPPER_LIMIT: FloatPayloadType = 9_223_372_036_854_775_808.0;
+    const INT_LOWER_LIMIT: FloatPayloadType = -9_223_372_036_854_775_808.0;
+    if bound.is_nan() {
+        None
+    } else if bound >= INT_UPPER_LIMIT {
+        Some(Ordering::Less)
+    } else if bound < INT_LOWER_LIMIT {
+        Some(Ordering::Greater)
+    } else {
+        let truncated = bound.trunc();
+        // `truncated` is an integer within the range of i64, so the cast is exact
+        Some(
+            value
+                .cmp(&(truncated as IntPayloadType))
+                .then(truncated.partial_cmp(&bound)?),
+        )
+    }
+}
+
+/// Numeric bound of the range filter.
+/// Integer bounds are kept as integers, so they are compared with integer values exactly
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum RangeValue {
+    Integer(IntPayloadType),
+    Float(FloatPayloadType),
+}
+
+impl RangeValue {
+    /// Ordering of the integer `value` relative to the bound
+    pub fn cmp_int(&self, value: IntPayloadType) -> Option<Ordering> {
+        match self {
+            RangeValue::Integer(bound) => Some(value.cmp(bound)),
+            RangeValue::Float(bound) => cmp_int_float(value, *bound),
+        }
+    }
+
+    /// Ordering of the float `value` relative to the bound
+    pub fn cmp_float(&self, value: FloatPayloadType) -> Option<Ordering> {
+        match self {
+            RangeValue::Integer(bound) => cmp_int_float(*bound, value).map(Ordering::reverse),
+            RangeValue::Float(bound) => value.partial_cmp(bound),
+        }
+    }
+
+    /// Total ordering of the values, same as in the numeric index: NaN is the largest value
+    pub fn cmp_value(&self, other: &RangeValue) -> Ordering {
+        match (self, other) {
+            (RangeValue::Integer(value), RangeValue::Integer(other)) => value.cmp(other),
+            (RangeValue::Float(value), RangeValue::Float(other)) => {
+                OrderedFloat(*value).cmp(&OrderedFloat(*other))
+            }
+            (RangeValue::Integer(value), RangeValue::Float(other)) => {
+                cmp_int_float(*value, *other).unwrap_or(Ordering::Less)
+            }
+            (RangeValue::Float(_), RangeValue::Integer(_)) => other.cmp_value(self).reverse(),
+        }
+    }
+}
+
+impl From<IntPayloadType> for RangeValue {
+    fn from(value: IntPayloadType) -> Self {
+        RangeValue::Integer(value)
+    }
+}
+
+impl From<FloatPayloadType> for RangeValue {
+    fn from(value: FloatPayloadType) -> Self {
+        RangeValue::Float(value)
+    }
 }
 
 /// Range filter request
+///
+/// Bounds of the datetime fields could be given as RFC 3339 strings
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct Range {
     /// point.key < range.lt
-    pub lt: Option<FloatPayloadType>,
+    #[serde(default, deserialize_with = "deserialize_range_bound")]
+    #[schemars(with = "Option<RangeBound>")]
+    pub lt: Option<RangeValue>,
     /// point.key > range.gt
-    pub gt: Option<FloatPayloadType>,
+    #[serde(default, deserialize_with = "deserialize_range_bound")]
+    #[schemars(with = "Option<RangeBound>")]
+    pub gt: Option<RangeValue>,
     /// point.key >= range.gte
-    pub gte: Option<FloatPayloadType>,
+    #[serde(default, deserialize_with = "deserialize_range_bound")]
+    #[schemars(with = "Option<RangeBound>")]
+    pub gte: Option<RangeValue>,
     /// point.key <= range.lte
-    pub lte: Option<FloatPayloadType>,
+    #[serde(default, deserialize_with = "deserialize_range_bound")]
+    #[schemars(with = "Option<RangeBound>")]
+    pub lte: Option<RangeValue>,
+}
+
+impl Range {
+    /// Check the value, given the function which compares it with the bounds
+    fn check_by<F>(&self, cmp: F) -> bool
+    where
+        F: Fn(&RangeValue) -> Option<Ordering>,
+    {
+        self.lt
+            .map_or(true, |bound| cmp(&bound) == Some(Ordering::Less))
+            && self
+                .gt
+                .map_or(true, |bound| cmp(&bound) == Some(Ordering::Greater))
+            && self.lte.map_or(true, |bound| {
+                matches!(cmp(&bound), Some(Ordering::Less | Ordering::Equal))
+            })
+            && self.gte.map_or(true, |bound| {
+                matches!(cmp(&bound), Some(Ordering::Greater | Ordering::Equal))
+            })
+    }
+
+    pub fn check_int(&self, value: IntPayloadType) -> bool {
+        self.check_by(|bound| bound.cmp_int(value))
+    }
+
+    pub fn check_float(&self, value: FloatPayloadType) -> bool {
+        self.check_by(|bound| bound.cmp_float(value))
+    }
+}
+
+/// Direction of the ordering
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    /// From the smallest value to the largest
+    Asc,
+    /// From the largest value to the smallest
+    Desc,
+}
+
+impl Default for Direction {
+    fn default() -> Self {
+        Direction::Asc
+    }
+}
+
+/// Order of the points by the value of the payload field
+///
+/// Field should be indexed and have integer, float or datetime type.
+/// Points without values of the field are skipped
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct OrderBy {
+    /// Payload field to order by
+    pub key: PayloadKeyType,
+    /// Direction of the ordering. Default: asc
+    #[serde(default)]
+    pub direction: Direction,
+    /// Value of the field to start from, including. Points with the same value are ordered by id,
+    /// starting from the `offset` of the request.
+    /// Datetime values are represented by the timestamps in microseconds
+    #[serde(default, deserialize_with = "deserialize_range_bound")]
+    #[schemars(with = "Option<RangeBound>")]
+    pub start_from: Option<RangeValue>,
+}
+
+impl OrderBy {
+    /// Ordering of the field values in the requested direction
+    pub fn cmp_values(&self, value: &RangeValue, other: &RangeValue) -> Ordering {
+        match self.direction {
+            Direction::Asc => value.cmp_value(other),
+            Direction::Desc => value.cmp_value(other).reverse(),
+        }
+    }
+
+    /// Value, which defines position of the point: the first of its values in the requested order
+    pub fn point_value(&self, payload: &PayloadType) -> Option<RangeValue> {
+        let values = match payload {
+            PayloadType::Integer(values) => {
+                values.iter().map(|x| RangeValue::Integer(*x)).collect()
+            }
+            PayloadType::Float(values) => values.iter().map(|x| RangeValue::Float(*x)).collect(),
+            PayloadType::Datetime(values) => values
+                .iter()
+                .map(|x| RangeValue::Integer(datetime_timestamp(x)))
+                .collect(),
+            _ => vec![],
+        };
+        values
+            .into_iter()
+            .min_by(|value, other| self.cmp_values(value, other))
+    }
+
+    /// Check if the point with given value and id is at or after the start of the ordering
+    pub fn is_started(
+        &self,
+        value: &RangeValue,
+        point_id: PointIdType,
+        offset: PointIdType,
+    ) -> bool {
+        match &self.start_from {
+            None => true,
+            Some(start_from) => match self.cmp_values(value, start_from) {
+                Ordering::Greater => true,
+                Ordering::Equal => point_id >= offset,
+                Ordering::Less => false,
+            },
+        }
+    }
 }
 
 /// Geo filter request
@@ -419,6 +1104,108 @@ pub struct GeoRadius {
     pub radius: f64,
 }
 
+/// Ordered sequence of geo points, forming a ring of the polygon
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct GeoLineString {
+    pub points: Vec<GeoPoint>,
+}
+
+impl GeoLineString {
+    fn to_line_string(&self) -> LineString<f64> {
+        self.points
+            .iter()
+            .map(|point| (point.lon, point.lat))
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+/// Geo filter request
+///
+/// Matches coordinates inside the polygon, bounded by the `exterior` ring and not inside any of
+/// the `interiors` rings. Rings are closed automatically, if the last point differs from the first
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct GeoPolygon {
+    /// The exterior ring bounds the area of the polygon
+    pub exterior: GeoLineString,
+    /// Interior rings bound holes within the area
+    pub interiors: Option<Vec<GeoLineString>>,
+    /// Polygon, built from the rings on the first check and reused for the rest of the query
+    #[serde(skip)]
+    #[schemars(skip)]
+    polygon: OnceCell<Polygon<f64>>,
+}
+
+impl GeoPolygon {
+    pub fn new(exterior: GeoLineString, interiors: Option<Vec<GeoLineString>>) -> Self {
+        GeoPolygon {
+            exterior,
+            interiors,
+            polygon: OnceCell::new(),
+        }
+    }
+
+    pub fn polygon(&self) -> &Polygon<f64> {
+        self.polygon.get_or_init(|| {
+            let interiors = self
+                .interiors
+                .iter()
+                .flatten()
+                .map(GeoLineString::to_line_string)
+                .collect();
+            Polygon::new(self.exterior.to_line_string(), interiors)
+        })
+    }
+}
+
+impl PartialEq for GeoPolygon {
+    fn eq(&self, other: &Self) -> bool {
+        self.exterior == other.exterior && self.interiors == other.interiors
+    }
+}
+
+/// Values count filter request
+///
+/// Matches points by the number of values in the field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct ValuesCount {
+    /// point.key.length() < values_count.lt
+    pub lt: Option<usize>,
+    /// point.key.length() > values_count.gt
+    pub gt: Option<usize>,
+    /// point.key.length() >= values_count.gte
+    pub gte: Option<usize>,
+    /// point.key.length() <= values_count.lte
+    pub lte: Option<usize>,
+}
+
+impl ValuesCount {
+    /// Inclusive bounds of the matched values count, `None` if nothing could match
+    pub fn bounds(&self) -> Option<(usize, usize)> {
+        let mut lower = self.gte.unwrap_or(0);
+        let mut upper = self.lte.unwrap_or(usize::MAX);
+        if let Some(gt) = self.gt {
+            lower = max(lower, gt.checked_add(1)?);
+        }
+        if let Some(lt) = self.lt {
+            upper = min(upper, lt.checked_sub(1)?);
+        }
+        if lower > upper {
+            None
+        } else {
+            Some((lower, upper))
+        }
+    }
+
+    pub fn check_count(&self, count: usize) -> bool {
+        self.bounds()
+            .map_or(false, |(lower, upper)| lower <= count && count <= upper)
+    }
+}
+
 /// All possible payload filtering conditions
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -432,6 +1219,31 @@ pub struct FieldCondition {
     pub geo_bounding_box: Option<GeoBoundingBox>,
     /// Check if geo point is within a given radius
     pub geo_radius: Option<GeoRadius>,
+    /// Check if geo point is within a given polygon
+    pub geo_polygon: Option<GeoPolygon>,
+    /// Check number of values of the field.
+    /// Points without the field are not matched, use `is_empty` condition to select them
+    pub values_count: Option<ValuesCount>,
+}
+
+/// Payload field, addressed by the dotted path
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct PayloadField {
+    /// Payload field name
+    pub key: PayloadKeyType,
+}
+
+/// Select points with empty payload for a specified field.
+/// Field is empty if it is missing or has no values, e.g. `[]`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct IsEmptyCondition {
+    pub is_empty: PayloadField,
+}
+
+/// Select points, which have a specified payload field, even if it has no values
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct HasFieldCondition {
+    pub has_field: PayloadField,
 }
 
 /// ID-based filtering condition
@@ -453,6 +1265,10 @@ pub enum Condition {
     Field(FieldCondition),
     /// Check if points id is in a given set
     HasId(HasIdCondition),
+    /// Check if payload field is missing or empty
+    IsEmpty(IsEmptyCondition),
+    /// Check if payload field is present
+    HasField(HasFieldCondition),
     /// Nested filter
     Filter(Filter),
 }
@@ -520,13 +1336,19 @@ impl PayloadSelector {
         PayloadSelector { include, exclude }
     }
 
+    /// Select payload values by the dotted paths of `include` and remove paths of `exclude`
     pub fn process(
         &self,
         x: TheMap<PayloadKeyType, PayloadType>,
     ) -> TheMap<PayloadKeyType, PayloadType> {
-        x.into_iter()
-            .filter(|(key, _)| self.include.contains(key) && !self.exclude.contains(key))
-            .collect()
+        let mut selected = TheMap::new();
+        for path in &self.include {
+            copy_by_path(&x, &mut selected, path);
+        }
+        for path in &self.exclude {
+            remove_by_path(&mut selected, path);
+        }
+        selected
     }
 }
 
@@ -539,12 +1361,23 @@ pub struct WithPayload {
     /// Filter include and exclude payloads
     pub payload_selector: Option<PayloadSelector>,
 }
+/// At least `min_count` of the conditions should match
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "snake_case")]
+pub struct MinShould {
+    pub conditions: Vec<Condition>,
+    pub min_count: usize,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "snake_case")]
 pub struct Filter {
     /// At least one of thous conditions should match
     pub should: Option<Vec<Condition>>,
+    /// At least minimum amount of given conditions should match
+    pub min_should: Option<MinShould>,
     /// All conditions must match
     pub must: Option<Vec<Condition>>,
     /// All conditions must NOT match
@@ -555,6 +1388,7 @@ impl Filter {
     pub fn new_should(condition: Condition) -> Self {
         Filter {
             should: Some(vec![condition]),
+            min_should: None,
             must: None,
             must_not: None,
         }
@@ -563,6 +1397,7 @@ impl Filter {
     pub fn new_must(condition: Condition) -> Self {
         Filter {
             should: None,
+            min_should: None,
             must: Some(vec![condition]),
             must_not: None,
         }
@@ -571,6 +1406,7 @@ impl Filter {
     pub fn new_must_not(condition: Condition) -> Self {
         Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![condition]),
         }
@@ -584,6 +1420,26 @@ mod tests {
     use serde::de::DeserializeOwned;
     use serde_json;
 
+    #[test]
+    fn test_point_id_serialization() {
+        let ids: Vec<PointIdType> =
+            serde_json::from_str(r#"[42, "4a1d2d62-9bb4-4e4a-9c4f-9f0f5b6fa81f"]"#).unwrap();
+        assert_eq!(ids[0], ExtendedPointId::NumId(42));
+        assert_eq!(
+            ids[1],
+            ExtendedPointId::Uuid(Uuid::parse_str("4a1d2d62-9bb4-4e4a-9c4f-9f0f5b6fa81f").unwrap())
+        );
+        assert!(ids[0] < ids[1]);
+
+        let cbor = serde_cbor::to_vec(&ids).unwrap();
+        let restored: Vec<PointIdType> = serde_cbor::from_slice(&cbor).unwrap();
+        assert_eq!(restored, ids);
+
+        assert_eq!("42".parse::<PointIdType>().unwrap(), ids[0]);
+        assert_eq!(ids[1].to_string().parse::<PointIdType>().unwrap(), ids[1]);
+        assert!("not-an-id".parse::<PointIdType>().is_err());
+    }
+
     #[test]
     fn test_value_parse() {
         let geo_query_strict = r#"{"type": "geo", "value": {"lon": 1.0, "lat": 1.0}}"#;
@@ -733,6 +1589,187 @@ mod tests {
         assert_eq!(record, de_record);
     }
 
+    #[test]
+    fn test_bool_and_datetime_parse() {
+        let payload_interface: PayloadInterface = serde_json::from_str("[true, false]").unwrap();
+        match PayloadType::from(&payload_interface) {
+            PayloadType::Bool(x) => assert_eq!(x, vec![true, false]),
+            _ => assert!(false),
+        }
+
+        let datetime_query = r#"{"type": "datetime", "value": "2021-06-01T12:30:00+02:00"}"#;
+        let payload_interface: PayloadInterface = serde_json::from_str(datetime_query).unwrap();
+        match PayloadType::from(&payload_interface) {
+            PayloadType::Datetime(x) => {
+                assert_eq!(x.len(), 1);
+                assert_eq!(x[0], parse_datetime("2021-06-01T10:30:00Z").unwrap());
+            }
+            _ => assert!(false),
+        }
+        assert!(parse_datetime("2021-06-01").is_err());
+
+        let range_query = r#"{"gte": "1970-01-01T00:00:01Z", "lt": 5}"#;
+        let range: Range = serde_json::from_str(range_query).unwrap();
+        assert_eq!(range.gte, Some(RangeValue::Integer(1_000_000)));
+        assert_eq!(range.lt, Some(RangeValue::Integer(5)));
+        assert_eq!(range.gt, None);
+
+        let range: Range = serde_json::from_str(r#"{"gt": 0.5}"#).unwrap();
+        assert_eq!(range.gt, Some(RangeValue::Float(0.5)));
+    }
+
+    #[test]
+    fn test_exact_integer_range() {
+        let big = 9_007_199_254_740_993; // 2^53 + 1, not representable as f64
+        let range: Range = serde_json::from_str(&format!(r#"{{"gt": {}}}"#, big - 1)).unwrap();
+        assert!(range.check_int(big));
+        assert!(!range.check_int(big - 1));
+
+        let range = Range {
+            lt: None,
+            gt: None,
+            gte: Some(RangeValue::Float(-1.5)),
+            lte: Some(RangeValue::Float(2.0)),
+        };
+        assert!(!range.check_int(-2));
+        assert!(range.check_int(-1));
+        assert!(range.check_int(2));
+        assert!(!range.check_int(3));
+        assert!(!range.check_int(i64::MIN));
+
+        let range = Range {
+            lt: Some(RangeValue::Integer(3)),
+            gt: None,
+            gte: None,
+            lte: Some(RangeValue::Float(f64::NAN)),
+        };
+        assert!(!range.check_int(1));
+        assert!(!range.check_float(1.0));
+
+        let range = Range {
+            lt: Some(RangeValue::Integer(i64::MAX)),
+            gt: Some(RangeValue::Float(1e30)),
+            gte: None,
+            lte: None,
+        };
+        assert!(!range.check_float(1e31));
+        assert!(!range.check_int(i64::MAX));
+    }
+
+    #[test]
+    fn test_order_by() {
+        let order_by: OrderBy = serde_json::from_str(
+            r#"{"key": "created_at", "direction": "desc", "start_from": "2021-06-01T12:30:00Z"}"#,
+        )
+        .unwrap();
+        let start_from = datetime_timestamp(&parse_datetime("2021-06-01T12:30:00Z").unwrap());
+        assert_eq!(order_by.start_from, Some(RangeValue::Integer(start_from)));
+
+        let payload = PayloadType::Float(vec![1.5, 3.0, -2.0]);
+        assert_eq!(order_by.point_value(&payload), Some(RangeValue::Float(3.0)));
+        let order_by = OrderBy {
+            key: "price".to_owned(),
+            direction: Direction::Asc,
+            start_from: Some(RangeValue::Integer(3)),
+        };
+        assert_eq!(
+            order_by.point_value(&payload),
+            Some(RangeValue::Float(-2.0))
+        );
+        assert_eq!(order_by.point_value(&PayloadType::Keyword(vec![])), None);
+
+        assert!(order_by.is_started(&RangeValue::Float(3.5), 1.into(), 5.into()));
+        assert!(order_by.is_started(&RangeValue::Float(3.0), 5.into(), 5.into()));
+        assert!(!order_by.is_started(&RangeValue::Integer(3), 4.into(), 5.into()));
+        assert!(!order_by.is_started(&RangeValue::Float(2.9), 6.into(), 5.into()));
+    }
+
+    #[test]
+    fn test_nested_payload_paths() {
+        let data = r#"{
+            "author": {"name": "Anna", "country": "DE"},
+            "reviews": [{"score": 5, "user": {"id": 1}}, {"score": 3, "user": {"id": 2}}]
+        }"#;
+        let interface: TheMap<PayloadKeyType, PayloadInterface> =
+            serde_json::from_str(data).unwrap();
+        let payload: TheMap<PayloadKeyType, PayloadType> = interface
+            .iter()
+            .map(|(key, value)| (key.clone(), value.into()))
+            .collect();
+
+        match get_value_by_path(&payload, "author.country").as_deref() {
+            Some(PayloadType::Keyword(x)) => assert_eq!(x, &vec!["DE".to_owned()]),
+            _ => assert!(false),
+        }
+        match get_value_by_path(&payload, "reviews.score").as_deref() {
+            Some(PayloadType::Integer(x)) => assert_eq!(x, &vec![5, 3]),
+            _ => assert!(false),
+        }
+        match get_value_by_path(&payload, "reviews.user.id").as_deref() {
+            Some(PayloadType::Integer(x)) => assert_eq!(x, &vec![1, 2]),
+            _ => assert!(false),
+        }
+        assert!(get_value_by_path(&payload, "author.age").is_none());
+        assert!(get_value_by_path(&payload, "author.name.first").is_none());
+
+        let selector = PayloadSelector::new_include_and_exclude(
+            vec!["author".to_owned(), "reviews.score".to_owned()],
+            vec!["author.name".to_owned()],
+        );
+        let selected = selector.process(payload);
+        assert_eq!(selected.len(), 2);
+        assert!(get_value_by_path(&selected, "author.name").is_none());
+        assert!(get_value_by_path(&selected, "author.country").is_some());
+        assert!(get_value_by_path(&selected, "reviews.user").is_none());
+        match get_value_by_path(&selected, "reviews.score").as_deref() {
+            Some(PayloadType::Integer(x)) => assert_eq!(x, &vec![5, 3]),
+            _ => assert!(false),
+        }
+    }
+
+    #[test]
+    fn test_is_empty_and_has_field_parse() {
+        let query = r#"{
+            "must": [{"is_empty": {"key": "labels"}}],
+            "must_not": [{"has_field": {"key": "author.country"}}]
+        }"#;
+        let filter: Filter = serde_json::from_str(query).unwrap();
+        match filter.must.unwrap().get(0) {
+            Some(Condition::IsEmpty(condition)) => assert_eq!(condition.is_empty.key, "labels"),
+            _ => assert!(false, "IsEmpty condition expected"),
+        }
+        match filter.must_not.unwrap().get(0) {
+            Some(Condition::HasField(condition)) => {
+                assert_eq!(condition.has_field.key, "author.country")
+            }
+            _ => assert!(false, "HasField condition expected"),
+        }
+    }
+
+    #[test]
+    fn test_values_count_parse() {
+        let query = r#"{"must": [{"key": "tags", "values_count": {"gte": 3, "lt": 5}}]}"#;
+        let filter: Filter = serde_json::from_str(query).unwrap();
+        let values_count = match filter.must.unwrap().get(0) {
+            Some(Condition::Field(condition)) => condition.values_count.unwrap(),
+            _ => panic!("Field condition expected"),
+        };
+        assert_eq!(values_count.bounds(), Some((3, 4)));
+        assert!(!values_count.check_count(2));
+        assert!(values_count.check_count(3));
+        assert!(values_count.check_count(4));
+        assert!(!values_count.check_count(5));
+
+        let empty_range = ValuesCount {
+            lt: Some(0),
+            gt: None,
+            gte: None,
+            lte: None,
+        };
+        assert_eq!(empty_range.bounds(), None);
+        assert!(!empty_range.check_count(0));
+    }
+
     #[test]
     fn test_strict_deserialize() {
         let de_record: PayloadInterface =
@@ -815,15 +1852,22 @@ mod tests {
     #[test]
     fn test_serialize_query() {
         let filter = Filter {
+            min_should: None,
             must: Some(vec![Condition::Field(FieldCondition {
                 key: "hello".to_owned(),
                 r#match: Some(Match {
                     keyword: Some("world".to_owned()),
                     integer: None,
+                    boolean: None,
+                    text: None,
+                    any: None,
+                    except: None,
                 }),
                 range: None,
                 geo_bounding_box: None,
                 geo_radius: None,
+                geo_polygon: None,
+                values_count: None,
             })]),
             must_not: None,
             should: None,
@@ -1,8 +1,11 @@
 use crate::entry::entry_point::OperationResult;
 use crate::spaces::metric::Metric;
 use crate::spaces::tools::{mertic_object, peek_top_scores_iterable};
-use crate::types::{Distance, PointOffsetType, ScoreType, VectorElementType};
+use crate::types::{
+    Distance, PointOffsetType, ScalarQuantizationConfig, ScoreType, VectorElementType,
+};
 use crate::vector_storage::mmap_vectors::MmapVectors;
+use crate::vector_storage::quantized_vectors::{QuantizedRawScorer, ScalarQuantizedVectors};
 use crate::vector_storage::{RawScorer, ScoredPointOffset, VectorStorage};
 use std::fs::{create_dir_all, OpenOptions};
 use std::io::Write;
@@ -65,8 +68,10 @@ impl RawScorer for MemmapRawScorer<'_> {
 pub struct MemmapVectorStorage {
     vectors_path: PathBuf,
     deleted_path: PathBuf,
+    quantized_path: PathBuf,
     mmap_store: Option<MmapVectors>,
     metric: Box<dyn Metric>,
+    quantized_vectors: Option<ScalarQuantizedVectors>,
 }
 
 impl MemmapVectorStorage {
@@ -75,16 +80,26 @@ impl MemmapVectorStorage {
 
         let vectors_path = path.join("matrix.dat");
         let deleted_path = path.join("deleted.dat");
+        let quantized_path = ScalarQuantizedVectors::get_path(path);
 
         let mmap_store = MmapVectors::open(&vectors_path, &deleted_path, dim)?;
 
         let metric = mertic_object(&distance);
 
+        // Quantized vectors are kept in RAM, original vectors are only accessed for rescoring
+        let quantized_vectors = if quantized_path.exists() {
+            Some(ScalarQuantizedVectors::load(&quantized_path)?)
+        } else {
+            None
+        };
+
         Ok(MemmapVectorStorage {
             vectors_path,
             deleted_path,
+            quantized_path,
             mmap_store: Some(mmap_store),
             metric,
+            quantized_vectors,
         })
     }
 }
@@ -148,6 +163,9 @@ impl VectorStorage for MemmapVectorStorage {
                 let vector = &other.get_vector(id).unwrap();
                 let raw_bites = vf_to_u8(vector);
                 file.write_all(raw_bites)?;
+                if let Some(quantized) = &mut self.quantized_vectors {
+                    quantized.set(end_index, vector);
+                }
                 end_index += 1;
             }
 
@@ -173,6 +191,10 @@ impl VectorStorage for MemmapVectorStorage {
             dim,
         )?);
 
+        if let Some(quantized) = &self.quantized_vectors {
+            quantized.save(&self.quantized_path)?;
+        }
+
         Ok(start_index..end_index)
     }
 
@@ -203,6 +225,14 @@ impl VectorStorage for MemmapVectorStorage {
     }
 
     fn raw_scorer(&self, vector: Vec<VectorElementType>) -> Box<dyn RawScorer + '_> {
+        if let Some(quantized) = &self.quantized_vectors {
+            return Box::new(QuantizedRawScorer::new(
+                &vector,
+                self.metric.as_ref(),
+                quantized,
+                self,
+            ));
+        }
         Box::new(MemmapRawScorer {
             query: self.metric.preprocess(&vector).unwrap_or(vector),
             metric: self.metric.as_ref(),
@@ -211,6 +241,14 @@ impl VectorStorage for MemmapVectorStorage {
     }
 
     fn raw_scorer_internal(&self, point_id: PointOffsetType) -> Box<dyn RawScorer + '_> {
+        if let Some(quantized) = &self.quantized_vectors {
+            return Box::new(QuantizedRawScorer::new(
+                &self.get_vector(point_id).unwrap(),
+                self.metric.as_ref(),
+                quantized,
+                self,
+            ));
+        }
         Box::new(MemmapRawScorer {
             query: self.get_vector(point_id).unwrap(),
             metric: self.metric.as_ref(),
@@ -254,6 +292,14 @@ impl VectorStorage for MemmapVectorStorage {
             .as_ref()
             .map(|x| x as &[_])
             .unwrap_or(vector);
+        if let Some(quantized) = &self.quantized_vectors {
+            let candidates = quantized.score_points(
+                preprocessed_vector,
+                &mut self.iter_ids(),
+                quantized.config().rescore_limit(top),
+            );
+            return self.score_points(vector, &mut candidates.iter().map(|x| x.idx), top);
+        }
         let scores = self.iter_ids().map(|point| {
             let other_vector = self.mmap_store.as_ref().unwrap().raw_vector(point).unwrap();
             ScoredPointOffset {
@@ -274,6 +320,19 @@ impl VectorStorage for MemmapVectorStorage {
         let vector = self.get_vector(point).unwrap();
         self.score_points(&vector, points, top)
     }
+
+    fn quantize(&mut self, config: &ScalarQuantizationConfig) -> OperationResult<()> {
+        let quantized = ScalarQuantizedVectors::build(self, self.metric.distance(), config);
+        quantized.save(&self.quantized_path)?;
+        self.quantized_vectors = Some(quantized);
+        Ok(())
+    }
+
+    fn quantization_config(&self) -> Option<ScalarQuantizationConfig> {
+        self.quantized_vectors
+            .as_ref()
+            .map(|quantized| *quantized.config())
+    }
 }
 
 #[cfg(test)]
@@ -383,6 +442,47 @@ mod tests {
         assert_eq!(res[2].score, -1.0);
     }
 
+    #[test]
+    fn test_quantized_search() {
+        let dist = Distance::Dot;
+        let dir = TempDir::new("storage_dir").unwrap();
+        let mut storage = MemmapVectorStorage::open(dir.path(), 4, dist).unwrap();
+
+        {
+            let dir2 = TempDir::new("storage_dir2").unwrap();
+            let mut storage2 = SimpleVectorStorage::open(dir2.path(), 4, dist).unwrap();
+            storage2.put_vector(vec![1.0, 0.0, 1.0, 1.0]).unwrap();
+            storage2.put_vector(vec![1.0, 0.0, 1.0, 0.0]).unwrap();
+            storage2.put_vector(vec![1.0, 1.0, 1.0, 1.0]).unwrap();
+            storage2.put_vector(vec![1.0, 1.0, 0.0, 1.0]).unwrap();
+            storage2.put_vector(vec![1.0, 0.0, 0.0, 0.0]).unwrap();
+            storage.update_from(&storage2).unwrap();
+        }
+
+        let query = vec![0.0, 1.0, 1.1, 1.0];
+        let exact = storage.score_all(&query, 2);
+
+        let config = ScalarQuantizationConfig {
+            quantile: None,
+            rescore_multiplier: Some(2),
+        };
+        storage.quantize(&config).unwrap();
+        assert_eq!(storage.quantization_config(), Some(config));
+
+        let rescored = storage.score_all(&query, 2);
+        assert_eq!(rescored, exact);
+
+        storage.delete(2).unwrap();
+        let res = storage.score_all(&query, 2);
+        assert_eq!(res.len(), 2);
+        assert_ne!(res[0].idx, 2);
+
+        // Quantized vectors are loaded back together with the storage
+        drop(storage);
+        let storage = MemmapVectorStorage::open(dir.path(), 4, dist).unwrap();
+        assert_eq!(storage.quantization_config(), Some(config));
+    }
+
     #[test]
     fn test_casts() {
         let data: Vec<VectorElementType> = vec![0.42, 0.069, 333.1, 100500.];
@@ -1,6 +1,10 @@
 pub mod memmap_vector_storage;
 mod mmap_vectors;
+pub mod pq_vector_storage;
+pub mod product_quantization;
+pub mod quantized_vectors;
 pub mod simple_vector_storage;
+pub mod sparse_vector_storage;
 mod vector_storage_base;
 
 pub use vector_storage_base::*;
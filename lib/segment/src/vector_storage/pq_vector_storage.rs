@@ -0,0 +1,420 @@
+use crate::common::file_operations::{atomic_save_bin, read_bin};
+use crate::entry::entry_point::OperationResult;
+use crate::spaces::metric::Metric;
+use crate::spaces::tools::{mertic_object, peek_top_scores_iterable};
+use crate::types::{
+    Distance, PointOffsetType, ProductQuantizationConfig, ScalarQuantizationConfig, ScoreType,
+    VectorCompressionInfo, VectorElementType,
+};
+use crate::vector_storage::memmap_vector_storage::MemmapVectorStorage;
+use crate::vector_storage::product_quantization::{
+    LookupTable, ProductQuantizer, TRAIN_SAMPLE_SIZE,
+};
+use crate::vector_storage::{RawScorer, ScoredPointOffset, VectorStorage};
+use itertools::Itertools;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::cmp::{max, min};
+use std::collections::HashSet;
+use std::mem::size_of;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+const PQ_FILE: &str = "pq.bin";
+
+/// Number of vectors used as a search base for recall estimation
+const RECALL_SAMPLE_SIZE: usize = 1000;
+/// Number of queries used for recall estimation
+const RECALL_QUERIES: usize = 20;
+/// Size of the search result used for recall estimation
+const RECALL_TOP: usize = 10;
+
+/// Persisted state of the trained product quantization
+#[derive(Debug, Deserialize, Serialize, Clone)]
+struct ProductQuantizedVectors {
+    quantizer: ProductQuantizer,
+    /// Codes of all vectors, `quantizer.buckets_count()` bytes per vector
+    codes: Vec<u8>,
+    estimated_recall: f32,
+}
+
+impl ProductQuantizedVectors {
+    fn codes(&self, key: PointOffsetType) -> &[u8] {
+        let size = self.quantizer.buckets_count();
+        let start = key as usize * size;
+        &self.codes[start..start + size]
+    }
+}
+
+pub struct PqRawScorer<'a> {
+    table: LookupTable,
+    quantized: &'a ProductQuantizedVectors,
+    storage: &'a dyn VectorStorage,
+}
+
+impl RawScorer for PqRawScorer<'_> {
+    fn score_points<'a>(
+        &'a self,
+        points: &'a mut dyn Iterator<Item = PointOffsetType>,
+    ) -> Box<dyn Iterator<Item = ScoredPointOffset> + 'a> {
+        let res_iter = points
+            .filter(move |point| !self.storage.is_deleted(*point))
+            .map(move |point| ScoredPointOffset {
+                idx: point,
+                score: self.table.score(self.quantized.codes(point)),
+            });
+        Box::new(res_iter)
+    }
+
+    fn check_point(&self, point: PointOffsetType) -> bool {
+        (point < self.storage.total_vector_count() as PointOffsetType)
+            && !self.storage.is_deleted(point)
+    }
+
+    fn score_point(&self, point: PointOffsetType) -> ScoreType {
+        self.table.score(self.quantized.codes(point))
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        self.quantized
+            .quantizer
+            .score_codes(self.quantized.codes(point_a), self.quantized.codes(point_b))
+    }
+}
+
+/// Vector storage with product quantization (PQ).
+///
+/// Original vectors are kept on disk in mem-mapped storage, so the segment could be re-built
+/// without loss of precision. Search only uses PQ codes, which are kept in RAM.
+///
+/// Codebooks are trained with `train`, once all vectors are loaded into the storage.
+/// Until then all scoring is performed with original vectors.
+pub struct PqVectorStorage {
+    config: ProductQuantizationConfig,
+    distance: Distance,
+    metric: Box<dyn Metric>,
+    pq_path: PathBuf,
+    originals: MemmapVectorStorage,
+    quantized: Option<ProductQuantizedVectors>,
+}
+
+impl PqVectorStorage {
+    pub fn open(
+        path: &Path,
+        dim: usize,
+        distance: Distance,
+        config: ProductQuantizationConfig,
+    ) -> OperationResult<Self> {
+        let originals = MemmapVectorStorage::open(path, dim, distance)?;
+        let pq_path = path.join(PQ_FILE);
+        let quantized = if pq_path.exists() {
+            Some(read_bin(&pq_path)?)
+        } else {
+            None
+        };
+
+        Ok(PqVectorStorage {
+            config,
+            distance,
+            metric: mertic_object(&distance),
+            pq_path,
+            originals,
+            quantized,
+        })
+    }
+
+    fn preprocess(&self, vector: &[VectorElementType]) -> Vec<VectorElementType> {
+        self.metric
+            .preprocess(vector)
+            .unwrap_or_else(|| vector.to_owned())
+    }
+
+    /// Estimate recall of the search with PQ codes, comparing with exact search on original vectors
+    fn estimate_recall(&self, quantized: &ProductQuantizedVectors) -> f32 {
+        let step = max(1, self.originals.vector_count() / RECALL_SAMPLE_SIZE);
+        let base_ids = self
+            .originals
+            .iter_ids()
+            .step_by(step)
+            .take(RECALL_SAMPLE_SIZE)
+            .collect_vec();
+
+        if base_ids.is_empty() {
+            return 1.0;
+        }
+
+        let queries_step = max(1, base_ids.len() / RECALL_QUERIES);
+        let mut recall_sum = 0.0;
+        let mut queries_count = 0;
+        for query_id in base_ids.iter().step_by(queries_step).take(RECALL_QUERIES) {
+            let query = self.originals.get_vector(*query_id).unwrap();
+            let top = min(RECALL_TOP, base_ids.len());
+
+            let exact: HashSet<_> = self
+                .originals
+                .score_points(&query, &mut base_ids.iter().cloned(), top)
+                .into_iter()
+                .map(|x| x.idx)
+                .collect();
+
+            let table = quantized.quantizer.lookup_table(&self.preprocess(&query));
+            let approximate = peek_top_scores_iterable(
+                base_ids.iter().map(|idx| ScoredPointOffset {
+                    idx: *idx,
+                    score: table.score(quantized.codes(*idx)),
+                }),
+                top,
+            );
+
+            let found = approximate
+                .iter()
+                .filter(|x| exact.contains(&x.idx))
+                .count();
+            recall_sum += found as f32 / top as f32;
+            queries_count += 1;
+        }
+        recall_sum / queries_count as f32
+    }
+}
+
+impl VectorStorage for PqVectorStorage {
+    fn vector_dim(&self) -> usize {
+        self.originals.vector_dim()
+    }
+
+    fn vector_count(&self) -> usize {
+        self.originals.vector_count()
+    }
+
+    fn deleted_count(&self) -> usize {
+        self.originals.deleted_count()
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.originals.total_vector_count()
+    }
+
+    fn get_vector(&self, key: PointOffsetType) -> Option<Vec<VectorElementType>> {
+        self.originals.get_vector(key)
+    }
+
+    fn put_vector(&mut self, _vector: Vec<VectorElementType>) -> OperationResult<PointOffsetType> {
+        panic!("Can't put vector in PQ storage")
+    }
+
+    fn update_vector(
+        &mut self,
+        _key: PointOffsetType,
+        _vector: Vec<VectorElementType>,
+    ) -> OperationResult<PointOffsetType> {
+        panic!("Can't directly update vector in PQ storage")
+    }
+
+    fn update_from(
+        &mut self,
+        other: &dyn VectorStorage,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let range = self.originals.update_from(other)?;
+        if let Some(quantized) = &mut self.quantized {
+            // Encode new vectors with already trained codebooks
+            for id in range.clone() {
+                match self.originals.get_vector(id) {
+                    Some(vector) => quantized.codes.extend(quantized.quantizer.encode(&vector)),
+                    None => quantized
+                        .codes
+                        .extend(vec![0; quantized.quantizer.buckets_count()]),
+                }
+            }
+            atomic_save_bin(&self.pq_path, quantized)?;
+        }
+        Ok(range)
+    }
+
+    fn delete(&mut self, key: PointOffsetType) -> OperationResult<()> {
+        self.originals.delete(key)
+    }
+
+    fn is_deleted(&self, key: PointOffsetType) -> bool {
+        self.originals.is_deleted(key)
+    }
+
+    fn iter_ids(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        self.originals.iter_ids()
+    }
+
+    fn flush(&self) -> OperationResult<()> {
+        self.originals.flush()
+    }
+
+    fn raw_scorer(&self, vector: Vec<VectorElementType>) -> Box<dyn RawScorer + '_> {
+        match &self.quantized {
+            None => self.originals.raw_scorer(vector),
+            Some(quantized) => Box::new(PqRawScorer {
+                table: quantized.quantizer.lookup_table(&self.preprocess(&vector)),
+                quantized,
+                storage: self,
+            }),
+        }
+    }
+
+    fn raw_scorer_internal(&self, point_id: PointOffsetType) -> Box<dyn RawScorer + '_> {
+        match &self.quantized {
+            None => self.originals.raw_scorer_internal(point_id),
+            Some(quantized) => Box::new(PqRawScorer {
+                // Stored vectors are already preprocessed
+                table: quantized
+                    .quantizer
+                    .lookup_table(&self.originals.get_vector(point_id).unwrap()),
+                quantized,
+                storage: self,
+            }),
+        }
+    }
+
+    fn score_points(
+        &self,
+        vector: &[VectorElementType],
+        points: &mut dyn Iterator<Item = PointOffsetType>,
+        top: usize,
+    ) -> Vec<ScoredPointOffset> {
+        match &self.quantized {
+            None => self.originals.score_points(vector, points, top),
+            Some(quantized) => {
+                let table = quantized.quantizer.lookup_table(&self.preprocess(vector));
+                let scores = points
+                    .filter(|point| !self.is_deleted(*point))
+                    .map(|point| ScoredPointOffset {
+                        idx: point,
+                        score: table.score(quantized.codes(point)),
+                    });
+                peek_top_scores_iterable(scores, top)
+            }
+        }
+    }
+
+    fn score_all(&self, vector: &[VectorElementType], top: usize) -> Vec<ScoredPointOffset> {
+        self.score_points(vector, &mut self.iter_ids(), top)
+    }
+
+    fn score_internal(
+        &self,
+        point: PointOffsetType,
+        points: &mut dyn Iterator<Item = PointOffsetType>,
+        top: usize,
+    ) -> Vec<ScoredPointOffset> {
+        let vector = self.get_vector(point).unwrap();
+        self.score_points(&vector, points, top)
+    }
+
+    fn quantize(&mut self, _config: &ScalarQuantizationConfig) -> OperationResult<()> {
+        // Vectors are already compressed with product quantization
+        Ok(())
+    }
+
+    fn quantization_config(&self) -> Option<ScalarQuantizationConfig> {
+        None
+    }
+
+    fn train(&mut self) -> OperationResult<()> {
+        let dim = self.vector_dim();
+        let step = max(1, self.vector_count() / TRAIN_SAMPLE_SIZE);
+        let sample_vectors = self
+            .iter_ids()
+            .step_by(step)
+            .take(TRAIN_SAMPLE_SIZE)
+            .map(|id| self.originals.get_vector(id).unwrap())
+            .collect_vec();
+
+        debug!("training PQ codebooks on {} vectors", sample_vectors.len());
+
+        let quantizer = ProductQuantizer::train(
+            dim,
+            self.distance,
+            self.config.bucket_size(),
+            &sample_vectors,
+        );
+
+        let mut codes = Vec::with_capacity(self.total_vector_count() * quantizer.buckets_count());
+        for id in 0..self.total_vector_count() as PointOffsetType {
+            match self.originals.get_vector(id) {
+                Some(vector) => codes.extend(quantizer.encode(&vector)),
+                // Deleted vectors are never scored, codes are only required to keep offsets
+                None => codes.extend(vec![0; quantizer.buckets_count()]),
+            }
+        }
+
+        let mut quantized = ProductQuantizedVectors {
+            quantizer,
+            codes,
+            estimated_recall: 0.0,
+        };
+        quantized.estimated_recall = self.estimate_recall(&quantized);
+
+        atomic_save_bin(&self.pq_path, &quantized)?;
+        self.quantized = Some(quantized);
+        Ok(())
+    }
+
+    fn compression_info(&self) -> Option<VectorCompressionInfo> {
+        self.quantized
+            .as_ref()
+            .map(|quantized| VectorCompressionInfo {
+                original_size_bytes: self.total_vector_count()
+                    * self.vector_dim()
+                    * size_of::<VectorElementType>(),
+                compressed_size_bytes: quantized.codes.len()
+                    + quantized.quantizer.codebooks_size_bytes(),
+                estimated_recall: quantized.estimated_recall,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector_storage::simple_vector_storage::SimpleVectorStorage;
+    use rand::Rng;
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_pq_storage() {
+        let dim = 16;
+        let distance = Distance::Dot;
+        let config = ProductQuantizationConfig {
+            bucket_size: Some(4),
+        };
+        let dir = TempDir::new("storage_dir").unwrap();
+        let mut storage = PqVectorStorage::open(dir.path(), dim, distance, config).unwrap();
+
+        let mut rng = rand::thread_rng();
+        {
+            let dir2 = TempDir::new("storage_dir2").unwrap();
+            let mut storage2 = SimpleVectorStorage::open(dir2.path(), dim, distance).unwrap();
+            for _ in 0..300 {
+                let vector = (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect();
+                storage2.put_vector(vector).unwrap();
+            }
+            storage.update_from(&storage2).unwrap();
+        }
+
+        assert!(storage.compression_info().is_none());
+        storage.delete(5).unwrap();
+        storage.train().unwrap();
+
+        let info = storage.compression_info().unwrap();
+        assert_eq!(info.original_size_bytes, 300 * dim * 4);
+        assert!(info.compressed_size_bytes < info.original_size_bytes);
+        assert!(info.estimated_recall > 0.0 && info.estimated_recall <= 1.0);
+
+        let query: Vec<_> = (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        let res = storage.score_all(&query, 10);
+        assert_eq!(res.len(), 10);
+        assert!(res.iter().all(|x| x.idx != 5));
+
+        // Codes are persisted and loaded back
+        drop(storage);
+        let storage = PqVectorStorage::open(dir.path(), dim, distance, config).unwrap();
+        assert_eq!(storage.compression_info(), Some(info));
+        assert_eq!(storage.score_all(&query, 10), res);
+    }
+}
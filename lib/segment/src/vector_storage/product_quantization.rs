@@ -0,0 +1,274 @@
+use crate::types::{Distance, ScoreType, VectorElementType};
+use rand::seq::index::sample;
+use serde::{Deserialize, Serialize};
+use std::cmp::min;
+use std::ops::Range;
+
+/// Number of centroids per sub-vector. Centroid index is encoded with a single byte
+const CENTROIDS_COUNT: usize = 256;
+
+/// Number of k-means iterations used for codebook training
+const KMEANS_ITERATIONS: usize = 10;
+
+/// Max number of vectors used to train codebooks
+pub const TRAIN_SAMPLE_SIZE: usize = 4096;
+
+/// Trained codebooks of product quantization.
+///
+/// Vector is split into sub-vectors of `bucket_size` dimensions (the last one might be shorter),
+/// each sub-vector is replaced with the index of the closest centroid of the corresponding codebook.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ProductQuantizer {
+    dim: usize,
+    distance: Distance,
+    bucket_size: usize,
+    /// Centroids of each sub-vector, stored as a flat list of `bucket_len` sized chunks
+    codebooks: Vec<Vec<VectorElementType>>,
+}
+
+/// Precomputed similarities between query sub-vectors and all centroids,
+/// allows to score encoded vectors with a single lookup per sub-vector
+pub struct LookupTable {
+    distance: Distance,
+    table: Vec<Vec<ScoreType>>,
+}
+
+impl LookupTable {
+    pub fn score(&self, codes: &[u8]) -> ScoreType {
+        let sum: ScoreType = codes
+            .iter()
+            .zip(&self.table)
+            .map(|(code, similarities)| similarities[*code as usize])
+            .sum();
+        match self.distance {
+            Distance::Cosine | Distance::Dot => sum,
+            Distance::Euclid => -sum.sqrt(),
+        }
+    }
+}
+
+/// Partial similarity of two sub-vectors, summed up into the final score.
+/// For Euclid distance it is a squared distance, square root is taken over the whole sum
+fn partial_similarity(
+    distance: Distance,
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> ScoreType {
+    match distance {
+        Distance::Cosine | Distance::Dot => v1.iter().zip(v2).map(|(a, b)| a * b).sum(),
+        Distance::Euclid => v1.iter().zip(v2).map(|(a, b)| (a - b).powi(2)).sum(),
+    }
+}
+
+fn squared_distance(v1: &[VectorElementType], v2: &[VectorElementType]) -> ScoreType {
+    v1.iter().zip(v2).map(|(a, b)| (a - b).powi(2)).sum()
+}
+
+impl ProductQuantizer {
+    /// Train codebooks of each sub-vector with k-means on a given sample of (preprocessed) vectors
+    pub fn train(
+        dim: usize,
+        distance: Distance,
+        bucket_size: usize,
+        sample_vectors: &[Vec<VectorElementType>],
+    ) -> Self {
+        let bucket_size = min(bucket_size, dim);
+        let mut quantizer = ProductQuantizer {
+            dim,
+            distance,
+            bucket_size,
+            codebooks: vec![],
+        };
+
+        quantizer.codebooks = (0..quantizer.buckets_count())
+            .map(|bucket| {
+                let range = quantizer.bucket_range(bucket);
+                let sub_vectors: Vec<&[VectorElementType]> = sample_vectors
+                    .iter()
+                    .map(|vector| &vector[range.clone()])
+                    .collect();
+                Self::kmeans(&sub_vectors, range.len())
+            })
+            .collect();
+
+        quantizer
+    }
+
+    fn kmeans(points: &[&[VectorElementType]], dim: usize) -> Vec<VectorElementType> {
+        let centroids_count = min(CENTROIDS_COUNT, points.len());
+        if centroids_count == 0 {
+            return vec![];
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut centroids: Vec<VectorElementType> = sample(&mut rng, points.len(), centroids_count)
+            .into_iter()
+            .flat_map(|idx| points[idx].iter().cloned())
+            .collect();
+
+        let mut assignments = vec![0usize; points.len()];
+
+        for _ in 0..KMEANS_ITERATIONS {
+            for (point, assignment) in points.iter().zip(assignments.iter_mut()) {
+                *assignment = Self::closest_centroid(&centroids, dim, point);
+            }
+
+            let mut sums = vec![0.0; centroids.len()];
+            let mut counts = vec![0usize; centroids_count];
+            for (point, assignment) in points.iter().zip(&assignments) {
+                counts[*assignment] += 1;
+                for (sum, value) in sums[assignment * dim..(assignment + 1) * dim]
+                    .iter_mut()
+                    .zip(point.iter())
+                {
+                    *sum += value;
+                }
+            }
+
+            for (centroid_idx, count) in counts.into_iter().enumerate() {
+                // Keep previous position of the centroid if no points assigned to it
+                if count == 0 {
+                    continue;
+                }
+                let range = centroid_idx * dim..(centroid_idx + 1) * dim;
+                for (centroid_value, sum) in centroids[range.clone()].iter_mut().zip(&sums[range]) {
+                    *centroid_value = sum / count as VectorElementType;
+                }
+            }
+        }
+        centroids
+    }
+
+    fn closest_centroid(
+        centroids: &[VectorElementType],
+        dim: usize,
+        sub_vector: &[VectorElementType],
+    ) -> usize {
+        centroids
+            .chunks(dim)
+            .map(|centroid| squared_distance(centroid, sub_vector))
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .map(|(idx, _)| idx)
+            .unwrap_or(0)
+    }
+
+    /// Number of bytes used to encode a single vector
+    pub fn buckets_count(&self) -> usize {
+        (self.dim + self.bucket_size - 1) / self.bucket_size
+    }
+
+    fn bucket_range(&self, bucket: usize) -> Range<usize> {
+        let start = bucket * self.bucket_size;
+        start..min(start + self.bucket_size, self.dim)
+    }
+
+    /// Size of the codebooks in bytes
+    pub fn codebooks_size_bytes(&self) -> usize {
+        self.codebooks
+            .iter()
+            .map(|codebook| codebook.len())
+            .sum::<usize>()
+            * std::mem::size_of::<VectorElementType>()
+    }
+
+    pub fn encode(&self, vector: &[VectorElementType]) -> Vec<u8> {
+        (0..self.buckets_count())
+            .map(|bucket| {
+                let range = self.bucket_range(bucket);
+                let dim = range.len();
+                Self::closest_centroid(&self.codebooks[bucket], dim, &vector[range]) as u8
+            })
+            .collect()
+    }
+
+    fn centroid(&self, bucket: usize, code: u8) -> &[VectorElementType] {
+        let dim = self.bucket_range(bucket).len();
+        let start = code as usize * dim;
+        &self.codebooks[bucket][start..start + dim]
+    }
+
+    /// Build lookup table for asymmetric scoring of a (preprocessed) query with encoded vectors
+    pub fn lookup_table(&self, query: &[VectorElementType]) -> LookupTable {
+        let table = (0..self.buckets_count())
+            .map(|bucket| {
+                let range = self.bucket_range(bucket);
+                let dim = range.len();
+                let query_part = &query[range];
+                self.codebooks[bucket]
+                    .chunks(dim)
+                    .map(|centroid| partial_similarity(self.distance, query_part, centroid))
+                    .collect()
+            })
+            .collect();
+        LookupTable {
+            distance: self.distance,
+            table,
+        }
+    }
+
+    /// Approximate similarity between two encoded vectors
+    pub fn score_codes(&self, codes_a: &[u8], codes_b: &[u8]) -> ScoreType {
+        let sum: ScoreType = codes_a
+            .iter()
+            .zip(codes_b)
+            .enumerate()
+            .map(|(bucket, (a, b))| {
+                partial_similarity(
+                    self.distance,
+                    self.centroid(bucket, *a),
+                    self.centroid(bucket, *b),
+                )
+            })
+            .sum();
+        match self.distance {
+            Distance::Cosine | Distance::Dot => sum,
+            Distance::Euclid => -sum.sqrt(),
+        }
+    }
+
+    /// Restore approximate vector from its codes
+    pub fn decode(&self, codes: &[u8]) -> Vec<VectorElementType> {
+        codes
+            .iter()
+            .enumerate()
+            .flat_map(|(bucket, code)| self.centroid(bucket, *code).iter().cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_lookup_table_scoring() {
+        let dim = 10;
+        let mut rng = rand::thread_rng();
+        let vectors: Vec<Vec<VectorElementType>> = (0..500)
+            .map(|_| (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect())
+            .collect();
+
+        for distance in [Distance::Dot, Distance::Euclid] {
+            let quantizer = ProductQuantizer::train(dim, distance, 4, &vectors);
+            assert_eq!(quantizer.buckets_count(), 3);
+
+            let query = &vectors[0];
+            let table = quantizer.lookup_table(query);
+
+            for vector in vectors.iter().take(10) {
+                let codes = quantizer.encode(vector);
+                assert_eq!(codes.len(), 3);
+                let decoded = quantizer.decode(&codes);
+                assert_eq!(decoded.len(), dim);
+
+                let expected = match distance {
+                    Distance::Euclid => -squared_distance(query, &decoded).sqrt(),
+                    _ => partial_similarity(distance, query, &decoded),
+                };
+                assert!((table.score(&codes) - expected).abs() < 1e-4);
+            }
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use crate::common::file_operations::{atomic_save_bin, read_bin};
+use crate::entry::entry_point::OperationResult;
+use crate::spaces::metric::Metric;
+use crate::spaces::tools::peek_top_scores_iterable;
+use crate::types::{
+    Distance, PointOffsetType, ScalarQuantizationConfig, ScoreType, VectorElementType,
+};
+use crate::vector_storage::{RawScorer, ScoredPointOffset, VectorStorage};
+use serde::{Deserialize, Serialize};
+use std::cmp::max;
+use std::path::{Path, PathBuf};
+
+pub const QUANTIZED_VECTORS_FILE: &str = "quantized.bin";
+
+/// Max number of vectors used to estimate quantization bounds
+const QUANTILE_SAMPLE_SIZE: usize = 10_000;
+
+const QUANTIZATION_LEVELS: f32 = u8::MAX as f32;
+
+/// Scalar (int8) quantized copy of the vectors of some storage.
+///
+/// Each element is encoded as `offset + alpha * code`, where `code` is `u8`.
+/// Quantized vectors take 4 times less memory than original ones, but scores are approximate.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ScalarQuantizedVectors {
+    config: ScalarQuantizationConfig,
+    dim: usize,
+    distance: Distance,
+    offset: f32,
+    alpha: f32,
+    codes: Vec<u8>,
+}
+
+impl ScalarQuantizedVectors {
+    pub fn get_path(path: &Path) -> PathBuf {
+        path.join(QUANTIZED_VECTORS_FILE)
+    }
+
+    /// Estimate quantization bounds on the stored vectors and encode all of them
+    pub fn build(
+        storage: &dyn VectorStorage,
+        distance: Distance,
+        config: &ScalarQuantizationConfig,
+    ) -> Self {
+        let dim = storage.vector_dim();
+        let (lower, upper) = Self::estimate_bounds(storage, config.quantile());
+        let alpha = if upper > lower {
+            (upper - lower) / QUANTIZATION_LEVELS
+        } else {
+            1.0
+        };
+
+        let mut quantized = ScalarQuantizedVectors {
+            config: *config,
+            dim,
+            distance,
+            offset: lower,
+            alpha,
+            codes: vec![0; storage.total_vector_count() * dim],
+        };
+
+        for id in storage.iter_ids() {
+            let vector = storage.get_vector(id).unwrap();
+            quantized.set(id, &vector);
+        }
+        quantized
+    }
+
+    fn estimate_bounds(storage: &dyn VectorStorage, quantile: f32) -> (f32, f32) {
+        let step = max(1, storage.vector_count() / QUANTILE_SAMPLE_SIZE);
+        let mut values: Vec<VectorElementType> = storage
+            .iter_ids()
+            .step_by(step)
+            .take(QUANTILE_SAMPLE_SIZE)
+            .flat_map(|id| storage.get_vector(id).unwrap())
+            .collect();
+
+        if values.is_empty() {
+            return (0.0, 0.0);
+        }
+
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let quantile = quantile.clamp(0.0, 1.0);
+        let last = values.len() - 1;
+        let lower_idx = ((1.0 - quantile) / 2.0 * last as f32) as usize;
+        let upper_idx = last - lower_idx;
+        (values[lower_idx], values[upper_idx])
+    }
+
+    pub fn load(path: &Path) -> OperationResult<Self> {
+        read_bin(path)
+    }
+
+    pub fn save(&self, path: &Path) -> OperationResult<()> {
+        atomic_save_bin(path, self)
+    }
+
+    pub fn config(&self) -> &ScalarQuantizationConfig {
+        &self.config
+    }
+
+    /// Size of the quantized data in bytes
+    pub fn size_bytes(&self) -> usize {
+        self.codes.len()
+    }
+
+    fn encode(&self, value: VectorElementType) -> u8 {
+        ((value - self.offset) / self.alpha)
+            .round()
+            .clamp(0.0, QUANTIZATION_LEVELS) as u8
+    }
+
+    fn decode(&self, code: u8) -> VectorElementType {
+        self.offset + self.alpha * code as f32
+    }
+
+    /// Encode and store vector under the given key, storage is extended if required
+    pub fn set(&mut self, key: PointOffsetType, vector: &[VectorElementType]) {
+        let start = key as usize * self.dim;
+        if self.codes.len() < start + self.dim {
+            self.codes.resize(start + self.dim, 0);
+        }
+        for (idx, value) in vector.iter().enumerate() {
+            self.codes[start + idx] = self.encode(*value);
+        }
+    }
+
+    fn codes(&self, key: PointOffsetType) -> &[u8] {
+        let start = key as usize * self.dim;
+        &self.codes[start..start + self.dim]
+    }
+
+    /// Approximate similarity between original (preprocessed) query and quantized stored vector
+    pub fn score(&self, query: &[VectorElementType], key: PointOffsetType) -> ScoreType {
+        let codes = self.codes(key);
+        match self.distance {
+            Distance::Cosine | Distance::Dot => query
+                .iter()
+                .zip(codes)
+                .map(|(q, c)| q * self.decode(*c))
+                .sum(),
+            Distance::Euclid => {
+                let s: ScoreType = query
+                    .iter()
+                    .zip(codes)
+                    .map(|(q, c)| (q - self.decode(*c)).powi(2))
+                    .sum();
+                -s.sqrt()
+            }
+        }
+    }
+
+    /// Approximate similarity between two quantized stored vectors
+    pub fn score_internal(&self, key_a: PointOffsetType, key_b: PointOffsetType) -> ScoreType {
+        let codes_a = self.codes(key_a);
+        let codes_b = self.codes(key_b);
+        match self.distance {
+            Distance::Cosine | Distance::Dot => codes_a
+                .iter()
+                .zip(codes_b)
+                .map(|(a, b)| self.decode(*a) * self.decode(*b))
+                .sum(),
+            Distance::Euclid => {
+                let s: ScoreType = codes_a
+                    .iter()
+                    .zip(codes_b)
+                    .map(|(a, b)| self.alpha * (*a as f32 - *b as f32))
+                    .map(|diff| diff.powi(2))
+                    .sum();
+                -s.sqrt()
+            }
+        }
+    }
+
+    /// Return the best `top` candidates among `points` by approximate score
+    pub fn score_points(
+        &self,
+        query: &[VectorElementType],
+        points: &mut dyn Iterator<Item = PointOffsetType>,
+        top: usize,
+    ) -> Vec<ScoredPointOffset> {
+        let scores = points.map(|idx| ScoredPointOffset {
+            idx,
+            score: self.score(query, idx),
+        });
+        peek_top_scores_iterable(scores, top)
+    }
+}
+
+/// Scorer which uses quantized vectors only.
+/// Storage is still used to check if the point is deleted.
+pub struct QuantizedRawScorer<'a> {
+    pub query: Vec<VectorElementType>,
+    pub quantized: &'a ScalarQuantizedVectors,
+    pub storage: &'a dyn VectorStorage,
+}
+
+impl<'a> QuantizedRawScorer<'a> {
+    pub fn new(
+        query: &[VectorElementType],
+        metric: &dyn Metric,
+        quantized: &'a ScalarQuantizedVectors,
+        storage: &'a dyn VectorStorage,
+    ) -> Self {
+        QuantizedRawScorer {
+            query: metric.preprocess(query).unwrap_or_else(|| query.to_owned()),
+            quantized,
+            storage,
+        }
+    }
+}
+
+impl RawScorer for QuantizedRawScorer<'_> {
+    fn score_points<'a>(
+        &'a self,
+        points: &'a mut dyn Iterator<Item = PointOffsetType>,
+    ) -> Box<dyn Iterator<Item = ScoredPointOffset> + 'a> {
+        let res_iter = points
+            .filter(move |point| !self.storage.is_deleted(*point))
+            .map(move |point| ScoredPointOffset {
+                idx: point,
+                score: self.quantized.score(&self.query, point),
+            });
+        Box::new(res_iter)
+    }
+
+    fn check_point(&self, point: PointOffsetType) -> bool {
+        (point < self.storage.total_vector_count() as PointOffsetType)
+            && !self.storage.is_deleted(point)
+    }
+
+    fn score_point(&self, point: PointOffsetType) -> ScoreType {
+        self.quantized.score(&self.query, point)
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        self.quantized.score_internal(point_a, point_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spaces::tools::mertic_object;
+    use crate::vector_storage::simple_vector_storage::SimpleVectorStorage;
+    use rand::Rng;
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_quantized_scores_close_to_original() {
+        let dir = TempDir::new("storage_dir").unwrap();
+        let distance = Distance::Dot;
+        let dim = 16;
+        let num_vectors = 100;
+        let mut rng = rand::thread_rng();
+        let mut storage = SimpleVectorStorage::open(dir.path(), dim, distance).unwrap();
+
+        for _ in 0..num_vectors {
+            let vector: Vec<_> = (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect();
+            storage.put_vector(vector).unwrap();
+        }
+
+        let config = ScalarQuantizationConfig {
+            quantile: Some(1.0),
+            rescore_multiplier: None,
+        };
+        let quantized = ScalarQuantizedVectors::build(&storage, distance, &config);
+        assert_eq!(quantized.size_bytes(), num_vectors * dim);
+
+        let metric = mertic_object(&distance);
+        let query: Vec<_> = (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect();
+
+        for id in storage.iter_ids() {
+            let original = metric.similarity(&query, &storage.get_vector(id).unwrap());
+            let approximate = quantized.score(&query, id);
+            assert!((original - approximate).abs() < 0.1);
+        }
+
+        let path = ScalarQuantizedVectors::get_path(dir.path());
+        quantized.save(&path).unwrap();
+        let loaded = ScalarQuantizedVectors::load(&path).unwrap();
+        assert_eq!(loaded.score(&query, 10), quantized.score(&query, 10));
+    }
+}
@@ -1,5 +1,5 @@
 use std::ops::Range;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use log::debug;
 use rocksdb::{IteratorMode, Options, DB};
@@ -7,7 +7,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::entry::entry_point::OperationResult;
 use crate::spaces::tools::{mertic_object, peek_top_scores_iterable};
-use crate::types::{Distance, PointOffsetType, ScoreType, VectorElementType};
+use crate::types::{
+    Distance, PointOffsetType, ScalarQuantizationConfig, ScoreType, VectorElementType,
+};
+use crate::vector_storage::quantized_vectors::{QuantizedRawScorer, ScalarQuantizedVectors};
 use crate::vector_storage::{RawScorer, ScoredPointOffset};
 
 use super::vector_storage_base::VectorStorage;
@@ -27,6 +30,8 @@ pub struct SimpleVectorStorage {
     deleted: BitVec,
     deleted_count: usize,
     store: DB,
+    quantized_path: PathBuf,
+    quantized_vectors: Option<ScalarQuantizedVectors>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -107,6 +112,18 @@ impl SimpleVectorStorage {
 
         let metric = mertic_object(&distance);
 
+        let quantized_path = ScalarQuantizedVectors::get_path(path);
+        let quantized_vectors = if quantized_path.exists() {
+            let mut quantized = ScalarQuantizedVectors::load(&quantized_path)?;
+            // Vectors might be updated after the quantization, re-encode them with stored bounds
+            for (point_id, vector) in vectors.iter().enumerate() {
+                quantized.set(point_id as PointOffsetType, vector.as_slice().unwrap());
+            }
+            Some(quantized)
+        } else {
+            None
+        };
+
         debug!("Segment vectors: {}", vectors.len());
         debug!(
             "Estimated segment size {} MB",
@@ -120,12 +137,18 @@ impl SimpleVectorStorage {
             deleted,
             deleted_count,
             store,
+            quantized_path,
+            quantized_vectors,
         })
     }
 
-    fn update_stored(&self, point_id: PointOffsetType) -> OperationResult<()> {
+    fn update_stored(&mut self, point_id: PointOffsetType) -> OperationResult<()> {
         let v = self.vectors.get(point_id as usize).unwrap();
 
+        if let Some(quantized) = &mut self.quantized_vectors {
+            quantized.set(point_id, v.as_slice().unwrap());
+        }
+
         let record = StoredRecord {
             deleted: self.deleted[point_id as usize],
             vector: v.to_vec(), // ToDo: try to reduce number of vector copies
@@ -227,6 +250,14 @@ impl VectorStorage for SimpleVectorStorage {
     }
 
     fn raw_scorer(&self, vector: Vec<VectorElementType>) -> Box<dyn RawScorer + '_> {
+        if let Some(quantized) = &self.quantized_vectors {
+            return Box::new(QuantizedRawScorer::new(
+                &vector,
+                self.metric.as_ref(),
+                quantized,
+                self,
+            ));
+        }
         Box::new(SimpleRawScorer {
             query: Array::from(self.metric.preprocess(&vector).unwrap_or(vector)),
             metric: self.metric.as_ref(),
@@ -236,6 +267,14 @@ impl VectorStorage for SimpleVectorStorage {
     }
 
     fn raw_scorer_internal(&self, point_id: PointOffsetType) -> Box<dyn RawScorer + '_> {
+        if let Some(quantized) = &self.quantized_vectors {
+            return Box::new(QuantizedRawScorer::new(
+                self.vectors[point_id as usize].as_slice().unwrap(),
+                self.metric.as_ref(),
+                quantized,
+                self,
+            ));
+        }
         Box::new(SimpleRawScorer {
             query: self.vectors[point_id as usize].clone(),
             metric: self.metric.as_ref(),
@@ -275,6 +314,14 @@ impl VectorStorage for SimpleVectorStorage {
                 .preprocess(vector)
                 .unwrap_or_else(|| vector.to_owned()),
         );
+        if let Some(quantized) = &self.quantized_vectors {
+            let candidates = quantized.score_points(
+                preprocessed_vector.as_slice().unwrap(),
+                &mut self.iter_ids(),
+                quantized.config().rescore_limit(top),
+            );
+            return self.score_points(vector, &mut candidates.iter().map(|x| x.idx), top);
+        }
         let scores = self
             .vectors
             .iter()
@@ -298,6 +345,19 @@ impl VectorStorage for SimpleVectorStorage {
         let vector = self.get_vector(point).unwrap();
         self.score_points(&vector, points, top)
     }
+
+    fn quantize(&mut self, config: &ScalarQuantizationConfig) -> OperationResult<()> {
+        let quantized = ScalarQuantizedVectors::build(self, self.metric.distance(), config);
+        quantized.save(&self.quantized_path)?;
+        self.quantized_vectors = Some(quantized);
+        Ok(())
+    }
+
+    fn quantization_config(&self) -> Option<ScalarQuantizationConfig> {
+        self.quantized_vectors
+            .as_ref()
+            .map(|quantized| *quantized.config())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,279 @@
+use std::ops::Range;
+use std::path::Path;
+
+use bit_vec::BitVec;
+use log::debug;
+use rocksdb::{IteratorMode, Options, DB};
+use serde::{Deserialize, Serialize};
+
+use crate::entry::entry_point::OperationResult;
+use crate::index::sparse_index::inverted_index::InvertedIndex;
+use crate::spaces::tools::peek_top_scores_iterable;
+use crate::types::{PointOffsetType, SparseVector};
+use crate::vector_storage::{PointsStorage, ScoredPointOffset};
+
+/// Since rocksdb is used for reading only during the initialization, large read cache is not required
+const DB_CACHE_SIZE: usize = 10 * 1024 * 1024; // 10 mb
+
+/// In-memory storage of sparse vectors with on-update persistence using `store`.
+///
+/// Keeps an inverted index of the stored vectors up to date, so it could be used for search
+/// in appendable segments as well. Vectors are kept sorted by dimension index.
+pub struct SparseVectorStorage {
+    vectors: Vec<SparseVector>,
+    deleted: BitVec,
+    deleted_count: usize,
+    inverted_index: InvertedIndex,
+    store: DB,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+struct StoredSparseRecord {
+    pub deleted: bool,
+    pub vector: SparseVector,
+}
+
+impl SparseVectorStorage {
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        let mut vectors: Vec<SparseVector> = vec![];
+        let mut deleted = BitVec::new();
+        let mut deleted_count = 0;
+
+        let mut options: Options = Options::default();
+        options.set_write_buffer_size(DB_CACHE_SIZE);
+        options.create_if_missing(true);
+
+        let store = DB::open(&options, path)?;
+
+        for (key, val) in store.iterator(IteratorMode::Start) {
+            let point_id: PointOffsetType = bincode::deserialize(&key).unwrap();
+            let stored_record: StoredSparseRecord = bincode::deserialize(&val).unwrap();
+            if stored_record.deleted {
+                deleted_count += 1;
+            }
+
+            if vectors.len() <= (point_id as usize) {
+                vectors.resize((point_id + 1) as usize, SparseVector::default());
+            }
+            while deleted.len() <= (point_id as usize) {
+                deleted.push(false)
+            }
+
+            deleted.set(point_id as usize, stored_record.deleted);
+            vectors[point_id as usize] = stored_record.vector;
+        }
+
+        let mut inverted_index = InvertedIndex::default();
+        for (point_id, vector) in vectors.iter().enumerate() {
+            if !deleted[point_id] {
+                inverted_index.insert(point_id as PointOffsetType, vector);
+            }
+        }
+
+        debug!("Segment sparse vectors: {}", vectors.len());
+
+        Ok(SparseVectorStorage {
+            vectors,
+            deleted,
+            deleted_count,
+            inverted_index,
+            store,
+        })
+    }
+
+    fn update_stored(&mut self, point_id: PointOffsetType) -> OperationResult<()> {
+        let record = StoredSparseRecord {
+            deleted: self.deleted[point_id as usize],
+            vector: self.vectors[point_id as usize].clone(),
+        };
+        self.store.put(
+            bincode::serialize(&point_id).unwrap(),
+            bincode::serialize(&record).unwrap(),
+        )?;
+        Ok(())
+    }
+
+    pub fn get_vector(&self, key: PointOffsetType) -> Option<SparseVector> {
+        if self.deleted.get(key as usize).unwrap_or(true) {
+            return None;
+        }
+        self.vectors.get(key as usize).cloned()
+    }
+
+    pub fn put_vector(&mut self, vector: SparseVector) -> OperationResult<PointOffsetType> {
+        let vector = vector.sorted();
+        let new_id = self.vectors.len() as PointOffsetType;
+        self.inverted_index.insert(new_id, &vector);
+        self.vectors.push(vector);
+        self.deleted.push(false);
+        self.update_stored(new_id)?;
+        Ok(new_id)
+    }
+
+    pub fn update_vector(
+        &mut self,
+        key: PointOffsetType,
+        vector: SparseVector,
+    ) -> OperationResult<PointOffsetType> {
+        let vector = vector.sorted();
+        self.inverted_index.remove(key, &self.vectors[key as usize]);
+        self.inverted_index.insert(key, &vector);
+        self.vectors[key as usize] = vector;
+        self.update_stored(key)?;
+        Ok(key)
+    }
+
+    pub fn update_from(
+        &mut self,
+        other: &SparseVectorStorage,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let start_index = self.vectors.len() as PointOffsetType;
+        for id in other.iter_ids() {
+            self.put_vector(other.vectors[id as usize].clone())?;
+        }
+        let end_index = self.vectors.len() as PointOffsetType;
+        Ok(start_index..end_index)
+    }
+
+    pub fn delete(&mut self, key: PointOffsetType) -> OperationResult<()> {
+        if (key as usize) >= self.deleted.len() {
+            return Ok(());
+        }
+        if !self.deleted[key as usize] {
+            self.deleted_count += 1;
+            self.inverted_index.remove(key, &self.vectors[key as usize]);
+        }
+        self.deleted.set(key as usize, true);
+        self.update_stored(key)?;
+        Ok(())
+    }
+
+    pub fn flush(&self) -> OperationResult<()> {
+        Ok(self.store.flush()?)
+    }
+
+    /// Score given points with the query directly, without the inverted index
+    pub fn score_points(
+        &self,
+        query: &SparseVector,
+        points: &mut dyn Iterator<Item = PointOffsetType>,
+        top: usize,
+    ) -> Vec<ScoredPointOffset> {
+        let query = query.sorted();
+        let scores = points
+            .filter(|point| !self.is_deleted(*point))
+            .map(|point| ScoredPointOffset {
+                idx: point,
+                score: self.vectors[point as usize].dot(&query),
+            });
+        peek_top_scores_iterable(scores, top)
+    }
+
+    /// Score all points, which share at least one dimension with the query, using inverted index.
+    /// Only points accepted by `check` are returned
+    pub fn score_all<F>(&self, query: &SparseVector, top: usize, check: F) -> Vec<ScoredPointOffset>
+    where
+        F: Fn(PointOffsetType) -> bool,
+    {
+        let scores = self
+            .inverted_index
+            .score_candidates(query)
+            .into_iter()
+            .filter(|(point, _)| check(*point))
+            .map(|(idx, score)| ScoredPointOffset { idx, score });
+        peek_top_scores_iterable(scores, top)
+    }
+}
+
+impl PointsStorage for SparseVectorStorage {
+    fn vector_count(&self) -> usize {
+        self.vectors.len() - self.deleted_count
+    }
+
+    fn deleted_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.vectors.len()
+    }
+
+    fn is_deleted(&self, key: PointOffsetType) -> bool {
+        self.deleted.get(key as usize).unwrap_or(true)
+    }
+
+    fn iter_ids(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        let iter = (0..self.vectors.len() as PointOffsetType)
+            .filter(move |id| !self.deleted[*id as usize]);
+        Box::new(iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempdir::TempDir;
+
+    #[test]
+    fn test_sparse_storage_search() {
+        let dir = TempDir::new("storage_dir").unwrap();
+        let vectors = vec![
+            SparseVector {
+                indices: vec![5, 1],
+                values: vec![1.0, 1.0],
+            },
+            SparseVector {
+                indices: vec![1, 2],
+                values: vec![2.0, 1.0],
+            },
+            SparseVector {
+                indices: vec![3],
+                values: vec![1.0],
+            },
+        ];
+
+        {
+            let mut storage = SparseVectorStorage::open(dir.path()).unwrap();
+            for vector in &vectors {
+                storage.put_vector(vector.clone()).unwrap();
+            }
+            storage.delete(0).unwrap();
+            storage.flush().unwrap();
+        }
+
+        let mut storage = SparseVectorStorage::open(dir.path()).unwrap();
+        assert_eq!(storage.vector_count(), 2);
+        assert_eq!(storage.get_vector(1), Some(vectors[1].clone()));
+        assert_eq!(storage.get_vector(0), None);
+        assert!(storage.is_deleted(0));
+        assert!(!storage.is_deleted(1));
+        // Ids out of the storage are not stored, so considered deleted
+        assert!(storage.is_deleted(100));
+
+        let query = SparseVector {
+            indices: vec![1, 3],
+            values: vec![1.0, 3.0],
+        };
+        let result = storage.score_all(&query, 10, |_| true);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].idx, 2);
+        assert_eq!(result[0].score, 3.0);
+        assert_eq!(result[1].idx, 1);
+
+        let direct = storage.score_points(&query, &mut [0, 1, 2].iter().cloned(), 10);
+        assert_eq!(direct, result);
+
+        storage
+            .update_vector(
+                2,
+                SparseVector {
+                    indices: vec![7],
+                    values: vec![1.0],
+                },
+            )
+            .unwrap();
+        let result = storage.score_all(&query, 10, |_| true);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].idx, 1);
+    }
+}
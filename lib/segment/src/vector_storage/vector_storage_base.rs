@@ -1,5 +1,7 @@
 use crate::entry::entry_point::OperationResult;
-use crate::types::{PointOffsetType, ScoreType, VectorElementType};
+use crate::types::{
+    PointOffsetType, ScalarQuantizationConfig, ScoreType, VectorCompressionInfo, VectorElementType,
+};
 use ordered_float::OrderedFloat;
 use rand::Rng;
 use std::cmp::Ordering;
@@ -88,6 +90,24 @@ pub trait VectorStorage {
         top: usize,
     ) -> Vec<ScoredPointOffset>;
 
+    /// Build quantized copy of the stored vectors.
+    /// Once built, `raw_scorer` and `score_all` use quantized vectors for approximate scoring
+    /// and original vectors are only used to rescore the best candidates.
+    fn quantize(&mut self, config: &ScalarQuantizationConfig) -> OperationResult<()>;
+    /// Quantization config of the storage, `None` if vectors are not quantized
+    fn quantization_config(&self) -> Option<ScalarQuantizationConfig>;
+
+    /// Train internal models of the storage (like compression codebooks) on the stored vectors.
+    /// Should be called once all vectors are loaded into the storage.
+    fn train(&mut self) -> OperationResult<()> {
+        Ok(())
+    }
+
+    /// Information about compression of the stored vectors, `None` if storage is not compressed
+    fn compression_info(&self) -> Option<VectorCompressionInfo> {
+        None
+    }
+
     /// Iterator over `n` random ids which are not deleted
     fn sample_ids(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         let total = self.total_vector_count() as PointOffsetType;
@@ -100,6 +120,42 @@ pub trait VectorStorage {
     }
 }
 
+/// Common part of all storages, which assign internal ids to points: dense and sparse ones.
+/// Used by components which only need to iterate over stored points, like payload index.
+pub trait PointsStorage {
+    /// Number of searchable vectors (not deleted)
+    fn vector_count(&self) -> usize;
+    /// Number of vectors, marked as deleted but still stored
+    fn deleted_count(&self) -> usize;
+    /// Number of all stored vectors including deleted
+    fn total_vector_count(&self) -> usize;
+    fn is_deleted(&self, key: PointOffsetType) -> bool;
+    /// Iterator over not-deleted ids
+    fn iter_ids(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_>;
+}
+
+impl<T: VectorStorage> PointsStorage for T {
+    fn vector_count(&self) -> usize {
+        VectorStorage::vector_count(self)
+    }
+
+    fn deleted_count(&self) -> usize {
+        VectorStorage::deleted_count(self)
+    }
+
+    fn total_vector_count(&self) -> usize {
+        VectorStorage::total_vector_count(self)
+    }
+
+    fn is_deleted(&self, key: PointOffsetType) -> bool {
+        VectorStorage::is_deleted(self, key)
+    }
+
+    fn iter_ids(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        VectorStorage::iter_ids(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
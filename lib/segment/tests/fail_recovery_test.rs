@@ -15,12 +15,12 @@ mod tests {
 
         let mut segment = empty_segment(dir.path());
 
-        segment.upsert_point(1, 1, &vec1).unwrap();
-        segment.upsert_point(1, 2, &vec1).unwrap();
+        segment.upsert_point(1, 1.into(), &vec1).unwrap();
+        segment.upsert_point(1, 2.into(), &vec1).unwrap();
 
         segment.error_status = Some(SegmentFailedState {
             version: 2,
-            point_id: Some(1),
+            point_id: Some(1.into()),
             error: OperationError::ServiceError {
                 description: "test error".to_string(),
             },
@@ -29,7 +29,7 @@ mod tests {
         // op_num is greater than errored. Skip because not recovered yet
         let fail_res = segment.set_payload(
             3,
-            1,
+            1.into(),
             &"color".to_string(),
             PayloadType::Keyword(vec!["red".to_string()]),
         );
@@ -38,7 +38,7 @@ mod tests {
         // Also skip even with another point operation
         let fail_res = segment.set_payload(
             3,
-            2,
+            2.into(),
             &"color".to_string(),
             PayloadType::Keyword(vec!["red".to_string()]),
         );
@@ -47,7 +47,7 @@ mod tests {
         // Perform operation, but keep error status: operation is not fully recovered yet
         let ok_res = segment.set_payload(
             2,
-            2,
+            2.into(),
             &"color".to_string(),
             PayloadType::Keyword(vec!["red".to_string()]),
         );
@@ -57,7 +57,7 @@ mod tests {
         // Perform operation anf recover the error - operation is fixed now
         let recover_res = segment.set_payload(
             2,
-            1,
+            1.into(),
             &"color".to_string(),
             PayloadType::Keyword(vec!["red".to_string()]),
         );
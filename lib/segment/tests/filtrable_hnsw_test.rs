@@ -11,9 +11,10 @@ mod tests {
     use segment::segment_constructor::build_segment;
     use segment::types::{
         Condition, Distance, FieldCondition, Filter, HnswConfig, Indexes, PayloadIndexType,
-        PayloadKeyType, PayloadType, PointIdType, Range, SearchParams, SegmentConfig,
-        SeqNumberType, StorageType, TheMap,
+        PayloadKeyType, PayloadType, Range, RangeValue, SearchParams, SegmentConfig, SeqNumberType,
+        StorageType, TheMap, VectorDataConfig, DEFAULT_VECTOR_NAME,
     };
+    use std::collections::HashMap;
     use std::sync::Arc;
     use tempdir::TempDir;
 
@@ -21,7 +22,7 @@ mod tests {
     fn test_filterable_hnsw() {
         let dim = 8;
         let m = 8;
-        let num_vectors: PointIdType = 5_000;
+        let num_vectors: u64 = 5_000;
         let ef = 32;
         let ef_construct = 16;
         let distance = Distance::Cosine;
@@ -35,11 +36,19 @@ mod tests {
         let hnsw_dir = TempDir::new("hnsw_dir").unwrap();
 
         let config = SegmentConfig {
-            vector_size: dim,
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
             index: Indexes::Plain {},
             payload_index: Some(PayloadIndexType::Plain),
             storage_type: StorageType::InMemory,
-            distance,
+            quantization_config: None,
         };
 
         let int_key = "int".to_string();
@@ -54,17 +63,17 @@ mod tests {
             );
 
             segment
-                .upsert_point(idx as SeqNumberType, idx, &vector)
+                .upsert_point(idx as SeqNumberType, idx.into(), &vector)
                 .unwrap();
             segment
-                .set_full_payload(idx as SeqNumberType, idx, payload.clone())
+                .set_full_payload(idx as SeqNumberType, idx.into(), payload.clone())
                 .unwrap();
         }
         // let opnum = num_vectors + 1;
 
         let payload_index = StructPayloadIndex::open(
             segment.condition_checker.clone(),
-            segment.vector_storage.clone(),
+            segment.points_storage.clone(),
             segment.payload_storage.clone(),
             segment.id_tracker.clone(),
             payload_index_dir.path(),
@@ -82,7 +91,9 @@ mod tests {
         let mut hnsw_index = HNSWIndex::open(
             hnsw_dir.path(),
             segment.condition_checker.clone(),
-            segment.vector_storage.clone(),
+            segment.vector_data[DEFAULT_VECTOR_NAME]
+                .vector_storage
+                .clone(),
             payload_index_ptr.clone(),
             hnsw_config,
         )
@@ -92,7 +103,7 @@ mod tests {
 
         payload_index_ptr
             .borrow_mut()
-            .set_indexed(&int_key)
+            .set_indexed(&int_key, None)
             .unwrap();
         let borrowed_payload_index = payload_index_ptr.borrow();
         let blocks = borrowed_payload_index
@@ -125,11 +136,13 @@ mod tests {
                 range: Some(Range {
                     lt: None,
                     gt: None,
-                    gte: Some(left_range as f64),
-                    lte: Some(right_range as f64),
+                    gte: Some(RangeValue::Integer(left_range)),
+                    lte: Some(RangeValue::Integer(right_range)),
                 }),
                 geo_bounding_box: None,
                 geo_radius: None,
+                geo_polygon: None,
+                values_count: None,
             }));
 
             let filter_query = Some(&filter);
@@ -142,11 +155,10 @@ mod tests {
                 Some(&SearchParams { hnsw_ef: Some(ef) }),
             );
 
-            let plain_result =
-                segment
-                    .vector_index
-                    .borrow()
-                    .search(&query, filter_query, top, None);
+            let plain_result = segment.vector_data[DEFAULT_VECTOR_NAME]
+                .vector_index
+                .borrow()
+                .search(&query, filter_query, top, None);
 
             if plain_result == index_result {
                 hits += 1;
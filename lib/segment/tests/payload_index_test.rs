@@ -1,15 +1,21 @@
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
+    use rand::Rng;
     use segment::entry::entry_point::SegmentEntry;
     use segment::fixtures::payload_fixtures::{
-        random_filter, random_int_payload, random_keyword_payload, random_vector,
+        random_filter, random_int_payload, random_keyword, random_keyword_payload, random_vector,
     };
     use segment::segment_constructor::build_segment;
     use segment::types::{
-        Condition, Distance, FieldCondition, Filter, Indexes, PayloadIndexType, PayloadKeyType,
-        PayloadType, Range, SegmentConfig, StorageType, TheMap, WithPayload,
+        parse_datetime, AnyVariants, Condition, Direction, Distance, FieldCondition, Filter,
+        GeoBoundingBox, GeoLineString, GeoPoint, GeoPolygon, GeoRadius, HasFieldCondition, Indexes,
+        IsEmptyCondition, Match, OrderBy, PayloadField, PayloadIndexParams, PayloadIndexType,
+        PayloadKeyType, PayloadType, PointIdType, Range, RangeValue, SegmentConfig, StorageType,
+        TextIndexParams, TheMap, ValuesCount, VectorDataConfig, WithPayload, DEFAULT_VECTOR_NAME,
     };
+    use std::cmp::Ordering;
+    use std::collections::HashMap;
     use tempdir::TempDir;
 
     #[test]
@@ -20,17 +26,25 @@ mod tests {
         let dim = 5;
 
         let config = SegmentConfig {
-            vector_size: dim,
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
             index: Indexes::Plain {},
             payload_index: Some(PayloadIndexType::Struct),
             storage_type: StorageType::InMemory,
-            distance: Distance::Dot,
+            quantization_config: None,
         };
 
         let str_key = "kvd".to_string();
         let int_key = "int".to_string();
 
-        let num_points = 10000;
+        let num_points: u64 = 10000;
         let mut struct_segment = build_segment(dir1.path(), &config).unwrap();
 
         let mut opnum = 0;
@@ -40,16 +54,22 @@ mod tests {
             payload.insert(str_key.clone(), random_keyword_payload(&mut rnd));
             payload.insert(int_key.clone(), random_int_payload(&mut rnd, 2));
 
-            struct_segment.upsert_point(opnum, idx, &vector).unwrap();
             struct_segment
-                .set_full_payload(opnum, idx, payload.clone())
+                .upsert_point(opnum, idx.into(), &vector)
+                .unwrap();
+            struct_segment
+                .set_full_payload(opnum, idx.into(), payload.clone())
                 .unwrap();
 
             opnum += 1;
         }
 
-        struct_segment.create_field_index(opnum, &str_key).unwrap();
-        struct_segment.create_field_index(opnum, &int_key).unwrap();
+        struct_segment
+            .create_field_index(opnum, &str_key, None)
+            .unwrap();
+        struct_segment
+            .create_field_index(opnum, &int_key, None)
+            .unwrap();
 
         let filter = Filter::new_must(Condition::Field(FieldCondition {
             key: int_key,
@@ -57,11 +77,13 @@ mod tests {
             range: Some(Range {
                 lt: None,
                 gt: None,
-                gte: Some(50.),
-                lte: Some(100.),
+                gte: Some(RangeValue::Integer(50)),
+                lte: Some(RangeValue::Integer(100)),
             }),
             geo_bounding_box: None,
             geo_radius: None,
+            geo_polygon: None,
+            values_count: None,
         }));
 
         let estimation = struct_segment
@@ -70,7 +92,7 @@ mod tests {
             .estimate_cardinality(&filter);
 
         let exact = struct_segment
-            .vector_storage
+            .points_storage
             .borrow()
             .iter_ids()
             .filter(|x| struct_segment.condition_checker.check(*x, &filter))
@@ -84,6 +106,101 @@ mod tests {
         assert!(exact >= estimation.min);
     }
 
+    #[test]
+    fn test_ordered_read() {
+        // Compare pagination in the order of the field with plain and struct indexes
+        let mut rnd = rand::thread_rng();
+
+        let dir1 = TempDir::new("segment1_dir").unwrap();
+        let dir2 = TempDir::new("segment2_dir").unwrap();
+        let dim = 5;
+
+        let mut config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
+            index: Indexes::Plain {},
+            payload_index: Some(PayloadIndexType::Plain),
+            storage_type: StorageType::InMemory,
+            quantization_config: None,
+        };
+
+        let mut plain_segment = build_segment(dir1.path(), &config).unwrap();
+        config.payload_index = Some(PayloadIndexType::Struct);
+        let mut struct_segment = build_segment(dir2.path(), &config).unwrap();
+
+        let str_key = "kvd".to_string();
+        let int_key = "int".to_string();
+
+        let num_points = 300;
+        for idx in 0..num_points {
+            let vector = random_vector(&mut rnd, dim);
+            let mut payload: TheMap<PayloadKeyType, PayloadType> = Default::default();
+            payload.insert(str_key.clone(), random_keyword_payload(&mut rnd));
+            // Some points have no values of the field
+            if idx % 10 != 0 {
+                payload.insert(int_key.clone(), random_int_payload(&mut rnd, 2));
+            }
+
+            for segment in [&mut plain_segment, &mut struct_segment] {
+                segment.upsert_point(idx, idx.into(), &vector).unwrap();
+                segment
+                    .set_full_payload(idx, idx.into(), payload.clone())
+                    .unwrap();
+            }
+        }
+        struct_segment
+            .create_field_index(num_points, &int_key, None)
+            .unwrap();
+
+        let read_all = |segment: &dyn SegmentEntry, order_by: &OrderBy, filter: Option<&Filter>| {
+            let limit = 7;
+            let mut order_by = order_by.clone();
+            let mut offset = PointIdType::MIN;
+            let mut points = vec![];
+            loop {
+                let mut page = segment.read_ordered_filtered(offset, limit + 1, filter, &order_by);
+                if page.len() <= limit {
+                    points.append(&mut page);
+                    return points;
+                }
+                let (next_value, next_offset) = page.pop().unwrap();
+                points.append(&mut page);
+                order_by.start_from = Some(next_value);
+                offset = next_offset;
+            }
+        };
+
+        for direction in [Direction::Asc, Direction::Desc] {
+            let order_by = OrderBy {
+                key: int_key.clone(),
+                direction,
+                start_from: None,
+            };
+            let filters = [None, Some(random_filter(&mut rnd))];
+            for filter in filters.iter().map(Option::as_ref) {
+                let plain_points = read_all(&plain_segment, &order_by, filter);
+                let struct_points = read_all(&struct_segment, &order_by, filter);
+                assert_eq!(plain_points, struct_points);
+
+                let unique_ids = struct_points.iter().map(|(_, id)| *id).unique().count();
+                assert_eq!(unique_ids, struct_points.len());
+                assert!(struct_points
+                    .windows(2)
+                    .all(|pair| order_by.cmp_values(&pair[0].0, &pair[1].0) != Ordering::Greater));
+                if filter.is_none() {
+                    assert_eq!(struct_points.len(), 270);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_struct_payload_index() {
         // Compare search with plain and struct indexes
@@ -95,11 +212,19 @@ mod tests {
         let dim = 5;
 
         let mut config = SegmentConfig {
-            vector_size: dim,
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
             index: Indexes::Plain {},
             payload_index: Some(PayloadIndexType::Plain),
             storage_type: StorageType::InMemory,
-            distance: Distance::Dot,
+            quantization_config: None,
         };
 
         let mut plain_segment = build_segment(dir1.path(), &config).unwrap();
@@ -122,21 +247,29 @@ mod tests {
                 random_int_payload(&mut rnd, num_int_values),
             );
 
-            plain_segment.upsert_point(idx, idx, &vector).unwrap();
-            struct_segment.upsert_point(idx, idx, &vector).unwrap();
+            plain_segment
+                .upsert_point(idx, idx.into(), &vector)
+                .unwrap();
+            struct_segment
+                .upsert_point(idx, idx.into(), &vector)
+                .unwrap();
 
             plain_segment
-                .set_full_payload(idx, idx, payload.clone())
+                .set_full_payload(idx, idx.into(), payload.clone())
                 .unwrap();
             struct_segment
-                .set_full_payload(idx, idx, payload.clone())
+                .set_full_payload(idx, idx.into(), payload.clone())
                 .unwrap();
 
             opnum += 1;
         }
 
-        struct_segment.create_field_index(opnum, &str_key).unwrap();
-        struct_segment.create_field_index(opnum, &int_key).unwrap();
+        struct_segment
+            .create_field_index(opnum, &str_key, None)
+            .unwrap();
+        struct_segment
+            .create_field_index(opnum, &int_key, None)
+            .unwrap();
 
         let attempts = 100;
         for _i in 0..attempts {
@@ -145,20 +278,24 @@ mod tests {
 
             let plain_result = plain_segment
                 .search(
+                    DEFAULT_VECTOR_NAME,
                     &query_vector,
                     &WithPayload::default(),
                     Some(&query_filter),
                     5,
                     None,
+                    None,
                 )
                 .unwrap();
             let struct_result = struct_segment
                 .search(
+                    DEFAULT_VECTOR_NAME,
                     &query_vector,
                     &WithPayload::default(),
                     Some(&query_filter),
                     5,
                     None,
+                    None,
                 )
                 .unwrap();
 
@@ -180,4 +317,755 @@ mod tests {
                 });
         }
     }
+
+    #[test]
+    fn test_match_any_and_except_index() {
+        let mut rnd = rand::thread_rng();
+
+        let dir = TempDir::new("segment_dir").unwrap();
+        let dim = 5;
+
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
+            index: Indexes::Plain {},
+            payload_index: Some(PayloadIndexType::Struct),
+            storage_type: StorageType::InMemory,
+            quantization_config: None,
+        };
+
+        let str_key = "kvd".to_string();
+        let int_key = "int".to_string();
+
+        let num_points: u64 = 1000;
+        let mut struct_segment = build_segment(dir.path(), &config).unwrap();
+
+        let mut opnum = 0;
+        for idx in 0..num_points {
+            let vector = random_vector(&mut rnd, dim);
+            let mut payload: TheMap<PayloadKeyType, PayloadType> = Default::default();
+            payload.insert(str_key.clone(), random_keyword_payload(&mut rnd));
+            payload.insert(int_key.clone(), random_int_payload(&mut rnd, 2));
+
+            struct_segment
+                .upsert_point(opnum, idx.into(), &vector)
+                .unwrap();
+            struct_segment
+                .set_full_payload(opnum, idx.into(), payload)
+                .unwrap();
+
+            opnum += 1;
+        }
+
+        struct_segment
+            .create_field_index(opnum, &str_key, None)
+            .unwrap();
+        struct_segment
+            .create_field_index(opnum, &int_key, None)
+            .unwrap();
+
+        let keywords = |n: usize, rnd: &mut rand::rngs::ThreadRng| {
+            AnyVariants::Keywords((0..n).map(|_| random_keyword(rnd)).collect())
+        };
+        let integers = AnyVariants::Integers((0..200).collect());
+
+        let match_filter = |key: &str, any: Option<AnyVariants>, except: Option<AnyVariants>| {
+            Filter::new_must(Condition::Field(FieldCondition {
+                key: key.to_string(),
+                r#match: Some(Match {
+                    keyword: None,
+                    integer: None,
+                    boolean: None,
+                    text: None,
+                    any,
+                    except,
+                }),
+                range: None,
+                geo_bounding_box: None,
+                geo_radius: None,
+                geo_polygon: None,
+                values_count: None,
+            }))
+        };
+
+        let filters = vec![
+            match_filter(&str_key, Some(keywords(10, &mut rnd)), None),
+            match_filter(&str_key, None, Some(keywords(50, &mut rnd))),
+            match_filter(&int_key, Some(integers.clone()), None),
+            match_filter(&int_key, None, Some(integers)),
+        ];
+
+        for filter in filters {
+            let exact = struct_segment
+                .points_storage
+                .borrow()
+                .iter_ids()
+                .filter(|x| struct_segment.condition_checker.check(*x, &filter))
+                .sorted()
+                .collect_vec();
+
+            let payload_index = struct_segment.payload_index.borrow();
+            let estimation = payload_index.estimate_cardinality(&filter);
+            let indexed = payload_index.query_points(&filter).sorted().collect_vec();
+
+            assert!(!estimation.primary_clauses.is_empty());
+            assert!(exact.len() <= estimation.max, "{:#?}", estimation);
+            assert!(exact.len() >= estimation.min, "{:#?}", estimation);
+            assert_eq!(exact, indexed);
+        }
+    }
+
+    #[test]
+    fn test_bool_and_datetime_index() {
+        let mut rnd = rand::thread_rng();
+
+        let dir = TempDir::new("segment_dir").unwrap();
+        let dim = 5;
+
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
+            index: Indexes::Plain {},
+            payload_index: Some(PayloadIndexType::Struct),
+            storage_type: StorageType::InMemory,
+            quantization_config: None,
+        };
+
+        let bool_key = "flag".to_string();
+        let datetime_key = "created".to_string();
+
+        let num_points: u64 = 1000;
+        let mut struct_segment = build_segment(dir.path(), &config).unwrap();
+
+        let mut opnum = 0;
+        for idx in 0..num_points {
+            let vector = random_vector(&mut rnd, dim);
+            let created = format!("2021-01-{:02}T{:02}:30:00Z", idx % 28 + 1, idx % 24);
+            let mut payload: TheMap<PayloadKeyType, PayloadType> = Default::default();
+            payload.insert(bool_key.clone(), PayloadType::Bool(vec![idx % 3 == 0]));
+            payload.insert(
+                datetime_key.clone(),
+                PayloadType::Datetime(vec![parse_datetime(&created).unwrap()]),
+            );
+
+            struct_segment
+                .upsert_point(opnum, idx.into(), &vector)
+                .unwrap();
+            struct_segment
+                .set_full_payload(opnum, idx.into(), payload)
+                .unwrap();
+
+            opnum += 1;
+        }
+
+        struct_segment
+            .create_field_index(opnum, &bool_key, None)
+            .unwrap();
+        struct_segment
+            .create_field_index(opnum, &datetime_key, None)
+            .unwrap();
+
+        let bool_filter = |boolean| {
+            Filter::new_must(Condition::Field(FieldCondition {
+                key: bool_key.clone(),
+                r#match: Some(Match {
+                    keyword: None,
+                    integer: None,
+                    boolean: Some(boolean),
+                    text: None,
+                    any: None,
+                    except: None,
+                }),
+                range: None,
+                geo_bounding_box: None,
+                geo_radius: None,
+                geo_polygon: None,
+                values_count: None,
+            }))
+        };
+        let datetime_filter = |range: &str| {
+            Filter::new_must(Condition::Field(FieldCondition {
+                key: datetime_key.clone(),
+                r#match: None,
+                range: Some(serde_json::from_str(range).unwrap()),
+                geo_bounding_box: None,
+                geo_radius: None,
+                geo_polygon: None,
+                values_count: None,
+            }))
+        };
+
+        let filters = vec![
+            bool_filter(true),
+            bool_filter(false),
+            datetime_filter(r#"{"gte": "2021-01-03T00:00:00Z", "lt": "2021-01-10T12:00:00Z"}"#),
+            datetime_filter(r#"{"gt": "2021-01-20T15:30:00+03:00"}"#),
+        ];
+
+        for filter in filters {
+            let exact = struct_segment
+                .points_storage
+                .borrow()
+                .iter_ids()
+                .filter(|x| struct_segment.condition_checker.check(*x, &filter))
+                .sorted()
+                .collect_vec();
+
+            let payload_index = struct_segment.payload_index.borrow();
+            let estimation = payload_index.estimate_cardinality(&filter);
+            let indexed = payload_index.query_points(&filter).sorted().collect_vec();
+
+            assert!(!exact.is_empty());
+            assert!(!estimation.primary_clauses.is_empty());
+            assert!(exact.len() <= estimation.max, "{:#?}", estimation);
+            assert!(exact.len() >= estimation.min, "{:#?}", estimation);
+            assert_eq!(exact, indexed);
+        }
+    }
+
+    #[test]
+    fn test_nested_payload_index() {
+        let mut rnd = rand::thread_rng();
+
+        let dir = TempDir::new("segment_dir").unwrap();
+        let dim = 5;
+
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
+            index: Indexes::Plain {},
+            payload_index: Some(PayloadIndexType::Struct),
+            storage_type: StorageType::InMemory,
+            quantization_config: None,
+        };
+
+        let num_points: u64 = 1000;
+        let mut struct_segment = build_segment(dir.path(), &config).unwrap();
+
+        let mut opnum = 0;
+        for idx in 0..num_points {
+            let vector = random_vector(&mut rnd, dim);
+            // Array of two review objects: {"reviews": [{"kvd": ..., "int": ...}, ...]}
+            let reviews = (0..2)
+                .map(|_| {
+                    let mut review: TheMap<PayloadKeyType, PayloadType> = Default::default();
+                    review.insert("kvd".to_string(), random_keyword_payload(&mut rnd));
+                    review.insert("int".to_string(), random_int_payload(&mut rnd, 1));
+                    review
+                })
+                .collect();
+            let mut payload: TheMap<PayloadKeyType, PayloadType> = Default::default();
+            payload.insert("reviews".to_string(), PayloadType::Object(reviews));
+
+            struct_segment
+                .upsert_point(opnum, idx.into(), &vector)
+                .unwrap();
+            struct_segment
+                .set_full_payload(opnum, idx.into(), payload)
+                .unwrap();
+
+            opnum += 1;
+        }
+
+        struct_segment
+            .create_field_index(opnum, "reviews.kvd", None)
+            .unwrap();
+        struct_segment
+            .create_field_index(opnum, "reviews.int", None)
+            .unwrap();
+
+        let keyword = random_keyword(&mut rnd);
+        let filters = vec![
+            Filter::new_must(Condition::Field(FieldCondition {
+                key: "reviews.kvd".to_string(),
+                r#match: Some(Match {
+                    keyword: Some(keyword),
+                    integer: None,
+                    boolean: None,
+                    text: None,
+                    any: None,
+                    except: None,
+                }),
+                range: None,
+                geo_bounding_box: None,
+                geo_radius: None,
+                geo_polygon: None,
+                values_count: None,
+            })),
+            Filter::new_must(Condition::Field(FieldCondition {
+                key: "reviews.int".to_string(),
+                r#match: None,
+                range: Some(Range {
+                    lt: None,
+                    gt: None,
+                    gte: Some(RangeValue::Integer(50)),
+                    lte: Some(RangeValue::Integer(100)),
+                }),
+                geo_bounding_box: None,
+                geo_radius: None,
+                geo_polygon: None,
+                values_count: None,
+            })),
+        ];
+
+        for filter in filters {
+            let exact = struct_segment
+                .points_storage
+                .borrow()
+                .iter_ids()
+                .filter(|x| struct_segment.condition_checker.check(*x, &filter))
+                .sorted()
+                .collect_vec();
+
+            let payload_index = struct_segment.payload_index.borrow();
+            let estimation = payload_index.estimate_cardinality(&filter);
+            let indexed = payload_index.query_points(&filter).sorted().collect_vec();
+
+            assert!(!exact.is_empty());
+            assert!(!estimation.primary_clauses.is_empty());
+            assert!(exact.len() <= estimation.max, "{:#?}", estimation);
+            assert!(exact.len() >= estimation.min, "{:#?}", estimation);
+            assert_eq!(exact, indexed);
+        }
+    }
+
+    #[test]
+    fn test_is_empty_and_has_field_index() {
+        let mut rnd = rand::thread_rng();
+
+        let dir = TempDir::new("segment_dir").unwrap();
+        let dim = 5;
+
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
+            index: Indexes::Plain {},
+            payload_index: Some(PayloadIndexType::Struct),
+            storage_type: StorageType::InMemory,
+            quantization_config: None,
+        };
+
+        let tags_key = "tags".to_string();
+
+        let num_points: u64 = 1000;
+        let mut struct_segment = build_segment(dir.path(), &config).unwrap();
+
+        let mut opnum = 0;
+        for idx in 0..num_points {
+            let vector = random_vector(&mut rnd, dim);
+            let mut payload: TheMap<PayloadKeyType, PayloadType> = Default::default();
+            // Field is missing, empty or filled
+            match idx % 3 {
+                0 => {}
+                1 => {
+                    payload.insert(tags_key.clone(), PayloadType::Keyword(vec![]));
+                }
+                _ => {
+                    payload.insert(tags_key.clone(), random_keyword_payload(&mut rnd));
+                }
+            }
+
+            struct_segment
+                .upsert_point(opnum, idx.into(), &vector)
+                .unwrap();
+            struct_segment
+                .set_full_payload(opnum, idx.into(), payload)
+                .unwrap();
+
+            opnum += 1;
+        }
+
+        struct_segment
+            .create_field_index(opnum, &tags_key, None)
+            .unwrap();
+
+        let field = PayloadField {
+            key: tags_key.clone(),
+        };
+        let filters = vec![
+            Filter::new_must(Condition::IsEmpty(IsEmptyCondition {
+                is_empty: field.clone(),
+            })),
+            Filter::new_must(Condition::HasField(HasFieldCondition {
+                has_field: field.clone(),
+            })),
+            Filter::new_must_not(Condition::IsEmpty(IsEmptyCondition { is_empty: field })),
+        ];
+
+        for filter in filters {
+            let exact = struct_segment
+                .points_storage
+                .borrow()
+                .iter_ids()
+                .filter(|x| struct_segment.condition_checker.check(*x, &filter))
+                .sorted()
+                .collect_vec();
+
+            let payload_index = struct_segment.payload_index.borrow();
+            let estimation = payload_index.estimate_cardinality(&filter);
+            let indexed = payload_index.query_points(&filter).sorted().collect_vec();
+
+            assert!(!exact.is_empty());
+            assert!(exact.len() <= estimation.max, "{:#?}", estimation);
+            assert!(exact.len() >= estimation.min, "{:#?}", estimation);
+            assert_eq!(exact, indexed);
+        }
+    }
+
+    #[test]
+    fn test_values_count_index() {
+        let mut rnd = rand::thread_rng();
+
+        let dir = TempDir::new("segment_dir").unwrap();
+        let dim = 5;
+
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
+            index: Indexes::Plain {},
+            payload_index: Some(PayloadIndexType::Struct),
+            storage_type: StorageType::InMemory,
+            quantization_config: None,
+        };
+
+        let int_key = "int".to_string();
+
+        let num_points: u64 = 1000;
+        let mut struct_segment = build_segment(dir.path(), &config).unwrap();
+
+        let mut opnum = 0;
+        for idx in 0..num_points {
+            let vector = random_vector(&mut rnd, dim);
+            let mut payload: TheMap<PayloadKeyType, PayloadType> = Default::default();
+            payload.insert(
+                int_key.clone(),
+                random_int_payload(&mut rnd, (idx % 5) as usize),
+            );
+
+            struct_segment
+                .upsert_point(opnum, idx.into(), &vector)
+                .unwrap();
+            struct_segment
+                .set_full_payload(opnum, idx.into(), payload)
+                .unwrap();
+
+            opnum += 1;
+        }
+
+        struct_segment
+            .create_field_index(opnum, &int_key, None)
+            .unwrap();
+
+        let values_count_filter = |gte, lt| {
+            Filter::new_must(Condition::Field(FieldCondition {
+                key: int_key.clone(),
+                r#match: None,
+                range: None,
+                geo_bounding_box: None,
+                geo_radius: None,
+                geo_polygon: None,
+                values_count: Some(ValuesCount {
+                    lt,
+                    gt: None,
+                    gte,
+                    lte: None,
+                }),
+            }))
+        };
+
+        let filters = vec![
+            values_count_filter(Some(3), None),
+            values_count_filter(None, Some(1)),
+            values_count_filter(Some(1), Some(3)),
+        ];
+
+        for filter in filters {
+            let exact = struct_segment
+                .points_storage
+                .borrow()
+                .iter_ids()
+                .filter(|x| struct_segment.condition_checker.check(*x, &filter))
+                .sorted()
+                .collect_vec();
+
+            let payload_index = struct_segment.payload_index.borrow();
+            let estimation = payload_index.estimate_cardinality(&filter);
+            let indexed = payload_index.query_points(&filter).sorted().collect_vec();
+
+            assert!(!exact.is_empty());
+            assert!(!estimation.primary_clauses.is_empty());
+            assert_eq!(exact.len(), estimation.exp, "{:#?}", estimation);
+            assert_eq!(exact, indexed);
+        }
+    }
+
+    #[test]
+    fn test_full_text_index() {
+        let mut rnd = rand::thread_rng();
+
+        let dir = TempDir::new("segment_dir").unwrap();
+        let dim = 5;
+
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
+            index: Indexes::Plain {},
+            payload_index: Some(PayloadIndexType::Struct),
+            storage_type: StorageType::InMemory,
+            quantization_config: None,
+        };
+
+        let text_key = "description".to_string();
+        let words = ["quick", "brown", "fox", "jumps", "over", "a", "lazy", "dog"];
+
+        let num_points: u64 = 1000;
+        let mut struct_segment = build_segment(dir.path(), &config).unwrap();
+
+        let mut opnum = 0;
+        for idx in 0..num_points {
+            let vector = random_vector(&mut rnd, dim);
+            let text = words
+                .iter()
+                .enumerate()
+                .filter(|(word_idx, _)| (idx as usize) % (word_idx + 2) == 0)
+                .map(|(_, word)| word.to_uppercase())
+                .join(", ");
+            let mut payload: TheMap<PayloadKeyType, PayloadType> = Default::default();
+            payload.insert(text_key.clone(), PayloadType::Keyword(vec![text]));
+
+            struct_segment
+                .upsert_point(opnum, idx.into(), &vector)
+                .unwrap();
+            struct_segment
+                .set_full_payload(opnum, idx.into(), payload)
+                .unwrap();
+
+            opnum += 1;
+        }
+
+        let index_params = PayloadIndexParams::Text(TextIndexParams {
+            min_token_len: Some(2),
+            max_token_len: None,
+        });
+        struct_segment
+            .create_field_index(opnum, &text_key, Some(&index_params))
+            .unwrap();
+
+        let text_filter = |text: &str| {
+            Filter::new_must(Condition::Field(FieldCondition {
+                key: text_key.clone(),
+                r#match: Some(Match {
+                    keyword: None,
+                    integer: None,
+                    boolean: None,
+                    text: Some(text.to_owned()),
+                    any: None,
+                    except: None,
+                }),
+                range: None,
+                geo_bounding_box: None,
+                geo_radius: None,
+                geo_polygon: None,
+                values_count: None,
+            }))
+        };
+
+        let filters = vec![
+            text_filter("fox"),
+            text_filter("quick brown"),
+            text_filter("Lazy, dog!"),
+            text_filter("a jumps"),
+        ];
+
+        for filter in filters {
+            let exact = struct_segment
+                .points_storage
+                .borrow()
+                .iter_ids()
+                .filter(|x| struct_segment.condition_checker.check(*x, &filter))
+                .sorted()
+                .collect_vec();
+
+            let payload_index = struct_segment.payload_index.borrow();
+            let estimation = payload_index.estimate_cardinality(&filter);
+            let indexed = payload_index.query_points(&filter).sorted().collect_vec();
+
+            assert!(!exact.is_empty());
+            assert!(!estimation.primary_clauses.is_empty());
+            assert!(exact.len() <= estimation.max, "{:#?}", estimation);
+            assert!(exact.len() >= estimation.min, "{:#?}", estimation);
+            assert_eq!(exact, indexed);
+        }
+    }
+
+    #[test]
+    fn test_geo_index() {
+        let mut rnd = rand::thread_rng();
+
+        let dir = TempDir::new("segment_dir").unwrap();
+        let dim = 5;
+
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
+            index: Indexes::Plain {},
+            payload_index: Some(PayloadIndexType::Struct),
+            storage_type: StorageType::InMemory,
+            quantization_config: None,
+        };
+
+        let location_key = "location".to_string();
+
+        let num_points: u64 = 1000;
+        let mut struct_segment = build_segment(dir.path(), &config).unwrap();
+
+        let mut opnum = 0;
+        for idx in 0..num_points {
+            let vector = random_vector(&mut rnd, dim);
+            let geo_points = (0..rnd.gen_range(1..3))
+                .map(|_| GeoPoint {
+                    lon: rnd.gen_range(10.0..20.0),
+                    lat: rnd.gen_range(50.0..55.0),
+                })
+                .collect();
+            let mut payload: TheMap<PayloadKeyType, PayloadType> = Default::default();
+            payload.insert(location_key.clone(), PayloadType::Geo(geo_points));
+
+            struct_segment
+                .upsert_point(opnum, idx.into(), &vector)
+                .unwrap();
+            struct_segment
+                .set_full_payload(opnum, idx.into(), payload)
+                .unwrap();
+
+            opnum += 1;
+        }
+
+        struct_segment
+            .create_field_index(opnum, &location_key, None)
+            .unwrap();
+
+        let geo_condition = |geo_bounding_box, geo_radius, geo_polygon| {
+            Filter::new_must(Condition::Field(FieldCondition {
+                key: location_key.clone(),
+                r#match: None,
+                range: None,
+                geo_bounding_box,
+                geo_radius,
+                geo_polygon,
+                values_count: None,
+            }))
+        };
+        let ring = |points: &[(f64, f64)]| GeoLineString {
+            points: points
+                .iter()
+                .map(|&(lon, lat)| GeoPoint { lon, lat })
+                .collect(),
+        };
+
+        let filters = vec![
+            geo_condition(
+                Some(GeoBoundingBox {
+                    top_left: GeoPoint {
+                        lon: 12.0,
+                        lat: 53.0,
+                    },
+                    bottom_right: GeoPoint {
+                        lon: 14.5,
+                        lat: 51.5,
+                    },
+                }),
+                None,
+                None,
+            ),
+            geo_condition(
+                None,
+                Some(GeoRadius {
+                    center: GeoPoint {
+                        lon: 15.0,
+                        lat: 52.5,
+                    },
+                    radius: 100_000.0,
+                }),
+                None,
+            ),
+            geo_condition(
+                None,
+                None,
+                Some(GeoPolygon::new(
+                    ring(&[(11.0, 51.0), (17.0, 51.0), (14.0, 54.0)]),
+                    Some(vec![ring(&[(13.0, 51.5), (15.0, 51.5), (14.0, 52.5)])]),
+                )),
+            ),
+        ];
+
+        for filter in filters {
+            let exact = struct_segment
+                .points_storage
+                .borrow()
+                .iter_ids()
+                .filter(|x| struct_segment.condition_checker.check(*x, &filter))
+                .sorted()
+                .collect_vec();
+
+            let payload_index = struct_segment.payload_index.borrow();
+            let estimation = payload_index.estimate_cardinality(&filter);
+            let indexed = payload_index.query_points(&filter).sorted().collect_vec();
+
+            assert!(!exact.is_empty());
+            assert!(!estimation.primary_clauses.is_empty());
+            assert!(exact.len() <= estimation.max, "{:#?}", estimation);
+            assert!(exact.len() >= estimation.min, "{:#?}", estimation);
+            assert_eq!(exact, indexed);
+        }
+    }
 }
@@ -7,6 +7,9 @@ mod tests {
     use segment::entry::entry_point::SegmentEntry;
     use segment::segment::Segment;
     use segment::segment_constructor::segment_builder::SegmentBuilder;
+    use segment::types::{
+        Indexes, ScalarQuantizationConfig, SegmentConfig, WithPayload, DEFAULT_VECTOR_NAME,
+    };
     use std::convert::TryInto;
     use tempdir::TempDir;
 
@@ -25,7 +28,9 @@ mod tests {
             SegmentBuilder::new(dir.path(), temp_dir.path(), &segment1.segment_config).unwrap();
 
         // Include overlapping with segment1 to check the
-        segment2.upsert_point(100, 3, &[0., 0., 0., 0.]).unwrap();
+        segment2
+            .upsert_point(100, 3.into(), &[0., 0., 0., 0.])
+            .unwrap();
 
         builder.update_from(&segment1).unwrap();
         builder.update_from(&segment2).unwrap();
@@ -58,6 +63,73 @@ mod tests {
                 .count()
         );
 
-        assert_eq!(merged_segment.point_version(3), Some(100));
+        assert_eq!(merged_segment.point_version(3.into()), Some(100));
+    }
+
+    #[test]
+    fn test_building_quantized_segment() {
+        let dir = TempDir::new("segment_dir").unwrap();
+        let temp_dir = TempDir::new("segment_temp_dir").unwrap();
+
+        let segment1 = build_segment_1(dir.path());
+        let segment2 = build_segment_2(dir.path());
+
+        let quantization_config = ScalarQuantizationConfig {
+            quantile: None,
+            rescore_multiplier: None,
+        };
+
+        let config = SegmentConfig {
+            index: Indexes::default_hnsw(),
+            quantization_config: Some(quantization_config),
+            ..segment1.segment_config.clone()
+        };
+
+        let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &config).unwrap();
+
+        builder.update_from(&segment1).unwrap();
+        builder.update_from(&segment2).unwrap();
+
+        let quantized_segment: Segment = builder.try_into().unwrap();
+
+        assert_eq!(
+            quantized_segment.vector_data[DEFAULT_VECTOR_NAME]
+                .vector_storage
+                .borrow()
+                .quantization_config(),
+            Some(quantization_config)
+        );
+
+        let query = [1.0, 1.0, 1.0, 1.0];
+        let with_payload = WithPayload::default();
+
+        let expected = segment1
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &query,
+                &with_payload,
+                None,
+                3,
+                None,
+                None,
+            )
+            .unwrap();
+        let found = quantized_segment
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &query,
+                &with_payload,
+                None,
+                3,
+                None,
+                None,
+            )
+            .unwrap();
+
+        // Final scores are computed with original vectors
+        assert_eq!(
+            found.iter().map(|x| x.score).collect_vec(),
+            expected.iter().map(|x| x.score).collect_vec()
+        );
     }
 }
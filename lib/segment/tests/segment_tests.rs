@@ -4,7 +4,7 @@ mod fixtures;
 mod tests {
     use crate::fixtures::segment::build_segment_1;
     use segment::entry::entry_point::SegmentEntry;
-    use segment::types::{Condition, Filter, WithPayload};
+    use segment::types::{Condition, Filter, PointIdType, WithPayload, DEFAULT_VECTOR_NAME};
     use std::collections::HashSet;
     use std::iter::FromIterator;
     use tempdir::TempDir;
@@ -15,31 +15,48 @@ mod tests {
 
         let segment = build_segment_1(dir.path());
 
-        assert!(segment.has_point(3));
+        assert!(segment.has_point(3.into()));
 
         let query_vector = vec![1.0, 1.0, 1.0, 1.0];
 
         let res = segment
-            .search(&query_vector, &WithPayload::default(), None, 1, None)
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &query_vector,
+                &WithPayload::default(),
+                None,
+                1,
+                None,
+                None,
+            )
             .unwrap();
 
         let best_match = res.get(0).expect("Non-empty result");
-        assert_eq!(best_match.id, 3);
+        assert_eq!(best_match.id, 3.into());
 
-        let ids: HashSet<_> = HashSet::from_iter([3]);
+        let ids: HashSet<PointIdType> = HashSet::from_iter([3.into()]);
 
         let frt = Filter {
             should: None,
+            min_should: None,
             must: None,
             must_not: Some(vec![Condition::HasId(ids.into())]),
         };
 
         let res = segment
-            .search(&query_vector, &WithPayload::default(), Some(&frt), 1, None)
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &query_vector,
+                &WithPayload::default(),
+                Some(&frt),
+                1,
+                None,
+                None,
+            )
             .unwrap();
 
         let best_match = res.get(0).expect("Non-empty result");
-        assert_ne!(best_match.id, 3);
+        assert_ne!(best_match.id, 3.into());
 
         let point_ids1: Vec<_> = segment.iter_points().collect();
         let point_ids2: Vec<_> = segment.iter_points().collect();
@@ -3,9 +3,10 @@ mod tests {
     use segment::entry::entry_point::SegmentEntry;
     use segment::segment_constructor::build_segment;
     use segment::types::{
-        Distance, Indexes, PayloadIndexType, PayloadKeyType, PayloadType, SegmentConfig,
-        StorageType,
+        get_value_by_path, Distance, Indexes, PayloadIndexType, PayloadKeyType, PayloadType,
+        SegmentConfig, StorageType, VectorDataConfig, DEFAULT_VECTOR_NAME,
     };
+    use std::collections::HashMap;
     use tempdir::TempDir;
 
     #[test]
@@ -35,34 +36,43 @@ mod tests {
         let dir = TempDir::new("payload_dir").unwrap();
         let dim = 2;
         let config = SegmentConfig {
-            vector_size: dim,
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: dim,
+                    distance: Distance::Dot,
+                    hnsw_config: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::new(),
             index: Indexes::Plain {},
             payload_index: Some(PayloadIndexType::Plain),
             storage_type: StorageType::InMemory,
-            distance: Distance::Dot,
+            quantization_config: None,
         };
 
         let mut segment = build_segment(dir.path(), &config).unwrap();
-        segment.upsert_point(0, 0, &[1.0, 1.0]).unwrap();
+        segment.upsert_point(0, 0.into(), &[1.0, 1.0]).unwrap();
         segment
-            .set_full_payload_with_json(0, 0, &data.to_string())
+            .set_full_payload_with_json(0, 0.into(), &data.to_string())
             .unwrap();
-        let payload = segment.payload(0).unwrap();
+        let payload = segment.payload(0.into()).unwrap();
         let keys: Vec<PayloadKeyType> = payload.keys().cloned().collect();
         assert!(keys.contains(&"geo_data".to_string()));
         assert!(keys.contains(&"name".to_string()));
         assert!(keys.contains(&"age".to_string()));
         assert!(keys.contains(&"boolean".to_string()));
         assert!(keys.contains(&"floating".to_string()));
-        assert!(keys.contains(&"metadata__temperature".to_string()));
-        assert!(keys.contains(&"metadata__width".to_string()));
-        assert!(keys.contains(&"metadata__height".to_string()));
-        assert!(keys.contains(&"metadata__nested__feature".to_string()));
+        assert!(keys.contains(&"metadata".to_string()));
+        assert!(get_value_by_path(&payload, "metadata.temperature").is_some());
+        assert!(get_value_by_path(&payload, "metadata.width").is_some());
+        assert!(get_value_by_path(&payload, "metadata.height").is_some());
+        assert!(get_value_by_path(&payload, "metadata.nested.feature").is_some());
         assert!(keys.contains(&"string_array".to_string()));
         assert!(keys.contains(&"float_array".to_string()));
         assert!(keys.contains(&"integer_array".to_string()));
         assert!(keys.contains(&"boolean_array".to_string()));
-        assert!(keys.contains(&"metadata__integer_array".to_string()));
+        assert!(get_value_by_path(&payload, "metadata.integer_array").is_some());
 
         match &payload[&"name".to_string()] {
             PayloadType::Keyword(x) => {
@@ -92,28 +102,40 @@ mod tests {
             }
             _ => assert!(false),
         }
-        match &payload[&"metadata__temperature".to_string()] {
+        match get_value_by_path(&payload, "metadata.temperature")
+            .unwrap()
+            .as_ref()
+        {
             PayloadType::Float(x) => {
                 assert_eq!(x.len(), 1);
                 assert_eq!(x[0], 60.5);
             }
             _ => assert!(false),
         }
-        match &payload[&"metadata__width".to_string()] {
+        match get_value_by_path(&payload, "metadata.width")
+            .unwrap()
+            .as_ref()
+        {
             PayloadType::Integer(x) => {
                 assert_eq!(x.len(), 1);
                 assert_eq!(x[0], 60);
             }
             _ => assert!(false),
         }
-        match &payload[&"metadata__height".to_string()] {
+        match get_value_by_path(&payload, "metadata.height")
+            .unwrap()
+            .as_ref()
+        {
             PayloadType::Integer(x) => {
                 assert_eq!(x.len(), 1);
                 assert_eq!(x[0], 50);
             }
             _ => assert!(false),
         }
-        match &payload[&"metadata__nested__feature".to_string()] {
+        match get_value_by_path(&payload, "metadata.nested.feature")
+            .unwrap()
+            .as_ref()
+        {
             PayloadType::Float(x) => {
                 assert_eq!(x.len(), 1);
                 assert_eq!(x[0], 30.5);
@@ -136,7 +158,10 @@ mod tests {
             }
             _ => assert!(false),
         }
-        match &payload[&"metadata__integer_array".to_string()] {
+        match get_value_by_path(&payload, "metadata.integer_array")
+            .unwrap()
+            .as_ref()
+        {
             PayloadType::Integer(x) => {
                 assert_eq!(x.len(), 2);
                 assert_eq!(x[0], 1);
@@ -1,4 +1,5 @@
 mod collections_ops;
 pub mod errors;
+pub mod snapshots;
 pub mod storage_ops;
 pub mod toc;
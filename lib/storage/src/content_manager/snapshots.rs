@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::fs::{read_dir, remove_file, rename, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use collection::snapshot::{SNAPSHOT_ALIASES_FILE, SNAPSHOT_FILE_EXTENSION};
+
+use crate::content_manager::errors::StorageError;
+
+/// Directory of the full snapshot archive, which contains snapshots of all collections
+const COLLECTION_SNAPSHOTS_DIR: &str = "collections";
+
+/// Packs snapshots of all collections and the alias mapping into a single tar archive
+///
+/// # Arguments
+///
+/// * `collection_snapshots` - pairs of collection name and path of its snapshot
+/// * `aliases` - mapping of all aliases into the collection names
+/// * `snapshot_path` - path of the archive to create
+pub fn pack_full_snapshot(
+    collection_snapshots: &[(String, PathBuf)],
+    aliases: &HashMap<String, String>,
+    snapshot_path: &Path,
+) -> Result<(), StorageError> {
+    let tmp_path = snapshot_path.with_extension("tmp");
+    let pack_res = (|| -> Result<(), StorageError> {
+        let mut builder = tar::Builder::new(BufWriter::new(File::create(&tmp_path)?));
+        for (collection_name, collection_snapshot_path) in collection_snapshots {
+            builder.append_path_with_name(
+                collection_snapshot_path,
+                Path::new(COLLECTION_SNAPSHOTS_DIR)
+                    .join(format!("{}.{}", collection_name, SNAPSHOT_FILE_EXTENSION)),
+            )?;
+        }
+        let aliases_bytes =
+            serde_json::to_vec(aliases).map_err(|err| StorageError::ServiceError {
+                description: format!("Can't serialize aliases, error: {}", err),
+            })?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(aliases_bytes.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, SNAPSHOT_ALIASES_FILE, aliases_bytes.as_slice())?;
+        builder.into_inner()?.flush()?;
+        Ok(())
+    })();
+
+    match pack_res {
+        Ok(()) => Ok(rename(&tmp_path, snapshot_path)?),
+        Err(err) => {
+            remove_file(&tmp_path).ok();
+            Err(err)
+        }
+    }
+}
+
+/// Unpacks full snapshot archive into the given directory
+///
+/// # Result
+///
+/// Pairs of collection name and path of its unpacked snapshot, mapping of all aliases
+#[allow(clippy::type_complexity)]
+pub fn unpack_full_snapshot(
+    snapshot_path: &Path,
+    target_path: &Path,
+) -> Result<(Vec<(String, PathBuf)>, HashMap<String, String>), StorageError> {
+    let snapshot_file = File::open(snapshot_path).map_err(|err| StorageError::BadInput {
+        description: format!("Can't open snapshot {:?}, error: {}", snapshot_path, err),
+    })?;
+    tar::Archive::new(BufReader::new(snapshot_file))
+        .unpack(target_path)
+        .map_err(|err| StorageError::BadInput {
+            description: format!("Can't unpack snapshot {:?}, error: {}", snapshot_path, err),
+        })?;
+
+    let aliases_file = File::open(target_path.join(SNAPSHOT_ALIASES_FILE)).map_err(|err| {
+        StorageError::BadInput {
+            description: format!(
+                "Snapshot {:?} has no aliases, error: {}",
+                snapshot_path, err
+            ),
+        }
+    })?;
+    let aliases = serde_json::from_reader(BufReader::new(aliases_file)).map_err(|err| {
+        StorageError::BadInput {
+            description: format!(
+                "Snapshot {:?} has invalid aliases, error: {}",
+                snapshot_path, err
+            ),
+        }
+    })?;
+
+    let mut collection_snapshots = vec![];
+    let collections_path = target_path.join(COLLECTION_SNAPSHOTS_DIR);
+    if collections_path.exists() {
+        for entry in read_dir(&collections_path)? {
+            let collection_snapshot_path = entry?.path();
+            let collection_name = collection_snapshot_path
+                .file_stem()
+                .and_then(|name| name.to_str())
+                .ok_or_else(|| StorageError::BadInput {
+                    description: format!(
+                        "Snapshot {:?} contains invalid collection file {:?}",
+                        snapshot_path, collection_snapshot_path
+                    ),
+                })?
+                .to_string();
+            collection_snapshots.push((collection_name, collection_snapshot_path));
+        }
+    }
+    Ok((collection_snapshots, aliases))
+}
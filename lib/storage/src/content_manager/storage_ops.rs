@@ -1,7 +1,9 @@
+use collection::config::{PayloadSchema, SparseVectorParams, VectorsConfig};
 use collection::operations::config_diff::{HnswConfigDiff, OptimizersConfigDiff, WalConfigDiff};
 use schemars::JsonSchema;
-use segment::types::Distance;
+use segment::types::{Distance, ProductQuantizationConfig, ScalarQuantizationConfig};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 // *Operation wrapper structure is only required for better OpenAPI generation
 
@@ -81,14 +83,37 @@ impl From<RenameAlias> for AliasOperations {
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct CreateCollection {
-    pub vector_size: usize,
-    pub distance: Distance,
+    /// Params of the vectors stored in the collection:
+    /// either a single unnamed vector or several named vectors, each with its own params.
+    #[serde(default)]
+    pub vectors: Option<VectorsConfig>,
+    /// Deprecated, use `vectors` instead. Size of a single unnamed vector
+    #[serde(default)]
+    pub vector_size: Option<usize>,
+    /// Deprecated, use `vectors` instead. Distance of a single unnamed vector
+    #[serde(default)]
+    pub distance: Option<Distance>,
+    /// Params of the named sparse vectors stored in the collection.
+    /// Collection might contain only sparse vectors, in this case `vectors` could be omitted.
+    #[serde(default)]
+    pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
     /// Custom params for HNSW index. If none - values from service configuration file are used.
     pub hnsw_config: Option<HnswConfigDiff>,
     /// Custom params for WAL. If none - values from service configuration file are used.
     pub wal_config: Option<WalConfigDiff>,
     /// Custom params for Optimizers.  If none - values from service configuration file are used.
     pub optimizers_config: Option<OptimizersConfigDiff>,
+    /// Quantization of the vectors in optimized segments. If none - vectors are not quantized.
+    #[serde(default)]
+    pub quantization_config: Option<ScalarQuantizationConfig>,
+    /// Product quantization of the vectors in large on-disk segments.
+    /// If none - original vectors are used in memmap storage.
+    #[serde(default)]
+    pub product_quantization_config: Option<ProductQuantizationConfig>,
+    /// Declared types of the payload fields. If none - types are inferred from the stored values.
+    /// Indexes of the fields, declared as indexed, are created automatically
+    #[serde(default)]
+    pub payload_schema: Option<PayloadSchema>,
 }
 
 /// Operation for creating new collection and (optionally) specify index params
@@ -107,6 +132,10 @@ pub struct UpdateCollection {
     /// Custom params for Optimizers.  If none - values from service configuration file are used.
     /// This operation is blocking, it will only proceed ones all current optimizations are complete
     pub optimizers_config: Option<OptimizersConfigDiff>, // ToDo: Allow updates for other configuration params as well
+    /// New payload schema of the collection, replaces the declared one. Empty schema removes
+    /// the declaration. Values, which are already stored, are not checked against the new schema
+    #[serde(default)]
+    pub payload_schema: Option<PayloadSchema>,
 }
 
 /// Operation for updating parameters of the existing collection
@@ -127,6 +156,14 @@ pub struct ChangeAliasesOperation {
     pub actions: Vec<AliasOperations>,
 }
 
+/// Operation for recreating collection from the snapshot file
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct RecoverSnapshot {
+    /// Path of the snapshot file on the server, relative to the snapshots directory
+    pub location: String,
+}
+
 /// Operation for deleting collection with given name
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -1,27 +1,38 @@
-use std::collections::HashMap;
-use std::fs::{create_dir_all, read_dir, remove_dir_all};
-use std::path::{Path, PathBuf};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{create_dir, create_dir_all, read_dir, remove_dir_all};
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
 use std::str::from_utf8;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use itertools::Itertools;
 use sled::transaction::UnabortableTransactionError;
-use sled::{Config, Db};
+use sled::{Batch, Config, Db};
 use tokio::runtime::Runtime;
 use tokio::sync::RwLock;
 
 use collection::collection::Collection;
 use collection::collection_builder::build_collection;
-use collection::collection_builder::collection_loader::load_collection;
-use collection::config::CollectionParams;
+use collection::collection_builder::collection_loader::{load_collection, try_load_collection};
+use collection::config::{
+    CollectionParams, PayloadSchema, SparseVectorParams, VectorParams, VectorsConfig,
+};
 use collection::operations::config_diff::DiffConfig;
 use collection::operations::types::{
-    RecommendRequest, Record, ScrollRequest, ScrollResult, SearchRequest, UpdateResult,
+    CountRequest, CountResult, GroupsResult, LookupLocation, RecommendGroupsRequest,
+    RecommendRequest, Record, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
+    SearchRequestBatch, UpdateResult,
 };
 use collection::operations::CollectionUpdateOperations;
-use segment::types::{PointIdType, ScoredPoint, WithPayload};
+use collection::snapshot::{unpack_snapshot, SnapshotDescription, SNAPSHOT_FILE_EXTENSION};
+use segment::types::{
+    PayloadIndexParams, PayloadSchemaType, PointIdType, ScoredPoint, WithPayload,
+};
 
 use crate::content_manager::collections_ops::{Checker, Collections};
 use crate::content_manager::errors::StorageError;
+use crate::content_manager::snapshots::{pack_full_snapshot, unpack_full_snapshot};
 use crate::content_manager::storage_ops::{
     AliasOperations, ChangeAliasesOperation, CreateAlias, CreateAliasOperation, CreateCollection,
     DeleteAlias, DeleteAliasOperation, RenameAlias, RenameAliasOperation, StorageOperations,
@@ -38,6 +49,8 @@ const SLED_CACHE_SIZE: u64 = 1 * 1024 * 1024; // 1 mb
 
 const COLLECTIONS_DIR: &str = "collections";
 
+const FULL_SNAPSHOT_PREFIX: &str = "full-snapshot";
+
 /// The main object of the service. It holds all objects, required for proper functioning.
 /// In most cases only one `TableOfContent` is enough for service. It is created only once during
 /// the launch of the service.
@@ -112,6 +125,29 @@ impl TableOfContent {
         Ok(path)
     }
 
+    /// Creates the directory of the new collection.
+    /// Fails if the directory already exists, so only one caller could fill it.
+    fn create_new_collection_path(&self, collection_name: &str) -> Result<PathBuf, StorageError> {
+        let path = self.get_collection_path(collection_name);
+
+        create_dir(&path).map_err(|err| match err.kind() {
+            ErrorKind::AlreadyExists => StorageError::BadInput {
+                description: format!(
+                    "Directory of the collection {} already exists",
+                    collection_name
+                ),
+            },
+            _ => StorageError::ServiceError {
+                description: format!(
+                    "Can't create directory for collection {}. Error: {}",
+                    collection_name, err
+                ),
+            },
+        })?;
+
+        Ok(path)
+    }
+
     /// Finds the original name of the collection
     ///
     /// # Arguments
@@ -138,17 +174,87 @@ impl TableOfContent {
         Ok(resolved_name)
     }
 
+    fn validate_vector_name(name: &str) -> Result<(), StorageError> {
+        // Vector name is used as a part of the storage path
+        if name.is_empty()
+            || name == "."
+            || name == ".."
+            || name.contains(|c| c == '/' || c == '\\')
+        {
+            return Err(StorageError::BadInput {
+                description: format!("Invalid vector name: \"{}\"", name),
+            });
+        }
+        Ok(())
+    }
+
+    fn validate_vectors_config(
+        vectors: &VectorsConfig,
+        sparse_vectors: &BTreeMap<String, SparseVectorParams>,
+    ) -> Result<(), StorageError> {
+        if let VectorsConfig::Multi(params) = vectors {
+            if params.is_empty() && sparse_vectors.is_empty() {
+                return Err(StorageError::BadInput {
+                    description: "At least one vector should be specified".to_owned(),
+                });
+            }
+            for name in params.keys() {
+                Self::validate_vector_name(name)?;
+            }
+        }
+        for name in sparse_vectors.keys() {
+            Self::validate_vector_name(name)?;
+            if vectors.get_params(name).is_some() {
+                return Err(StorageError::BadInput {
+                    description: format!(
+                        "Sparse vector \"{}\" has the same name as a dense vector",
+                        name
+                    ),
+                });
+            }
+        }
+        for (vector_name, params) in vectors.params_iter() {
+            if params.size == 0 {
+                return Err(StorageError::BadInput {
+                    description: format!("Size of vector \"{}\" should be positive", vector_name),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_payload_schema(payload_schema: &PayloadSchema) -> Result<(), StorageError> {
+        for (field_name, field_schema) in payload_schema {
+            let is_text_index =
+                matches!(field_schema.index_params, Some(PayloadIndexParams::Text(_)));
+            if is_text_index && field_schema.field_type != PayloadSchemaType::Keyword {
+                return Err(StorageError::BadInput {
+                    description: format!(
+                        "Full-text index of the field \"{}\" requires keyword type",
+                        field_name
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
     pub async fn create_collection(
         &self,
         collection_name: &str,
         operation: CreateCollection,
     ) -> Result<bool, StorageError> {
         let CreateCollection {
+            vectors,
             vector_size,
             distance,
+            sparse_vectors,
             hnsw_config: hnsw_config_diff,
             wal_config: wal_config_diff,
             optimizers_config: optimizers_config_diff,
+            quantization_config,
+            product_quantization_config,
+            payload_schema,
         } = operation;
 
         self.collections
@@ -157,11 +263,39 @@ impl TableOfContent {
             .validate_collection_not_exists(collection_name)
             .await?;
 
+        let sparse_vectors = sparse_vectors.unwrap_or_default();
+        let vectors =
+            match (vectors, vector_size, distance) {
+                (Some(vectors), None, None) => vectors,
+                (None, Some(size), Some(distance)) => VectorsConfig::Single(VectorParams {
+                    size,
+                    distance,
+                    hnsw_config: None,
+                }),
+                // Collection with sparse vectors only
+                (None, None, None) if !sparse_vectors.is_empty() => {
+                    VectorsConfig::Multi(BTreeMap::new())
+                }
+                _ => return Err(StorageError::BadInput {
+                    description:
+                        "Either `vectors` or both `vector_size` and `distance` should be specified"
+                            .to_owned(),
+                }),
+            };
+        Self::validate_vectors_config(&vectors, &sparse_vectors)?;
+        let payload_schema = payload_schema.filter(|schema| !schema.is_empty());
+        if let Some(payload_schema) = &payload_schema {
+            Self::validate_payload_schema(payload_schema)?;
+        }
+
         let collection_path = self.create_collection_path(collection_name)?;
 
         let collection_params = CollectionParams {
-            vector_size,
-            distance,
+            vectors,
+            quantization_config,
+            product_quantization_config,
+            sparse_vectors: Some(sparse_vectors).filter(|params| !params.is_empty()),
+            payload_schema,
         };
         let wal_config = match wal_config_diff {
             None => self.storage_config.wal.clone(),
@@ -186,6 +320,12 @@ impl TableOfContent {
             &hnsw_config,
         )?;
 
+        // Indexes of the declared fields are created before the collection becomes available
+        if let Err(err) = collection.create_payload_schema_indexes().await {
+            Self::discard_collection(collection, &collection_path).await;
+            return Err(err.into());
+        }
+
         let mut write_collections = self.collections.write().await;
         write_collections
             .validate_collection_not_exists(collection_name)
@@ -194,6 +334,14 @@ impl TableOfContent {
         Ok(true)
     }
 
+    /// Stops the collection, which is not registered in the storage, and removes its files
+    async fn discard_collection(collection: Collection, collection_path: &Path) {
+        collection.stop().await.ok();
+        collection.wait_update_workers_stop().await.ok();
+        drop(collection);
+        remove_dir_all(collection_path).ok();
+    }
+
     pub async fn update_collection(
         &self,
         collection_name: &str,
@@ -208,6 +356,16 @@ impl TableOfContent {
                     .await?
             }
         }
+        match operation.payload_schema {
+            None => {}
+            Some(new_payload_schema) => {
+                Self::validate_payload_schema(&new_payload_schema)?;
+                let collection = self.get_collection(collection_name).await?;
+                collection
+                    .update_payload_schema(Some(new_payload_schema).filter(|x| !x.is_empty()))
+                    .await?
+            }
+        }
         Ok(true)
     }
 
@@ -232,6 +390,337 @@ impl TableOfContent {
         }
     }
 
+    /// Creates snapshot of the collection together with its aliases
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - name of the collection or alias to snapshot
+    ///
+    /// # Result
+    ///
+    /// Description of the snapshot file, created in the `snapshots_path` directory
+    pub async fn create_snapshot(
+        &self,
+        collection_name: &str,
+    ) -> Result<SnapshotDescription, StorageError> {
+        let collection_name = self.resolve_name(collection_name).await?;
+        let collection = self.get_collection(&collection_name).await?;
+        let aliases = self.collection_aliases(&collection_name)?;
+
+        let snapshots_path = Path::new(&self.storage_config.snapshots_path).join(&collection_name);
+        create_dir_all(&snapshots_path).map_err(|err| StorageError::ServiceError {
+            description: format!(
+                "Can't create snapshots directory for collection {}. Error: {}",
+                collection_name, err
+            ),
+        })?;
+        let snapshot_name = Self::snapshot_file_name(&collection_name);
+        let snapshot_path = snapshots_path.join(&snapshot_name);
+
+        collection.create_snapshot(&snapshot_path, &aliases).await?;
+
+        Ok(SnapshotDescription {
+            name: snapshot_name,
+            size: snapshot_path.metadata()?.len(),
+        })
+    }
+
+    /// Resolves user-provided location of the snapshot file.
+    /// Location is relative to the `snapshots_path` directory and can't point outside of it,
+    /// so the API can't be used to read arbitrary files of the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `location` - path of the snapshot file, relative to the snapshots directory
+    ///
+    /// # Result
+    ///
+    /// Path of the existing snapshot file within the snapshots directory
+    pub fn snapshot_location_path(&self, location: &str) -> Result<PathBuf, StorageError> {
+        let location_path = Path::new(location);
+        let is_relative = location_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+        if location.is_empty() || !is_relative {
+            return Err(StorageError::BadInput {
+                description: format!(
+                    "Snapshot location {} should be a path relative to the snapshots directory",
+                    location
+                ),
+            });
+        }
+
+        let snapshots_path = Path::new(&self.storage_config.snapshots_path);
+        let not_found = |err: std::io::Error| StorageError::BadInput {
+            description: format!("Snapshot {} is not found, error: {}", location, err),
+        };
+        let snapshots_path = snapshots_path.canonicalize().map_err(not_found)?;
+        let snapshot_path = snapshots_path
+            .join(location_path)
+            .canonicalize()
+            .map_err(not_found)?;
+        // Symlinks might still point outside of the snapshots directory
+        if !snapshot_path.starts_with(&snapshots_path) || !snapshot_path.is_file() {
+            return Err(StorageError::BadInput {
+                description: format!(
+                    "Snapshot location {} is not a file within the snapshots directory",
+                    location
+                ),
+            });
+        }
+        Ok(snapshot_path)
+    }
+
+    fn snapshot_file_name(prefix: &str) -> String {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        format!("{}-{}.{}", prefix, timestamp, SNAPSHOT_FILE_EXTENSION)
+    }
+
+    /// Creates snapshot of all collections together with the alias mapping.
+    /// Collections and aliases can't be created, removed or changed until the snapshot is finished,
+    /// so the snapshot is consistent with the alias mapping.
+    ///
+    /// # Result
+    ///
+    /// Description of the snapshot file, created in the `snapshots_path` directory
+    pub async fn create_full_snapshot(&self) -> Result<SnapshotDescription, StorageError> {
+        // Collections and aliases are only modified under the write lock
+        let collections = self.collections.read().await;
+
+        let snapshots_path = Path::new(&self.storage_config.snapshots_path);
+        let snapshot_name = Self::snapshot_file_name(FULL_SNAPSHOT_PREFIX);
+        let snapshot_path = snapshots_path.join(&snapshot_name);
+        let tmp_path = snapshot_path.with_extension("parts");
+        create_dir_all(&tmp_path).map_err(|err| StorageError::ServiceError {
+            description: format!("Can't create snapshots directory. Error: {}", err),
+        })?;
+
+        let snapshot_res: Result<(), StorageError> = async {
+            let mut collection_snapshots = vec![];
+            for (collection_name, collection) in collections.iter() {
+                let collection_snapshot_path =
+                    tmp_path.join(format!("{}.{}", collection_name, SNAPSHOT_FILE_EXTENSION));
+                collection
+                    .create_snapshot(&collection_snapshot_path, &[])
+                    .await?;
+                collection_snapshots.push((collection_name.clone(), collection_snapshot_path));
+            }
+            pack_full_snapshot(&collection_snapshots, &self.all_aliases()?, &snapshot_path)
+        }
+        .await;
+        remove_dir_all(&tmp_path).ok();
+        snapshot_res?;
+
+        Ok(SnapshotDescription {
+            name: snapshot_name,
+            size: snapshot_path.metadata()?.len(),
+        })
+    }
+
+    /// Recreates all collections and aliases from the full snapshot file.
+    /// Neither of the collections and aliases of the snapshot should exist.
+    /// Either all collections and aliases are recovered, or none of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot_path` - path of the snapshot file, created by [`Self::create_full_snapshot`]
+    pub async fn recover_full_snapshot(&self, snapshot_path: &Path) -> Result<bool, StorageError> {
+        let tmp_path = Path::new(&self.storage_config.snapshots_path)
+            .join(Self::snapshot_file_name(FULL_SNAPSHOT_PREFIX))
+            .with_extension("recover");
+        create_dir_all(&tmp_path).map_err(|err| StorageError::ServiceError {
+            description: format!("Can't create snapshots directory. Error: {}", err),
+        })?;
+
+        let mut loaded_collections: Vec<(String, Collection, PathBuf)> = vec![];
+        let load_res: Result<HashMap<String, String>, StorageError> = async {
+            let (collection_snapshots, mut aliases) =
+                unpack_full_snapshot(snapshot_path, &tmp_path)?;
+            {
+                let collections = self.collections.read().await;
+                for (collection_name, _) in &collection_snapshots {
+                    collections
+                        .validate_collection_not_exists(collection_name)
+                        .await?;
+                }
+                let alias_names = aliases.keys().cloned().collect_vec();
+                self.validate_snapshot_aliases(&collections, &alias_names)
+                    .await?;
+            }
+
+            for (collection_name, collection_snapshot_path) in &collection_snapshots {
+                let collection_path = self.create_new_collection_path(collection_name)?;
+                let (collection, collection_aliases) =
+                    Self::load_collection_snapshot(collection_snapshot_path, &collection_path)
+                        .await?;
+                for alias_name in collection_aliases {
+                    aliases.insert(alias_name, collection_name.clone());
+                }
+                loaded_collections.push((collection_name.clone(), collection, collection_path));
+            }
+            Ok(aliases)
+        }
+        .await;
+        remove_dir_all(&tmp_path).ok();
+
+        let aliases = match load_res {
+            Ok(aliases) => aliases,
+            Err(err) => {
+                Self::discard_collections(loaded_collections).await;
+                return Err(err);
+            }
+        };
+
+        let mut write_collections = self.collections.write().await;
+        let validation_res: Result<(), StorageError> = async {
+            for (collection_name, _, _) in &loaded_collections {
+                write_collections
+                    .validate_collection_not_exists(collection_name)
+                    .await?;
+            }
+            let alias_names = aliases.keys().cloned().collect_vec();
+            self.validate_snapshot_aliases(&write_collections, &alias_names)
+                .await
+        }
+        .await;
+        if let Err(err) = validation_res {
+            drop(write_collections);
+            Self::discard_collections(loaded_collections).await;
+            return Err(err);
+        }
+
+        let mut aliases_batch = Batch::default();
+        for (alias_name, collection_name) in &aliases {
+            aliases_batch.insert(alias_name.as_bytes(), collection_name.as_bytes());
+        }
+        let persist_res = self
+            .alias_persistence
+            .apply_batch(aliases_batch)
+            .and_then(|_| self.alias_persistence.flush());
+        if let Err(err) = persist_res {
+            drop(write_collections);
+            Self::discard_collections(loaded_collections).await;
+            return Err(err.into());
+        }
+
+        for (collection_name, collection, _) in loaded_collections {
+            write_collections.insert(collection_name, Arc::new(collection));
+        }
+        Ok(true)
+    }
+
+    /// Recreates collection and its aliases from the snapshot file.
+    /// Neither the collection, nor any of its aliases should exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - name of the collection to create
+    /// * `snapshot_path` - path of the snapshot file, created by [`Self::create_snapshot`]
+    pub async fn recover_snapshot(
+        &self,
+        collection_name: &str,
+        snapshot_path: &Path,
+    ) -> Result<bool, StorageError> {
+        self.collections
+            .read()
+            .await
+            .validate_collection_not_exists(collection_name)
+            .await?;
+
+        let collection_path = self.create_new_collection_path(collection_name)?;
+        let (collection, aliases) =
+            Self::load_collection_snapshot(snapshot_path, &collection_path).await?;
+
+        let mut write_collections = self.collections.write().await;
+        let validation_res = write_collections
+            .validate_collection_not_exists(collection_name)
+            .await;
+        let validation_res = match validation_res {
+            Ok(()) => {
+                self.validate_snapshot_aliases(&write_collections, &aliases)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+        if let Err(err) = validation_res {
+            drop(write_collections);
+            Self::discard_collection(collection, &collection_path).await;
+            return Err(err);
+        }
+
+        write_collections.insert(collection_name.to_string(), Arc::new(collection));
+        for alias_name in aliases {
+            self.alias_persistence
+                .insert(alias_name.as_bytes(), collection_name.as_bytes())?;
+        }
+        self.alias_persistence.flush()?;
+        Ok(true)
+    }
+
+    /// Unpacks the collection snapshot into the empty collection directory and loads it.
+    /// Snapshot files are not trusted, so loading errors are reported as bad input.
+    /// The directory is removed on failure.
+    ///
+    /// # Result
+    ///
+    /// Loaded collection, which is not registered yet, and the aliases of the snapshot
+    async fn load_collection_snapshot(
+        snapshot_path: &Path,
+        collection_path: &Path,
+    ) -> Result<(Collection, Vec<String>), StorageError> {
+        let snapshot_path = snapshot_path.to_path_buf();
+        let unpacked_path = collection_path.to_path_buf();
+        // Loading reads all segments and replays WAL, so it is not done on the async workers
+        let load_res = tokio::task::spawn_blocking(move || -> Result<_, StorageError> {
+            let aliases = unpack_snapshot(&snapshot_path, &unpacked_path)?;
+            let collection =
+                try_load_collection(&unpacked_path).map_err(|err| StorageError::BadInput {
+                    description: format!("Can't load collection from the snapshot: {}", err),
+                })?;
+            Ok((collection, aliases))
+        })
+        .await
+        .unwrap_or_else(|err| {
+            // Broken WAL entries cause panic during the loading
+            Err(StorageError::BadInput {
+                description: format!("Can't load collection from the snapshot: {}", err),
+            })
+        });
+
+        if load_res.is_err() {
+            remove_dir_all(collection_path).ok();
+        }
+        load_res
+    }
+
+    /// Discards collections, loaded from the snapshot, but not registered in the storage
+    async fn discard_collections(collections: Vec<(String, Collection, PathBuf)>) {
+        for (_, collection, collection_path) in collections {
+            Self::discard_collection(collection, &collection_path).await;
+        }
+    }
+
+    async fn validate_snapshot_aliases(
+        &self,
+        collections: &Collections,
+        aliases: &[String],
+    ) -> Result<(), StorageError> {
+        for alias_name in aliases {
+            collections
+                .validate_collection_not_exists(alias_name)
+                .await?;
+            if self.alias_persistence.contains_key(alias_name.as_bytes())? {
+                return Err(StorageError::BadInput {
+                    description: format!("Alias {} already exists!", alias_name),
+                });
+            }
+        }
+        Ok(())
+    }
+
     pub async fn update_aliases(
         &self,
         operation: ChangeAliasesOperation,
@@ -325,7 +814,28 @@ impl TableOfContent {
         Ok(read_collection.get(&real_collection_name).unwrap().clone())
     }
 
-    /// Recommend points using positive and negative example from the request
+    /// Collection to look up the recommendation examples in.
+    /// Looking up in the same collection, maybe through an alias, is the same as
+    /// not specifying the lookup collection at all
+    async fn lookup_collection(
+        &self,
+        collection_name: &str,
+        lookup_from: Option<&LookupLocation>,
+    ) -> Result<Option<Arc<Collection>>, StorageError> {
+        let lookup_from = match lookup_from {
+            None => return Ok(None),
+            Some(lookup_from) => lookup_from,
+        };
+        let lookup_collection_name = self.resolve_name(&lookup_from.collection).await?;
+        if lookup_collection_name == self.resolve_name(collection_name).await? {
+            Ok(None)
+        } else {
+            Ok(Some(self.get_collection(&lookup_collection_name).await?))
+        }
+    }
+
+    /// Recommend points using positive and negative example from the request.
+    /// Example point ids are looked up in the `lookup_from` collection of the request, if any
     ///
     /// # Arguments
     ///
@@ -341,9 +851,43 @@ impl TableOfContent {
         request: Arc<RecommendRequest>,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
         let collection = self.get_collection(collection_name).await?;
+        let lookup_collection = self
+            .lookup_collection(collection_name, request.lookup_from.as_ref())
+            .await?;
         collection
             .recommend_by(
                 request,
+                lookup_collection.as_deref(),
+                self.segment_searcher.deref(),
+                self.search_runtime.handle(),
+            )
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Recommend points and group them by the value of the payload field
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - for what collection do we recommend
+    /// * `request` - [`RecommendGroupsRequest`]
+    ///
+    /// # Result
+    ///
+    /// Groups of points with recommendation score
+    pub async fn recommend_groups(
+        &self,
+        collection_name: &str,
+        request: RecommendGroupsRequest,
+    ) -> Result<GroupsResult, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        let lookup_collection = self
+            .lookup_collection(collection_name, request.lookup_from.as_ref())
+            .await?;
+        collection
+            .recommend_groups(
+                request,
+                lookup_collection.as_deref(),
                 self.segment_searcher.deref(),
                 self.search_runtime.handle(),
             )
@@ -378,6 +922,58 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Perform multiple searches in a single pass over the collection segments
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we search
+    /// * `request` - [`SearchRequestBatch`]
+    ///
+    /// # Result
+    ///
+    /// Points with search score for each search of the batch, in the order of the searches
+    pub async fn search_batch(
+        &self,
+        collection_name: &str,
+        request: SearchRequestBatch,
+    ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        self.segment_searcher
+            .search_batch(
+                collection.segments(),
+                Arc::new(request),
+                self.search_runtime.handle(),
+            )
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Search for the closest points and group them by the value of the payload field
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we search
+    /// * `request` - [`SearchGroupsRequest`]
+    ///
+    /// # Result
+    ///
+    /// Groups of points with search score, ordered by the score of the best point
+    pub async fn search_groups(
+        &self,
+        collection_name: &str,
+        request: SearchGroupsRequest,
+    ) -> Result<GroupsResult, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection
+            .search_groups(
+                request,
+                self.segment_searcher.deref(),
+                self.search_runtime.handle(),
+            )
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Return specific points by IDs
     ///
     /// # Arguments
@@ -409,6 +1005,19 @@ impl TableOfContent {
         self.collections.read().await.keys().cloned().collect()
     }
 
+    /// Mapping of all aliases into the collection names
+    pub fn all_aliases(&self) -> Result<HashMap<String, String>, StorageError> {
+        let mut result = HashMap::new();
+        for pair in self.alias_persistence.iter() {
+            let (alias_bt, target_collection_bt) = pair?;
+            result.insert(
+                from_utf8(&alias_bt).unwrap().to_string(),
+                from_utf8(&target_collection_bt).unwrap().to_string(),
+            );
+        }
+        Ok(result)
+    }
+
     /// List of all aliases for a given collection
     pub fn collection_aliases(&self, collection_name: &str) -> Result<Vec<String>, StorageError> {
         let mut result = vec![];
@@ -445,6 +1054,25 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Count points which satisfy the filtering conditions
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - which collection to use
+    /// * `request` - [`CountRequest`]
+    ///
+    /// # Result
+    ///
+    /// Exact or estimated number of points
+    pub async fn count(
+        &self,
+        collection_name: &str,
+        request: CountRequest,
+    ) -> Result<CountResult, StorageError> {
+        let collection = self.get_collection(collection_name).await?;
+        collection.count(request).await.map_err(|err| err.into())
+    }
+
     pub async fn update(
         &self,
         collection_name: &str,
@@ -13,8 +13,15 @@ pub struct PerformanceConfig {
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct StorageConfig {
     pub storage_path: String,
+    /// Where to store collection snapshots
+    #[serde(default = "default_snapshots_path")]
+    pub snapshots_path: String,
     pub optimizers: OptimizersConfig,
     pub wal: WalConfig,
     pub performance: PerformanceConfig,
     pub hnsw_index: HnswConfig,
 }
+
+fn default_snapshots_path() -> String {
+    "./snapshots".to_string()
+}
@@ -7,6 +7,7 @@ use tokio::runtime::Runtime;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use collection::config::{VectorParams, VectorsConfig};
     use segment::types::Distance;
     use storage::content_manager::storage_ops::{
         ChangeAliasesOperation, CreateAlias, CreateCollection, CreateCollectionOperation,
@@ -19,6 +20,12 @@ mod tests {
 
         let config = StorageConfig {
             storage_path: storage_dir.path().to_str().unwrap().to_string(),
+            snapshots_path: storage_dir
+                .path()
+                .join("snapshots")
+                .to_str()
+                .unwrap()
+                .to_string(),
             optimizers: OptimizersConfig {
                 deleted_threshold: 0.5,
                 vacuum_min_vector_number: 100,
@@ -47,11 +54,20 @@ mod tests {
                     CreateCollectionOperation {
                         name: "test".to_string(),
                         create_collection: CreateCollection {
-                            vector_size: 10,
-                            distance: Distance::Cosine,
+                            vectors: Some(VectorsConfig::Single(VectorParams {
+                                size: 10,
+                                distance: Distance::Cosine,
+                                hnsw_config: None,
+                            })),
+                            vector_size: None,
+                            distance: None,
+                            sparse_vectors: None,
                             hnsw_config: None,
                             wal_config: None,
                             optimizers_config: None,
+                            quantization_config: None,
+                            product_quantization_config: None,
+                            payload_schema: None,
                         },
                     },
                 )),
@@ -0,0 +1,349 @@
+use collection::collection_builder::optimizers_builder::OptimizersConfig;
+use storage::content_manager::toc::TableOfContent;
+use storage::types::{PerformanceConfig, StorageConfig};
+use tempdir::TempDir;
+use tokio::runtime::Runtime;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collection::config::{VectorParams, VectorsConfig};
+    use collection::operations::point_ops::PointInsertOperations::BatchPoints;
+    use collection::operations::point_ops::PointOperations;
+    use collection::operations::types::CountRequest;
+    use collection::operations::CollectionUpdateOperations;
+    use segment::types::Distance;
+    use std::path::Path;
+    use storage::content_manager::storage_ops::{
+        ChangeAliasesOperation, CreateAlias, CreateCollection, CreateCollectionOperation,
+        DeleteAlias, StorageOperations,
+    };
+
+    #[test]
+    fn test_snapshot_and_recover() {
+        let storage_dir = TempDir::new("storage").unwrap();
+
+        let config = StorageConfig {
+            storage_path: storage_dir.path().to_str().unwrap().to_string(),
+            snapshots_path: storage_dir
+                .path()
+                .join("snapshots")
+                .to_str()
+                .unwrap()
+                .to_string(),
+            optimizers: OptimizersConfig {
+                deleted_threshold: 0.5,
+                vacuum_min_vector_number: 100,
+                max_segment_number: 2,
+                memmap_threshold: 100,
+                indexing_threshold: 100,
+                payload_indexing_threshold: 100,
+                flush_interval_sec: 2,
+                max_optimization_threads: 2,
+            },
+            wal: Default::default(),
+            performance: PerformanceConfig {
+                max_search_threads: 1,
+            },
+            hnsw_index: Default::default(),
+        };
+
+        let runtime = Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+
+        let toc = TableOfContent::new(&config, runtime);
+
+        handle
+            .block_on(
+                toc.perform_collection_operation(StorageOperations::CreateCollection(
+                    CreateCollectionOperation {
+                        name: "test".to_string(),
+                        create_collection: CreateCollection {
+                            vectors: Some(VectorsConfig::Single(VectorParams {
+                                size: 4,
+                                distance: Distance::Dot,
+                                hnsw_config: None,
+                            })),
+                            vector_size: None,
+                            distance: None,
+                            sparse_vectors: None,
+                            hnsw_config: None,
+                            wal_config: None,
+                            optimizers_config: None,
+                            quantization_config: None,
+                            product_quantization_config: None,
+                            payload_schema: None,
+                        },
+                    },
+                )),
+            )
+            .unwrap();
+
+        handle
+            .block_on(
+                toc.perform_collection_operation(StorageOperations::ChangeAliases(
+                    ChangeAliasesOperation {
+                        actions: vec![CreateAlias {
+                            collection_name: "test".to_string(),
+                            alias_name: "test_alias".to_string(),
+                        }
+                        .into()],
+                    },
+                )),
+            )
+            .unwrap();
+
+        let insert_points = CollectionUpdateOperations::PointOperation(
+            PointOperations::UpsertPoints(BatchPoints {
+                ids: vec![0.into(), 1.into(), 2.into()],
+                vectors: vec![
+                    vec![1.0, 0.0, 1.0, 1.0].into(),
+                    vec![1.0, 0.0, 1.0, 0.0].into(),
+                    vec![1.0, 1.0, 1.0, 1.0].into(),
+                ],
+                payloads: None,
+            }),
+        );
+        handle
+            .block_on(toc.update("test", insert_points, true))
+            .unwrap();
+
+        let snapshot = handle.block_on(toc.create_snapshot("test_alias")).unwrap();
+        assert!(snapshot.size > 0);
+        let snapshot_path = Path::new(&config.snapshots_path)
+            .join("test")
+            .join(&snapshot.name);
+        assert!(snapshot_path.exists());
+
+        // Collection, which already exists, can't be overwritten by the snapshot
+        assert!(handle
+            .block_on(toc.recover_snapshot("test", &snapshot_path))
+            .is_err());
+
+        handle
+            .block_on(
+                toc.perform_collection_operation(StorageOperations::ChangeAliases(
+                    ChangeAliasesOperation {
+                        actions: vec![DeleteAlias {
+                            alias_name: "test_alias".to_string(),
+                        }
+                        .into()],
+                    },
+                )),
+            )
+            .unwrap();
+        handle.block_on(toc.delete_collection("test")).unwrap();
+
+        handle
+            .block_on(toc.recover_snapshot("test_recovered", &snapshot_path))
+            .unwrap();
+
+        let count = handle
+            .block_on(toc.count(
+                "test_alias",
+                CountRequest {
+                    filter: None,
+                    exact: true,
+                },
+            ))
+            .unwrap();
+        assert_eq!(count.count, 3);
+    }
+
+    #[test]
+    fn test_full_snapshot_and_recover() {
+        let storage_dir = TempDir::new("storage").unwrap();
+
+        let config = StorageConfig {
+            storage_path: storage_dir.path().to_str().unwrap().to_string(),
+            snapshots_path: storage_dir
+                .path()
+                .join("snapshots")
+                .to_str()
+                .unwrap()
+                .to_string(),
+            optimizers: OptimizersConfig {
+                deleted_threshold: 0.5,
+                vacuum_min_vector_number: 100,
+                max_segment_number: 2,
+                memmap_threshold: 100,
+                indexing_threshold: 100,
+                payload_indexing_threshold: 100,
+                flush_interval_sec: 2,
+                max_optimization_threads: 2,
+            },
+            wal: Default::default(),
+            performance: PerformanceConfig {
+                max_search_threads: 1,
+            },
+            hnsw_index: Default::default(),
+        };
+
+        let runtime = Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+
+        let toc = TableOfContent::new(&config, runtime);
+
+        for (collection_name, ids) in [("test1", vec![0, 1, 2]), ("test2", vec![3, 4])] {
+            handle
+                .block_on(
+                    toc.perform_collection_operation(StorageOperations::CreateCollection(
+                        CreateCollectionOperation {
+                            name: collection_name.to_string(),
+                            create_collection: CreateCollection {
+                                vectors: Some(VectorsConfig::Single(VectorParams {
+                                    size: 4,
+                                    distance: Distance::Dot,
+                                    hnsw_config: None,
+                                })),
+                                vector_size: None,
+                                distance: None,
+                                sparse_vectors: None,
+                                hnsw_config: None,
+                                wal_config: None,
+                                optimizers_config: None,
+                                quantization_config: None,
+                                product_quantization_config: None,
+                                payload_schema: None,
+                            },
+                        },
+                    )),
+                )
+                .unwrap();
+
+            let insert_points = CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(BatchPoints {
+                    vectors: ids
+                        .iter()
+                        .map(|&id| vec![id as f32, 0.0, 1.0, 1.0].into())
+                        .collect(),
+                    ids: ids.into_iter().map(|id: u64| id.into()).collect(),
+                    payloads: None,
+                }),
+            );
+            handle
+                .block_on(toc.update(collection_name, insert_points, true))
+                .unwrap();
+        }
+
+        handle
+            .block_on(
+                toc.perform_collection_operation(StorageOperations::ChangeAliases(
+                    ChangeAliasesOperation {
+                        actions: vec![CreateAlias {
+                            collection_name: "test2".to_string(),
+                            alias_name: "test_alias".to_string(),
+                        }
+                        .into()],
+                    },
+                )),
+            )
+            .unwrap();
+
+        let snapshot = handle.block_on(toc.create_full_snapshot()).unwrap();
+        let snapshot_path = Path::new(&config.snapshots_path).join(&snapshot.name);
+        assert!(snapshot_path.exists());
+
+        // Collections of the snapshot already exist in the source storage
+        assert!(handle
+            .block_on(toc.recover_full_snapshot(&snapshot_path))
+            .is_err());
+
+        let new_storage_dir = TempDir::new("new_storage").unwrap();
+        let new_config = StorageConfig {
+            storage_path: new_storage_dir.path().to_str().unwrap().to_string(),
+            snapshots_path: new_storage_dir
+                .path()
+                .join("snapshots")
+                .to_str()
+                .unwrap()
+                .to_string(),
+            ..config.clone()
+        };
+
+        let new_runtime = Runtime::new().unwrap();
+        let new_handle = new_runtime.handle().clone();
+
+        let new_toc = TableOfContent::new(&new_config, new_runtime);
+
+        new_handle
+            .block_on(new_toc.recover_full_snapshot(&snapshot_path))
+            .unwrap();
+
+        let mut collections = new_handle.block_on(new_toc.all_collections());
+        collections.sort();
+        assert_eq!(collections, vec!["test1".to_string(), "test2".to_string()]);
+        assert_eq!(
+            new_toc.collection_aliases("test2").unwrap(),
+            vec!["test_alias".to_string()]
+        );
+
+        for (collection_name, expected_count) in [("test1", 3), ("test_alias", 2)] {
+            let count = new_handle
+                .block_on(new_toc.count(
+                    collection_name,
+                    CountRequest {
+                        filter: None,
+                        exact: true,
+                    },
+                ))
+                .unwrap();
+            assert_eq!(count.count, expected_count);
+        }
+    }
+
+    #[test]
+    fn test_snapshot_location() {
+        let storage_dir = TempDir::new("storage").unwrap();
+
+        let config = StorageConfig {
+            storage_path: storage_dir.path().to_str().unwrap().to_string(),
+            snapshots_path: storage_dir
+                .path()
+                .join("snapshots")
+                .to_str()
+                .unwrap()
+                .to_string(),
+            optimizers: OptimizersConfig {
+                deleted_threshold: 0.5,
+                vacuum_min_vector_number: 100,
+                max_segment_number: 2,
+                memmap_threshold: 100,
+                indexing_threshold: 100,
+                payload_indexing_threshold: 100,
+                flush_interval_sec: 2,
+                max_optimization_threads: 2,
+            },
+            wal: Default::default(),
+            performance: PerformanceConfig {
+                max_search_threads: 1,
+            },
+            hnsw_index: Default::default(),
+        };
+
+        let runtime = Runtime::new().unwrap();
+
+        let toc = TableOfContent::new(&config, runtime);
+
+        let snapshots_path = Path::new(&config.snapshots_path);
+        std::fs::create_dir_all(snapshots_path.join("test")).unwrap();
+        std::fs::write(snapshots_path.join("test").join("test.snapshot"), b"data").unwrap();
+        std::fs::write(storage_dir.path().join("secret"), b"data").unwrap();
+
+        let snapshot_path = toc.snapshot_location_path("test/test.snapshot").unwrap();
+        assert!(snapshot_path.ends_with("test/test.snapshot"));
+
+        // Files outside of the snapshots directory are not accessible
+        assert!(toc.snapshot_location_path("../secret").is_err());
+        assert!(toc.snapshot_location_path("test/../../secret").is_err());
+        let absolute_path = storage_dir.path().join("secret");
+        assert!(toc
+            .snapshot_location_path(absolute_path.to_str().unwrap())
+            .is_err());
+
+        // Location should point to the existing file
+        assert!(toc.snapshot_location_path("test").is_err());
+        assert!(toc.snapshot_location_path("test/missing.snapshot").is_err());
+        assert!(toc.snapshot_location_path("").is_err());
+    }
+}
@@ -8,7 +8,7 @@ use pyo3::prelude::*;
 use pyo3::PyErr;
 use pyo3::types::PyBytes;
 use pyo3::exceptions::PyException;
-use segment::types::{PointIdType, VectorElementType, ScoredPoint, ScoreType, PayloadKeyType, TheMap, PayloadType, WithPayload};
+use segment::types::{ExtendedPointId, PointIdType, VectorElementType, ScoredPoint, ScoreType, PayloadKeyType, TheMap, PayloadType, WithPayload, DEFAULT_VECTOR_NAME};
 use segment::entry::entry_point::{OperationResult, SegmentEntry, OperationError};
 use segment::segment_constructor::build_segment;
 use prost::Message;
@@ -28,6 +28,22 @@ fn handle_inner_result<T> (result: OperationResult<T>) -> PyResult<T> {
                     Err(PyErr::new::<PyException, _>(
                         format!("Wrong vector. Expected_dim {} is different from received_dim {}",
                                 expected_dim, received_dim))),
+                OperationError::VectorNameNotExists {received_name} =>
+                    Err(PyErr::new::<PyException, _>(
+                        format!("Wrong vector name. Vector {} does not exist",
+                                received_name))),
+                OperationError::MissedVectorName {received_name} =>
+                    Err(PyErr::new::<PyException, _>(
+                        format!("Missed vector. Vector {} is required",
+                                received_name))),
+                OperationError::WrongVectorType {vector_name, expected_type} =>
+                    Err(PyErr::new::<PyException, _>(
+                        format!("Wrong vector type. Vector {} should be {}",
+                                vector_name, expected_type))),
+                OperationError::WrongSparseVector {vector_name, description} =>
+                    Err(PyErr::new::<PyException, _>(
+                        format!("Wrong sparse vector {}: {}",
+                                vector_name, description))),
                 OperationError::PointIdError {missed_point_id} =>
                     Err(PyErr::new::<PyException, _>(
                         format!("Wrong point id. Missed id {}",
@@ -46,6 +62,14 @@ fn handle_inner_result<T> (result: OperationResult<T>) -> PyResult<T> {
     }
 }
 
+/// Python segment is only ever filled with numeric ids
+fn num_point_id(point_id: PointIdType) -> u64 {
+    match point_id {
+        ExtendedPointId::NumId(id) => id,
+        ExtendedPointId::Uuid(uuid) => panic!("Unexpected UUID point id {}", uuid),
+    }
+}
+
 #[pymethods]
 impl PySegment {
     const DEFAULT_OP_NUM: u64 = u64::MAX; // Disable skip_by_version for now
@@ -56,17 +80,17 @@ impl PySegment {
         segment_result.map(|segment| PySegment { segment }).unwrap()
     }
 
-    pub fn index(&mut self, point_id: PointIdType, vector: &PyArray1<VectorElementType>) -> PyResult<bool> {
-        let result = self.segment.upsert_point(PySegment::DEFAULT_OP_NUM, point_id, &vector.to_vec().unwrap());
+    pub fn index(&mut self, point_id: u64, vector: &PyArray1<VectorElementType>) -> PyResult<bool> {
+        let result = self.segment.upsert_point(PySegment::DEFAULT_OP_NUM, point_id.into(), &vector.to_vec().unwrap());
         handle_inner_result(result)
     }
 
-    pub fn set_full_payload(&mut self, point_id: PointIdType, payload: &str) -> PyResult<bool> {
-        let result = self.segment.set_full_payload_with_json(PySegment::DEFAULT_OP_NUM, point_id, payload);
+    pub fn set_full_payload(&mut self, point_id: u64, payload: &str) -> PyResult<bool> {
+        let result = self.segment.set_full_payload_with_json(PySegment::DEFAULT_OP_NUM, point_id.into(), payload);
         handle_inner_result(result)
     }
 
-    pub fn set_full_payload_document(&mut self, point_id: PointIdType, payload: Vec<u8>) -> PyResult<bool> {
+    pub fn set_full_payload_document(&mut self, point_id: u64, payload: Vec<u8>) -> PyResult<bool> {
         fn _convert_doc_into_payload(doc: &jina_proto::DocumentProto) -> TheMap<PayloadKeyType, PayloadType> {
             let mut payload = TheMap::new();
             payload.insert("id".to_string(), PayloadType::Keyword(vec![doc.id.to_string()]));
@@ -107,6 +131,7 @@ impl PySegment {
                             // TODO: problem, qdrant matching only works on integers
                             Some(value::Kind::NumberValue(x)) => payload.insert(k.to_string(), PayloadType::Integer(vec![x.clone() as i64])),
                             Some(value::Kind::StringValue(x)) => payload.insert(k.to_string(), PayloadType::Keyword(vec![x.to_string()])),
+                            Some(value::Kind::BoolValue(x)) => payload.insert(k.to_string(), PayloadType::Bool(vec![*x])),
                             _ => None
                         };
                     }
@@ -117,25 +142,27 @@ impl PySegment {
         }
         let doc = jina_proto::DocumentProto::decode(&mut Cursor::new(payload)).unwrap();
         let inner_payload = _convert_doc_into_payload(&doc);
-        let result = self.segment.set_full_payload(PySegment::DEFAULT_OP_NUM, point_id, inner_payload);
+        let result = self.segment.set_full_payload(PySegment::DEFAULT_OP_NUM, point_id.into(), inner_payload);
         handle_inner_result(result)
     }
 
-    fn get_full_payload(&self, point_id: PointIdType) -> TheMap<PayloadKeyType, String> {
-        let payload = self.segment.payload(point_id).unwrap();
+    fn get_full_payload(&self, point_id: u64) -> TheMap<PayloadKeyType, String> {
+        let payload = self.segment.payload(point_id.into()).unwrap();
         let mut results = TheMap::new();
         for (k, _v) in payload {
             match _v {
                 PayloadType::Keyword(x) => results.insert(k, x.iter().map(|y| y.to_string()).collect()),
                 PayloadType::Integer(x) => results.insert(k, x.iter().map(|y| y.to_string()).collect()),
                 PayloadType::Float(x) => results.insert(k, x.iter().map(|y| y.to_string()).collect()),
+                PayloadType::Bool(x) => results.insert(k, x.iter().map(|y| y.to_string()).collect()),
+                PayloadType::Datetime(x) => results.insert(k, x.iter().map(|y| y.to_rfc3339()).collect()),
                 _ => None
             };
         }
         results
     }
 
-    fn get_full_payload_as_document(&self, point_id: PointIdType) -> PyObject {
+    fn get_full_payload_as_document(&self, point_id: u64) -> PyObject {
         //TODO: See how to better pass bytes without getting GIL: move all logic to new object.
         // Maybe create a PyDocument that wraps the conversion from Bytes and to Bytes and so on
         fn _get_string_value(value: &PayloadType) -> Option<String> {
@@ -177,7 +204,7 @@ impl PySegment {
             }
         }
 
-        let payload = self.segment.payload(point_id).unwrap();
+        let payload = self.segment.payload(point_id.into()).unwrap();
         let mut document = jina_proto::DocumentProto::default();
         let mut fields: Option<TheMap<String, Value>> = None;// TheMap::new(); //TheMap::new();
         for (k, v) in payload {
@@ -222,6 +249,18 @@ impl PySegment {
                             fields.as_mut().unwrap().insert(x.to_string(), Value{kind: Some(value::Kind::StringValue(y[0].to_string()))});
                             ()
                         },
+                        PayloadType::Bool(y) => {
+                            if let None = fields {
+                                fields = Some(TheMap::new());
+                            }
+                            fields.as_mut().unwrap().insert(x.to_string(), Value{kind: Some(value::Kind::BoolValue(y[0]))});
+                        },
+                        PayloadType::Datetime(y) => {
+                            if let None = fields {
+                                fields = Some(TheMap::new());
+                            }
+                            fields.as_mut().unwrap().insert(x.to_string(), Value{kind: Some(value::Kind::StringValue(y[0].to_rfc3339()))});
+                        },
                         _ => ()
                     }
                 }
@@ -237,15 +276,15 @@ impl PySegment {
         PyBytes::new(py, &buf).into()
     }
 
-    pub fn delete(&mut self, point_id: PointIdType) -> PyResult<bool> {
-        let result = self.segment.delete_point(PySegment::DEFAULT_OP_NUM, point_id);
+    pub fn delete(&mut self, point_id: u64) -> PyResult<bool> {
+        let result = self.segment.delete_point(PySegment::DEFAULT_OP_NUM, point_id.into());
         handle_inner_result(result)
     }
 
-    pub fn search(&self, vector: &PyArray1<VectorElementType>, filter: Option<String>, top_k: usize, params: Option<&PySearchParams>) -> PyResult<(Vec<PointIdType>, Vec<ScoreType>)> {
-        fn _convert_scored_point_vec(vec: Vec<ScoredPoint>) -> (Vec<PointIdType>, Vec<ScoreType>) {
+    pub fn search(&self, vector: &PyArray1<VectorElementType>, filter: Option<String>, top_k: usize, params: Option<&PySearchParams>) -> PyResult<(Vec<u64>, Vec<ScoreType>)> {
+        fn _convert_scored_point_vec(vec: Vec<ScoredPoint>) -> (Vec<u64>, Vec<ScoreType>) {
             vec.into_iter().map(
-                |scored_point| (scored_point.id, scored_point.score)).unzip()
+                |scored_point| (num_point_id(scored_point.id), scored_point.score)).unzip()
         }
         let qdrant_filter = filter.map(|f| {
             serde_json::from_str(&f).unwrap()
@@ -253,7 +292,7 @@ impl PySegment {
         let search_params = params.map(|p| p.params);
 
         let with_payload = WithPayload { enable: false, payload_selector: None };
-        let result = self.segment.search(&vector.to_vec().unwrap(), &with_payload, Option::from(&qdrant_filter), top_k, search_params.as_ref());
+        let result = self.segment.search(DEFAULT_VECTOR_NAME, &vector.to_vec().unwrap(), &with_payload, Option::from(&qdrant_filter), top_k, search_params.as_ref(), None);
         handle_inner_result(result.map(|vec| _convert_scored_point_vec(vec)))
     }
 }
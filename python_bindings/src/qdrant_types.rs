@@ -2,7 +2,8 @@ use pyo3::prelude::*;
 use std::path::Path;
 use std::io::BufReader;
 use std::fs::File;
-use segment::types::{Distance, Indexes, PayloadIndexType, StorageType, SegmentConfig, SearchParams, HnswConfig};
+use std::collections::HashMap;
+use segment::types::{Distance, Indexes, PayloadIndexType, StorageType, SegmentConfig, SearchParams, HnswConfig, VectorDataConfig, DEFAULT_VECTOR_NAME};
 use segment::segment::Segment;
 
 
@@ -155,11 +156,18 @@ impl PySegmentConfig {
            distance: &PyDistanceType,
            storage_type: &PyStorageType) -> Self {
 
-        let config = SegmentConfig { vector_size,
+        let vector_data = HashMap::from([(DEFAULT_VECTOR_NAME.to_owned(), VectorDataConfig {
+            size: vector_size,
+            distance: distance.distance,
+            hnsw_config: None,
+        })]);
+
+        let config = SegmentConfig { vector_data,
+            sparse_vector_data: HashMap::new(),
             index: index.index,
             payload_index: payload_index.map(|pid| pid.payload_index_type),
-            distance: distance.distance,
-            storage_type: storage_type.storage};
+            storage_type: storage_type.storage,
+            quantization_config: None};
 
         PySegmentConfig { config }
     }
@@ -170,11 +178,16 @@ impl PySegmentConfig {
         match file {
             Ok(f) => PySegmentConfig {config: serde_json::from_reader( BufReader::new(f)).unwrap() },
             _ => PySegmentConfig { config: SegmentConfig {
-                vector_size: 0,
+                vector_data: HashMap::from([(DEFAULT_VECTOR_NAME.to_owned(), VectorDataConfig {
+                    size: 0,
+                    distance: Distance::Cosine,
+                    hnsw_config: None,
+                })]),
+                sparse_vector_data: HashMap::new(),
                 index: Default::default(),
                 payload_index: None,
-                distance: Distance::Cosine,
-                storage_type: Default::default()
+                storage_type: Default::default(),
+                quantization_config: None
             } }
         }
     }
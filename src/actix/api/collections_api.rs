@@ -4,7 +4,7 @@ use actix_web::rt::time::Instant;
 use actix_web::{delete, get, patch, post, put, web, Responder};
 use std::sync::Arc;
 use storage::content_manager::storage_ops::{
-    ChangeAliasesOperation, CreateCollection, StorageOperations, UpdateCollection,
+    ChangeAliasesOperation, CreateCollection, RecoverSnapshot, StorageOperations, UpdateCollection,
 };
 use storage::content_manager::toc::TableOfContent;
 
@@ -81,6 +81,39 @@ async fn update_aliases(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/snapshots")]
+async fn create_snapshot(
+    toc: web::Data<Arc<TableOfContent>>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let name = path.into_inner();
+    let response = toc.create_snapshot(&name).await;
+    process_response(response, timing)
+}
+
+#[put("/collections/{name}/snapshots/recover")]
+async fn recover_snapshot(
+    toc: web::Data<Arc<TableOfContent>>,
+    path: web::Path<String>,
+    operation: web::Json<RecoverSnapshot>,
+) -> impl Responder {
+    let timing = Instant::now();
+    let name = path.into_inner();
+    let response = match toc.snapshot_location_path(&operation.location) {
+        Ok(snapshot_path) => toc.recover_snapshot(&name, &snapshot_path).await,
+        Err(err) => Err(err),
+    };
+    process_response(response, timing)
+}
+
+#[post("/snapshots")]
+async fn create_full_snapshot(toc: web::Data<Arc<TableOfContent>>) -> impl Responder {
+    let timing = Instant::now();
+    let response = toc.create_full_snapshot().await;
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
     cfg.service(get_collections)
@@ -89,5 +122,8 @@ pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
         .service(create_collection)
         .service(update_collection)
         .service(delete_collection)
-        .service(update_aliases);
+        .service(update_aliases)
+        .service(create_snapshot)
+        .service(recover_snapshot)
+        .service(create_full_snapshot);
 }
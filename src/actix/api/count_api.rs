@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use actix_web::rt::time::Instant;
+use actix_web::{post, web, Responder};
+
+use collection::operations::types::{CountRequest, CountResult};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+
+use crate::actix::helpers::process_response;
+
+async fn do_count_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: CountRequest,
+) -> Result<CountResult, StorageError> {
+    toc.count(collection_name, request).await
+}
+
+#[post("/collections/{name}/points/count")]
+pub async fn count_points(
+    toc: web::Data<Arc<TableOfContent>>,
+    path: web::Path<String>,
+    request: web::Json<CountRequest>,
+) -> impl Responder {
+    let collection_name = path.into_inner();
+    let timing = Instant::now();
+
+    let response = do_count_points(&toc.into_inner(), &collection_name, request.into_inner()).await;
+
+    process_response(response, timing)
+}
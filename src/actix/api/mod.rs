@@ -1,4 +1,5 @@
 pub mod collections_api;
+pub mod count_api;
 pub mod recommend_api;
 pub mod retrieve_api;
 pub mod search_api;
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use actix_web::rt::time::Instant;
 use actix_web::{post, web, Responder};
 
-use collection::operations::types::RecommendRequest;
+use collection::operations::types::{GroupsResult, RecommendGroupsRequest, RecommendRequest};
 use segment::types::ScoredPoint;
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
@@ -31,3 +31,25 @@ pub async fn recommend_points(
 
     process_response(response, timing)
 }
+
+async fn do_recommend_point_groups(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: RecommendGroupsRequest,
+) -> Result<GroupsResult, StorageError> {
+    toc.recommend_groups(collection_name, request).await
+}
+
+#[post("/collections/{name}/points/recommend/groups")]
+pub async fn recommend_point_groups(
+    toc: web::Data<Arc<TableOfContent>>,
+    path: web::Path<String>,
+    request: web::Json<RecommendGroupsRequest>,
+) -> impl Responder {
+    let name = path.into_inner();
+    let timing = Instant::now();
+
+    let response = do_recommend_point_groups(&toc.into_inner(), &name, request.into_inner()).await;
+
+    process_response(response, timing)
+}
@@ -52,11 +52,19 @@ async fn scroll_get_points(
 #[get("/collections/{name}/points/{id}")]
 pub async fn get_point(
     toc: web::Data<Arc<TableOfContent>>,
-    path: web::Path<(String, PointIdType)>,
+    path: web::Path<(String, String)>,
 ) -> impl Responder {
     let (collection_name, point_id) = path.into_inner();
     let timing = Instant::now();
 
+    let point_id: PointIdType = match point_id.parse() {
+        Ok(point_id) => point_id,
+        Err(description) => {
+            let response: Result<Record, _> = Err(StorageError::BadInput { description });
+            return process_response(response, timing);
+        }
+    };
+
     let response = do_get_point(&toc.into_inner(), &collection_name, point_id).await;
 
     let response = match response {
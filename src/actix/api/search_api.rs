@@ -3,7 +3,9 @@ use std::sync::Arc;
 use actix_web::rt::time::Instant;
 use actix_web::{post, web, Responder};
 
-use collection::operations::types::SearchRequest;
+use collection::operations::types::{
+    GroupsResult, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
+};
 use segment::types::ScoredPoint;
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
@@ -32,3 +34,49 @@ pub async fn search_points(
 
     process_response(response, timing)
 }
+
+async fn do_search_batch_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: SearchRequestBatch,
+) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+    toc.search_batch(collection_name, request).await
+}
+
+#[post("/collections/{name}/points/search/batch")]
+pub async fn search_batch_points(
+    toc: web::Data<Arc<TableOfContent>>,
+    path: web::Path<String>,
+    request: web::Json<SearchRequestBatch>,
+) -> impl Responder {
+    let collection_name = path.into_inner();
+    let timing = Instant::now();
+
+    let response =
+        do_search_batch_points(&toc.into_inner(), &collection_name, request.into_inner()).await;
+
+    process_response(response, timing)
+}
+
+async fn do_search_point_groups(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: SearchGroupsRequest,
+) -> Result<GroupsResult, StorageError> {
+    toc.search_groups(collection_name, request).await
+}
+
+#[post("/collections/{name}/points/search/groups")]
+pub async fn search_point_groups(
+    toc: web::Data<Arc<TableOfContent>>,
+    path: web::Path<String>,
+    request: web::Json<SearchGroupsRequest>,
+) -> impl Responder {
+    let collection_name = path.into_inner();
+    let timing = Instant::now();
+
+    let response =
+        do_search_point_groups(&toc.into_inner(), &collection_name, request.into_inner()).await;
+
+    process_response(response, timing)
+}
@@ -9,9 +9,10 @@ use actix_web::{error, get, web, App, HttpRequest, HttpResponse, HttpServer, Res
 use std::sync::Arc;
 use storage::content_manager::toc::TableOfContent;
 
-use crate::actix::api::recommend_api::recommend_points;
+use crate::actix::api::count_api::count_points;
+use crate::actix::api::recommend_api::{recommend_point_groups, recommend_points};
 use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points};
-use crate::actix::api::search_api::search_points;
+use crate::actix::api::search_api::{search_batch_points, search_point_groups, search_points};
 use crate::actix::api::update_api::update_points;
 use crate::common::models::VersionInfo;
 use crate::settings::Settings;
@@ -54,8 +55,12 @@ pub fn init(toc: Arc<TableOfContent>, settings: Settings) -> std::io::Result<()>
                 .service(get_point)
                 .service(get_points)
                 .service(scroll_points)
+                .service(count_points)
                 .service(search_points)
+                .service(search_batch_points)
+                .service(search_point_groups)
                 .service(recommend_points)
+                .service(recommend_point_groups)
         })
         // .workers(4)
         .bind(format!(
@@ -8,7 +8,9 @@ mod settings;
 #[cfg(feature = "grpc")]
 mod tonic;
 
+use clap::{crate_version, App, Arg, ArgMatches};
 use std::io::Error;
+use std::path::Path;
 use std::sync::Arc;
 use std::thread;
 use std::thread::JoinHandle;
@@ -17,7 +19,36 @@ use storage::content_manager::toc::TableOfContent;
 use crate::common::helpers::create_search_runtime;
 use crate::settings::Settings;
 
+fn parse_args() -> ArgMatches<'static> {
+    App::new("qdrant")
+        .version(crate_version!())
+        .arg(
+            Arg::with_name("snapshot")
+                .long("snapshot")
+                .value_name("PATH:COLLECTION")
+                .help(
+                    "Recover collection from the snapshot file on startup. \
+                    Skipped, if the collection already exists. Could be specified multiple times",
+                )
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("storage-snapshot")
+                .long("storage-snapshot")
+                .value_name("PATH")
+                .help(
+                    "Recover all collections and aliases from the full storage snapshot on startup. \
+                    Skipped, if the storage already contains any collection",
+                )
+                .takes_value(true),
+        )
+        .get_matches()
+}
+
 fn main() -> std::io::Result<()> {
+    let args = parse_args();
     let settings = Settings::new().expect("Can't read config.");
     std::env::set_var("RUST_LOG", &settings.log_level);
     env_logger::init();
@@ -31,6 +62,47 @@ fn main() -> std::io::Result<()> {
 
     let toc = TableOfContent::new(&settings.storage, runtime);
     runtime_handle.block_on(async {
+        if let Some(snapshot_path) = args.value_of("storage-snapshot") {
+            if toc.all_collections().await.is_empty() {
+                toc.recover_full_snapshot(Path::new(snapshot_path))
+                    .await
+                    .unwrap_or_else(|err| {
+                        panic!("Can't recover snapshot {}, error: {}", snapshot_path, err)
+                    });
+                info!("recovered storage from {}", snapshot_path);
+            } else {
+                warn!(
+                    "storage is not empty, snapshot {} is skipped",
+                    snapshot_path
+                );
+            }
+        }
+        for snapshot in args.values_of("snapshot").into_iter().flatten() {
+            let (snapshot_path, collection_name) = snapshot
+                .rsplit_once(':')
+                .expect("Snapshot should be specified as PATH:COLLECTION");
+            if toc
+                .all_collections()
+                .await
+                .iter()
+                .any(|x| x == collection_name)
+            {
+                warn!(
+                    "collection {} already exists, snapshot {} is skipped",
+                    collection_name, snapshot_path
+                );
+                continue;
+            }
+            toc.recover_snapshot(collection_name, Path::new(snapshot_path))
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("Can't recover snapshot {}, error: {}", snapshot_path, err)
+                });
+            info!(
+                "recovered collection {} from {}",
+                collection_name, snapshot_path
+            );
+        }
         for collection in toc.all_collections().await {
             info!("loaded collection: {}", collection);
         }
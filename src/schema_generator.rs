@@ -2,13 +2,15 @@ use schemars::{schema_for, JsonSchema};
 use serde::{Deserialize, Serialize};
 
 use collection::operations::types::{
-    CollectionInfo, RecommendRequest, Record, ScrollRequest, ScrollResult, SearchRequest,
-    UpdateResult,
+    CollectionInfo, CountRequest, CountResult, GroupsResult, RecommendGroupsRequest,
+    RecommendRequest, Record, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
+    SearchRequestBatch, UpdateResult,
 };
 use collection::operations::CollectionUpdateOperations;
+use collection::snapshot::SnapshotDescription;
 use segment::types::ScoredPoint;
 use storage::content_manager::storage_ops::{
-    ChangeAliasesOperation, CreateCollection, StorageOperations, UpdateCollection,
+    ChangeAliasesOperation, CreateCollection, RecoverSnapshot, StorageOperations, UpdateCollection,
 };
 
 use crate::actix::api::retrieve_api::PointRequest;
@@ -35,6 +37,14 @@ struct AllDefinitions {
     ad: CreateCollection,
     ae: UpdateCollection,
     af: ChangeAliasesOperation,
+    ag: SearchRequestBatch,
+    ah: SearchGroupsRequest,
+    ai: RecommendGroupsRequest,
+    aj: GroupsResult,
+    ak: CountRequest,
+    al: CountResult,
+    am: RecoverSnapshot,
+    an: SnapshotDescription,
 }
 
 fn save_schema<T: JsonSchema>() {
@@ -6,10 +6,14 @@ use crate::tonic::qdrant::collections_server::Collections;
 use crate::tonic::qdrant::{
     CollectionDescription, CollectionOperationResponse, CreateCollection, DeleteCollection,
     GetCollectionsRequest, GetCollectionsResponse, HnswConfigDiff, OptimizersConfigDiff,
-    UpdateCollection, WalConfigDiff,
+    PayloadFieldSchema, PayloadSchemaType, ProductQuantization, ScalarQuantization,
+    SparseVectorParams, TextIndexParams, UpdateCollection, VectorParams, WalConfigDiff,
 };
+use collection::config::{PayloadSchema, VectorsConfig};
 use num_traits::FromPrimitive;
-use std::convert::TryFrom;
+use segment::types::PayloadIndexParams;
+use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
 use std::sync::Arc;
 use std::time::Instant;
 use storage::content_manager::errors::StorageError;
@@ -61,8 +65,9 @@ impl Collections for CollectionsService {
         &self,
         request: Request<UpdateCollection>,
     ) -> Result<Response<CollectionOperationResponse>, Status> {
-        let operations =
-            storage::content_manager::storage_ops::StorageOperations::from(request.into_inner());
+        let operations = storage::content_manager::storage_ops::StorageOperations::try_from(
+            request.into_inner(),
+        )?;
         let timing = Instant::now();
         let result = self.toc.perform_collection_operation(operations).await;
 
@@ -103,23 +108,130 @@ impl TryFrom<CreateCollection> for storage::content_manager::storage_ops::Storag
     type Error = Status;
 
     fn try_from(value: CreateCollection) -> Result<Self, Self::Error> {
+        let sparse_vectors = if value.sparse_vectors.is_empty() {
+            None
+        } else {
+            Some(
+                value
+                    .sparse_vectors
+                    .into_iter()
+                    .map(|(name, params)| (name, params.into()))
+                    .collect(),
+            )
+        };
+
+        let (vectors, vector_size, distance) = if !value.vectors.is_empty() {
+            let vectors = value
+                .vectors
+                .into_iter()
+                .map(|(name, params)| Ok((name, params.try_into()?)))
+                .collect::<Result<_, Status>>()?;
+            (Some(VectorsConfig::Multi(vectors)), None, None)
+        } else if value.vector_size == 0 && sparse_vectors.is_some() {
+            // Collection with sparse vectors only
+            (None, None, None)
+        } else {
+            let distance = FromPrimitive::from_i32(value.distance)
+                .ok_or_else(|| Status::failed_precondition("Bad value of distance field!"))?;
+            (None, Some(value.vector_size as usize), Some(distance))
+        };
+
+        let payload_schema = if value.payload_schema.is_empty() {
+            None
+        } else {
+            Some(payload_schema_helper(value.payload_schema)?)
+        };
+
+        Ok(Self::CreateCollection(CreateCollectionOperation {
+            name: value.name,
+            create_collection: StorageCreateCollection {
+                vectors,
+                vector_size,
+                distance,
+                sparse_vectors,
+                hnsw_config: value.hnsw_config.map(|v| v.into()),
+                wal_config: value.wal_config.map(|v| v.into()),
+                optimizers_config: value.optimizers_config.map(|v| v.into()),
+                quantization_config: value.quantization_config.map(|v| v.into()),
+                product_quantization_config: value.product_quantization_config.map(|v| v.into()),
+                payload_schema,
+            },
+        }))
+    }
+}
+
+fn payload_schema_helper(
+    fields: HashMap<String, PayloadFieldSchema>,
+) -> Result<PayloadSchema, Status> {
+    fields
+        .into_iter()
+        .map(|(name, field_schema)| Ok((name, field_schema.try_into()?)))
+        .collect()
+}
+
+impl TryFrom<PayloadFieldSchema> for collection::config::PayloadFieldSchema {
+    type Error = Status;
+
+    fn try_from(value: PayloadFieldSchema) -> Result<Self, Self::Error> {
+        let field_type = PayloadSchemaType::from_i32(value.r#type)
+            .ok_or_else(|| Status::failed_precondition("Bad value of payload type field!"))?;
+        Ok(Self {
+            field_type: field_type.into(),
+            index: value.index,
+            index_params: value
+                .text_index_params
+                .map(|params| PayloadIndexParams::Text(params.into())),
+        })
+    }
+}
+
+impl From<PayloadSchemaType> for segment::types::PayloadSchemaType {
+    fn from(value: PayloadSchemaType) -> Self {
+        match value {
+            PayloadSchemaType::Keyword => Self::Keyword,
+            PayloadSchemaType::Integer => Self::Integer,
+            PayloadSchemaType::Float => Self::Float,
+            PayloadSchemaType::Geo => Self::Geo,
+            PayloadSchemaType::Bool => Self::Bool,
+            PayloadSchemaType::Datetime => Self::Datetime,
+            PayloadSchemaType::Object => Self::Object,
+        }
+    }
+}
+
+impl From<TextIndexParams> for segment::types::TextIndexParams {
+    fn from(value: TextIndexParams) -> Self {
+        Self {
+            min_token_len: value.min_token_len.map(|v| v as usize),
+            max_token_len: value.max_token_len.map(|v| v as usize),
+        }
+    }
+}
+
+impl TryFrom<VectorParams> for collection::config::VectorParams {
+    type Error = Status;
+
+    fn try_from(value: VectorParams) -> Result<Self, Self::Error> {
         if let Some(distance) = FromPrimitive::from_i32(value.distance) {
-            Ok(Self::CreateCollection(CreateCollectionOperation {
-                name: value.name,
-                create_collection: StorageCreateCollection {
-                    vector_size: value.vector_size as usize,
-                    distance,
-                    hnsw_config: value.hnsw_config.map(|v| v.into()),
-                    wal_config: value.wal_config.map(|v| v.into()),
-                    optimizers_config: value.optimizers_config.map(|v| v.into()),
-                },
-            }))
+            Ok(Self {
+                size: value.size as usize,
+                distance,
+                hnsw_config: value.hnsw_config.map(|v| v.into()),
+            })
         } else {
             Err(Status::failed_precondition("Bad value of distance field!"))
         }
     }
 }
 
+impl From<SparseVectorParams> for collection::config::SparseVectorParams {
+    fn from(value: SparseVectorParams) -> Self {
+        Self {
+            full_scan_threshold: value.full_scan_threshold.map(|v| v as usize),
+        }
+    }
+}
+
 impl From<HnswConfigDiff> for collection::operations::config_diff::HnswConfigDiff {
     fn from(value: HnswConfigDiff) -> Self {
         Self {
@@ -130,6 +242,23 @@ impl From<HnswConfigDiff> for collection::operations::config_diff::HnswConfigDif
     }
 }
 
+impl From<ScalarQuantization> for segment::types::ScalarQuantizationConfig {
+    fn from(value: ScalarQuantization) -> Self {
+        Self {
+            quantile: value.quantile,
+            rescore_multiplier: value.rescore_multiplier.map(|v| v as usize),
+        }
+    }
+}
+
+impl From<ProductQuantization> for segment::types::ProductQuantizationConfig {
+    fn from(value: ProductQuantization) -> Self {
+        Self {
+            bucket_size: value.bucket_size.map(|v| v as usize),
+        }
+    }
+}
+
 impl From<WalConfigDiff> for collection::operations::config_diff::WalConfigDiff {
     fn from(value: WalConfigDiff) -> Self {
         Self {
@@ -180,14 +309,20 @@ impl From<(Instant, Result<bool, StorageError>)> for CollectionOperationResponse
     }
 }
 
-impl From<UpdateCollection> for storage::content_manager::storage_ops::StorageOperations {
-    fn from(value: UpdateCollection) -> Self {
-        Self::UpdateCollection(UpdateCollectionOperation {
+impl TryFrom<UpdateCollection> for storage::content_manager::storage_ops::StorageOperations {
+    type Error = Status;
+
+    fn try_from(value: UpdateCollection) -> Result<Self, Self::Error> {
+        Ok(Self::UpdateCollection(UpdateCollectionOperation {
             name: value.name,
             update_collection: StorageUpdateCollection {
                 optimizers_config: value.optimizers_config.map(|v| v.into()),
+                payload_schema: value
+                    .payload_schema
+                    .map(|schema| payload_schema_helper(schema.fields))
+                    .transpose()?,
             },
-        })
+        }))
     }
 }
 
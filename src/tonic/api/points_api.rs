@@ -2,15 +2,30 @@ use tonic::{Request, Response, Status};
 
 use crate::common::points::do_update_points;
 use crate::tonic::qdrant::points_server::Points;
+use crate::tonic::qdrant::recommend_example::Example;
 use crate::tonic::qdrant::{
-    FloatPayload, GeoPayload, GeoPoint, IntegerPayload, KeywordPayload, PointStruct,
-    PointsOperationResponse, UpdateResult, UpsertPoints,
+    BoolPayload, Condition, CountPoints, CountResponse, CountResult, DatetimePayload,
+    FieldCondition, Filter, FloatPayload, GeoBoundingBox, GeoLineString, GeoPayload, GeoPoint,
+    GeoPolygon, GeoRadius, HasFieldCondition, HasIdCondition, IntegerPayload, IsEmptyCondition,
+    KeywordPayload, LookupLocation, Match, MinShould, ObjectPayload, Payload, PointId, PointStruct,
+    PointsOperationResponse, Range, RecommendExample as GrpcRecommendExample, RecommendPoints,
+    RecommendResponse, RecommendStrategy, ScoredPoint, UpdateResult, UpsertPoints, ValuesCount,
+    Vector,
 };
 use collection::operations::point_ops::{PointInsertOperations, PointOperations};
-use collection::operations::types::UpdateResult as CollectionUpdateResult;
+use collection::operations::types::{
+    default_exact_count, CountRequest, CountResult as CollectionCountResult,
+    LookupLocation as CollectionLookupLocation, RecommendExample, RecommendRequest,
+    RecommendStrategy as CollectionRecommendStrategy, UpdateResult as CollectionUpdateResult,
+    VectorStruct,
+};
 use collection::operations::CollectionUpdateOperations;
-use segment::types::{PayloadInterface, PayloadInterfaceStrict, PayloadVariant};
-use std::collections::HashMap;
+use segment::types::{
+    parse_datetime, AnyVariants, PayloadField, PayloadInterface, PayloadInterfaceStrict,
+    PayloadObjectInterface, PayloadVariant, PointIdType, RangeValue,
+    ScoredPoint as SegmentScoredPoint, SearchParams, SparseVector,
+};
+use std::collections::{HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
 use std::sync::Arc;
 use std::time::Instant;
@@ -59,6 +74,154 @@ impl Points for PointsService {
         let response = PointsOperationResponse::from((timing, result));
         Ok(Response::new(response))
     }
+
+    async fn count(
+        &self,
+        request: Request<CountPoints>,
+    ) -> Result<Response<CountResponse>, Status> {
+        let CountPoints {
+            collection,
+            filter,
+            exact,
+        } = request.into_inner();
+
+        let request = CountRequest {
+            filter: filter.map(|filter| filter.try_into()).transpose()?,
+            exact: exact.unwrap_or_else(default_exact_count),
+        };
+
+        let timing = Instant::now();
+        let result = self.toc.count(&collection, request).await;
+
+        let response = CountResponse::from((timing, result));
+        Ok(Response::new(response))
+    }
+
+    async fn recommend(
+        &self,
+        request: Request<RecommendPoints>,
+    ) -> Result<Response<RecommendResponse>, Status> {
+        let RecommendPoints {
+            collection,
+            positive,
+            negative,
+            filter,
+            top,
+            hnsw_ef,
+            using,
+            strategy,
+            positive_weights,
+            negative_weights,
+            positive_examples,
+            negative_examples,
+            lookup_from,
+        } = request.into_inner();
+
+        let strategy = RecommendStrategy::from_i32(strategy)
+            .ok_or_else(|| Status::failed_precondition("Bad value of strategy field!"))?;
+        // Empty weights are the same as not specified ones
+        let weights = |weights: Vec<f32>| {
+            if weights.is_empty() {
+                None
+            } else {
+                Some(weights)
+            }
+        };
+
+        let request = RecommendRequest {
+            positive: recommend_examples_helper(positive, positive_examples)?,
+            negative: recommend_examples_helper(negative, negative_examples)?,
+            filter: filter.map(|filter| filter.try_into()).transpose()?,
+            params: hnsw_ef.map(|hnsw_ef| SearchParams {
+                hnsw_ef: Some(hnsw_ef as usize),
+            }),
+            top: top as usize,
+            using,
+            strategy: strategy.into(),
+            positive_weights: weights(positive_weights),
+            negative_weights: weights(negative_weights),
+            lookup_from: lookup_from.map(|lookup_from| lookup_from.into()),
+        };
+
+        let timing = Instant::now();
+        let result = self.toc.recommend(&collection, Arc::new(request)).await;
+
+        let response = RecommendResponse::from((timing, result));
+        Ok(Response::new(response))
+    }
+}
+
+/// Examples given by ids come first, so the weights of the examples follow the same order
+fn recommend_examples_helper(
+    ids: Vec<PointId>,
+    examples: Vec<GrpcRecommendExample>,
+) -> Result<Vec<RecommendExample>, Status> {
+    ids.into_iter()
+        .map(|id| PointIdType::try_from(id).map(RecommendExample::from))
+        .chain(examples.into_iter().map(RecommendExample::try_from))
+        .collect()
+}
+
+impl TryFrom<GrpcRecommendExample> for RecommendExample {
+    type Error = Status;
+
+    fn try_from(value: GrpcRecommendExample) -> Result<Self, Self::Error> {
+        match value.example {
+            None => Err(Status::failed_precondition(
+                "Recommend example is not specified",
+            )),
+            Some(Example::Id(id)) => Ok(PointIdType::try_from(id)?.into()),
+            Some(Example::Vector(vector)) => {
+                if !vector.indices.is_empty() {
+                    return Err(Status::failed_precondition(
+                        "Sparse vectors are not supported as recommend examples",
+                    ));
+                }
+                Ok(vector.data.into())
+            }
+        }
+    }
+}
+
+impl From<LookupLocation> for CollectionLookupLocation {
+    fn from(value: LookupLocation) -> Self {
+        let LookupLocation { collection, vector } = value;
+        Self { collection, vector }
+    }
+}
+
+impl TryFrom<PointId> for PointIdType {
+    type Error = Status;
+
+    fn try_from(value: PointId) -> Result<Self, Self::Error> {
+        let PointId { num, uuid } = value;
+        match uuid {
+            None => Ok(num.into()),
+            Some(uuid) => uuid.parse().map_err(Status::failed_precondition),
+        }
+    }
+}
+
+impl From<PointIdType> for PointId {
+    fn from(value: PointIdType) -> Self {
+        match value {
+            PointIdType::NumId(num) => Self { num, uuid: None },
+            PointIdType::Uuid(uuid) => Self {
+                num: 0,
+                uuid: Some(uuid.to_string()),
+            },
+        }
+    }
+}
+
+impl From<RecommendStrategy> for CollectionRecommendStrategy {
+    fn from(value: RecommendStrategy) -> Self {
+        match value {
+            RecommendStrategy::AverageVector => Self::AverageVector,
+            RecommendStrategy::BestScore => Self::BestScore,
+            RecommendStrategy::Weighted => Self::Weighted,
+        }
+    }
 }
 
 impl TryFrom<PointStruct> for collection::operations::point_ops::PointStruct {
@@ -69,24 +232,35 @@ impl TryFrom<PointStruct> for collection::operations::point_ops::PointStruct {
             id,
             vector,
             payload,
+            vectors,
+            uuid,
         } = value;
 
+        let id = match uuid {
+            None => id.into(),
+            Some(uuid) => uuid.parse().map_err(Status::failed_precondition)?,
+        };
+
         let mut converted_payload = HashMap::new();
         for (key, payload_value) in payload.into_iter() {
-            let value = if let Some(keyword) = payload_value.keyword {
-                keyword.into()
-            } else if let Some(integer) = payload_value.integer {
-                integer.into()
-            } else if let Some(float) = payload_value.float {
-                float.into()
-            } else if let Some(geo) = payload_value.geo {
-                geo.into()
-            } else {
-                return Err(Status::failed_precondition("Unknown payload type"));
-            };
-            converted_payload.insert(key, value);
+            converted_payload.insert(key, PayloadInterface::try_from(payload_value)?);
         }
 
+        let vector = if vectors.is_empty() {
+            VectorStruct::Single(vector)
+        } else if vector.is_empty() {
+            VectorStruct::Multi(
+                vectors
+                    .into_iter()
+                    .map(|(name, vector)| (name, vector.into()))
+                    .collect(),
+            )
+        } else {
+            return Err(Status::failed_precondition(
+                "Only one of `vector` or `vectors` should be specified",
+            ));
+        };
+
         Ok(Self {
             id,
             vector,
@@ -95,6 +269,43 @@ impl TryFrom<PointStruct> for collection::operations::point_ops::PointStruct {
     }
 }
 
+impl From<Vector> for segment::types::Vector {
+    fn from(value: Vector) -> Self {
+        if value.indices.is_empty() {
+            Self::Dense(value.data)
+        } else {
+            Self::Sparse(SparseVector {
+                indices: value.indices,
+                values: value.data,
+            })
+        }
+    }
+}
+
+impl TryFrom<Payload> for PayloadInterface {
+    type Error = Status;
+
+    fn try_from(value: Payload) -> Result<Self, Self::Error> {
+        if let Some(keyword) = value.keyword {
+            Ok(keyword.into())
+        } else if let Some(integer) = value.integer {
+            Ok(integer.into())
+        } else if let Some(float) = value.float {
+            Ok(float.into())
+        } else if let Some(geo) = value.geo {
+            Ok(geo.into())
+        } else if let Some(boolean) = value.bool {
+            Ok(boolean.into())
+        } else if let Some(datetime) = value.datetime {
+            datetime.try_into()
+        } else if let Some(object) = value.object {
+            object.try_into()
+        } else {
+            Err(Status::failed_precondition("Unknown payload type"))
+        }
+    }
+}
+
 impl From<KeywordPayload> for PayloadInterface {
     fn from(value: KeywordPayload) -> Self {
         PayloadInterface::Payload(PayloadInterfaceStrict::Keyword(PayloadVariant::List(
@@ -127,6 +338,51 @@ impl From<GeoPayload> for PayloadInterface {
     }
 }
 
+impl From<BoolPayload> for PayloadInterface {
+    fn from(value: BoolPayload) -> Self {
+        PayloadInterface::Payload(PayloadInterfaceStrict::Bool(PayloadVariant::List(
+            value.value,
+        )))
+    }
+}
+
+impl TryFrom<DatetimePayload> for PayloadInterface {
+    type Error = Status;
+
+    fn try_from(value: DatetimePayload) -> Result<Self, Self::Error> {
+        let datetimes = value
+            .value
+            .iter()
+            .map(|datetime| parse_datetime(datetime))
+            .collect::<Result<_, _>>()
+            .map_err(Status::failed_precondition)?;
+        Ok(PayloadInterface::Payload(PayloadInterfaceStrict::Datetime(
+            PayloadVariant::List(datetimes),
+        )))
+    }
+}
+
+impl TryFrom<ObjectPayload> for PayloadInterface {
+    type Error = Status;
+
+    fn try_from(value: ObjectPayload) -> Result<Self, Self::Error> {
+        let objects = value
+            .value
+            .into_iter()
+            .map(|object| {
+                object
+                    .fields
+                    .into_iter()
+                    .map(|(key, payload)| Ok((key, PayloadInterface::try_from(payload)?)))
+                    .collect::<Result<PayloadObjectInterface, Status>>()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(PayloadInterface::Payload(PayloadInterfaceStrict::Object(
+            PayloadVariant::List(objects),
+        )))
+    }
+}
+
 impl From<GeoPoint> for segment::types::GeoPoint {
     fn from(value: GeoPoint) -> Self {
         Self {
@@ -136,6 +392,289 @@ impl From<GeoPoint> for segment::types::GeoPoint {
     }
 }
 
+fn conditions_helper(
+    conditions: Vec<Condition>,
+) -> Result<Option<Vec<segment::types::Condition>>, Status> {
+    if conditions.is_empty() {
+        Ok(None)
+    } else {
+        let converted = conditions
+            .into_iter()
+            .map(|condition| condition.try_into())
+            .collect::<Result<_, _>>()?;
+        Ok(Some(converted))
+    }
+}
+
+impl TryFrom<Filter> for segment::types::Filter {
+    type Error = Status;
+
+    fn try_from(value: Filter) -> Result<Self, Self::Error> {
+        let Filter {
+            should,
+            must,
+            must_not,
+            min_should,
+        } = value;
+
+        Ok(Self {
+            should: conditions_helper(should)?,
+            min_should: min_should.map(|x| x.try_into()).transpose()?,
+            must: conditions_helper(must)?,
+            must_not: conditions_helper(must_not)?,
+        })
+    }
+}
+
+impl TryFrom<MinShould> for segment::types::MinShould {
+    type Error = Status;
+
+    fn try_from(value: MinShould) -> Result<Self, Self::Error> {
+        let MinShould {
+            conditions,
+            min_count,
+        } = value;
+
+        Ok(Self {
+            conditions: conditions
+                .into_iter()
+                .map(|condition| condition.try_into())
+                .collect::<Result<_, _>>()?,
+            min_count: min_count as usize,
+        })
+    }
+}
+
+impl TryFrom<Condition> for segment::types::Condition {
+    type Error = Status;
+
+    fn try_from(value: Condition) -> Result<Self, Self::Error> {
+        let Condition {
+            field,
+            has_id,
+            filter,
+            is_empty,
+            has_field,
+        } = value;
+
+        match (field, has_id, filter, is_empty, has_field) {
+            (Some(field), None, None, None, None) => Ok(Self::Field(field.try_into()?)),
+            (None, Some(has_id), None, None, None) => Ok(Self::HasId(has_id.try_into()?)),
+            (None, None, Some(filter), None, None) => Ok(Self::Filter((*filter).try_into()?)),
+            (None, None, None, Some(IsEmptyCondition { key }), None) => {
+                Ok(Self::IsEmpty(segment::types::IsEmptyCondition {
+                    is_empty: PayloadField { key },
+                }))
+            }
+            (None, None, None, None, Some(HasFieldCondition { key })) => {
+                Ok(Self::HasField(segment::types::HasFieldCondition {
+                    has_field: PayloadField { key },
+                }))
+            }
+            _ => Err(Status::failed_precondition(
+                "Exactly one of the condition fields should be set",
+            )),
+        }
+    }
+}
+
+impl TryFrom<HasIdCondition> for segment::types::HasIdCondition {
+    type Error = Status;
+
+    fn try_from(value: HasIdCondition) -> Result<Self, Self::Error> {
+        let HasIdCondition { ids, uuids } = value;
+
+        let mut has_id: HashSet<PointIdType> = ids.into_iter().map(|id| id.into()).collect();
+        for uuid in uuids {
+            has_id.insert(uuid.parse().map_err(Status::failed_precondition)?);
+        }
+
+        Ok(Self { has_id })
+    }
+}
+
+impl TryFrom<FieldCondition> for segment::types::FieldCondition {
+    type Error = Status;
+
+    fn try_from(value: FieldCondition) -> Result<Self, Self::Error> {
+        let FieldCondition {
+            key,
+            r#match,
+            range,
+            geo_bounding_box,
+            geo_radius,
+            values_count,
+            geo_polygon,
+        } = value;
+
+        Ok(Self {
+            key,
+            r#match: r#match.map(|r#match| r#match.into()),
+            range: range.map(|range| range.try_into()).transpose()?,
+            geo_bounding_box: geo_bounding_box.map(|bbox| bbox.try_into()).transpose()?,
+            geo_radius: geo_radius.map(|radius| radius.try_into()).transpose()?,
+            geo_polygon: geo_polygon.map(|polygon| polygon.try_into()).transpose()?,
+            values_count: values_count.map(|count| count.into()),
+        })
+    }
+}
+
+fn any_variants_helper(keywords: Vec<String>, integers: Vec<i64>) -> Option<AnyVariants> {
+    if !keywords.is_empty() {
+        Some(AnyVariants::Keywords(keywords))
+    } else if !integers.is_empty() {
+        Some(AnyVariants::Integers(integers))
+    } else {
+        None
+    }
+}
+
+impl From<Match> for segment::types::Match {
+    fn from(value: Match) -> Self {
+        let Match {
+            keyword,
+            integer,
+            any_keywords,
+            any_integers,
+            except_keywords,
+            except_integers,
+            boolean,
+            text,
+        } = value;
+
+        Self {
+            keyword,
+            integer,
+            boolean,
+            text,
+            any: any_variants_helper(any_keywords, any_integers),
+            except: any_variants_helper(except_keywords, except_integers),
+        }
+    }
+}
+
+fn range_bound_helper(
+    float: Option<f64>,
+    integer: Option<i64>,
+) -> Result<Option<RangeValue>, Status> {
+    match (float, integer) {
+        (Some(_), Some(_)) => Err(Status::failed_precondition(
+            "Either float or integer range bound should be set, not both",
+        )),
+        (Some(float), None) => Ok(Some(RangeValue::Float(float))),
+        (None, integer) => Ok(integer.map(RangeValue::Integer)),
+    }
+}
+
+impl TryFrom<Range> for segment::types::Range {
+    type Error = Status;
+
+    fn try_from(value: Range) -> Result<Self, Self::Error> {
+        let Range {
+            lt,
+            gt,
+            gte,
+            lte,
+            lt_integer,
+            gt_integer,
+            gte_integer,
+            lte_integer,
+        } = value;
+
+        Ok(Self {
+            lt: range_bound_helper(lt, lt_integer)?,
+            gt: range_bound_helper(gt, gt_integer)?,
+            gte: range_bound_helper(gte, gte_integer)?,
+            lte: range_bound_helper(lte, lte_integer)?,
+        })
+    }
+}
+
+impl From<ValuesCount> for segment::types::ValuesCount {
+    fn from(value: ValuesCount) -> Self {
+        Self {
+            lt: value.lt.map(|x| x as usize),
+            gt: value.gt.map(|x| x as usize),
+            gte: value.gte.map(|x| x as usize),
+            lte: value.lte.map(|x| x as usize),
+        }
+    }
+}
+
+impl TryFrom<GeoBoundingBox> for segment::types::GeoBoundingBox {
+    type Error = Status;
+
+    fn try_from(value: GeoBoundingBox) -> Result<Self, Self::Error> {
+        match (value.top_left, value.bottom_right) {
+            (Some(top_left), Some(bottom_right)) => Ok(Self {
+                top_left: top_left.into(),
+                bottom_right: bottom_right.into(),
+            }),
+            _ => Err(Status::failed_precondition(
+                "Both corners of the geo bounding box should be set",
+            )),
+        }
+    }
+}
+
+impl TryFrom<GeoRadius> for segment::types::GeoRadius {
+    type Error = Status;
+
+    fn try_from(value: GeoRadius) -> Result<Self, Self::Error> {
+        match value.center {
+            Some(center) => Ok(Self {
+                center: center.into(),
+                radius: value.radius,
+            }),
+            None => Err(Status::failed_precondition(
+                "Center of the geo radius should be set",
+            )),
+        }
+    }
+}
+
+impl From<GeoLineString> for segment::types::GeoLineString {
+    fn from(value: GeoLineString) -> Self {
+        Self {
+            points: value.points.into_iter().map(|point| point.into()).collect(),
+        }
+    }
+}
+
+impl TryFrom<GeoPolygon> for segment::types::GeoPolygon {
+    type Error = Status;
+
+    fn try_from(value: GeoPolygon) -> Result<Self, Self::Error> {
+        let GeoPolygon {
+            exterior,
+            interiors,
+        } = value;
+
+        match exterior {
+            Some(exterior) => Ok(Self::new(
+                exterior.into(),
+                if interiors.is_empty() {
+                    None
+                } else {
+                    Some(interiors.into_iter().map(|ring| ring.into()).collect())
+                },
+            )),
+            None => Err(Status::failed_precondition(
+                "Exterior ring of the geo polygon should be set",
+            )),
+        }
+    }
+}
+
+fn error_description(err: StorageError) -> String {
+    match err {
+        StorageError::BadInput { description } => description,
+        StorageError::NotFound { description } => description,
+        StorageError::ServiceError { description } => description,
+        StorageError::BadRequest { description } => description,
+    }
+}
+
 impl From<(Instant, Result<CollectionUpdateResult, StorageError>)> for PointsOperationResponse {
     fn from(value: (Instant, Result<CollectionUpdateResult, StorageError>)) -> Self {
         let (timing, response) = value;
@@ -145,19 +684,59 @@ impl From<(Instant, Result<CollectionUpdateResult, StorageError>)> for PointsOpe
                 error: None,
                 time: timing.elapsed().as_secs_f64(),
             },
-            Err(err) => {
-                let error_description = match err {
-                    StorageError::BadInput { description } => description,
-                    StorageError::NotFound { description } => description,
-                    StorageError::ServiceError { description } => description,
-                    StorageError::BadRequest { description } => description,
-                };
-                Self {
-                    result: None,
-                    error: Some(error_description),
-                    time: timing.elapsed().as_secs_f64(),
-                }
-            }
+            Err(err) => Self {
+                result: None,
+                error: Some(error_description(err)),
+                time: timing.elapsed().as_secs_f64(),
+            },
+        }
+    }
+}
+
+impl From<(Instant, Result<CollectionCountResult, StorageError>)> for CountResponse {
+    fn from(value: (Instant, Result<CollectionCountResult, StorageError>)) -> Self {
+        let (timing, response) = value;
+        match response {
+            Ok(res) => Self {
+                result: Some(CountResult {
+                    count: res.count as u64,
+                }),
+                error: None,
+                time: timing.elapsed().as_secs_f64(),
+            },
+            Err(err) => Self {
+                result: None,
+                error: Some(error_description(err)),
+                time: timing.elapsed().as_secs_f64(),
+            },
+        }
+    }
+}
+
+impl From<(Instant, Result<Vec<SegmentScoredPoint>, StorageError>)> for RecommendResponse {
+    fn from(value: (Instant, Result<Vec<SegmentScoredPoint>, StorageError>)) -> Self {
+        let (timing, response) = value;
+        match response {
+            Ok(res) => Self {
+                result: res.into_iter().map(|point| point.into()).collect(),
+                error: None,
+                time: timing.elapsed().as_secs_f64(),
+            },
+            Err(err) => Self {
+                result: vec![],
+                error: Some(error_description(err)),
+                time: timing.elapsed().as_secs_f64(),
+            },
+        }
+    }
+}
+
+impl From<SegmentScoredPoint> for ScoredPoint {
+    fn from(value: SegmentScoredPoint) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            version: value.version,
+            score: value.score,
         }
     }
 }
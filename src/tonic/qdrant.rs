@@ -48,6 +48,58 @@ pub struct OptimizersConfigDiff {
     pub max_optimization_threads: ::core::option::Option<u64>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScalarQuantization {
+    #[prost(float, optional, tag = "1")]
+    pub quantile: ::core::option::Option<f32>,
+    #[prost(uint64, optional, tag = "2")]
+    pub rescore_multiplier: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProductQuantization {
+    #[prost(uint64, optional, tag = "1")]
+    pub bucket_size: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VectorParams {
+    #[prost(uint64, tag = "1")]
+    pub size: u64,
+    #[prost(enumeration = "Distance", tag = "2")]
+    pub distance: i32,
+    #[prost(message, optional, tag = "3")]
+    pub hnsw_config: ::core::option::Option<HnswConfigDiff>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SparseVectorParams {
+    #[prost(uint64, optional, tag = "1")]
+    pub full_scan_threshold: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TextIndexParams {
+    /// Shorter tokens are not indexed
+    #[prost(uint64, optional, tag = "1")]
+    pub min_token_len: ::core::option::Option<u64>,
+    /// Longer tokens are not indexed
+    #[prost(uint64, optional, tag = "2")]
+    pub max_token_len: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadFieldSchema {
+    /// Payloads with values of another type are rejected
+    #[prost(enumeration = "PayloadSchemaType", tag = "1")]
+    pub r#type: i32,
+    /// If true - index of the field is created automatically
+    #[prost(bool, tag = "2")]
+    pub index: bool,
+    /// If set - full-text index is created for the keyword field
+    #[prost(message, optional, tag = "3")]
+    pub text_index_params: ::core::option::Option<TextIndexParams>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadSchema {
+    #[prost(map = "string, message", tag = "1")]
+    pub fields: ::std::collections::HashMap<::prost::alloc::string::String, PayloadFieldSchema>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateCollection {
     #[prost(string, tag = "1")]
     pub name: ::prost::alloc::string::String,
@@ -61,6 +113,21 @@ pub struct CreateCollection {
     pub wal_config: ::core::option::Option<WalConfigDiff>,
     #[prost(message, optional, tag = "6")]
     pub optimizers_config: ::core::option::Option<OptimizersConfigDiff>,
+    #[prost(message, optional, tag = "7")]
+    pub quantization_config: ::core::option::Option<ScalarQuantization>,
+    #[prost(message, optional, tag = "8")]
+    pub product_quantization_config: ::core::option::Option<ProductQuantization>,
+    /// Named vectors of the collection
+    #[prost(map = "string, message", tag = "9")]
+    pub vectors: ::std::collections::HashMap<::prost::alloc::string::String, VectorParams>,
+    /// Named sparse vectors of the collection
+    #[prost(map = "string, message", tag = "10")]
+    pub sparse_vectors:
+        ::std::collections::HashMap<::prost::alloc::string::String, SparseVectorParams>,
+    /// Declared types of the payload fields
+    #[prost(map = "string, message", tag = "11")]
+    pub payload_schema:
+        ::std::collections::HashMap<::prost::alloc::string::String, PayloadFieldSchema>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UpdateCollection {
@@ -68,6 +135,9 @@ pub struct UpdateCollection {
     pub name: ::prost::alloc::string::String,
     #[prost(message, optional, tag = "2")]
     pub optimizers_config: ::core::option::Option<OptimizersConfigDiff>,
+    /// New payload schema, empty schema removes the declaration
+    #[prost(message, optional, tag = "3")]
+    pub payload_schema: ::core::option::Option<PayloadSchema>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DeleteCollection {
@@ -90,6 +160,17 @@ pub enum Distance {
     Euclid = 1,
     Dot = 2,
 }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PayloadSchemaType {
+    Keyword = 0,
+    Integer = 1,
+    Float = 2,
+    Geo = 3,
+    Bool = 4,
+    Datetime = 5,
+    Object = 6,
+}
 #[doc = r" Generated client implementations."]
 pub mod collections_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -440,13 +521,28 @@ pub struct UpsertPoints {
     pub points: ::prost::alloc::vec::Vec<PointStruct>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Vector {
+    #[prost(float, repeated, tag = "1")]
+    pub data: ::prost::alloc::vec::Vec<f32>,
+    /// If set - vector is sparse and `data` holds values of these dimensions
+    #[prost(uint32, repeated, tag = "2")]
+    pub indices: ::prost::alloc::vec::Vec<u32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PointStruct {
     #[prost(uint64, tag = "1")]
     pub id: u64,
+    /// Unnamed vector of the point
     #[prost(float, repeated, tag = "2")]
     pub vector: ::prost::alloc::vec::Vec<f32>,
     #[prost(map = "string, message", tag = "3")]
     pub payload: ::std::collections::HashMap<::prost::alloc::string::String, Payload>,
+    /// Named vectors of the point, used instead of `vector`
+    #[prost(map = "string, message", tag = "4")]
+    pub vectors: ::std::collections::HashMap<::prost::alloc::string::String, Vector>,
+    /// If set - point is identified by this UUID instead of `id`
+    #[prost(string, optional, tag = "5")]
+    pub uuid: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Payload {
@@ -458,6 +554,12 @@ pub struct Payload {
     pub float: ::core::option::Option<FloatPayload>,
     #[prost(message, optional, tag = "4")]
     pub geo: ::core::option::Option<GeoPayload>,
+    #[prost(message, optional, tag = "5")]
+    pub bool: ::core::option::Option<BoolPayload>,
+    #[prost(message, optional, tag = "6")]
+    pub datetime: ::core::option::Option<DatetimePayload>,
+    #[prost(message, optional, tag = "7")]
+    pub object: ::core::option::Option<ObjectPayload>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct KeywordPayload {
@@ -480,6 +582,29 @@ pub struct GeoPayload {
     pub value: ::prost::alloc::vec::Vec<GeoPoint>,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BoolPayload {
+    #[prost(bool, repeated, tag = "1")]
+    pub value: ::prost::alloc::vec::Vec<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DatetimePayload {
+    /// RFC 3339 datetime, e.g. 2021-06-01T12:30:00Z
+    #[prost(string, repeated, tag = "1")]
+    pub value: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ObjectPayload {
+    /// Several objects for the array of objects
+    #[prost(message, repeated, tag = "1")]
+    pub value: ::prost::alloc::vec::Vec<PayloadObject>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadObject {
+    /// Nested values are addressed by dotted paths in filters
+    #[prost(map = "string, message", tag = "1")]
+    pub fields: ::std::collections::HashMap<::prost::alloc::string::String, Payload>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GeoPoint {
     #[prost(double, tag = "1")]
     pub lon: f64,
@@ -487,6 +612,246 @@ pub struct GeoPoint {
     pub lat: f64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CountPoints {
+    #[prost(string, tag = "1")]
+    pub collection: ::prost::alloc::string::String,
+    /// If not set - count all points
+    #[prost(message, optional, tag = "2")]
+    pub filter: ::core::option::Option<Filter>,
+    /// If false - return an estimation of the count, default: true
+    #[prost(bool, optional, tag = "3")]
+    pub exact: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointId {
+    #[prost(uint64, tag = "1")]
+    pub num: u64,
+    /// If set - point is identified by this UUID instead of `num`
+    #[prost(string, optional, tag = "2")]
+    pub uuid: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecommendPoints {
+    #[prost(string, tag = "1")]
+    pub collection: ::prost::alloc::string::String,
+    /// Look for vectors closest to those
+    #[prost(message, repeated, tag = "2")]
+    pub positive: ::prost::alloc::vec::Vec<PointId>,
+    /// Try to avoid vectors like this
+    #[prost(message, repeated, tag = "3")]
+    pub negative: ::prost::alloc::vec::Vec<PointId>,
+    /// Look only for points which satisfies this conditions
+    #[prost(message, optional, tag = "4")]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of result to return
+    #[prost(uint64, tag = "5")]
+    pub top: u64,
+    /// Size of the beam in a beam-search
+    #[prost(uint64, optional, tag = "6")]
+    pub hnsw_ef: ::core::option::Option<u64>,
+    /// Name of the vector space to use, if not set - the default (unnamed) vector is used
+    #[prost(string, optional, tag = "7")]
+    pub using: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(enumeration = "RecommendStrategy", tag = "8")]
+    pub strategy: i32,
+    /// Weights of the positive examples, only used by `Weighted` strategy
+    #[prost(float, repeated, tag = "9")]
+    pub positive_weights: ::prost::alloc::vec::Vec<f32>,
+    /// Weights of the negative examples, only used by `Weighted` strategy
+    #[prost(float, repeated, tag = "10")]
+    pub negative_weights: ::prost::alloc::vec::Vec<f32>,
+    /// Look for vectors closest to those, follow the `positive` ids
+    #[prost(message, repeated, tag = "11")]
+    pub positive_examples: ::prost::alloc::vec::Vec<RecommendExample>,
+    /// Try to avoid vectors like this, follow the `negative` ids
+    #[prost(message, repeated, tag = "12")]
+    pub negative_examples: ::prost::alloc::vec::Vec<RecommendExample>,
+    /// Collection to look up the example point ids in, if not set - the current collection is used
+    #[prost(message, optional, tag = "13")]
+    pub lookup_from: ::core::option::Option<LookupLocation>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecommendExample {
+    #[prost(oneof = "recommend_example::Example", tags = "1, 2")]
+    pub example: ::core::option::Option<recommend_example::Example>,
+}
+/// Nested message and enum types in `RecommendExample`.
+pub mod recommend_example {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Example {
+        /// Id of the stored point, which vector is used as an example
+        #[prost(message, tag = "1")]
+        Id(super::PointId),
+        /// Raw dense vector, e.g. embedding of the current user session
+        #[prost(message, tag = "2")]
+        Vector(super::Vector),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LookupLocation {
+    /// Name of the collection to look up the example points in
+    #[prost(string, tag = "1")]
+    pub collection: ::prost::alloc::string::String,
+    /// Name of the vector of the lookup collection, if not set - the same vector name as in `using` is used
+    #[prost(string, optional, tag = "2")]
+    pub vector: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Filter {
+    /// At least one of these conditions should match
+    #[prost(message, repeated, tag = "1")]
+    pub should: ::prost::alloc::vec::Vec<Condition>,
+    /// All conditions must match
+    #[prost(message, repeated, tag = "2")]
+    pub must: ::prost::alloc::vec::Vec<Condition>,
+    /// All conditions must NOT match
+    #[prost(message, repeated, tag = "3")]
+    pub must_not: ::prost::alloc::vec::Vec<Condition>,
+    /// At least minimum amount of given conditions should match
+    #[prost(message, optional, tag = "4")]
+    pub min_should: ::core::option::Option<MinShould>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MinShould {
+    #[prost(message, repeated, tag = "1")]
+    pub conditions: ::prost::alloc::vec::Vec<Condition>,
+    #[prost(uint64, tag = "2")]
+    pub min_count: u64,
+}
+/// Exactly one of the fields should be set
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Condition {
+    #[prost(message, optional, tag = "1")]
+    pub field: ::core::option::Option<FieldCondition>,
+    #[prost(message, optional, tag = "2")]
+    pub has_id: ::core::option::Option<HasIdCondition>,
+    #[prost(message, optional, boxed, tag = "3")]
+    pub filter: ::core::option::Option<::prost::alloc::boxed::Box<Filter>>,
+    #[prost(message, optional, tag = "4")]
+    pub is_empty: ::core::option::Option<IsEmptyCondition>,
+    #[prost(message, optional, tag = "5")]
+    pub has_field: ::core::option::Option<HasFieldCondition>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IsEmptyCondition {
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HasFieldCondition {
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HasIdCondition {
+    #[prost(uint64, repeated, tag = "1")]
+    pub ids: ::prost::alloc::vec::Vec<u64>,
+    #[prost(string, repeated, tag = "2")]
+    pub uuids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FieldCondition {
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub r#match: ::core::option::Option<Match>,
+    #[prost(message, optional, tag = "3")]
+    pub range: ::core::option::Option<Range>,
+    #[prost(message, optional, tag = "4")]
+    pub geo_bounding_box: ::core::option::Option<GeoBoundingBox>,
+    #[prost(message, optional, tag = "5")]
+    pub geo_radius: ::core::option::Option<GeoRadius>,
+    #[prost(message, optional, tag = "6")]
+    pub values_count: ::core::option::Option<ValuesCount>,
+    #[prost(message, optional, tag = "7")]
+    pub geo_polygon: ::core::option::Option<GeoPolygon>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Match {
+    #[prost(string, optional, tag = "1")]
+    pub keyword: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(int64, optional, tag = "2")]
+    pub integer: ::core::option::Option<i64>,
+    /// Match if any of the point values is in the list
+    #[prost(string, repeated, tag = "3")]
+    pub any_keywords: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Match if any of the point values is in the list
+    #[prost(int64, repeated, tag = "4")]
+    pub any_integers: ::prost::alloc::vec::Vec<i64>,
+    /// Match if none of the point values is in the list
+    #[prost(string, repeated, tag = "5")]
+    pub except_keywords: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Match if none of the point values is in the list
+    #[prost(int64, repeated, tag = "6")]
+    pub except_integers: ::prost::alloc::vec::Vec<i64>,
+    #[prost(bool, optional, tag = "7")]
+    pub boolean: ::core::option::Option<bool>,
+    /// Match if all of the query words are in the point text
+    #[prost(string, optional, tag = "8")]
+    pub text: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Range {
+    #[prost(double, optional, tag = "1")]
+    pub lt: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "2")]
+    pub gt: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "3")]
+    pub gte: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "4")]
+    pub lte: ::core::option::Option<f64>,
+    /// Integer bound is compared exactly, set either float or integer bound
+    #[prost(int64, optional, tag = "5")]
+    pub lt_integer: ::core::option::Option<i64>,
+    /// Integer bound is compared exactly, set either float or integer bound
+    #[prost(int64, optional, tag = "6")]
+    pub gt_integer: ::core::option::Option<i64>,
+    /// Integer bound is compared exactly, set either float or integer bound
+    #[prost(int64, optional, tag = "7")]
+    pub gte_integer: ::core::option::Option<i64>,
+    /// Integer bound is compared exactly, set either float or integer bound
+    #[prost(int64, optional, tag = "8")]
+    pub lte_integer: ::core::option::Option<i64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValuesCount {
+    #[prost(uint64, optional, tag = "1")]
+    pub lt: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "2")]
+    pub gt: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "3")]
+    pub gte: ::core::option::Option<u64>,
+    #[prost(uint64, optional, tag = "4")]
+    pub lte: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoBoundingBox {
+    #[prost(message, optional, tag = "1")]
+    pub top_left: ::core::option::Option<GeoPoint>,
+    #[prost(message, optional, tag = "2")]
+    pub bottom_right: ::core::option::Option<GeoPoint>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoRadius {
+    #[prost(message, optional, tag = "1")]
+    pub center: ::core::option::Option<GeoPoint>,
+    /// In meters
+    #[prost(double, tag = "2")]
+    pub radius: f64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoLineString {
+    #[prost(message, repeated, tag = "1")]
+    pub points: ::prost::alloc::vec::Vec<GeoPoint>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GeoPolygon {
+    #[prost(message, optional, tag = "1")]
+    pub exterior: ::core::option::Option<GeoLineString>,
+    #[prost(message, repeated, tag = "2")]
+    pub interiors: ::prost::alloc::vec::Vec<GeoLineString>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PointsOperationResponse {
     #[prost(message, optional, tag = "1")]
     pub result: ::core::option::Option<UpdateResult>,
@@ -502,12 +867,54 @@ pub struct UpdateResult {
     #[prost(enumeration = "UpdateStatus", tag = "2")]
     pub status: i32,
 }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CountResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<CountResult>,
+    #[prost(string, optional, tag = "2")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(double, tag = "3")]
+    pub time: f64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CountResult {
+    #[prost(uint64, tag = "1")]
+    pub count: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecommendResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
+    #[prost(string, optional, tag = "2")]
+    pub error: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(double, tag = "3")]
+    pub time: f64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScoredPoint {
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    #[prost(uint64, tag = "2")]
+    pub version: u64,
+    #[prost(float, tag = "3")]
+    pub score: f32,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum UpdateStatus {
     Acknowledged = 0,
     Completed = 1,
 }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum RecommendStrategy {
+    /// Search for the average of the positive examples, shifted away from the negative ones
+    AverageVector = 0,
+    /// Score candidates against each example separately and take the best match
+    BestScore = 1,
+    /// Sum of the example similarities, multiplied by the example weights
+    Weighted = 2,
+}
 #[doc = r" Generated client implementations."]
 pub mod points_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -582,6 +989,34 @@ pub mod points_client {
             let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Upsert");
             self.inner.unary(request.into_request(), path, codec).await
         }
+        pub async fn count(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CountPoints>,
+        ) -> Result<tonic::Response<super::CountResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Count");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
+        pub async fn recommend(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RecommendPoints>,
+        ) -> Result<tonic::Response<super::RecommendResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Recommend");
+            self.inner.unary(request.into_request(), path, codec).await
+        }
     }
 }
 #[doc = r" Generated server implementations."]
@@ -595,6 +1030,14 @@ pub mod points_server {
             &self,
             request: tonic::Request<super::UpsertPoints>,
         ) -> Result<tonic::Response<super::PointsOperationResponse>, tonic::Status>;
+        async fn count(
+            &self,
+            request: tonic::Request<super::CountPoints>,
+        ) -> Result<tonic::Response<super::CountResponse>, tonic::Status>;
+        async fn recommend(
+            &self,
+            request: tonic::Request<super::RecommendPoints>,
+        ) -> Result<tonic::Response<super::RecommendResponse>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct PointsServer<T: Points> {
@@ -666,6 +1109,68 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/Count" => {
+                    #[allow(non_camel_case_types)]
+                    struct CountSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::CountPoints> for CountSvc<T> {
+                        type Response = super::CountResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CountPoints>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).count(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CountSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/Recommend" => {
+                    #[allow(non_camel_case_types)]
+                    struct RecommendSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::RecommendPoints> for RecommendSvc<T> {
+                        type Response = super::RecommendResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RecommendPoints>,
+                        ) -> Self::Future {
+                            let inner = self.0.clone();
+                            let fut = async move { (*inner).recommend(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RecommendSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec).apply_compression_config(
+                            accept_compression_encodings,
+                            send_compression_encodings,
+                        );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => Box::pin(async move {
                     Ok(http::Response::builder()
                         .status(200)